@@ -0,0 +1,127 @@
+//! Times drawing a steep best fit curve across a 4000 pixel canvas the way curves used to be drawn, a
+//! `DataPoint::draw_point` for each of `canvas_width * 2` samples per unit of x, against one sample per
+//! pixel column of the axis joined by straight lines
+//!
+//! Run with `cargo bench --bench best_fit`
+
+use image::{ImageBuffer, Rgba};
+use plotrs::{
+	canvas::{
+		best_fit::{draw_curve, BestFit},
+		plot::{get_x_pixel, get_y_pixel, DataPoint, DataSymbol},
+	},
+	colours::Colour,
+};
+use std::time::{Duration, Instant};
+
+/// Width and height of the canvas in pixels
+const CANVAS: (u32, u32) = (4000, 3000);
+/// The range of the x-axis
+const X_LIMITS: (i32, i32) = (-10, 10);
+/// The range of the y-axis
+const Y_LIMITS: (i32, i32) = (-100, 100);
+/// Pixel thickness of the curve
+const THICKNESS: u32 = 4;
+/// How many times each approach is run, the fastest run is reported
+const RUNS: u32 = 5;
+
+/// How many pixels there are to one unit of x and y
+fn scale_factors() -> (f32, f32) {
+	(
+		CANVAS.0 as f32 / (X_LIMITS.1 - X_LIMITS.0) as f32,
+		CANVAS.1 as f32 / (Y_LIMITS.1 - Y_LIMITS.0) as f32,
+	)
+}
+
+/// The pixel of the origin of the axes, which lies in the middle of the canvas
+fn axes_origin() -> (u32, u32) {
+	(CANVAS.0 / 2, CANVAS.1 / 2)
+}
+
+/// Evaluate the curve on a grid of `canvas_width * 2` samples per unit of x and draw each sample within
+/// the y bounds as its own point, returning how many samples were drawn and how long it took
+fn draw_points(curve: &BestFit) -> (usize, Duration) {
+	let mut canvas = ImageBuffer::from_pixel(CANVAS.0, CANVAS.1, Rgba([255, 255, 255, 255]));
+	let (x_scale, y_scale) = scale_factors();
+	let start = Instant::now();
+	let scale_factor = CANVAS.0 as i32 * 2;
+	let points: Vec<DataPoint> = (X_LIMITS.0 * scale_factor..=X_LIMITS.1 * scale_factor)
+		.map(|scaled_x| scaled_x as f32 / scale_factor as f32)
+		.map(|x| (x, curve.evaluate(x)))
+		.filter(|(_, y)| y.is_finite() && *y > Y_LIMITS.0 as f32 && *y < Y_LIMITS.1 as f32)
+		.map(|(x, y)| DataPoint {
+			x,
+			ux: None,
+			y,
+			uy: None,
+			colour: Colour::Black,
+			symbol: DataSymbol::Point,
+			symbol_radius: 1,
+			symbol_thickness: 1,
+			line_thickness: THICKNESS,
+			antialias: false,
+		})
+		.collect();
+	for point in points.iter() {
+		point.draw_point(&mut canvas, x_scale, y_scale, axes_origin());
+	}
+	(points.len(), start.elapsed())
+}
+
+/// Sample the curve once per pixel column and join the samples with lines, returning how many samples
+/// were drawn and how long it took
+fn draw_lines(curve: &BestFit) -> (usize, Duration) {
+	let mut canvas = ImageBuffer::from_pixel(CANVAS.0, CANVAS.1, Rgba([255, 255, 255, 255]));
+	let (x_scale, y_scale) = scale_factors();
+	let origin = axes_origin();
+	let start = Instant::now();
+	let runs = curve
+		.find_coordinates(
+			X_LIMITS.0, X_LIMITS.1, Y_LIMITS.0, Y_LIMITS.1, CANVAS.0, None,
+		)
+		.unwrap();
+	for run in runs.iter() {
+		let pixels: Vec<(u32, u32)> = run
+			.iter()
+			.map(|p| {
+				(
+					get_x_pixel(p.x, x_scale, origin.0),
+					get_y_pixel(p.y, y_scale, origin.1),
+				)
+			})
+			.collect();
+		draw_curve(
+			&mut canvas,
+			&pixels,
+			THICKNESS,
+			1,
+			Colour::get_pixel_colour(Colour::Black),
+			false,
+		);
+	}
+	(runs.iter().map(Vec::len).sum(), start.elapsed())
+}
+
+/// Report the fastest of `RUNS` runs of each approach
+fn main() {
+	let curve = BestFit::Cubic {
+		intercept: 0.0,
+		linear_coeff: -20.0,
+		quadratic_coeff: 0.0,
+		cubic_coeff: 1.0,
+		colour: Colour::Black,
+	};
+	for (name, draw) in [
+		(
+			"draw_point",
+			draw_points as fn(&BestFit) -> (usize, Duration),
+		),
+		("joined lines", draw_lines),
+	] {
+		let (samples, fastest) = (0..RUNS)
+			.map(|_| draw(&curve))
+			.min_by_key(|(_, elapsed)| *elapsed)
+			.unwrap();
+		println!("{:>12}: {:>8} samples in {:?}", name, samples, fastest);
+	}
+}
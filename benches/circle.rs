@@ -0,0 +1,79 @@
+//! Times finding the pixels of circular data points with the midpoint circle algorithm against sampling
+//! every thousandth of a degree of each ring with `sin` and `cos`, as circles used to be drawn
+//!
+//! Run with `cargo bench --bench circle`
+
+use plotrs::canvas::plot::DataSymbol;
+use std::{
+	collections::HashSet,
+	time::{Duration, Instant},
+};
+
+/// Number of points found by the midpoint circle algorithm, a large scatter plot
+const POINTS: u32 = 50_000;
+/// Number of points found by trigonometric sampling, far fewer as each takes so long
+const SAMPLED_POINTS: u32 = 100;
+/// Radius and thickness of each symbol
+const SYMBOL: (u32, u32) = (4, 1);
+/// How many times each approach is run, the fastest run is reported
+const RUNS: u32 = 3;
+
+/// Finds the pixels of a circle from its origin, thickness and radius
+type FindPixels = dyn Fn((u32, u32), u32, u32) -> Vec<(u32, u32)>;
+
+/// The pixels of a circle as they used to be found, each ring sampled at 360,000 angles
+fn sampled_circle(origin: (u32, u32), thickness: u32, radius: u32) -> Vec<(u32, u32)> {
+	let mut pixel_coords = vec![origin];
+	for n in 0..=thickness {
+		let r = if (radius + 1) & 1 == 1 {
+			radius + 2 + n
+		} else {
+			radius + 1 + n
+		};
+		for angle_deg in 0..360_000 {
+			let angle = (angle_deg as f32 / 1000.0).to_radians();
+			pixel_coords.push((
+				(origin.0 as f32 + angle.cos() * r as f32) as u32,
+				(origin.1 as f32 + angle.sin() * r as f32) as u32,
+			));
+		}
+	}
+	pixel_coords
+}
+
+/// Find the pixels of `points` circles with `find`, returning how long it took and the coordinates of
+/// the last point
+fn time(points: u32, find: &FindPixels) -> (Duration, Vec<(u32, u32)>) {
+	let start = Instant::now();
+	let mut last = Vec::new();
+	for i in 0..points {
+		last = find((100 + i % 1000, 100 + i / 1000), SYMBOL.1, SYMBOL.0);
+	}
+	(start.elapsed(), last)
+}
+
+/// Report the fastest of `RUNS` runs of each approach along with the coordinates each generates per point
+fn main() {
+	let approaches: [(&str, u32, &FindPixels); 2] = [
+		("trig sampling", SAMPLED_POINTS, &sampled_circle),
+		("midpoint", POINTS, &|origin, thickness, radius| {
+			DataSymbol::Circle.find_pixels(origin, thickness, radius)
+		}),
+	];
+	for (name, points, find) in approaches {
+		let runs: Vec<(Duration, Vec<(u32, u32)>)> =
+			(0..RUNS).map(|_| time(points, find)).collect();
+		let fastest = runs.iter().map(|(elapsed, _)| *elapsed).min().unwrap();
+		let coordinates = &runs[0].1;
+		let distinct: HashSet<&(u32, u32)> = coordinates.iter().collect();
+		println!(
+			"{:>13}: {:>6} points in {:>12?}, {:>12?} per point, {} coordinates for {} pixels",
+			name,
+			points,
+			fastest,
+			fastest / points,
+			coordinates.len(),
+			distinct.len()
+		);
+	}
+}
@@ -1,17 +1,24 @@
 //! Draws the x-axis with labels and scale markers
 
 use image::{ImageBuffer, Rgba};
-use rusttype::PositionedGlyph;
+use rusttype::{Font, PositionedGlyph};
 use tracing::{debug, trace};
 
 use crate::{
 	canvas::{
+		axes::{
+			get_line_extents, get_minor_ticks, get_reversed_value, get_tick_label_every,
+			get_tick_label_size, put_thick_pixel_block, AxisStyle, MinorTicks, Spread,
+		},
+		format::{format_tick_value, TickFormat},
 		glyphs::{create_glyphs, draw_glyphs, get_maximum_height_of_glyphs, get_width_of_glyphs},
+		plot::{find_filled_triangle_pixels, find_triangle_corners},
+		put_pixel_block,
 		quadrants::Quadrants,
 		VHConsumedCanvasSpace, CANVAS_BORDER_PIXELS,
 	},
 	colours::*,
-	get_system_font,
+	error::PlotError,
 };
 
 /// Draws the x-axis label onto the canvas, returns how much new vertical-horizontal space has been consumed on the canvas
@@ -19,22 +26,24 @@ use crate::{
 pub fn build_x_axis_label(
 	canvas: &mut ImageBuffer<Rgba<u8>, Vec<u8>>,
 	label: String,
+	font: &Font,
 	font_size: f32,
 	quadrants: &Quadrants,
 	vertical_pixels_from_top: u32,
 	horizontal_pixels_from_right: u32,
 	vertical_pixels_from_bottom: u32,
 	horizontal_pixels_from_left: u32,
-) -> VHConsumedCanvasSpace {
-	let font = get_system_font();
-	let glyphs: Vec<PositionedGlyph> = create_glyphs(font_size, label.as_str(), &font);
+	scale: u32,
+) -> Result<VHConsumedCanvasSpace, PlotError> {
+	let border = CANVAS_BORDER_PIXELS * scale;
+	let glyphs: Vec<PositionedGlyph> = create_glyphs(font_size, label.as_str(), font);
 	let width = get_width_of_glyphs(&glyphs);
 	let height = get_maximum_height_of_glyphs(&glyphs);
-	match quadrants {
+	Ok(match quadrants {
 		Quadrants::AllQuadrants => {
 			debug!("Placing x-axis label in centre right");
 			let position: (u32, u32) = (
-				canvas.dimensions().0 - CANVAS_BORDER_PIXELS - width - horizontal_pixels_from_right,
+				canvas.dimensions().0 - border - width - horizontal_pixels_from_right,
 				(canvas.dimensions().1 - vertical_pixels_from_top - vertical_pixels_from_bottom)
 					/ 2 + vertical_pixels_from_top
 					+ height,
@@ -42,15 +51,15 @@ pub fn build_x_axis_label(
 			draw_glyphs(canvas, BLACK, glyphs, position);
 			VHConsumedCanvasSpace {
 				v_space_from_top: 0,
-				h_space_from_left: width + CANVAS_BORDER_PIXELS,
+				h_space_from_left: width + border,
 				v_space_from_bottom: 0,
-				h_space_from_right: width + CANVAS_BORDER_PIXELS,
+				h_space_from_right: width + border,
 			}
 		}
 		Quadrants::RightPair => {
 			debug!("Placing x-axis label in centre right");
 			let position: (u32, u32) = (
-				canvas.dimensions().0 - CANVAS_BORDER_PIXELS - width - horizontal_pixels_from_right,
+				canvas.dimensions().0 - border - width - horizontal_pixels_from_right,
 				(canvas.dimensions().1 - vertical_pixels_from_top - vertical_pixels_from_bottom)
 					/ 2 + vertical_pixels_from_top
 					+ height,
@@ -60,7 +69,7 @@ pub fn build_x_axis_label(
 				v_space_from_top: 0,
 				h_space_from_left: 0,
 				v_space_from_bottom: 0,
-				h_space_from_right: width + CANVAS_BORDER_PIXELS,
+				h_space_from_right: width + border,
 			}
 		}
 		Quadrants::LeftPair => {
@@ -74,7 +83,7 @@ pub fn build_x_axis_label(
 			draw_glyphs(canvas, BLACK, glyphs, position);
 			VHConsumedCanvasSpace {
 				v_space_from_top: 0,
-				h_space_from_left: width + CANVAS_BORDER_PIXELS,
+				h_space_from_left: width + border,
 				v_space_from_bottom: 0,
 				h_space_from_right: 0,
 			}
@@ -82,42 +91,42 @@ pub fn build_x_axis_label(
 		Quadrants::TopPair | Quadrants::TopRight => {
 			debug!("Placing x-axis label in bottom right corner");
 			let position: (u32, u32) = (
-				canvas.dimensions().0 - CANVAS_BORDER_PIXELS - width - horizontal_pixels_from_right,
-				canvas.dimensions().1 - CANVAS_BORDER_PIXELS - height,
+				canvas.dimensions().0 - border - width - horizontal_pixels_from_right,
+				canvas.dimensions().1 - border - height,
 			);
 			draw_glyphs(canvas, BLACK, glyphs, position);
 			VHConsumedCanvasSpace {
 				v_space_from_top: 0,
 				h_space_from_left: 0,
-				v_space_from_bottom: CANVAS_BORDER_PIXELS + (height * 2) + CANVAS_BORDER_PIXELS,
-				h_space_from_right: width + CANVAS_BORDER_PIXELS + CANVAS_BORDER_PIXELS,
+				v_space_from_bottom: border + (height * 2) + border,
+				h_space_from_right: width + border + border,
 			}
 		}
 		Quadrants::BottomPair | Quadrants::BottomRight => {
 			debug!("Placing x-axis label in top right corner");
 			let position: (u32, u32) = (
 				canvas.dimensions().0 - horizontal_pixels_from_right - width,
-				vertical_pixels_from_top + CANVAS_BORDER_PIXELS + height,
+				vertical_pixels_from_top + border + height,
 			);
 			draw_glyphs(canvas, BLACK, glyphs, position);
 			VHConsumedCanvasSpace {
-				v_space_from_top: height + CANVAS_BORDER_PIXELS,
+				v_space_from_top: height + border,
 				h_space_from_left: 0,
 				v_space_from_bottom: 0,
-				h_space_from_right: width + CANVAS_BORDER_PIXELS,
+				h_space_from_right: width + border,
 			}
 		}
 		Quadrants::TopLeft => {
 			debug!("Placing x-axis label in bottom left corner");
 			let position: (u32, u32) = (
 				horizontal_pixels_from_left + (width / 2),
-				canvas.dimensions().1 - CANVAS_BORDER_PIXELS - height,
+				canvas.dimensions().1 - border - height,
 			);
 			draw_glyphs(canvas, BLACK, glyphs, position);
 			VHConsumedCanvasSpace {
 				v_space_from_top: 0,
-				h_space_from_left: width + CANVAS_BORDER_PIXELS,
-				v_space_from_bottom: CANVAS_BORDER_PIXELS + (height * 2) + CANVAS_BORDER_PIXELS,
+				h_space_from_left: width + border,
+				v_space_from_bottom: border + (height * 2) + border,
 				h_space_from_right: 0,
 			}
 		}
@@ -129,13 +138,13 @@ pub fn build_x_axis_label(
 			);
 			draw_glyphs(canvas, BLACK, glyphs, position);
 			VHConsumedCanvasSpace {
-				v_space_from_top: (height * 2) + CANVAS_BORDER_PIXELS + CANVAS_BORDER_PIXELS,
-				h_space_from_left: width + CANVAS_BORDER_PIXELS,
+				v_space_from_top: (height * 2) + border + border,
+				h_space_from_left: width + border,
 				v_space_from_bottom: 0,
 				h_space_from_right: 0,
 			}
 		}
-	}
+	})
 }
 /// Get the pixel length of the x-axis
 pub fn get_x_axis_pixel_length(min_pixel: u32, max_pixel: u32) -> u32 {
@@ -152,15 +161,47 @@ pub fn draw_x_axis(
 	axis_min_pixel: (u32, u32),
 	axis_origin_pixel: (u32, u32),
 	axis_max_pixel: (u32, u32),
+	axis_style: AxisStyle,
+	scale: u32,
 ) {
 	debug!("Drawing x-axis");
+	let thickness = axis_style.axis_thickness;
+	// a thick axis is lengthened by its own half thickness so that it joins the y-axis squarely
+	let (before, after) = get_line_extents(thickness, scale);
 	// draw from the origin to max pixel
-	for px in axis_origin_pixel.0..=(axis_max_pixel.0) {
-		canvas.put_pixel(px, axis_origin_pixel.1, Rgba(BLACK));
+	for px in axis_origin_pixel.0..=(axis_max_pixel.0 + after) {
+		put_thick_pixel_block(
+			canvas,
+			px,
+			axis_origin_pixel.1,
+			scale,
+			thickness,
+			Spread::Rows,
+			axis_style.axis_colour,
+		);
 	}
 	// draw from min pixel to origin
-	for px in axis_min_pixel.0..=(axis_origin_pixel.0) {
-		canvas.put_pixel(px, axis_origin_pixel.1, Rgba(BLACK));
+	for px in axis_min_pixel.0.saturating_sub(before)..=(axis_origin_pixel.0) {
+		put_thick_pixel_block(
+			canvas,
+			px,
+			axis_origin_pixel.1,
+			scale,
+			thickness,
+			Spread::Rows,
+			axis_style.axis_colour,
+		);
+	}
+	if axis_style.has_arrows {
+		// the arrowhead is centred on the end of the axis and points right
+		let corners = find_triangle_corners(
+			(axis_max_pixel.0 + after, axis_origin_pixel.1),
+			axis_style.get_arrow_size(scale) as f32,
+			(1.0, 0.0),
+		);
+		for (px, py) in find_filled_triangle_pixels(corners) {
+			put_pixel_block(canvas, px, py, 1, axis_style.axis_colour);
+		}
 	}
 }
 /// Draws the scale markings along the x-axis
@@ -173,12 +214,28 @@ pub fn draw_x_axis_scale_markings(
 	axis_max_pixel: (u32, u32),
 	x_axis_length: u32,
 	x_data_min_max_limits: (i32, i32),
+	font: &Font,
 	font_size: f32,
 	has_grid: bool,
+	minor_ticks: MinorTicks,
+	has_tick_labels: bool,
 	x_axis_resolution: u32,
-) {
-	let font = get_system_font();
+	tick_format: &TickFormat,
+	axis_style: AxisStyle,
+	scale: u32,
+) -> Result<(), PlotError> {
 	debug!("Drawing x-axis scale markings");
+	// every label of the axis shares the notation suited to its span
+	let tick_format =
+		tick_format.for_span(x_data_min_max_limits.1 as f64 - x_data_min_max_limits.0 as f64);
+	// the widest label decides how many labels fit between the scale markers
+	let label_size = get_tick_label_size(
+		x_data_min_max_limits,
+		x_axis_resolution * if quadrants.is_x_paired() { 2 } else { 1 },
+		&tick_format,
+		font,
+		font_size,
+	);
 	// we find the appropriate layout to draw markers, this ensures scale resolution markers are correctly drawn across positive and negative axes
 	match quadrants {
 		// vareints with a positive and negative x-axis
@@ -188,32 +245,57 @@ pub fn draw_x_axis_scale_markings(
 			// We halve the axis length as we need to draw the resolution twice, once is the
 			// positive direction and once in the negative direction
 			let x_subdivision_length = (x_axis_length / 2) / x_axis_resolution;
+			// Number of mini-markings between scale markers and the pixels between each
+			let (minor_count, marker_spacing) =
+				get_minor_ticks(x_subdivision_length, minor_ticks.count);
 			// The pixel length of each data label
-			let data_label_length = 5;
+			let data_label_length = 5 * scale;
 			// For writing a value for each data label we need to know the overall data size that corrpesonds to the axis
-			let x_value_range = x_data_min_max_limits.1 as f32 - x_data_min_max_limits.0 as f32;
+			let x_value_range =
+				(x_data_min_max_limits.1 as f64 - x_data_min_max_limits.0 as f64) as f32;
 			// Find how much a suddivsion is in terms of data value
 			let x_value_per_subdivision = (x_value_range / 2.0) / x_axis_resolution as f32;
+			// Write a label at every this many markers so that neighbouring labels do not overlap
+			let label_every = get_tick_label_every(
+				tick_format.label_every,
+				label_size.0,
+				label_size.1 / 2,
+				x_subdivision_length,
+			);
 			// If required draw the x part of a background grid as grey vertical lines
 			if has_grid {
 				trace!("Drawing grey background grid...");
 				// draw in positive x direction
 				for i in 0..(x_axis_resolution + 1) {
 					for py in axis_max_pixel.1..axis_min_pixel.1 {
-						canvas.put_pixel(
+						if !axis_style.is_grid_drawn(py.abs_diff(axis_origin_pixel.1), scale) {
+							continue;
+						}
+						put_thick_pixel_block(
+							canvas,
 							axis_origin_pixel.0 + (i * x_subdivision_length),
 							py,
-							Rgba(GREY),
+							scale,
+							axis_style.grid_thickness,
+							Spread::Columns,
+							axis_style.grid_colour,
 						);
 					}
 				}
 				// draw in negative x direction
 				for i in 0..(x_axis_resolution + 1) {
 					for py in axis_max_pixel.1..axis_min_pixel.1 {
-						canvas.put_pixel(
+						if !axis_style.is_grid_drawn(py.abs_diff(axis_origin_pixel.1), scale) {
+							continue;
+						}
+						put_thick_pixel_block(
+							canvas,
 							axis_origin_pixel.0 - (i * x_subdivision_length),
 							py,
-							Rgba(GREY),
+							scale,
+							axis_style.grid_thickness,
+							Spread::Columns,
+							axis_style.grid_colour,
 						);
 					}
 				}
@@ -224,25 +306,31 @@ pub fn draw_x_axis_scale_markings(
 			for i in 0..(x_axis_resolution + 1) {
 				// Draw each even section slightly longer
 				let label_length_scale = if i & 1 == 1 { 2 } else { 3 };
-				for n in 0..(data_label_length * label_length_scale) {
-					// So that scale markers are not drawn on the graph area itself check which quadrant type
-					// and flip if necessary so they are drawn in the available whitespace outside the axis
-					let px = axis_origin_pixel.0 + (i * x_subdivision_length);
-					let py = if *quadrants == Quadrants::BottomPair {
-						axis_origin_pixel.1 - n
-					} else {
-						axis_origin_pixel.1 + n
-					};
-					canvas.put_pixel(px, py, Rgba(BLACK));
+				if axis_style.has_ticks {
+					for n in 0..(data_label_length * label_length_scale) {
+						// So that scale markers are not drawn on the graph area itself check which quadrant type
+						// and flip if necessary so they are drawn in the available whitespace outside the axis
+						let px = axis_origin_pixel.0 + (i * x_subdivision_length);
+						let py = if *quadrants == Quadrants::BottomPair {
+							axis_origin_pixel.1 - n
+						} else {
+							axis_origin_pixel.1 + n
+						};
+						put_thick_pixel_block(
+							canvas,
+							px,
+							py,
+							scale,
+							axis_style.axis_thickness,
+							Spread::Columns,
+							axis_style.axis_colour,
+						);
+					}
 				}
-				// If there's enough space between each scale marker create mini-markings
-				// mini-marker varients
-				let marker_count = vec![9, 4, 3, 2, 1];
-				'outer_pos: for mc in marker_count.iter() {
-					// (mc + 1) because there needs to be a gap between final mini-marker and scale marker
-					if x_subdivision_length % (mc + 1) == 0 && i < x_axis_resolution {
-						for j in 1..=*mc {
-							let marker_spacing = x_subdivision_length / (mc + 1);
+				// Mini-markings between this scale marker and the next
+				if i < x_axis_resolution {
+					for j in 1..=minor_count {
+						if axis_style.has_ticks {
 							for n in 0..data_label_length {
 								let px = axis_origin_pixel.0
 									+ ((i * x_subdivision_length) + (j * marker_spacing));
@@ -252,17 +340,58 @@ pub fn draw_x_axis_scale_markings(
 								} else {
 									axis_origin_pixel.1 + n
 								};
-								canvas.put_pixel(px, py, Rgba(BLACK));
+								put_thick_pixel_block(
+									canvas,
+									px,
+									py,
+									scale,
+									axis_style.axis_thickness,
+									Spread::Columns,
+									axis_style.axis_colour,
+								);
+							}
+						}
+						// faint gridlines at the mini-markings
+						if minor_ticks.has_grid {
+							let px = axis_origin_pixel.0
+								+ ((i * x_subdivision_length) + (j * marker_spacing));
+							for py in axis_max_pixel.1..axis_min_pixel.1 {
+								if !axis_style
+									.is_grid_drawn(py.abs_diff(axis_origin_pixel.1), scale)
+								{
+									continue;
+								}
+								put_thick_pixel_block(
+									canvas,
+									px,
+									py,
+									scale,
+									axis_style.grid_thickness,
+									Spread::Columns,
+									axis_style.minor_grid_colour,
+								);
 							}
 						}
-						break 'outer_pos;
 					}
 				}
 				// Draw the data label text
+				if !has_tick_labels || i % label_every != 0 {
+					continue;
+				}
 				// For AllQuadrants don't draw the origin marker text otherwise it sits on the axis obscurring text
-				if i == 0 && *quadrants == Quadrants::AllQuadrants {continue}
-				let text = (x_value_per_subdivision * i as f32).to_string();
-				let glyphs = create_glyphs(font_size, &text, &font);
+				if i == 0 && *quadrants == Quadrants::AllQuadrants {
+					continue;
+				}
+				let text = format_tick_value(
+					get_reversed_value(
+						(x_value_per_subdivision * i as f32) as f64,
+						x_data_min_max_limits,
+						axis_style.is_x_reversed,
+					),
+					x_value_per_subdivision as f64,
+					&tick_format,
+				);
+				let glyphs = create_glyphs(font_size, &text, font);
 				let origin_x = axis_origin_pixel.0 + (i * x_subdivision_length);
 				// So that scale markers are not drawn on the graph area itself check which quadrant type
 				// and flip if necessary so they are drawn in the available whitespace outside the axis
@@ -272,35 +401,40 @@ pub fn draw_x_axis_scale_markings(
 					axis_origin_pixel.1 + (data_label_length * label_length_scale)
 				};
 				// let origin_y = origin_pixel.1 + (data_label_length);
-				let offset =
-					get_x_axis_scale_label_offset(&glyphs, origin_x, origin_y, quadrants);
+				let offset = get_x_axis_scale_label_offset(&glyphs, origin_x, origin_y, quadrants);
 				trace!("Drawing x-axis label {} at {:?}", text, offset);
-				draw_glyphs(canvas, BLACK, glyphs, offset);
+				draw_glyphs(canvas, axis_style.tick_label_colour, glyphs, offset);
 			}
 			// draw markers in negative x direction
 			for i in 0..(x_axis_resolution + 1) {
 				// Draw each even section slightly longer
 				let label_length_scale = if i & 1 == 1 { 2 } else { 3 };
-				for n in 0..(data_label_length * label_length_scale) {
-					// So that scale markers are not drawn on the graph area itself check which quadrant type
-					// and flip if necessary so they are drawn in the available whitespace outside the axis
-					let px = axis_origin_pixel.0 - (i * x_subdivision_length);
-					let py = if *quadrants == Quadrants::BottomPair {
-						axis_origin_pixel.1 - n
-					} else {
-						axis_origin_pixel.1 + n
-					};
-					canvas.put_pixel(px, py, Rgba(BLACK));
+				if axis_style.has_ticks {
+					for n in 0..(data_label_length * label_length_scale) {
+						// So that scale markers are not drawn on the graph area itself check which quadrant type
+						// and flip if necessary so they are drawn in the available whitespace outside the axis
+						let px = axis_origin_pixel.0 - (i * x_subdivision_length);
+						let py = if *quadrants == Quadrants::BottomPair {
+							axis_origin_pixel.1 - n
+						} else {
+							axis_origin_pixel.1 + n
+						};
+						put_thick_pixel_block(
+							canvas,
+							px,
+							py,
+							scale,
+							axis_style.axis_thickness,
+							Spread::Columns,
+							axis_style.axis_colour,
+						);
+					}
 				}
 
-				// If there's enough space between each scale marker create mini-markings
-				// mini-marker varients
-				let marker_count = vec![9, 4, 3, 2, 1];
-				'outer_neg: for mc in marker_count.iter() {
-					// (mc + 1) because there needs to be a gap between final mini-marker and scale marker
-					if x_subdivision_length % (mc + 1) == 0 && i < x_axis_resolution {
-						for j in 1..=*mc {
-							let marker_spacing = x_subdivision_length / (mc + 1);
+				// Mini-markings between this scale marker and the next
+				if i < x_axis_resolution {
+					for j in 1..=minor_count {
+						if axis_style.has_ticks {
 							for n in 0..data_label_length {
 								let px = axis_origin_pixel.0
 									- ((i * x_subdivision_length) + (j * marker_spacing));
@@ -310,21 +444,66 @@ pub fn draw_x_axis_scale_markings(
 								} else {
 									axis_origin_pixel.1 + n
 								};
-								canvas.put_pixel(px, py, Rgba(BLACK));
+								put_thick_pixel_block(
+									canvas,
+									px,
+									py,
+									scale,
+									axis_style.axis_thickness,
+									Spread::Columns,
+									axis_style.axis_colour,
+								);
+							}
+						}
+						// faint gridlines at the mini-markings
+						if minor_ticks.has_grid {
+							let px = axis_origin_pixel.0
+								- ((i * x_subdivision_length) + (j * marker_spacing));
+							for py in axis_max_pixel.1..axis_min_pixel.1 {
+								if !axis_style
+									.is_grid_drawn(py.abs_diff(axis_origin_pixel.1), scale)
+								{
+									continue;
+								}
+								put_thick_pixel_block(
+									canvas,
+									px,
+									py,
+									scale,
+									axis_style.grid_thickness,
+									Spread::Columns,
+									axis_style.minor_grid_colour,
+								);
 							}
 						}
-						break 'outer_neg;
 					}
 				}
 				// Draw the data label text
+				if !has_tick_labels || i % label_every != 0 {
+					continue;
+				}
 				// For TopPair don't draw the origin marker text otherwise it sits on the axis obscurring text
-				if i == 0 && *quadrants == Quadrants::TopPair {continue}
+				if i == 0 && *quadrants == Quadrants::TopPair {
+					continue;
+				}
 				// For BottomPair don't draw the origin marker text otherwise it sits on the axis obscurring text
-				if i == 0 && *quadrants == Quadrants::BottomPair {continue}
+				if i == 0 && *quadrants == Quadrants::BottomPair {
+					continue;
+				}
 				// For AllQuadrants don't draw the origin marker text otherwise it sits on the axis obscurring text
-				if i == 0 && *quadrants == Quadrants::AllQuadrants {continue}
-				let text = (-x_value_per_subdivision * i as f32).to_string();
-				let glyphs = create_glyphs(font_size, &text, &font);
+				if i == 0 && *quadrants == Quadrants::AllQuadrants {
+					continue;
+				}
+				let text = format_tick_value(
+					get_reversed_value(
+						(-x_value_per_subdivision * i as f32) as f64,
+						x_data_min_max_limits,
+						axis_style.is_x_reversed,
+					),
+					x_value_per_subdivision as f64,
+					&tick_format,
+				);
+				let glyphs = create_glyphs(font_size, &text, font);
 				let origin_x = axis_origin_pixel.0 - (i * x_subdivision_length);
 				// So that scale markers are not drawn on the graph area itself check which quadrant type
 				// and flip if necessary so they are drawn in the available whitespace outside the axis
@@ -334,10 +513,9 @@ pub fn draw_x_axis_scale_markings(
 					axis_origin_pixel.1 + (data_label_length * label_length_scale)
 				};
 				// let origin_y = origin_pixel.1 + (data_label_length);
-				let offset =
-					get_x_axis_scale_label_offset(&glyphs, origin_x, origin_y, quadrants);
+				let offset = get_x_axis_scale_label_offset(&glyphs, origin_x, origin_y, quadrants);
 				trace!("Drawing x-axis label {} at {:?}", text, offset);
-				draw_glyphs(canvas, BLACK, glyphs, offset);
+				draw_glyphs(canvas, axis_style.tick_label_colour, glyphs, offset);
 			}
 		}
 		// varients with only a positive x-axis
@@ -345,21 +523,39 @@ pub fn draw_x_axis_scale_markings(
 			// Subdivide the x-axis length into a number of points we can draw labels at.
 			// The number of pixels along the x-axis between each data label
 			let x_subdivision_length = x_axis_length / x_axis_resolution;
+			// Number of mini-markings between scale markers and the pixels between each
+			let (minor_count, marker_spacing) =
+				get_minor_ticks(x_subdivision_length, minor_ticks.count);
 			// The pixel length of each data label
-			let data_label_length = 5;
+			let data_label_length = 5 * scale;
 			// For writing a value for each data label we need to know the overall data size that corrpesonds to the axis
-			let x_value_range = x_data_min_max_limits.1 as f32 - x_data_min_max_limits.0 as f32;
+			let x_value_range =
+				(x_data_min_max_limits.1 as f64 - x_data_min_max_limits.0 as f64) as f32;
 			// Find how much a suddivsion is in terms of data value
 			let x_value_per_subdivision = x_value_range / x_axis_resolution as f32;
+			// Write a label at every this many markers so that neighbouring labels do not overlap
+			let label_every = get_tick_label_every(
+				tick_format.label_every,
+				label_size.0,
+				label_size.1 / 2,
+				x_subdivision_length,
+			);
 			// If required draw the x part of a background grid as grey vertical lines
 			if has_grid {
 				trace!("Drawing grey background grid...");
 				for i in 0..(x_axis_resolution + 1) {
 					for py in axis_max_pixel.1..axis_min_pixel.1 {
-						canvas.put_pixel(
+						if !axis_style.is_grid_drawn(py.abs_diff(axis_origin_pixel.1), scale) {
+							continue;
+						}
+						put_thick_pixel_block(
+							canvas,
 							axis_min_pixel.0 + (i * x_subdivision_length),
 							py,
-							Rgba(GREY),
+							scale,
+							axis_style.grid_thickness,
+							Spread::Columns,
+							axis_style.grid_colour,
 						);
 					}
 				}
@@ -369,25 +565,31 @@ pub fn draw_x_axis_scale_markings(
 			for i in 0..(x_axis_resolution + 1) {
 				// Draw each even section slightly longer
 				let label_length_scale = if i & 1 == 1 { 2 } else { 3 };
-				for n in 0..(data_label_length * label_length_scale) {
-					// So that scale markers are not drawn on the graph area itself check which quadrant type
-					// and flip if necessary so they are drawn in the available whitespace outside the axis
-					let px = axis_min_pixel.0 + (i * x_subdivision_length);
-					let py = if *quadrants == Quadrants::BottomRight {
-						axis_origin_pixel.1 - n
-					} else {
-						axis_origin_pixel.1 + n
-					};
-					canvas.put_pixel(px, py, Rgba(BLACK));
+				if axis_style.has_ticks {
+					for n in 0..(data_label_length * label_length_scale) {
+						// So that scale markers are not drawn on the graph area itself check which quadrant type
+						// and flip if necessary so they are drawn in the available whitespace outside the axis
+						let px = axis_min_pixel.0 + (i * x_subdivision_length);
+						let py = if *quadrants == Quadrants::BottomRight {
+							axis_origin_pixel.1 - n
+						} else {
+							axis_origin_pixel.1 + n
+						};
+						put_thick_pixel_block(
+							canvas,
+							px,
+							py,
+							scale,
+							axis_style.axis_thickness,
+							Spread::Columns,
+							axis_style.axis_colour,
+						);
+					}
 				}
-				// If there's enough space between each scale marker create mini-markings
-				// mini-marker varients
-				let marker_count = vec![9, 4, 3, 2, 1];
-				'outer: for mc in marker_count.iter() {
-					// (mc + 1) because there needs to be a gap between final mini-marker and scale marker
-					if x_subdivision_length % (mc + 1) == 0 && i < x_axis_resolution {
-						for j in 1..=*mc {
-							let marker_spacing = x_subdivision_length / (mc + 1);
+				// Mini-markings between this scale marker and the next
+				if i < x_axis_resolution {
+					for j in 1..=minor_count {
+						if axis_style.has_ticks {
 							for n in 0..data_label_length {
 								// So that scale markers are not drawn on the graph area itself check which quadrant type
 								// and flip if necessary so they are drawn in the available whitespace outside the axis
@@ -398,18 +600,59 @@ pub fn draw_x_axis_scale_markings(
 								} else {
 									axis_origin_pixel.1 + n
 								};
-								canvas.put_pixel(px, py, Rgba(BLACK));
+								put_thick_pixel_block(
+									canvas,
+									px,
+									py,
+									scale,
+									axis_style.axis_thickness,
+									Spread::Columns,
+									axis_style.axis_colour,
+								);
+							}
+						}
+						// faint gridlines at the mini-markings
+						if minor_ticks.has_grid {
+							let px = axis_min_pixel.0
+								+ ((i * x_subdivision_length) + (j * marker_spacing));
+							for py in axis_max_pixel.1..axis_min_pixel.1 {
+								if !axis_style
+									.is_grid_drawn(py.abs_diff(axis_origin_pixel.1), scale)
+								{
+									continue;
+								}
+								put_thick_pixel_block(
+									canvas,
+									px,
+									py,
+									scale,
+									axis_style.grid_thickness,
+									Spread::Columns,
+									axis_style.minor_grid_colour,
+								);
 							}
 						}
-						break 'outer;
 					}
 				}
 				// Draw the data label text
+				if !has_tick_labels || i % label_every != 0 {
+					continue;
+				}
 				// For RightPair don't draw the origin marker text otherwise it sits on the axis obscurring text
-				if i == 0 && *quadrants == Quadrants::RightPair {continue}
-				let text = (x_data_min_max_limits.0 as f32 + (x_value_per_subdivision * i as f32))
-					.to_string();
-				let glyphs = create_glyphs(font_size, &text, &font);
+				if i == 0 && *quadrants == Quadrants::RightPair {
+					continue;
+				}
+				let text = format_tick_value(
+					get_reversed_value(
+						x_data_min_max_limits.0 as f64
+							+ (x_value_per_subdivision * i as f32) as f64,
+						x_data_min_max_limits,
+						axis_style.is_x_reversed,
+					),
+					x_value_per_subdivision as f64,
+					&tick_format,
+				);
+				let glyphs = create_glyphs(font_size, &text, font);
 				let origin_x = axis_min_pixel.0 + (i * x_subdivision_length);
 				// So that scale markers are not drawn on the graph area itself check which quadrant type
 				// and flip if necessary so they are drawn in the available whitespace outside the axis
@@ -421,7 +664,7 @@ pub fn draw_x_axis_scale_markings(
 				// let origin_y = origin_pixel.1 + (data_label_length);
 				let offset = get_x_axis_scale_label_offset(&glyphs, origin_x, origin_y, quadrants);
 				trace!("Drawing x-axis label {} at {:?}", text, offset);
-				draw_glyphs(canvas, BLACK, glyphs, offset);
+				draw_glyphs(canvas, axis_style.tick_label_colour, glyphs, offset);
 			}
 		}
 		// varients with a negative x-axis
@@ -429,21 +672,39 @@ pub fn draw_x_axis_scale_markings(
 			// Subdivide the x-axis length into a number of points we can draw labels at.
 			// The number of pixels along the x-axis between each data label
 			let x_subdivision_length = x_axis_length / x_axis_resolution;
+			// Number of mini-markings between scale markers and the pixels between each
+			let (minor_count, marker_spacing) =
+				get_minor_ticks(x_subdivision_length, minor_ticks.count);
 			// The pixel length of each data label
-			let data_label_length = 5;
+			let data_label_length = 5 * scale;
 			// For writing a value for each data label we need to know the overall data size that corrpesonds to the axis
-			let x_value_range = x_data_min_max_limits.1 as f32 - x_data_min_max_limits.0 as f32;
+			let x_value_range =
+				(x_data_min_max_limits.1 as f64 - x_data_min_max_limits.0 as f64) as f32;
 			// Find how much a suddivsion is in terms of data value
 			let x_value_per_subdivision = x_value_range / x_axis_resolution as f32;
+			// Write a label at every this many markers so that neighbouring labels do not overlap
+			let label_every = get_tick_label_every(
+				tick_format.label_every,
+				label_size.0,
+				label_size.1 / 2,
+				x_subdivision_length,
+			);
 			// If required draw the x part of a background grid as grey vertical lines
 			if has_grid {
 				trace!("Drawing grey background grid...");
 				for i in 0..(x_axis_resolution + 1) {
 					for py in axis_max_pixel.1..axis_min_pixel.1 {
-						canvas.put_pixel(
+						if !axis_style.is_grid_drawn(py.abs_diff(axis_origin_pixel.1), scale) {
+							continue;
+						}
+						put_thick_pixel_block(
+							canvas,
 							axis_origin_pixel.0 - (i * x_subdivision_length),
 							py,
-							Rgba(GREY),
+							scale,
+							axis_style.grid_thickness,
+							Spread::Columns,
+							axis_style.grid_colour,
 						);
 					}
 				}
@@ -453,25 +714,31 @@ pub fn draw_x_axis_scale_markings(
 			for i in 0..(x_axis_resolution + 1) {
 				// Draw each even section slightly longer
 				let label_length_scale = if i & 1 == 1 { 2 } else { 3 };
-				for n in 0..(data_label_length * label_length_scale) {
-					// So that scale markers are not drawn on the graph area itself check which quadrant type
-					// and flip if necessary so they are drawn in the available whitespace outside the axis
-					let px = axis_origin_pixel.0 - (i * x_subdivision_length);
-					let py = if *quadrants == Quadrants::BottomLeft {
-						axis_origin_pixel.1 - n
-					} else {
-						axis_origin_pixel.1 + n
-					};
-					canvas.put_pixel(px, py, Rgba(BLACK));
+				if axis_style.has_ticks {
+					for n in 0..(data_label_length * label_length_scale) {
+						// So that scale markers are not drawn on the graph area itself check which quadrant type
+						// and flip if necessary so they are drawn in the available whitespace outside the axis
+						let px = axis_origin_pixel.0 - (i * x_subdivision_length);
+						let py = if *quadrants == Quadrants::BottomLeft {
+							axis_origin_pixel.1 - n
+						} else {
+							axis_origin_pixel.1 + n
+						};
+						put_thick_pixel_block(
+							canvas,
+							px,
+							py,
+							scale,
+							axis_style.axis_thickness,
+							Spread::Columns,
+							axis_style.axis_colour,
+						);
+					}
 				}
-				// If there's enough space between each scale marker create mini-markings
-				// mini-marker varients
-				let marker_count = vec![9, 4, 3, 2, 1];
-				'outer_l_neg: for mc in marker_count.iter() {
-					// (mc + 1) because there needs to be a gap between final mini-marker and scale marker
-					if x_subdivision_length % (mc + 1) == 0 && i < x_axis_resolution {
-						for j in 1..=*mc {
-							let marker_spacing = x_subdivision_length / (mc + 1);
+				// Mini-markings between this scale marker and the next
+				if i < x_axis_resolution {
+					for j in 1..=minor_count {
+						if axis_style.has_ticks {
 							for n in 0..data_label_length {
 								// So that scale markers are not drawn on the graph area itself check which quadrant type
 								// and flip if necessary so they are drawn in the available whitespace outside the axis
@@ -482,17 +749,58 @@ pub fn draw_x_axis_scale_markings(
 								} else {
 									axis_origin_pixel.1 + n
 								};
-								canvas.put_pixel(px, py, Rgba(BLACK));
+								put_thick_pixel_block(
+									canvas,
+									px,
+									py,
+									scale,
+									axis_style.axis_thickness,
+									Spread::Columns,
+									axis_style.axis_colour,
+								);
+							}
+						}
+						// faint gridlines at the mini-markings
+						if minor_ticks.has_grid {
+							let px = axis_origin_pixel.0
+								- ((i * x_subdivision_length) + (j * marker_spacing));
+							for py in axis_max_pixel.1..axis_min_pixel.1 {
+								if !axis_style
+									.is_grid_drawn(py.abs_diff(axis_origin_pixel.1), scale)
+								{
+									continue;
+								}
+								put_thick_pixel_block(
+									canvas,
+									px,
+									py,
+									scale,
+									axis_style.grid_thickness,
+									Spread::Columns,
+									axis_style.minor_grid_colour,
+								);
 							}
 						}
-						break 'outer_l_neg;
 					}
 				}
 				// Draw the data label text
+				if !has_tick_labels || i % label_every != 0 {
+					continue;
+				}
 				// For LeftPair don't draw the origin marker text otherwise it sits on the axis obscurring text
-				if i == 0 && *quadrants == Quadrants::LeftPair {continue}
-				let text = (-x_value_per_subdivision * i as f32).to_string();
-				let glyphs = create_glyphs(font_size, &text, &font);
+				if i == 0 && *quadrants == Quadrants::LeftPair {
+					continue;
+				}
+				let text = format_tick_value(
+					get_reversed_value(
+						(-x_value_per_subdivision * i as f32) as f64,
+						x_data_min_max_limits,
+						axis_style.is_x_reversed,
+					),
+					x_value_per_subdivision as f64,
+					&tick_format,
+				);
+				let glyphs = create_glyphs(font_size, &text, font);
 				let origin_x = axis_origin_pixel.0 - (i * x_subdivision_length);
 				// So that scale markers are not drawn on the graph area itself check which quadrant type
 				// and flip if necessary so they are drawn in the available whitespace outside the axis
@@ -504,10 +812,86 @@ pub fn draw_x_axis_scale_markings(
 				// let origin_y = origin_pixel.1 + (data_label_length);
 				let offset = get_x_axis_scale_label_offset(&glyphs, origin_x, origin_y, quadrants);
 				trace!("Drawing x-axis label {} at {:?}", text, offset);
-				draw_glyphs(canvas, BLACK, glyphs, offset);
+				draw_glyphs(canvas, axis_style.tick_label_colour, glyphs, offset);
+			}
+		}
+	}
+	Ok(())
+}
+
+/// Draws a scale marker, its label and optionally a gridline at each of `ticks`, pixel columns paired with
+/// the text of their label, in place of the markings spread by the resolution of the axis
+#[allow(clippy::too_many_arguments)]
+pub fn draw_x_axis_custom_ticks(
+	quadrants: &Quadrants,
+	canvas: &mut ImageBuffer<Rgba<u8>, Vec<u8>>,
+	axis_min_pixel: (u32, u32),
+	axis_origin_pixel: (u32, u32),
+	axis_max_pixel: (u32, u32),
+	ticks: &[(u32, String)],
+	font: &Font,
+	font_size: f32,
+	has_grid: bool,
+	has_tick_labels: bool,
+	axis_style: AxisStyle,
+	scale: u32,
+) -> Result<(), PlotError> {
+	debug!("Drawing custom x-axis scale markings");
+	// markers are drawn in the whitespace above the axis when the plot hangs beneath it
+	let is_above = matches!(
+		quadrants,
+		Quadrants::BottomPair | Quadrants::BottomRight | Quadrants::BottomLeft
+	);
+	// every custom marker has the length of the longer resolution markers
+	let marker_length = 15 * scale;
+	for (px, text) in ticks {
+		if has_grid {
+			for py in axis_max_pixel.1..axis_min_pixel.1 {
+				if !axis_style.is_grid_drawn(py.abs_diff(axis_origin_pixel.1), scale) {
+					continue;
+				}
+				put_thick_pixel_block(
+					canvas,
+					*px,
+					py,
+					scale,
+					axis_style.grid_thickness,
+					Spread::Columns,
+					axis_style.grid_colour,
+				);
+			}
+		}
+		if axis_style.has_ticks {
+			for n in 0..marker_length {
+				let py = if is_above {
+					axis_origin_pixel.1 - n
+				} else {
+					axis_origin_pixel.1 + n
+				};
+				put_thick_pixel_block(
+					canvas,
+					*px,
+					py,
+					scale,
+					axis_style.axis_thickness,
+					Spread::Columns,
+					axis_style.axis_colour,
+				);
 			}
 		}
+		if has_tick_labels {
+			let glyphs = create_glyphs(font_size, text, font);
+			let origin_y = if is_above {
+				axis_origin_pixel.1 - marker_length
+			} else {
+				axis_origin_pixel.1 + marker_length
+			};
+			let offset = get_x_axis_scale_label_offset(&glyphs, *px, origin_y, quadrants);
+			trace!("Drawing x-axis label {} at {:?}", text, offset);
+			draw_glyphs(canvas, axis_style.tick_label_colour, glyphs, offset);
+		}
 	}
+	Ok(())
 }
 
 /// Using glyph sizes calculate by how much the axis data label should be offset from an origin point
@@ -521,8 +905,9 @@ fn get_x_axis_scale_label_offset(
 	let height = get_maximum_height_of_glyphs(glyphs);
 	trace!("X-axis data label width: {}", width);
 	trace!("X-axis data label height: {}", height);
-	//TODO: there must be a better way than using a scale factor of 2?
-	let horizontal_position = origin_x - (width / 2);
+	// centre the label beneath its marker whatever the width of its text, a label wider than the space left
+	// of the marker is drawn from the edge of the canvas instead
+	let horizontal_position = origin_x.saturating_sub(width / 2);
 	trace!(
 		"X-axis data label horizontal offset: {}",
 		horizontal_position
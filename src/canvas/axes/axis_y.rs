@@ -1,43 +1,70 @@
 //! Draws the y-axis with labels and scale markers
 
 use image::{ImageBuffer, Rgba};
-use rusttype::PositionedGlyph;
+use rusttype::{Font, PositionedGlyph};
 use tracing::{debug, trace};
 
 use crate::{
 	canvas::{
-		glyphs::{create_glyphs, draw_glyphs, get_maximum_height_of_glyphs, get_width_of_glyphs},
+		axes::{
+			get_line_extents, get_minor_ticks, get_reversed_value, get_tick_label_every,
+			get_tick_label_size, put_thick_pixel_block, AxisStyle, LabelOrientation, MinorTicks,
+			Spread,
+		},
+		format::{format_tick_value, TickFormat},
+		glyphs::{
+			create_glyphs, draw_glyphs, draw_glyphs_vertically, get_maximum_height_of_glyphs,
+			get_vertical_size_of_glyphs, get_width_of_glyphs,
+		},
+		plot::{find_filled_triangle_pixels, find_triangle_corners},
+		put_pixel_block,
 		quadrants::Quadrants,
 		VHConsumedCanvasSpace, CANVAS_BORDER_PIXELS,
 	},
 	colours::*,
-	get_system_font,
+	error::PlotError,
 };
 
-/// Draws the y-axis label onto the canvas, returns how much new vertical-horizontal space has been consumed on the canvas
+/// Draws the y-axis label onto the canvas, returns how much new vertical-horizontal space has been consumed on the canvas.
+/// A `Vertical` label is turned to read upwards alongside the plot instead of being written above it
 #[allow(clippy::too_many_arguments)]
 pub fn build_y_axis_label(
 	canvas: &mut ImageBuffer<Rgba<u8>, Vec<u8>>,
 	label: String,
+	font: &Font,
 	font_size: f32,
 	quadrants: &Quadrants,
+	orientation: LabelOrientation,
 	vertical_pixels_from_top: u32,
 	horizontal_pixels_from_right: u32,
 	vertical_pixels_from_bottom: u32,
 	horizontal_pixels_from_left: u32,
-) -> VHConsumedCanvasSpace {
-	let font = get_system_font();
-	let glyphs: Vec<PositionedGlyph> = create_glyphs(font_size, label.as_str(), &font);
+	scale: u32,
+) -> Result<VHConsumedCanvasSpace, PlotError> {
+	let border = CANVAS_BORDER_PIXELS * scale;
+	let glyphs: Vec<PositionedGlyph> = create_glyphs(font_size, label.as_str(), font);
+	if orientation == LabelOrientation::Vertical {
+		return Ok(build_vertical_y_axis_label(
+			canvas,
+			glyphs,
+			quadrants,
+			vertical_pixels_from_top,
+			horizontal_pixels_from_right,
+			vertical_pixels_from_bottom,
+			horizontal_pixels_from_left,
+			border,
+		));
+	}
 	let width = get_width_of_glyphs(&glyphs);
 	let height = get_maximum_height_of_glyphs(&glyphs);
-	match quadrants {
+	Ok(match quadrants {
 		Quadrants::RightPair | Quadrants::TopRight => {
 			debug!("Placing y-axis label in top left corner");
-			let position: (u32, u32) = (CANVAS_BORDER_PIXELS, vertical_pixels_from_top);
+			let position: (u32, u32) = (border, vertical_pixels_from_top);
 			draw_glyphs(canvas, BLACK, glyphs, position);
 			VHConsumedCanvasSpace {
-				v_space_from_top: height + CANVAS_BORDER_PIXELS,
-				h_space_from_left: width + CANVAS_BORDER_PIXELS,
+				v_space_from_top: height + border,
+				h_space_from_left: width + border,
 				v_space_from_bottom: 0,
 				h_space_from_right: 0,
 			}
@@ -45,15 +72,15 @@ pub fn build_y_axis_label(
 		Quadrants::LeftPair | Quadrants::TopLeft => {
 			debug!("Placing y-axis label in top right corner");
 			let position: (u32, u32) = (
-				canvas.dimensions().0 - width - CANVAS_BORDER_PIXELS - horizontal_pixels_from_right,
+				canvas.dimensions().0 - width - border - horizontal_pixels_from_right,
 				vertical_pixels_from_top,
 			);
 			draw_glyphs(canvas, BLACK, glyphs, position);
 			VHConsumedCanvasSpace {
-				v_space_from_top: height + CANVAS_BORDER_PIXELS,
+				v_space_from_top: height + border,
 				h_space_from_left: 0,
 				v_space_from_bottom: 0,
-				h_space_from_right: width + CANVAS_BORDER_PIXELS,
+				h_space_from_right: width + border,
 			}
 		}
 		Quadrants::TopPair | Quadrants::AllQuadrants => {
@@ -64,9 +91,9 @@ pub fn build_y_axis_label(
 			);
 			draw_glyphs(canvas, BLACK, glyphs, position);
 			VHConsumedCanvasSpace {
-				v_space_from_top: height + CANVAS_BORDER_PIXELS,
+				v_space_from_top: height + border,
 				h_space_from_left: 0,
-				v_space_from_bottom: CANVAS_BORDER_PIXELS,
+				v_space_from_bottom: border,
 				h_space_from_right: 0,
 			}
 		}
@@ -81,21 +108,21 @@ pub fn build_y_axis_label(
 			VHConsumedCanvasSpace {
 				v_space_from_top: 0,
 				h_space_from_left: 0,
-				v_space_from_bottom: height + CANVAS_BORDER_PIXELS,
+				v_space_from_bottom: height + border,
 				h_space_from_right: 0,
 			}
 		}
 		Quadrants::BottomRight => {
 			debug!("Placing y-axis label in bottom left corner");
 			let position: (u32, u32) = (
-				horizontal_pixels_from_left + width + CANVAS_BORDER_PIXELS,
+				horizontal_pixels_from_left + width + border,
 				canvas.dimensions().1 - vertical_pixels_from_bottom - height,
 			);
 			draw_glyphs(canvas, BLACK, glyphs, position);
 			VHConsumedCanvasSpace {
 				v_space_from_top: 0,
-				h_space_from_left: width + CANVAS_BORDER_PIXELS,
-				v_space_from_bottom: height + CANVAS_BORDER_PIXELS,
+				h_space_from_left: width + border,
+				v_space_from_bottom: height + border,
 				h_space_from_right: 0,
 			}
 		}
@@ -109,8 +136,55 @@ pub fn build_y_axis_label(
 			VHConsumedCanvasSpace {
 				v_space_from_top: 0,
 				h_space_from_left: 0,
-				v_space_from_bottom: height + CANVAS_BORDER_PIXELS,
-				h_space_from_right: width + CANVAS_BORDER_PIXELS,
+				v_space_from_bottom: height + border,
+				h_space_from_right: width + border,
+			}
+		}
+	})
+}
+/// Draws the y-axis label reading upwards, centred down the left edge of the plot or down the right edge
+/// when the y-axis is drawn along the right of the plot. Only the height of the glyphs is consumed from the
+/// side rather than their width
+#[allow(clippy::too_many_arguments)]
+fn build_vertical_y_axis_label(
+	canvas: &mut ImageBuffer<Rgba<u8>, Vec<u8>>,
+	glyphs: Vec<PositionedGlyph>,
+	quadrants: &Quadrants,
+	vertical_pixels_from_top: u32,
+	horizontal_pixels_from_right: u32,
+	vertical_pixels_from_bottom: u32,
+	horizontal_pixels_from_left: u32,
+	border: u32,
+) -> VHConsumedCanvasSpace {
+	let (width, height) = get_vertical_size_of_glyphs(&glyphs);
+	let free_height = canvas
+		.dimensions()
+		.1
+		.saturating_sub(vertical_pixels_from_top + vertical_pixels_from_bottom);
+	let y = vertical_pixels_from_top + free_height.saturating_sub(height) / 2;
+	match quadrants {
+		Quadrants::LeftPair | Quadrants::TopLeft | Quadrants::BottomLeft => {
+			debug!("Placing vertical y-axis label along the right edge");
+			let x = canvas
+				.dimensions()
+				.0
+				.saturating_sub(horizontal_pixels_from_right + width);
+			draw_glyphs_vertically(canvas, BLACK, glyphs, (x, y));
+			VHConsumedCanvasSpace {
+				v_space_from_top: 0,
+				h_space_from_left: 0,
+				v_space_from_bottom: 0,
+				h_space_from_right: width + border,
+			}
+		}
+		_ => {
+			debug!("Placing vertical y-axis label along the left edge");
+			draw_glyphs_vertically(canvas, BLACK, glyphs, (horizontal_pixels_from_left, y));
+			VHConsumedCanvasSpace {
+				v_space_from_top: 0,
+				h_space_from_left: width + border,
+				v_space_from_bottom: 0,
+				h_space_from_right: 0,
 			}
 		}
 	}
@@ -129,15 +203,47 @@ pub fn draw_y_axis(
 	axis_min_pixel: (u32, u32),
 	axis_origin_pixel: (u32, u32),
 	axis_max_pixel: (u32, u32),
+	axis_style: AxisStyle,
+	scale: u32,
 ) {
 	debug!("Drawing y-axis");
+	let thickness = axis_style.axis_thickness;
+	// a thick axis is lengthened by its own half thickness so that it joins the x-axis squarely
+	let (before, after) = get_line_extents(thickness, scale);
 	// max to origin
-	for py in axis_max_pixel.1..=axis_origin_pixel.1 {
-		canvas.put_pixel(axis_origin_pixel.0, py, Rgba(BLACK));
+	for py in axis_max_pixel.1.saturating_sub(before)..=axis_origin_pixel.1 {
+		put_thick_pixel_block(
+			canvas,
+			axis_origin_pixel.0,
+			py,
+			scale,
+			thickness,
+			Spread::Columns,
+			axis_style.axis_colour,
+		);
 	}
 	// origin to min
-	for py in axis_origin_pixel.1..=axis_min_pixel.1 {
-		canvas.put_pixel(axis_origin_pixel.0, py, Rgba(BLACK));
+	for py in axis_origin_pixel.1..=(axis_min_pixel.1 + after) {
+		put_thick_pixel_block(
+			canvas,
+			axis_origin_pixel.0,
+			py,
+			scale,
+			thickness,
+			Spread::Columns,
+			axis_style.axis_colour,
+		);
+	}
+	if axis_style.has_arrows {
+		// the arrowhead is centred on the end of the axis and points up
+		let corners = find_triangle_corners(
+			(axis_origin_pixel.0, axis_max_pixel.1.saturating_sub(before)),
+			axis_style.get_arrow_size(scale) as f32,
+			(0.0, -1.0),
+		);
+		for (px, py) in find_filled_triangle_pixels(corners) {
+			put_pixel_block(canvas, px, py, 1, axis_style.axis_colour);
+		}
 	}
 }
 
@@ -151,12 +257,28 @@ pub fn draw_y_axis_scale_markings(
 	axis_max_pixel: (u32, u32),
 	y_axis_length: u32,
 	y_data_min_max_limits: (i32, i32),
+	font: &Font,
 	font_size: f32,
 	has_grid: bool,
+	minor_ticks: MinorTicks,
+	has_tick_labels: bool,
 	y_axis_resolution: u32,
-) {
-	let font = get_system_font();
+	tick_format: &TickFormat,
+	axis_style: AxisStyle,
+	scale: u32,
+) -> Result<(), PlotError> {
 	debug!("Drawing y-axis scale markers");
+	// every label of the axis shares the notation suited to its span
+	let tick_format =
+		tick_format.for_span(y_data_min_max_limits.1 as f64 - y_data_min_max_limits.0 as f64);
+	// the tallest label decides how many labels fit between the scale markers
+	let label_size = get_tick_label_size(
+		y_data_min_max_limits,
+		y_axis_resolution * if quadrants.is_y_paired() { 2 } else { 1 },
+		&tick_format,
+		font,
+		font_size,
+	);
 	// we find the appropriate layout to draw markers, this ensures scale resolution markers are correctly drawn across positive and negative axes
 	match quadrants {
 		// varients with a positive and negative y-axis
@@ -165,32 +287,57 @@ pub fn draw_y_axis_scale_markings(
 			// The number of pixels along the x-axis between each data label
 			// Halve axis length as resolution needs to fit both positive and negative wings
 			let subdivision_length = (y_axis_length / 2) / y_axis_resolution;
+			// Number of mini-markings between scale markers and the pixels between each
+			let (minor_count, marker_spacing) =
+				get_minor_ticks(subdivision_length, minor_ticks.count);
 			// The pixel length of each data label
-			let data_label_length = 5;
+			let data_label_length = 5 * scale;
 			// For writing a value for each data label we need to know the overall data size that corrpesonds to the axis
-			let value_range = y_data_min_max_limits.1 as f32 - y_data_min_max_limits.0 as f32;
+			let value_range =
+				(y_data_min_max_limits.1 as f64 - y_data_min_max_limits.0 as f64) as f32;
 			// Find how much a suddivsion is in terms of data value
 			let value_per_subdivision = (value_range / 2.0) / y_axis_resolution as f32;
+			// Write a label at every this many markers so that neighbouring labels do not overlap
+			let label_every = get_tick_label_every(
+				tick_format.label_every,
+				label_size.1,
+				label_size.1 / 2,
+				subdivision_length,
+			);
 			// If required draw the y part of a background grid as grey vertical lines
 			if has_grid {
 				trace!("Drawing grey background grid...");
 				// draw lines in positive space
 				for i in 1..(y_axis_resolution + 1) {
 					for px in axis_min_pixel.0..axis_max_pixel.0 {
-						canvas.put_pixel(
+						if !axis_style.is_grid_drawn(px.abs_diff(axis_origin_pixel.0), scale) {
+							continue;
+						}
+						put_thick_pixel_block(
+							canvas,
 							px,
 							axis_origin_pixel.1 - (i * subdivision_length),
-							Rgba(GREY),
+							scale,
+							axis_style.grid_thickness,
+							Spread::Rows,
+							axis_style.grid_colour,
 						);
 					}
 				}
 				// draw lines in negative space
 				for i in 1..(y_axis_resolution + 1) {
 					for px in axis_min_pixel.0..axis_max_pixel.0 {
-						canvas.put_pixel(
+						if !axis_style.is_grid_drawn(px.abs_diff(axis_origin_pixel.0), scale) {
+							continue;
+						}
+						put_thick_pixel_block(
+							canvas,
 							px,
 							axis_origin_pixel.1 + (i * subdivision_length),
-							Rgba(GREY),
+							scale,
+							axis_style.grid_thickness,
+							Spread::Rows,
+							axis_style.grid_colour,
 						);
 					}
 				}
@@ -201,25 +348,31 @@ pub fn draw_y_axis_scale_markings(
 			for i in 0..(y_axis_resolution + 1) {
 				// Draw each even section slightly longer
 				let label_length_scale = if i & 1 == 1 { 2 } else { 3 };
-				for n in 0..(data_label_length * label_length_scale) {
-					// So that scale markers are not drawn on the graph area itself check which quadrant type
-					// and flip if necessary so they are drawn in the available whitespace outside the axis
-					let px = if *quadrants == Quadrants::LeftPair {
-						axis_origin_pixel.0 + n
-					} else {
-						axis_origin_pixel.0 - n
-					};
-					let py = axis_origin_pixel.1 - (i * subdivision_length);
-					canvas.put_pixel(px, py, Rgba(BLACK));
+				if axis_style.has_ticks {
+					for n in 0..(data_label_length * label_length_scale) {
+						// So that scale markers are not drawn on the graph area itself check which quadrant type
+						// and flip if necessary so they are drawn in the available whitespace outside the axis
+						let px = if *quadrants == Quadrants::LeftPair {
+							axis_origin_pixel.0 + n
+						} else {
+							axis_origin_pixel.0 - n
+						};
+						let py = axis_origin_pixel.1 - (i * subdivision_length);
+						put_thick_pixel_block(
+							canvas,
+							px,
+							py,
+							scale,
+							axis_style.axis_thickness,
+							Spread::Rows,
+							axis_style.axis_colour,
+						);
+					}
 				}
-				// If there's enough space between each scale marker create mini-markings
-				// mini-marker varients
-				let marker_count = vec![9, 4, 3, 2, 1];
-				'outer_pos: for mc in marker_count.iter() {
-					// (mc + 1) because there needs to be a gap between final mini-marker and scale marker
-					if subdivision_length % (mc + 1) == 0 && i < y_axis_resolution {
-						for j in 1..=*mc {
-							let marker_spacing = subdivision_length / (mc + 1);
+				// Mini-markings between this scale marker and the next
+				if i < y_axis_resolution {
+					for j in 1..=minor_count {
+						if axis_style.has_ticks {
 							for n in 0..data_label_length {
 								// So that scale markers are not drawn on the graph area itself check which quadrant type
 								// and flip if necessary so they are drawn in the available whitespace outside the axis
@@ -230,47 +383,93 @@ pub fn draw_y_axis_scale_markings(
 								};
 								let py = axis_origin_pixel.1
 									- ((i * subdivision_length) + (j * marker_spacing));
-								canvas.put_pixel(px, py, Rgba(BLACK));
+								put_thick_pixel_block(
+									canvas,
+									px,
+									py,
+									scale,
+									axis_style.axis_thickness,
+									Spread::Rows,
+									axis_style.axis_colour,
+								);
+							}
+						}
+						// faint gridlines at the mini-markings
+						if minor_ticks.has_grid {
+							let py = axis_origin_pixel.1
+								- ((i * subdivision_length) + (j * marker_spacing));
+							for px in axis_min_pixel.0..axis_max_pixel.0 {
+								if !axis_style
+									.is_grid_drawn(px.abs_diff(axis_origin_pixel.0), scale)
+								{
+									continue;
+								}
+								put_thick_pixel_block(
+									canvas,
+									px,
+									py,
+									scale,
+									axis_style.grid_thickness,
+									Spread::Rows,
+									axis_style.minor_grid_colour,
+								);
 							}
 						}
-						break 'outer_pos;
 					}
 				}
 				// Draw the data label text
+				if !has_tick_labels || i % label_every != 0 {
+					continue;
+				}
 				// For AllQuadrants don't draw the origin marker text otherwise it sits on the axis obscurring text
-				if i == 0 && *quadrants == Quadrants::AllQuadrants {continue}
-				let text = (value_per_subdivision * i as f32).to_string();
-				let glyphs = create_glyphs(font_size, &text, &font);
+				if i == 0 && *quadrants == Quadrants::AllQuadrants {
+					continue;
+				}
+				let text = format_tick_value(
+					get_reversed_value(
+						(value_per_subdivision * i as f32) as f64,
+						y_data_min_max_limits,
+						axis_style.is_y_reversed,
+					),
+					value_per_subdivision as f64,
+					&tick_format,
+				);
+				let glyphs = create_glyphs(font_size, &text, font);
 				let origin_x = axis_origin_pixel.0 - (data_label_length * label_length_scale);
 				let origin_y = axis_origin_pixel.1 - (i * subdivision_length);
-				let offset =
-					get_y_axis_scale_label_offset(&glyphs, origin_x, origin_y, quadrants);
+				let offset = get_y_axis_scale_label_offset(&glyphs, origin_x, origin_y, quadrants);
 				trace!("Drawing y-axis label {} at {:?}", text, offset);
-				draw_glyphs(canvas, BLACK, glyphs, offset);
+				draw_glyphs(canvas, axis_style.tick_label_colour, glyphs, offset);
 			}
 			// markers in negative space
 			for i in 0..(y_axis_resolution + 1) {
 				// Draw each even section slightly longer
 				let label_length_scale = if i & 1 == 1 { 2 } else { 3 };
-				for n in 0..(data_label_length * label_length_scale) {
-					// So that scale markers are not drawn on the graph area itself check which quadrant type
-					// and flip if necessary so they are drawn in the available whitespace outside the axis
-					let px = if *quadrants == Quadrants::LeftPair {
-						axis_origin_pixel.0 + n
-					} else {
-						axis_origin_pixel.0 - n
-					};
-					let py = axis_origin_pixel.1 + (i * subdivision_length);
-					canvas.put_pixel(px, py, Rgba(BLACK));
+				if axis_style.has_ticks {
+					for n in 0..(data_label_length * label_length_scale) {
+						// So that scale markers are not drawn on the graph area itself check which quadrant type
+						// and flip if necessary so they are drawn in the available whitespace outside the axis
+						let px = if *quadrants == Quadrants::LeftPair {
+							axis_origin_pixel.0 + n
+						} else {
+							axis_origin_pixel.0 - n
+						};
+						let py = axis_origin_pixel.1 + (i * subdivision_length);
+						put_thick_pixel_block(
+							canvas,
+							px,
+							py,
+							scale,
+							axis_style.axis_thickness,
+							Spread::Rows,
+							axis_style.axis_colour,
+						);
+					}
 				}
-				// If there's enough space between each scale marker create mini-markings
-				// mini-marker varients
-				let marker_count = vec![9, 4, 3, 2, 1];
-				'outer_neg: for mc in marker_count.iter() {
-					// (mc + 1) because there needs to be a gap between final mini-marker and scale marker
-					if subdivision_length % (mc + 1) == 0 && i < y_axis_resolution {
-						for j in 1..=*mc {
-							let marker_spacing = subdivision_length / (mc + 1);
+				// Mini-markings between this scale marker and the next
+				if i < y_axis_resolution {
+					for j in 1..=minor_count {
+						if axis_style.has_ticks {
 							for n in 0..data_label_length {
 								// So that scale markers are not drawn on the graph area itself check which quadrant type
 								// and flip if necessary so they are drawn in the available whitespace outside the axis
@@ -281,27 +480,71 @@ pub fn draw_y_axis_scale_markings(
 								};
 								let py = axis_origin_pixel.1
 									+ ((i * subdivision_length) + (j * marker_spacing));
-								canvas.put_pixel(px, py, Rgba(BLACK));
+								put_thick_pixel_block(
+									canvas,
+									px,
+									py,
+									scale,
+									axis_style.axis_thickness,
+									Spread::Rows,
+									axis_style.axis_colour,
+								);
+							}
+						}
+						// faint gridlines at the mini-markings
+						if minor_ticks.has_grid {
+							let py = axis_origin_pixel.1
+								+ ((i * subdivision_length) + (j * marker_spacing));
+							for px in axis_min_pixel.0..axis_max_pixel.0 {
+								if !axis_style
+									.is_grid_drawn(px.abs_diff(axis_origin_pixel.0), scale)
+								{
+									continue;
+								}
+								put_thick_pixel_block(
+									canvas,
+									px,
+									py,
+									scale,
+									axis_style.grid_thickness,
+									Spread::Rows,
+									axis_style.minor_grid_colour,
+								);
 							}
 						}
-						break 'outer_neg;
 					}
 				}
 				// Draw the data label text
+				if !has_tick_labels || i % label_every != 0 {
+					continue;
+				}
 				// For RightPair don't draw the origin marker text otherwise it sits on the axis obscurring text
-				if i == 0 && *quadrants == Quadrants::RightPair {continue}
+				if i == 0 && *quadrants == Quadrants::RightPair {
+					continue;
+				}
 				// For LeftPair don't draw the origin marker text otherwise it sits on the axis obscurring text
-				if i == 0 && *quadrants == Quadrants::LeftPair {continue}
+				if i == 0 && *quadrants == Quadrants::LeftPair {
+					continue;
+				}
 				// For AllQuadrants don't draw the origin marker text otherwise it sits on the axis obscurring text
-				if i == 0 && *quadrants == Quadrants::AllQuadrants {continue}
-				let text = (-value_per_subdivision * i as f32).to_string();
-				let glyphs = create_glyphs(font_size, &text, &font);
+				if i == 0 && *quadrants == Quadrants::AllQuadrants {
+					continue;
+				}
+				let text = format_tick_value(
+					get_reversed_value(
+						(-value_per_subdivision * i as f32) as f64,
+						y_data_min_max_limits,
+						axis_style.is_y_reversed,
+					),
+					value_per_subdivision as f64,
+					&tick_format,
+				);
+				let glyphs = create_glyphs(font_size, &text, font);
 				let origin_x = axis_origin_pixel.0 - (data_label_length * label_length_scale);
 				let origin_y = axis_origin_pixel.1 + (i * subdivision_length);
-				let offset =
-					get_y_axis_scale_label_offset(&glyphs, origin_x, origin_y, quadrants);
+				let offset = get_y_axis_scale_label_offset(&glyphs, origin_x, origin_y, quadrants);
 				trace!("Drawing y-axis label {} at {:?}", text, offset);
-				draw_glyphs(canvas, BLACK, glyphs, offset);
+				draw_glyphs(canvas, axis_style.tick_label_colour, glyphs, offset);
 			}
 		}
 		// varients with just a positive y-axis
@@ -309,21 +552,39 @@ pub fn draw_y_axis_scale_markings(
 			// Subdivide the y-axis length into a number of points we can draw labels at.
 			// The number of pixels along the x-axis between each data label
 			let subdivision_length = y_axis_length / y_axis_resolution;
+			// Number of mini-markings between scale markers and the pixels between each
+			let (minor_count, marker_spacing) =
+				get_minor_ticks(subdivision_length, minor_ticks.count);
 			// The pixel length of each data label
-			let data_label_length = 5;
+			let data_label_length = 5 * scale;
 			// For writing a value for each data label we need to know the overall data size that corrpesonds to the axis
-			let value_range = y_data_min_max_limits.1 as f32 - y_data_min_max_limits.0 as f32;
+			let value_range =
+				(y_data_min_max_limits.1 as f64 - y_data_min_max_limits.0 as f64) as f32;
 			// Find how much a suddivsion is in terms of data value
 			let value_per_subdivision = value_range / y_axis_resolution as f32;
+			// Write a label at every this many markers so that neighbouring labels do not overlap
+			let label_every = get_tick_label_every(
+				tick_format.label_every,
+				label_size.1,
+				label_size.1 / 2,
+				subdivision_length,
+			);
 			// If required draw the y part of a background grid as grey vertical lines
 			if has_grid {
 				trace!("Drawing grey background grid...");
 				for i in 0..(y_axis_resolution + 1) {
 					for px in axis_min_pixel.0..axis_max_pixel.0 {
-						canvas.put_pixel(
+						if !axis_style.is_grid_drawn(px.abs_diff(axis_origin_pixel.0), scale) {
+							continue;
+						}
+						put_thick_pixel_block(
+							canvas,
 							px,
 							axis_min_pixel.1 - (i * subdivision_length),
-							Rgba(GREY),
+							scale,
+							axis_style.grid_thickness,
+							Spread::Rows,
+							axis_style.grid_colour,
 						);
 					}
 				}
@@ -333,25 +594,31 @@ pub fn draw_y_axis_scale_markings(
 			for i in 0..(y_axis_resolution + 1) {
 				// Draw each even section slightly longer
 				let label_length_scale = if i & 1 == 1 { 2 } else { 3 };
-				for n in 0..(data_label_length * label_length_scale) {
-					// So that scale markers are not drawn on the graph area itself check which quadrant type
-					// and flip if necessary so they are drawn in the available whitespace outside the axis
-					let px = if *quadrants == Quadrants::TopLeft {
-						axis_origin_pixel.0 + n
-					} else {
-						axis_origin_pixel.0 - n
-					};
-					let py = axis_min_pixel.1 - (i * subdivision_length);
-					canvas.put_pixel(px, py, Rgba(BLACK));
+				if axis_style.has_ticks {
+					for n in 0..(data_label_length * label_length_scale) {
+						// So that scale markers are not drawn on the graph area itself check which quadrant type
+						// and flip if necessary so they are drawn in the available whitespace outside the axis
+						let px = if *quadrants == Quadrants::TopLeft {
+							axis_origin_pixel.0 + n
+						} else {
+							axis_origin_pixel.0 - n
+						};
+						let py = axis_min_pixel.1 - (i * subdivision_length);
+						put_thick_pixel_block(
+							canvas,
+							px,
+							py,
+							scale,
+							axis_style.axis_thickness,
+							Spread::Rows,
+							axis_style.axis_colour,
+						);
+					}
 				}
-				// If there's enough space between each scale marker create mini-markings
-				// mini-marker varients
-				let marker_count = vec![9, 4, 3, 2, 1];
-				'outer: for mc in marker_count.iter() {
-					// (mc + 1) because there needs to be a gap between final mini-marker and scale marker
-					if subdivision_length % (mc + 1) == 0 && i < y_axis_resolution {
-						for j in 1..=*mc {
-							let marker_spacing = subdivision_length / (mc + 1);
+				// Mini-markings between this scale marker and the next
+				if i < y_axis_resolution {
+					for j in 1..=minor_count {
+						if axis_style.has_ticks {
 							for n in 0..data_label_length {
 								// So that scale markers are not drawn on the graph area itself check which quadrant type
 								// and flip if necessary so they are drawn in the available whitespace outside the axis
@@ -362,18 +629,59 @@ pub fn draw_y_axis_scale_markings(
 								};
 								let py = axis_min_pixel.1
 									- ((i * subdivision_length) + (j * marker_spacing));
-								canvas.put_pixel(px, py, Rgba(BLACK));
+								put_thick_pixel_block(
+									canvas,
+									px,
+									py,
+									scale,
+									axis_style.axis_thickness,
+									Spread::Rows,
+									axis_style.axis_colour,
+								);
+							}
+						}
+						// faint gridlines at the mini-markings
+						if minor_ticks.has_grid {
+							let py = axis_min_pixel.1
+								- ((i * subdivision_length) + (j * marker_spacing));
+							for px in axis_min_pixel.0..axis_max_pixel.0 {
+								if !axis_style
+									.is_grid_drawn(px.abs_diff(axis_origin_pixel.0), scale)
+								{
+									continue;
+								}
+								put_thick_pixel_block(
+									canvas,
+									px,
+									py,
+									scale,
+									axis_style.grid_thickness,
+									Spread::Rows,
+									axis_style.minor_grid_colour,
+								);
 							}
 						}
-						break 'outer;
 					}
 				}
 				// Draw the data label text
+				if !has_tick_labels || i % label_every != 0 {
+					continue;
+				}
 				// For TopPair don't draw the origin marker text otherwise it sits on x-axis
-				if i == 0 && *quadrants == Quadrants::TopPair {continue}
-				let text = (y_data_min_max_limits.0 as f32 + (value_per_subdivision * i as f32))
-					.to_string();
-				let glyphs = create_glyphs(font_size, &text, &font);
+				if i == 0 && *quadrants == Quadrants::TopPair {
+					continue;
+				}
+				let text = format_tick_value(
+					get_reversed_value(
+						(y_data_min_max_limits.0 as f32 + (value_per_subdivision * i as f32))
+							as f64,
+						y_data_min_max_limits,
+						axis_style.is_y_reversed,
+					),
+					value_per_subdivision as f64,
+					&tick_format,
+				);
+				let glyphs = create_glyphs(font_size, &text, font);
 				// So that scale markers are not drawn on the graph area itself check which quadrant type
 				// and flip if necessary so they are drawn in the available whitespace outside the axis
 				let origin_x = if *quadrants == Quadrants::TopLeft {
@@ -384,7 +692,7 @@ pub fn draw_y_axis_scale_markings(
 				let origin_y = axis_min_pixel.1 - (i * subdivision_length);
 				let offset = get_y_axis_scale_label_offset(&glyphs, origin_x, origin_y, quadrants);
 				trace!("Drawing y-axis label {} at {:?}", text, offset);
-				draw_glyphs(canvas, BLACK, glyphs, offset);
+				draw_glyphs(canvas, axis_style.tick_label_colour, glyphs, offset);
 			}
 		}
 		// varients with just a negative y-axis
@@ -393,22 +701,40 @@ pub fn draw_y_axis_scale_markings(
 			// The number of pixels along the x-axis between each data label
 			// Halve axis length as resolution needs to fit both positive and negative wings
 			let subdivision_length = y_axis_length / y_axis_resolution;
+			// Number of mini-markings between scale markers and the pixels between each
+			let (minor_count, marker_spacing) =
+				get_minor_ticks(subdivision_length, minor_ticks.count);
 			// The pixel length of each data label
-			let data_label_length = 5;
+			let data_label_length = 5 * scale;
 			// For writing a value for each data label we need to know the overall data size that corrpesonds to the axis
-			let value_range = y_data_min_max_limits.1 as f32 - y_data_min_max_limits.0 as f32;
+			let value_range =
+				(y_data_min_max_limits.1 as f64 - y_data_min_max_limits.0 as f64) as f32;
 			// Find how much a suddivsion is in terms of data value
 			let value_per_subdivision = value_range / y_axis_resolution as f32;
+			// Write a label at every this many markers so that neighbouring labels do not overlap
+			let label_every = get_tick_label_every(
+				tick_format.label_every,
+				label_size.1,
+				label_size.1 / 2,
+				subdivision_length,
+			);
 			// If required draw the y part of a background grid as grey vertical lines
 			if has_grid {
 				trace!("Drawing grey background grid...");
 				// draw lines in negative space
 				for i in 0..(y_axis_resolution + 1) {
 					for px in axis_min_pixel.0..axis_max_pixel.0 {
-						canvas.put_pixel(
+						if !axis_style.is_grid_drawn(px.abs_diff(axis_origin_pixel.0), scale) {
+							continue;
+						}
+						put_thick_pixel_block(
+							canvas,
 							px,
 							axis_origin_pixel.1 + (i * subdivision_length),
-							Rgba(GREY),
+							scale,
+							axis_style.grid_thickness,
+							Spread::Rows,
+							axis_style.grid_colour,
 						);
 					}
 				}
@@ -419,25 +745,31 @@ pub fn draw_y_axis_scale_markings(
 			for i in 0..(y_axis_resolution + 1) {
 				// Draw each even section slightly longer
 				let label_length_scale = if i & 1 == 1 { 2 } else { 3 };
-				for n in 0..(data_label_length * label_length_scale) {
-					// So that scale markers are not drawn on the graph area itself check which quadrant type
-					// and flip if necessary so they are drawn in the available whitespace outside the axis
-					let px = if *quadrants == Quadrants::BottomLeft {
-						axis_origin_pixel.0 + n
-					} else {
-						axis_origin_pixel.0 - n
-					};
-					let py = axis_origin_pixel.1 + (i * subdivision_length);
-					canvas.put_pixel(px, py, Rgba(BLACK));
+				if axis_style.has_ticks {
+					for n in 0..(data_label_length * label_length_scale) {
+						// So that scale markers are not drawn on the graph area itself check which quadrant type
+						// and flip if necessary so they are drawn in the available whitespace outside the axis
+						let px = if *quadrants == Quadrants::BottomLeft {
+							axis_origin_pixel.0 + n
+						} else {
+							axis_origin_pixel.0 - n
+						};
+						let py = axis_origin_pixel.1 + (i * subdivision_length);
+						put_thick_pixel_block(
+							canvas,
+							px,
+							py,
+							scale,
+							axis_style.axis_thickness,
+							Spread::Rows,
+							axis_style.axis_colour,
+						);
+					}
 				}
-				// If there's enough space between each scale marker create mini-markings
-				// mini-marker varients
-				let marker_count = vec![9, 4, 3, 2, 1];
-				'outer_b_neg: for mc in marker_count.iter() {
-					// (mc + 1) because there needs to be a gap between final mini-marker and scale marker
-					if subdivision_length % (mc + 1) == 0 && i < y_axis_resolution {
-						for j in 1..=*mc {
-							let marker_spacing = subdivision_length / (mc + 1);
+				// Mini-markings between this scale marker and the next
+				if i < y_axis_resolution {
+					for j in 1..=minor_count {
+						if axis_style.has_ticks {
 							for n in 0..data_label_length {
 								// So that scale markers are not drawn on the graph area itself check which quadrant type
 								// and flip if necessary so they are drawn in the available whitespace outside the axis
@@ -448,17 +780,58 @@ pub fn draw_y_axis_scale_markings(
 								};
 								let py = axis_origin_pixel.1
 									+ ((i * subdivision_length) + (j * marker_spacing));
-								canvas.put_pixel(px, py, Rgba(BLACK));
+								put_thick_pixel_block(
+									canvas,
+									px,
+									py,
+									scale,
+									axis_style.axis_thickness,
+									Spread::Rows,
+									axis_style.axis_colour,
+								);
+							}
+						}
+						// faint gridlines at the mini-markings
+						if minor_ticks.has_grid {
+							let py = axis_origin_pixel.1
+								+ ((i * subdivision_length) + (j * marker_spacing));
+							for px in axis_min_pixel.0..axis_max_pixel.0 {
+								if !axis_style
+									.is_grid_drawn(px.abs_diff(axis_origin_pixel.0), scale)
+								{
+									continue;
+								}
+								put_thick_pixel_block(
+									canvas,
+									px,
+									py,
+									scale,
+									axis_style.grid_thickness,
+									Spread::Rows,
+									axis_style.minor_grid_colour,
+								);
 							}
 						}
-						break 'outer_b_neg;
 					}
 				}
 				// Draw the data label text
+				if !has_tick_labels || i % label_every != 0 {
+					continue;
+				}
 				// For BottomPair don't draw the origin marker text otherwise it sits on the axis obscurring text
-				if i == 0 && *quadrants == Quadrants::BottomPair {continue}
-				let text = (-value_per_subdivision * i as f32).to_string();
-				let glyphs = create_glyphs(font_size, &text, &font);
+				if i == 0 && *quadrants == Quadrants::BottomPair {
+					continue;
+				}
+				let text = format_tick_value(
+					get_reversed_value(
+						(-value_per_subdivision * i as f32) as f64,
+						y_data_min_max_limits,
+						axis_style.is_y_reversed,
+					),
+					value_per_subdivision as f64,
+					&tick_format,
+				);
+				let glyphs = create_glyphs(font_size, &text, font);
 				// So that scale markers are not drawn on the graph area itself check which quadrant type
 				// and flip if necessary so they are drawn in the available whitespace outside the axis
 				let origin_x = if *quadrants == Quadrants::BottomLeft {
@@ -467,13 +840,88 @@ pub fn draw_y_axis_scale_markings(
 					axis_origin_pixel.0 - (data_label_length * label_length_scale)
 				};
 				let origin_y = axis_origin_pixel.1 + (i * subdivision_length);
-				let offset =
-					get_y_axis_scale_label_offset(&glyphs, origin_x, origin_y, quadrants);
+				let offset = get_y_axis_scale_label_offset(&glyphs, origin_x, origin_y, quadrants);
 				trace!("Drawing y-axis label {} at {:?}", text, offset);
-				draw_glyphs(canvas, BLACK, glyphs, offset);
+				draw_glyphs(canvas, axis_style.tick_label_colour, glyphs, offset);
+			}
+		}
+	}
+	Ok(())
+}
+
+/// Draws a scale marker, its label and optionally a gridline at each of `ticks`, pixel rows paired with
+/// the text of their label, in place of the markings spread by the resolution of the axis
+#[allow(clippy::too_many_arguments)]
+pub fn draw_y_axis_custom_ticks(
+	quadrants: &Quadrants,
+	canvas: &mut ImageBuffer<Rgba<u8>, Vec<u8>>,
+	axis_min_pixel: (u32, u32),
+	axis_origin_pixel: (u32, u32),
+	axis_max_pixel: (u32, u32),
+	ticks: &[(u32, String)],
+	font: &Font,
+	font_size: f32,
+	has_grid: bool,
+	has_tick_labels: bool,
+	axis_style: AxisStyle,
+	scale: u32,
+) -> Result<(), PlotError> {
+	debug!("Drawing custom y-axis scale markings");
+	// markers are drawn in the whitespace right of the axis when the plot extends left of it
+	let is_right = matches!(
+		quadrants,
+		Quadrants::LeftPair | Quadrants::TopLeft | Quadrants::BottomLeft
+	);
+	// every custom marker has the length of the longer resolution markers
+	let marker_length = 15 * scale;
+	for (py, text) in ticks {
+		if has_grid {
+			for px in axis_min_pixel.0..axis_max_pixel.0 {
+				if !axis_style.is_grid_drawn(px.abs_diff(axis_origin_pixel.0), scale) {
+					continue;
+				}
+				put_thick_pixel_block(
+					canvas,
+					px,
+					*py,
+					scale,
+					axis_style.grid_thickness,
+					Spread::Rows,
+					axis_style.grid_colour,
+				);
 			}
 		}
+		if axis_style.has_ticks {
+			for n in 0..marker_length {
+				let px = if is_right {
+					axis_origin_pixel.0 + n
+				} else {
+					axis_origin_pixel.0 - n
+				};
+				put_thick_pixel_block(
+					canvas,
+					px,
+					*py,
+					scale,
+					axis_style.axis_thickness,
+					Spread::Rows,
+					axis_style.axis_colour,
+				);
+			}
+		}
+		if has_tick_labels {
+			let glyphs = create_glyphs(font_size, text, font);
+			let origin_x = if is_right {
+				axis_origin_pixel.0 + marker_length
+			} else {
+				axis_origin_pixel.0 - marker_length
+			};
+			let offset = get_y_axis_scale_label_offset(&glyphs, origin_x, *py, quadrants);
+			trace!("Drawing y-axis label {} at {:?}", text, offset);
+			draw_glyphs(canvas, axis_style.tick_label_colour, glyphs, offset);
+		}
 	}
+	Ok(())
 }
 
 /// Using glyph sizes calculate by how much the axis data label should be offset from an origin point
@@ -491,7 +939,8 @@ fn get_y_axis_scale_label_offset(
 		Quadrants::TopLeft => origin_x + width,
 		Quadrants::BottomLeft => origin_x + width,
 		Quadrants::LeftPair => origin_x + (width * 3),
-		_ => origin_x - width,
+		// a label wider than the space left of its marker is drawn from the edge of the canvas
+		_ => origin_x.saturating_sub(width),
 	};
 	trace!(
 		"Y-axis data label horizontal offset: {}",
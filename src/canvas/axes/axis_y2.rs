@@ -0,0 +1,185 @@
+//! Methods for drawing a secondary y-axis, with its own scale and label, along the right edge of the plot
+
+use image::{ImageBuffer, Rgba};
+use rusttype::{Font, PositionedGlyph};
+use tracing::{debug, trace};
+
+use crate::{
+	canvas::{
+		axes::{get_line_extents, put_thick_pixel_block, AxisStyle, Spread},
+		format::{format_tick_value, TickFormat},
+		glyphs::{create_glyphs, draw_glyphs, get_maximum_height_of_glyphs, get_width_of_glyphs},
+		quadrants::Quadrants,
+		VHConsumedCanvasSpace, CANVAS_BORDER_PIXELS,
+	},
+	colours::BLACK,
+	error::PlotError,
+};
+
+/// The values written beside each scale marker of the secondary y-axis, from the bottom of the axis upward
+fn get_y2_axis_tick_values(
+	y2_data_min_max_limits: (i32, i32),
+	y2_axis_resolution: u32,
+	tick_format: &TickFormat,
+) -> Vec<String> {
+	let span = y2_data_min_max_limits.1 as f64 - y2_data_min_max_limits.0 as f64;
+	let tick_format = tick_format.for_span(span);
+	let step = span / y2_axis_resolution as f64;
+	(0..=y2_axis_resolution)
+		.map(|i| {
+			format_tick_value(
+				y2_data_min_max_limits.0 as f64 + step * i as f64,
+				step,
+				&tick_format,
+			)
+		})
+		.collect()
+}
+/// Find the space needed to the right of the plot for the scale markers of the secondary y-axis and,
+/// optionally, their values
+#[allow(clippy::too_many_arguments)]
+pub fn get_y2_axis_space(
+	font: &Font,
+	font_size: f32,
+	y2_data_min_max_limits: (i32, i32),
+	y2_axis_resolution: u32,
+	has_tick_labels: bool,
+	tick_format: &TickFormat,
+	axis_style: &AxisStyle,
+	scale: u32,
+) -> Result<VHConsumedCanvasSpace, PlotError> {
+	// The longest scale marker is three times the base marker length of 5 pixels
+	let marker_length = 15 * scale;
+	let text_width = if has_tick_labels {
+		get_y2_axis_tick_values(y2_data_min_max_limits, y2_axis_resolution, tick_format)
+			.iter()
+			.map(|text| get_width_of_glyphs(&create_glyphs(font_size, text, font)))
+			.max()
+			.unwrap_or(0)
+	} else {
+		0
+	};
+	let (_, after) = get_line_extents(axis_style.axis_thickness, scale);
+	let h_space_from_right = if has_tick_labels || axis_style.has_ticks {
+		marker_length + text_width + after
+	} else {
+		after
+	};
+	Ok(VHConsumedCanvasSpace {
+		v_space_from_top: 0,
+		h_space_from_right,
+		v_space_from_bottom: 0,
+		h_space_from_left: 0,
+	})
+}
+/// Draws the secondary y-axis label in the top right corner on the same row as the y-axis label, returns
+/// how much new vertical-horizontal space has been consumed on the canvas
+#[allow(clippy::too_many_arguments)]
+pub fn build_y2_axis_label(
+	canvas: &mut ImageBuffer<Rgba<u8>, Vec<u8>>,
+	label: &str,
+	font: &Font,
+	font_size: f32,
+	quadrants: &Quadrants,
+	vertical_pixels_from_top: u32,
+	horizontal_pixels_from_right: u32,
+	scale: u32,
+) -> Result<VHConsumedCanvasSpace, PlotError> {
+	let border = CANVAS_BORDER_PIXELS * scale;
+	let glyphs: Vec<PositionedGlyph> = create_glyphs(font_size, label, font);
+	let width = get_width_of_glyphs(&glyphs);
+	let height = get_maximum_height_of_glyphs(&glyphs);
+	debug!("Placing secondary y-axis label in top right corner");
+	let position: (u32, u32) = (
+		canvas
+			.dimensions()
+			.0
+			.saturating_sub(width + horizontal_pixels_from_right),
+		vertical_pixels_from_top,
+	);
+	draw_glyphs(canvas, BLACK, glyphs, position);
+	// the y-axis label already reserves the top row when it is placed there
+	let is_row_reserved = matches!(
+		quadrants,
+		Quadrants::TopRight | Quadrants::RightPair | Quadrants::TopPair | Quadrants::AllQuadrants
+	);
+	Ok(VHConsumedCanvasSpace {
+		v_space_from_top: if is_row_reserved { 0 } else { height + border },
+		h_space_from_right: 0,
+		v_space_from_bottom: 0,
+		h_space_from_left: 0,
+	})
+}
+/// Draws the secondary y-axis along the right edge of the plot with its scale markers and their values.
+/// The axis is divided into `y2_axis_resolution` parts spanning `y2_data_min_max_limits` from the bottom
+/// of the plot to the top
+#[allow(clippy::too_many_arguments)]
+pub fn draw_y2_axis(
+	canvas: &mut ImageBuffer<Rgba<u8>, Vec<u8>>,
+	axis_min_pixel: (u32, u32),
+	axis_max_pixel: (u32, u32),
+	y2_data_min_max_limits: (i32, i32),
+	y2_axis_resolution: u32,
+	font: &Font,
+	font_size: f32,
+	has_tick_labels: bool,
+	tick_format: &TickFormat,
+	axis_style: AxisStyle,
+	scale: u32,
+) -> Result<(), PlotError> {
+	debug!("Drawing secondary y-axis");
+	let thickness = axis_style.axis_thickness;
+	let (before, after) = get_line_extents(thickness, scale);
+	let px = axis_max_pixel.0;
+	for py in axis_max_pixel.1.saturating_sub(before)..=(axis_min_pixel.1 + after) {
+		put_thick_pixel_block(
+			canvas,
+			px,
+			py,
+			scale,
+			thickness,
+			Spread::Columns,
+			axis_style.axis_colour,
+		);
+	}
+	let y_axis_length = (axis_min_pixel.1 - axis_max_pixel.1) as u64;
+	let mut values =
+		get_y2_axis_tick_values(y2_data_min_max_limits, y2_axis_resolution, tick_format);
+	// a reversed axis counts down from the bottom of the plot
+	if axis_style.is_y_reversed {
+		values.reverse();
+	}
+	// The pixel length of each data label
+	let data_label_length = 5 * scale;
+	for (i, text) in values.iter().enumerate() {
+		// markers are rounded to the nearest pixel as the axis is fitted to the resolution of the y-axis
+		let py = axis_min_pixel.1
+			- ((y_axis_length * i as u64 + y2_axis_resolution as u64 / 2)
+				/ y2_axis_resolution as u64) as u32;
+		// Draw each even section slightly longer
+		let label_length_scale = if i & 1 == 1 { 2 } else { 3 };
+		let marker_length = data_label_length * label_length_scale;
+		if axis_style.has_ticks {
+			for n in 0..marker_length {
+				put_thick_pixel_block(
+					canvas,
+					px + after + n,
+					py,
+					scale,
+					thickness,
+					Spread::Rows,
+					axis_style.axis_colour,
+				);
+			}
+		}
+		if !has_tick_labels {
+			continue;
+		}
+		let glyphs = create_glyphs(font_size, text, font);
+		let height = get_maximum_height_of_glyphs(&glyphs);
+		let offset = (px + after + marker_length, py.saturating_sub(height / 2));
+		trace!("Drawing secondary y-axis label {} at {:?}", text, offset);
+		draw_glyphs(canvas, axis_style.tick_label_colour, glyphs, offset);
+	}
+	Ok(())
+}
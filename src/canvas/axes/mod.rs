@@ -1,20 +1,48 @@
 //! Methods for creating and labeling axes and determining dimensions
 
 use image::{ImageBuffer, Rgba};
+use rusttype::Font;
+use serde::{Deserialize, Serialize};
+use tracing::{trace, warn};
 
 use self::{
-	axis_x::{draw_x_axis, draw_x_axis_scale_markings, get_x_axis_pixel_length},
-	axis_y::{draw_y_axis, draw_y_axis_scale_markings, get_y_axis_pixel_length},
+	axis_x::{
+		draw_x_axis, draw_x_axis_custom_ticks, draw_x_axis_scale_markings, get_x_axis_pixel_length,
+	},
+	axis_y::{
+		draw_y_axis, draw_y_axis_custom_ticks, draw_y_axis_scale_markings, get_y_axis_pixel_length,
+	},
 };
 
-use super::quadrants::Quadrants;
+use super::{
+	format::{format_tick_value, TickFormat},
+	glyphs::{create_glyphs, get_maximum_height_of_glyphs, get_width_of_glyphs},
+	put_pixel_block,
+	quadrants::Quadrants,
+	VHConsumedCanvasSpace,
+};
+use crate::{
+	colours::{BLACK, DARK_GREY, GREY, LIGHT_GREY},
+	error::PlotError,
+};
 
 pub mod axis_x;
 pub mod axis_y;
+pub mod axis_y2;
 
 /// Find the pixel pair which pinpoints the maxmium length and height of the axes. Resolutions are
 /// used to ensure that the length of each axis is a natural scale factor of the resolution. This
-/// allows for accurately plotting data points
+/// allows for accurately plotting data points.
+///
+/// Each axis is shrunk by the fewest pixels which give it a valid length, see
+/// [`fit_axis_to_resolution`], and the pixels removed are split evenly between both ends so that the
+/// plot stays centred within the available space regardless of which quadrants are drawn.
+///
+/// `x_axis_pixel_bounds` imposes the left and right pixel columns of the x-axis instead, for instance to
+/// line up with another graph, in which case the caller is responsible for them suiting the resolution.
+///
+/// Should the space taken from either side of a small canvas leave no room for an axis it collapses to
+/// its start rather than extending past the canvas
 #[allow(clippy::too_many_arguments)]
 pub fn get_xy_axis_pixel_min_max(
 	quadrants: &Quadrants,
@@ -25,254 +53,541 @@ pub fn get_xy_axis_pixel_min_max(
 	canvas_size: (u32, u32),
 	x_axis_resolution: u32,
 	y_axis_resolution: u32,
+	x_axis_pixel_bounds: Option<(u32, u32)>,
 ) -> ((u32, u32), (u32, u32)) {
+	let (x_min, x_max) = x_axis_pixel_bounds.unwrap_or_else(|| {
+		fit_axis_to_resolution(
+			horizontal_pixels_from_left,
+			canvas_size
+				.0
+				.saturating_sub(horizontal_pixels_from_right)
+				.max(horizontal_pixels_from_left),
+			x_axis_resolution,
+			quadrants.is_x_paired(),
+		)
+	});
+	let (y_min, y_max) = fit_axis_to_resolution(
+		vertical_pixels_from_top,
+		canvas_size
+			.1
+			.saturating_sub(vertical_pixels_from_bottom)
+			.max(vertical_pixels_from_top),
+		y_axis_resolution,
+		quadrants.is_y_paired(),
+	);
+	// the image origin is in the top left so the bottom of the y-axis is the largest pixel
+	((x_min, y_max), (x_max, y_min))
+}
+/// Whether an axis `length` pixels long can be divided evenly by `resolution`, see
+/// [`fit_axis_to_resolution`]
+pub fn is_axis_fitted(length: u32, resolution: u32, is_paired: bool) -> bool {
+	if is_paired {
+		length % 2 == 0 && (length / 2) % resolution == 0 && (length / 2) % 2 == 0
+	} else {
+		length % resolution == 0
+	}
+}
+/// Shrink the pixel range `min_pixel..max_pixel` available to an axis so that its length is a multiple
+/// of `resolution`, so that axis scale markings accurately line up with plotted points. A paired axis
+/// extends into negative space so must have two equal halves which are each a multiple of the
+/// resolution, the halves must also be of even length so that the origin and the centre marker of
+/// each half fall on whole pixels.
+///
+/// The fewest pixels which achieve this are removed, half from each end. When an odd number of pixels is
+/// removed the extra pixel is taken from the `max_pixel` end
+pub fn fit_axis_to_resolution(
+	min_pixel: u32,
+	max_pixel: u32,
+	resolution: u32,
+	is_paired: bool,
+) -> (u32, u32) {
+	let length = max_pixel.saturating_sub(min_pixel);
+	// a zero length always fits so the search terminates
+	let removed = (0..=length)
+		.find(|removed| is_axis_fitted(length - removed, resolution, is_paired))
+		.unwrap_or(length);
+	let from_min = removed / 2;
+	let from_max = removed - from_min;
+	(min_pixel + from_min, max_pixel - from_max)
+}
+/// When the axis labels are not drawn their space isn't reserved on the canvas, which the scale markings
+/// otherwise rely upon. This finds the space required to fit the scale markers, and optionally their text,
+/// outside of the axes so that they are not drawn off the edge of the canvas. Hidden axes and scale markers
+/// take no space so that the plot grows into it
+#[allow(clippy::too_many_arguments)]
+pub fn get_scale_marking_space(
+	quadrants: &Quadrants,
+	font: &Font,
+	font_size: f32,
+	y_data_min_max_limits: (i32, i32),
+	has_tick_labels: bool,
+	y_tick_format: &TickFormat,
+	axis_style: &AxisStyle,
+	scale: u32,
+) -> Result<VHConsumedCanvasSpace, PlotError> {
+	// The longest scale marker is three times the base marker length of 5 pixels
+	let marker_length = 15 * scale;
+	let (text_width, text_height) = if has_tick_labels {
+		let y_tick_format =
+			y_tick_format.for_span(y_data_min_max_limits.1 as f64 - y_data_min_max_limits.0 as f64);
+		// Estimate the widest y-axis label from the data limits
+		let width = [y_data_min_max_limits.0, y_data_min_max_limits.1]
+			.iter()
+			.map(|limit| {
+				// the limits are whole numbers so a step of one gives them no decimal places, in scientific
+				// notation they keep every digit and so overestimate the width of the labels drawn
+				let text = format_tick_value(*limit as f64, 1.0, &y_tick_format);
+				get_width_of_glyphs(&create_glyphs(font_size, &text, font))
+			})
+			.max()
+			.unwrap_or(0);
+		let height = get_maximum_height_of_glyphs(&create_glyphs(font_size, "0123456789", font));
+		(width, height)
+	} else {
+		(0, 0)
+	};
+	// x-axis labels are drawn a glyph height beneath the end of a marker
+	let mut x_marking_space = marker_length + (text_height * 2);
+	let mut y_marking_space = marker_length + text_width;
+	// labels are placed beyond the markers whether or not the markers are drawn
+	if !has_tick_labels && !axis_style.has_ticks {
+		x_marking_space = 0;
+		y_marking_space = 0;
+	}
+	if !axis_style.has_x_axis {
+		x_marking_space = 0;
+	}
+	if !axis_style.has_y_axis {
+		y_marking_space = 0;
+	}
+	let mut space = VHConsumedCanvasSpace {
+		v_space_from_top: 0,
+		h_space_from_right: 0,
+		v_space_from_bottom: 0,
+		h_space_from_left: 0,
+	};
 	match quadrants {
-		Quadrants::RightPair => {
-			let minimum_possible_x = horizontal_pixels_from_left;
-			let maximum_possible_x = canvas_size.0 - horizontal_pixels_from_right;
-			// The true length of the axis must be a factor of the resolution so that axis scale markings
-			// accurately line up with plotted points
-			let mut x = maximum_possible_x;
-			'outer_x_rp: for i in 0..maximum_possible_x {
-				if get_x_axis_pixel_length(minimum_possible_x, x - i) % x_axis_resolution == 0 {
-					x -= i;
-					break 'outer_x_rp;
-				}
-			}
-			let minimum_possible_y = vertical_pixels_from_top;
-			let maximum_possible_y = canvas_size.1 - vertical_pixels_from_bottom;
-			// The true length of the axis must be a factor of the resolution so that axis scale markings
-			// accurately line up with plotted points
-			let mut y1 = minimum_possible_y;
-			let y0 = maximum_possible_y;
-			'outer_y_rp: for i in 0..minimum_possible_y {
-				// axis extends into negative space so ensure resolution fitting matches half overall length
-				if get_y_axis_pixel_length(y1 + i, maximum_possible_y) / 2 % y_axis_resolution == 0
-					&& get_y_axis_pixel_length(y1 + i, maximum_possible_y) / 2 % 2 == 0
-				{
-					y1 += i;
-					break 'outer_y_rp;
-				}
-			}
-			((minimum_possible_x, y0), (x, y1))
+		Quadrants::TopRight | Quadrants::TopLeft | Quadrants::TopPair => {
+			space.v_space_from_bottom = x_marking_space
 		}
-		Quadrants::LeftPair => {
-			let minimum_possible_x = horizontal_pixels_from_left;
-			let maximum_possible_x = canvas_size.0 - horizontal_pixels_from_right;
-			// The true length of the axis must be a factor of the resolution so that axis scale markings
-			// accurately line up with plotted points
-			let mut x = maximum_possible_x;
-			'outer_x_lp: for i in 0..maximum_possible_x {
-				if get_x_axis_pixel_length(minimum_possible_x, x - i) % x_axis_resolution == 0 {
-					x -= i;
-					break 'outer_x_lp;
-				}
-			}
-			let minimum_possible_y = vertical_pixels_from_top;
-			let maximum_possible_y = canvas_size.1 - vertical_pixels_from_bottom;
-			// The true length of the axis must be a factor of the resolution so that axis scale markings
-			// accurately line up with plotted points
-			let mut y1 = minimum_possible_y;
-			let y0 = maximum_possible_y;
-			'outer_y_lp: for i in 0..minimum_possible_y {
-				// axis extends into negative space so ensure resolution fitting matches half overall length
-				if get_y_axis_pixel_length(y1 + i, maximum_possible_y) / 2 % y_axis_resolution == 0
-					// && get_y_axis_pixel_length(y1 + i, maximum_possible_y) / 2 % 2 == 0
-				{
-					y1 += i;
-					break 'outer_y_lp;
-				}
-			}
-			((minimum_possible_x, y0), (x, y1))
+		Quadrants::BottomRight | Quadrants::BottomLeft | Quadrants::BottomPair => {
+			space.v_space_from_top = x_marking_space
 		}
-		Quadrants::TopPair => {
-			let minimum_possible_x = horizontal_pixels_from_left;
-			let maximum_possible_x = canvas_size.0 - horizontal_pixels_from_right;
-			// The true length of the axis must be a factor of the resolution so that axis scale markings
-			// accurately line up with plotted points
-			let mut x0 = minimum_possible_x;
-			let x1 = maximum_possible_x;
-			'outer_x_tp: for i in 0..maximum_possible_x {
-				// axis extends into negative space so ensure resolution fitting matches half overall length
-				if get_x_axis_pixel_length(x0 + i, maximum_possible_x) / 2 % x_axis_resolution == 0
-				{
-					x0 += i;
-					break 'outer_x_tp;
-				}
-			}
-			let minimum_possible_y = vertical_pixels_from_top;
-			let maximum_possible_y = canvas_size.1 - vertical_pixels_from_bottom;
-			// The true length of the axis must be a factor of the resolution so that axis scale markings
-			// accurately line up with plotted points
-			let mut y = minimum_possible_y;
-			'outer_y_tp: for i in 0..minimum_possible_y {
-				if get_y_axis_pixel_length(y + i, maximum_possible_y) % y_axis_resolution == 0 {
-					y += i;
-					break 'outer_y_tp;
-				}
-			}
-			((x0, maximum_possible_y), (x1, y))
+		_ => {}
+	}
+	match quadrants {
+		Quadrants::TopRight | Quadrants::BottomRight | Quadrants::RightPair => {
+			space.h_space_from_left = y_marking_space
 		}
-		Quadrants::BottomPair => {
-			let minimum_possible_x = horizontal_pixels_from_left;
-			let maximum_possible_x = canvas_size.0 - horizontal_pixels_from_right;
-			// The true length of the axis must be a factor of the resolution so that axis scale markings
-			// accurately line up with plotted points
-			let mut x0 = minimum_possible_x;
-			let x1 = maximum_possible_x;
-			'outer_x_bp: for i in 0..maximum_possible_x {
-				// axis extends into negative space so ensure resolution fitting matches half overall length
-				if get_x_axis_pixel_length(x0 + i, maximum_possible_x) / 2 % x_axis_resolution == 0
-				{
-					x0 += i;
-					break 'outer_x_bp;
-				}
-			}
-			let minimum_possible_y = vertical_pixels_from_top;
-			let maximum_possible_y = canvas_size.1 - vertical_pixels_from_bottom;
-			// The true length of the axis must be a factor of the resolution so that axis scale markings
-			// accurately line up with plotted points
-			let mut y = minimum_possible_y;
-			'outer_y_bp: for i in 0..maximum_possible_y {
-				if get_y_axis_pixel_length(y + i, maximum_possible_y) % y_axis_resolution == 0 {
-					y += i;
-					break 'outer_y_bp;
-				}
-			}
+		// labels of the left variants are offset by an additional width
+		Quadrants::TopLeft | Quadrants::BottomLeft if y_marking_space > 0 => {
+			space.h_space_from_right = y_marking_space + text_width
+		}
+		Quadrants::LeftPair if y_marking_space > 0 => {
+			space.h_space_from_right = (text_width * 4)
+				.saturating_sub(marker_length)
+				.max(marker_length)
+		}
+		_ => {}
+	}
+	Ok(space)
+}
+/// Find a "nice" step between scale markings, `1`, `2` or `5` multiplied by a power of ten, which divides
+/// `span` into no more than `target` subdivisions
+pub fn get_nice_step(span: f64, target: u32) -> f64 {
+	let raw_step = span.abs() / target.max(1) as f64;
+	if !raw_step.is_normal() {
+		return 1.0;
+	}
+	let magnitude = 10_f64.powf(raw_step.log10().floor());
+	[1.0, 2.0, 5.0, 10.0]
+		.iter()
+		.map(|nice| nice * magnitude)
+		// tolerate the rounding of `powf` so a raw step of exactly `20` keeps a step of `20`
+		.find(|step| *step >= raw_step * (1.0 - 1e-9))
+		.unwrap_or(10.0 * magnitude)
+}
+/// Round the data limits of an axis outward to multiples of a nice step for when a resolution has not been
+/// provided, giving the new limits and the resolution which divides them into that step. Like
+/// [`get_auto_axis_resolution`] an axis spanning negative and positive values aims for half as many
+/// subdivisions in each direction, and is made symmetric about zero as its resolution is drawn once in each
+/// direction
+pub fn get_nice_axis_limits(data_min_max_limits: (i32, i32)) -> ((i32, i32), u32) {
+	let (min, max) = (data_min_max_limits.0 as f64, data_min_max_limits.1 as f64);
+	if min < 0.0 && max > 0.0 {
+		let half = (-min).max(max);
+		let step = get_nice_step(half, 5);
+		let steps = (half / step).ceil();
+		let extent = (steps * step).round() as i32;
+		((-extent, extent), steps as u32)
+	} else {
+		let step = get_nice_step(max - min, 10);
+		// steps smaller than one divide the whole number limits exactly
+		let low = (min / step).floor() * step;
+		let high = (max / step).ceil() * step;
+		let steps = ((high - low) / step).round().max(1.0);
+		((low.round() as i32, high.round() as i32), steps as u32)
+	}
+}
+/// Find whole number limits covering `data_min_max_limits` which are divided into exactly `divisions` nice
+/// steps, so that the scale markers of a secondary axis line up with those of the primary axis
+pub fn get_aligned_axis_limits(data_min_max_limits: (f32, f32), divisions: u32) -> (i32, i32) {
+	let (min, max) = (data_min_max_limits.0 as f64, data_min_max_limits.1 as f64);
+	let divisions = divisions.max(1) as f64;
+	// limits are whole numbers so a step is never smaller than one
+	let mut step = get_nice_step(max - min, divisions as u32).max(1.0);
+	loop {
+		let low = (min / step).floor() * step;
+		let high = low + step * divisions;
+		if high >= max {
+			return (low.round() as i32, high.round() as i32);
+		}
+		// the next nice step, e.g `2` after `1` or `10` after `5`
+		step = get_nice_step(step * 1.5, 1);
+	}
+}
+/// A scale marker placed at a value of its own rather than spread by the resolution of the axis
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum CustomTick {
+	/// A marker labelled with its value, written in the same way as the other labels of the axis
+	Value(f32),
+	/// A marker labelled with text of its own, e.g `(3.14159, "π")`
+	Labelled(f32, String),
+}
 
-			((x0, maximum_possible_y), (x1, y))
+impl CustomTick {
+	/// The value the marker is placed at
+	pub fn value(&self) -> f32 {
+		match self {
+			CustomTick::Value(value) | CustomTick::Labelled(value, _) => *value,
 		}
-		Quadrants::AllQuadrants => {
-			let minimum_possible_x = horizontal_pixels_from_left;
-			let maximum_possible_x = canvas_size.0 - horizontal_pixels_from_right;
-			// The true length of the axis must be a factor of the resolution so that axis scale markings
-			// accurately line up with plotted points
-			let mut x0 = minimum_possible_x;
-			let x1 = maximum_possible_x;
-			'outer_x0: for i in 0..maximum_possible_x {
-				// axis extends into negative space so ensure resolution fitting matches half overall length
-				if get_x_axis_pixel_length(x0 + i, maximum_possible_x) / 2 % x_axis_resolution == 0
-				{
-					x0 += i;
-					break 'outer_x0;
-				}
+	}
+}
+
+/// The values of the custom scale markers of an axis spanning `limits` paired with the text of their labels.
+/// Values outside of the axis are skipped with a warning, the others are written with `tick_format` to the
+/// precision of the most precise value unless they have text of their own
+pub fn get_custom_ticks(
+	axis_name: &str,
+	ticks: &[CustomTick],
+	limits: (i32, i32),
+	tick_format: &TickFormat,
+) -> Vec<(f32, String)> {
+	let range = limits.0 as f32..=limits.1 as f32;
+	let ticks: Vec<&CustomTick> = ticks
+		.iter()
+		.filter(|tick| {
+			let is_within = range.contains(&tick.value());
+			if !is_within {
+				warn!(
+					"Skipping the {}-axis tick at {} as it lies outside of the axis from {} to {}",
+					axis_name,
+					tick.value(),
+					limits.0,
+					limits.1
+				);
 			}
-			let minimum_possible_y = vertical_pixels_from_top;
-			let maximum_possible_y = canvas_size.1 - vertical_pixels_from_bottom;
-			// The true length of the axis must be a factor of the resolution so that axis scale markings
-			// accurately line up with plotted points
-			let mut y1 = minimum_possible_y;
-			let y0 = maximum_possible_y;
-			'outer_y1: for i in 0..minimum_possible_y {
-				// axis extends into negative space so ensure resolution fitting matches half overall length
-				if get_y_axis_pixel_length(y1 + i, maximum_possible_y) / 2 % y_axis_resolution == 0
-					&& get_y_axis_pixel_length(y1 + i, maximum_possible_y) / 2 % 2 == 0
-				{
-					y1 += i;
-					break 'outer_y1;
-				}
+			is_within
+		})
+		.collect();
+	// every label has as many decimal places as the most precise value was given with
+	let step = ticks
+		.iter()
+		.map(|tick| {
+			let places = tick
+				.value()
+				.to_string()
+				.split_once('.')
+				.map_or(0, |(_, decimals)| decimals.len());
+			10_f64.powi(-(places as i32))
+		})
+		.fold(1.0, f64::min);
+	let tick_format = tick_format.for_span(limits.1 as f64 - limits.0 as f64);
+	ticks
+		.into_iter()
+		.map(|tick| match tick {
+			CustomTick::Value(value) => {
+				(*value, format_tick_value(*value as f64, step, &tick_format))
 			}
-			((x0, y0), (x1, y1))
+			CustomTick::Labelled(value, text) => (*value, text.clone()),
+		})
+		.collect()
+}
+
+/// Which vertical axis the values of a data set are plotted against
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Default)]
+pub enum YAxis {
+	/// The y-axis drawn by the quadrants of the graph
+	#[default]
+	Primary,
+	/// A second y-axis with its own scale drawn along the right edge of the plot
+	Secondary,
+}
+
+/// The pattern gridlines are drawn with
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Default)]
+pub enum GridStyle {
+	/// Unbroken lines
+	#[default]
+	Solid,
+	/// Dashes separated by gaps of the same length
+	Dashed,
+	/// Dots as long as the gridlines are thick, separated by twice their length
+	Dotted,
+}
+
+/// The direction an axis label reads in
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Default)]
+pub enum LabelOrientation {
+	/// Left to right
+	#[default]
+	Horizontal,
+	/// Bottom to top, turned a quarter anticlockwise
+	Vertical,
+}
+
+/// How the mini-markings between the scale markers of an axis are drawn
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct MinorTicks {
+	/// Number of mini-markings between each pair of scale markers, `0` disables them and `None` picks as
+	/// many as evenly divide the pixels between the markers
+	pub count: Option<u32>,
+	/// Whether faint gridlines are drawn at the mini-markings
+	pub has_grid: bool,
+}
+
+/// Which parts of the axes are drawn, the thickness and colour of their lines and the colour of their tick
+/// labels
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct AxisStyle {
+	/// Whether the x-axis line, its scale markers and their labels are drawn
+	pub has_x_axis: bool,
+	/// Whether the y-axis line, its scale markers and their labels are drawn
+	pub has_y_axis: bool,
+	/// Whether the scale markers of the axes are drawn
+	pub has_ticks: bool,
+	/// Whether arrowheads are drawn at the maximum pixel ends of the axes
+	pub has_arrows: bool,
+	/// Whether the x-axis is drawn with its values decreasing to the right
+	pub is_x_reversed: bool,
+	/// Whether the y-axis is drawn with its values decreasing upward
+	pub is_y_reversed: bool,
+	/// Thickness of the axis lines and their scale markers
+	pub axis_thickness: u32,
+	/// Thickness of the background gridlines
+	pub grid_thickness: u32,
+	/// The pattern the background gridlines are drawn with
+	pub grid_style: GridStyle,
+	/// Length in pixels of the dashes of `GridStyle::Dashed` gridlines
+	pub grid_dash_length: u32,
+	/// Colour of the axis lines and their scale markers
+	pub axis_colour: [u8; 4],
+	/// Colour of the values written along the axes
+	pub tick_label_colour: [u8; 4],
+	/// Colour of the background gridlines
+	pub grid_colour: [u8; 4],
+	/// Colour of the faint gridlines at the mini-markings
+	pub minor_grid_colour: [u8; 4],
+}
+
+impl Default for AxisStyle {
+	fn default() -> Self {
+		AxisStyle {
+			has_x_axis: true,
+			has_y_axis: true,
+			has_ticks: true,
+			has_arrows: false,
+			is_x_reversed: false,
+			is_y_reversed: false,
+			axis_thickness: 1,
+			grid_thickness: 1,
+			grid_style: GridStyle::Solid,
+			grid_dash_length: 4,
+			axis_colour: BLACK,
+			tick_label_colour: BLACK,
+			grid_colour: GREY,
+			minor_grid_colour: LIGHT_GREY,
 		}
-		Quadrants::TopRight => {
-			let minimum_possible_x = horizontal_pixels_from_left;
-			let maximum_possible_x = canvas_size.0 - horizontal_pixels_from_right;
-			// The true length of the axis must be a factor of the resolution so that axis scale markings
-			// accurately line up with plotted points
-			let mut x = maximum_possible_x;
-			'outer_x: for i in 0..maximum_possible_x {
-				if get_x_axis_pixel_length(minimum_possible_x, x - i) % x_axis_resolution == 0 {
-					x -= i;
-					break 'outer_x;
-				}
-			}
-			let minimum_possible_y = vertical_pixels_from_top;
-			let maximum_possible_y = canvas_size.1 - vertical_pixels_from_bottom;
-			// The true length of the axis must be a factor of the resolution so that axis scale markings
-			// accurately line up with plotted points
-			let mut y = minimum_possible_y;
-			'outer_y: for i in 0..minimum_possible_y {
-				if get_y_axis_pixel_length(y + i, maximum_possible_y) % y_axis_resolution == 0 {
-					y += i;
-					break 'outer_y;
-				}
-			}
-			((minimum_possible_x, maximum_possible_y), (x, y))
+	}
+}
+
+impl AxisStyle {
+	/// Whether the pixel of a gridline `distance` pixels from the axis it crosses is drawn on a canvas
+	/// supersampled by `scale`. The pattern starts at the axis so that gridlines in both directions share it
+	pub fn is_grid_drawn(&self, distance: u32, scale: u32) -> bool {
+		match self.grid_style {
+			GridStyle::Solid => true,
+			GridStyle::Dashed => (distance / (self.grid_dash_length * scale)) % 2 == 0,
+			GridStyle::Dotted => (distance / (self.grid_thickness * scale)) % 3 == 0,
 		}
-		Quadrants::TopLeft => {
-			let minimum_possible_x = horizontal_pixels_from_left;
-			let maximum_possible_x = canvas_size.0 - horizontal_pixels_from_right;
-			// The true length of the axis must be a factor of the resolution so that axis scale markings
-			// accurately line up with plotted points
-			let mut x = minimum_possible_x;
-			'outer_x_tl: for i in 0..maximum_possible_x {
-				if get_x_axis_pixel_length(x + i, maximum_possible_x) % x_axis_resolution == 0 {
-					x += i;
-					break 'outer_x_tl;
-				}
-			}
-			let minimum_possible_y = vertical_pixels_from_top;
-			let maximum_possible_y = canvas_size.1 - vertical_pixels_from_bottom;
-			// The true length of the axis must be a factor of the resolution so that axis scale markings
-			// accurately line up with plotted points
-			let mut y = minimum_possible_y;
-			'outer_y_tl: for i in 0..minimum_possible_y {
-				if get_y_axis_pixel_length(y + i, maximum_possible_y) % y_axis_resolution == 0 {
-					y += i;
-					break 'outer_y_tl;
-				}
-			}
-			((x, maximum_possible_y), (maximum_possible_x, y))
+	}
+	/// Length in pixels of the sides of the arrowheads drawn at the ends of the axes on a canvas
+	/// supersampled by `scale`, they grow with the thickness of the axes
+	pub fn get_arrow_size(&self, scale: u32) -> u32 {
+		3 * (self.axis_thickness + 2) * scale
+	}
+	/// How many pixels beyond the maximum pixel ends of the axes their arrowheads reach, zero without arrows
+	pub fn get_arrow_space(&self, scale: u32) -> u32 {
+		if self.has_arrows {
+			self.get_arrow_size(scale)
+		} else {
+			0
 		}
-		Quadrants::BottomRight => {
-			let minimum_possible_x = horizontal_pixels_from_left;
-			let maximum_possible_x = canvas_size.0 - horizontal_pixels_from_right;
-			// The true length of the axis must be a factor of the resolution so that axis scale markings
-			// accurately line up with plotted points
-			let mut x = maximum_possible_x;
-			'outer_x_br: for i in 0..maximum_possible_x {
-				if get_x_axis_pixel_length(minimum_possible_x, x - i) % x_axis_resolution == 0 {
-					x -= i;
-					break 'outer_x_br;
-				}
-			}
-			let minimum_possible_y = vertical_pixels_from_top;
-			let maximum_possible_y = canvas_size.1 - vertical_pixels_from_bottom;
-			// The true length of the axis must be a factor of the resolution so that axis scale markings
-			// accurately line up with plotted points
-			let mut y = minimum_possible_y;
-			'outer_y_br: for i in 0..maximum_possible_y {
-				if get_y_axis_pixel_length(y + i, maximum_possible_y) % y_axis_resolution == 0 {
-					y += i;
-					break 'outer_y_br;
-				}
+	}
+}
+
+/// Which way a line thicker than a pixel is widened
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Spread {
+	/// Widen across rows, for horizontal lines
+	Rows,
+	/// Widen across columns, for vertical lines
+	Columns,
+}
+
+/// Find how many pixels a line `thickness` pixels thick, on a canvas supersampled by `scale`, extends before
+/// and after the position it is centred on. An even thickness puts the extra pixel after the position
+pub fn get_line_extents(thickness: u32, scale: u32) -> (u32, u32) {
+	let before = thickness.saturating_sub(1) / 2;
+	let after = thickness.saturating_sub(1) - before;
+	(before * scale, after * scale)
+}
+
+/// Draw a block of a line `thickness` pixels thick centred on `(x, y)`, see [`put_pixel_block`]
+pub fn put_thick_pixel_block(
+	canvas: &mut ImageBuffer<Rgba<u8>, Vec<u8>>,
+	x: u32,
+	y: u32,
+	scale: u32,
+	thickness: u32,
+	spread: Spread,
+	colour: [u8; 4],
+) {
+	let (before, _) = get_line_extents(thickness, scale);
+	for k in 0..thickness.max(1) {
+		match spread {
+			Spread::Rows => put_pixel_block(
+				canvas,
+				x,
+				y.saturating_sub(before) + k * scale,
+				scale,
+				colour,
+			),
+			Spread::Columns => put_pixel_block(
+				canvas,
+				x.saturating_sub(before) + k * scale,
+				y,
+				scale,
+				colour,
+			),
+		};
+	}
+}
+
+/// The width of the widest and the height of the tallest scale label of an axis spanning `limits` which is
+/// split into `divisions`
+pub fn get_tick_label_size(
+	limits: (i32, i32),
+	divisions: u32,
+	tick_format: &TickFormat,
+	font: &Font,
+	font_size: f32,
+) -> (u32, u32) {
+	let step = (limits.1 as f64 - limits.0 as f64) / divisions.max(1) as f64;
+	(0..=divisions)
+		.map(|i| {
+			let text = format_tick_value(limits.0 as f64 + step * i as f64, step, tick_format);
+			let glyphs = create_glyphs(font_size, &text, font);
+			(
+				get_width_of_glyphs(&glyphs),
+				get_maximum_height_of_glyphs(&glyphs),
+			)
+		})
+		.fold((0, 0), |(width, height), label| {
+			(width.max(label.0), height.max(label.1))
+		})
+}
+/// Every how many scale markers `spacing` pixels apart a label is written, counted from the origin. An
+/// explicit `every` is honoured, otherwise as few labels are skipped as keep labels which are
+/// `label_length` pixels long along the axis at least `gap` pixels apart
+pub fn get_tick_label_every(every: Option<u32>, label_length: u32, gap: u32, spacing: u32) -> u32 {
+	match every {
+		Some(every) => every.max(1),
+		None if spacing == 0 => 1,
+		None => ((label_length + gap + spacing - 1) / spacing).max(1),
+	}
+}
+/// Find how many mini-markings are drawn between two scale markers `subdivision_length` pixels apart and
+/// the pixels between each of them. Without a `count` the most of `9`, `4`, `3`, `2` or `1` which evenly
+/// divides the subdivision is used. A `count` too large for the markers to be a pixel apart draws none
+pub fn get_minor_ticks(subdivision_length: u32, count: Option<u32>) -> (u32, u32) {
+	match count {
+		Some(0) => (0, 0),
+		Some(count) => {
+			let spacing = subdivision_length / (count + 1);
+			if spacing == 0 {
+				(0, 0)
+			} else {
+				(count, spacing)
 			}
-			((minimum_possible_x, maximum_possible_y), (x, y))
 		}
-		Quadrants::BottomLeft => {
-			let minimum_possible_x = horizontal_pixels_from_left;
-			let maximum_possible_x = canvas_size.0 - horizontal_pixels_from_right;
-			// The true length of the axis must be a factor of the resolution so that axis scale markings
-			// accurately line up with plotted points
-			let mut x = maximum_possible_x;
-			'outer_x_bl: for i in 0..maximum_possible_x {
-				if get_x_axis_pixel_length(minimum_possible_x, x - i) % x_axis_resolution == 0 {
-					x -= i;
-					break 'outer_x_bl;
-				}
-			}
-			let minimum_possible_y = vertical_pixels_from_top;
-			let maximum_possible_y = canvas_size.1 - vertical_pixels_from_bottom;
-			// The true length of the axis must be a factor of the resolution so that axis scale markings
-			// accurately line up with plotted points
-			let mut y = minimum_possible_y;
-			'outer_y_bl: for i in 0..maximum_possible_y {
-				if get_y_axis_pixel_length(y + i, maximum_possible_y) % y_axis_resolution == 0 {
-					y += i;
-					break 'outer_y_bl;
-				}
-			}
-			((minimum_possible_x, maximum_possible_y), (x, y))
+		None => [9, 4, 3, 2, 1]
+			.iter()
+			.find(|count| subdivision_length % (*count + 1) == 0)
+			.map(|count| (*count, subdivision_length / (count + 1)))
+			.unwrap_or((0, 0)),
+	}
+}
+
+/// Mirror a value about the middle of an axis spanning `data_min_max_limits` when the axis is reversed, so
+/// the smallest value is drawn where the largest would be and vice versa
+pub fn get_reversed_value(value: f64, data_min_max_limits: (i32, i32), is_reversed: bool) -> f64 {
+	if is_reversed {
+		data_min_max_limits.0 as f64 + data_min_max_limits.1 as f64 - value
+	} else {
+		value
+	}
+}
+
+/// Choose how many times an axis should be divided when a resolution has not been provided but its range has
+/// been fixed, so its limits cannot be rounded to a nice step. The
+/// subdivision value aims to be a "nice" step, `1`, `2`, `2.5` or `5` multiplied by a power of ten, with
+/// roughly ten subdivisions along the axis. An axis spanning negative and positive values draws its
+/// resolution twice, once in each direction, so `is_paired` should be set for such axes in which case
+/// the resolution is found against half of the data range
+pub fn get_auto_axis_resolution(data_min_max_limits: (i32, i32), is_paired: bool) -> u32 {
+	let range = ((data_min_max_limits.1 as f64 - data_min_max_limits.0 as f64) as f32).abs();
+	let (range, target) = if is_paired {
+		(range / 2.0, 5)
+	} else {
+		(range, 10)
+	};
+	if !range.is_normal() {
+		return 1;
+	}
+	let is_nice = |step: f32| {
+		let mantissa = step / 10_f32.powf(step.log10().floor());
+		[1.0, 2.0, 2.5, 5.0, 10.0]
+			.iter()
+			.any(|nice| (mantissa - nice).abs() < 1e-4)
+	};
+	// Prefer a resolution which divides the range exactly into nice steps, closest to the target
+	let exact = (1..=target * 3)
+		.filter(|resolution| is_nice(range / *resolution as f32))
+		.min_by_key(|resolution| (*resolution as i32 - target as i32).abs());
+	match exact {
+		Some(resolution) => resolution,
+		None => {
+			// Otherwise round the step to a nice value and divide the range by it
+			let raw_step = range / target as f32;
+			let magnitude = 10_f32.powf(raw_step.log10().floor());
+			let nice_step = [1.0, 2.0, 2.5, 5.0, 10.0]
+				.iter()
+				.map(|nice| nice * magnitude)
+				.find(|step| *step >= raw_step)
+				.unwrap_or(10.0 * magnitude);
+			((range / nice_step).round() as u32).max(1)
 		}
 	}
 }
@@ -340,7 +655,9 @@ pub fn get_xy_axis_pixel_origin(
 	}
 }
 
-/// Within the acceptable pixel space for the axes draw them, note the top left corner of the canvas is the origin `(0, 0)` with bottom right `(canvas.dimensions().0, canvas.dimensions().1)`
+/// Within the acceptable pixel space for the axes draw them, note the top left corner of the canvas is the origin `(0, 0)` with bottom right `(canvas.dimensions().0, canvas.dimensions().1)`.
+/// The optional `zero_lines` are the pixel column where x is zero and the pixel row where y is zero, drawn
+/// over the grid darker and twice as thick
 #[allow(clippy::too_many_arguments)]
 pub fn draw_xy_axes(
 	quadrants: &Quadrants,
@@ -352,39 +669,392 @@ pub fn draw_xy_axes(
 	y_axis_length: u32,
 	x_data_min_max_limits: (i32, i32),
 	y_data_min_max_limits: (i32, i32),
+	font: &Font,
 	font_size: f32,
 	has_grid: bool,
+	zero_lines: (Option<u32>, Option<u32>),
+	x_custom_ticks: Option<&[(u32, String)]>,
+	y_custom_ticks: Option<&[(u32, String)]>,
+	x_minor_ticks: MinorTicks,
+	y_minor_ticks: MinorTicks,
+	has_tick_labels: bool,
 	x_axis_resolution: u32,
 	y_axis_resolution: u32,
-) {
-	// x-axis data labels
-	draw_x_axis_scale_markings(
-		quadrants,
-		canvas,
-		axis_min_pixel,
-		axis_origin_pixel,
-		axis_max_pixel,
-		x_axis_length,
-		x_data_min_max_limits,
-		font_size,
-		has_grid,
-		x_axis_resolution,
-	);
+	x_tick_format: &TickFormat,
+	y_tick_format: &TickFormat,
+	axis_style: AxisStyle,
+	scale: u32,
+) -> Result<(), PlotError> {
+	// a hidden axis still draws its part of the grid, just without scale markers or labels
+	let x_axis_style = AxisStyle {
+		has_ticks: axis_style.has_ticks && axis_style.has_x_axis,
+		..axis_style
+	};
+	let y_axis_style = AxisStyle {
+		has_ticks: axis_style.has_ticks && axis_style.has_y_axis,
+		..axis_style
+	};
+	// x-axis data labels, custom markers replace those spread by the resolution
+	match x_custom_ticks {
+		Some(ticks) => draw_x_axis_custom_ticks(
+			quadrants,
+			canvas,
+			axis_min_pixel,
+			axis_origin_pixel,
+			axis_max_pixel,
+			ticks,
+			font,
+			font_size,
+			has_grid,
+			has_tick_labels && axis_style.has_x_axis,
+			x_axis_style,
+			scale,
+		)?,
+		None => draw_x_axis_scale_markings(
+			quadrants,
+			canvas,
+			axis_min_pixel,
+			axis_origin_pixel,
+			axis_max_pixel,
+			x_axis_length,
+			x_data_min_max_limits,
+			font,
+			font_size,
+			has_grid,
+			x_minor_ticks,
+			has_tick_labels && axis_style.has_x_axis,
+			x_axis_resolution,
+			x_tick_format,
+			x_axis_style,
+			scale,
+		)?,
+	}
 	// y-axis data labels
-	draw_y_axis_scale_markings(
-		quadrants,
-		canvas,
-		axis_min_pixel,
-		axis_origin_pixel,
-		axis_max_pixel,
-		y_axis_length,
-		y_data_min_max_limits,
-		font_size,
-		has_grid,
-		y_axis_resolution,
-	);
+	match y_custom_ticks {
+		Some(ticks) => draw_y_axis_custom_ticks(
+			quadrants,
+			canvas,
+			axis_min_pixel,
+			axis_origin_pixel,
+			axis_max_pixel,
+			ticks,
+			font,
+			font_size,
+			has_grid,
+			has_tick_labels && axis_style.has_y_axis,
+			y_axis_style,
+			scale,
+		)?,
+		None => draw_y_axis_scale_markings(
+			quadrants,
+			canvas,
+			axis_min_pixel,
+			axis_origin_pixel,
+			axis_max_pixel,
+			y_axis_length,
+			y_data_min_max_limits,
+			font,
+			font_size,
+			has_grid,
+			y_minor_ticks,
+			has_tick_labels && axis_style.has_y_axis,
+			y_axis_resolution,
+			y_tick_format,
+			y_axis_style,
+			scale,
+		)?,
+	}
+	if let Some(px) = zero_lines.0 {
+		trace!("Emphasising x = 0 at pixel column {}", px);
+		for py in axis_max_pixel.1..axis_min_pixel.1 {
+			put_thick_pixel_block(
+				canvas,
+				px,
+				py,
+				scale,
+				2 * axis_style.grid_thickness,
+				Spread::Columns,
+				DARK_GREY,
+			);
+		}
+	}
+	if let Some(py) = zero_lines.1 {
+		trace!("Emphasising y = 0 at pixel row {}", py);
+		for px in axis_min_pixel.0..axis_max_pixel.0 {
+			put_thick_pixel_block(
+				canvas,
+				px,
+				py,
+				scale,
+				2 * axis_style.grid_thickness,
+				Spread::Rows,
+				DARK_GREY,
+			);
+		}
+	}
 	// x-axis
-	draw_x_axis(canvas, axis_min_pixel, axis_origin_pixel, axis_max_pixel);
+	if axis_style.has_x_axis {
+		draw_x_axis(
+			canvas,
+			axis_min_pixel,
+			axis_origin_pixel,
+			axis_max_pixel,
+			axis_style,
+			scale,
+		);
+	}
 	// y-axis
-	draw_y_axis(canvas, axis_min_pixel, axis_origin_pixel, axis_max_pixel);
+	if axis_style.has_y_axis {
+		draw_y_axis(
+			canvas,
+			axis_min_pixel,
+			axis_origin_pixel,
+			axis_max_pixel,
+			axis_style,
+			scale,
+		);
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::canvas::{
+		axes::{
+			fit_axis_to_resolution, get_aligned_axis_limits, get_auto_axis_resolution,
+			get_custom_ticks, get_minor_ticks, get_nice_axis_limits, get_nice_step,
+			get_reversed_value, get_tick_label_every, get_xy_axis_pixel_min_max, AxisStyle,
+			CustomTick, GridStyle,
+		},
+		format::TickFormat,
+		quadrants::Quadrants,
+	};
+	#[test]
+	fn auto_resolution_divides_exactly() {
+		assert_eq!(11, get_auto_axis_resolution((0, 11), false));
+		assert_eq!(10, get_auto_axis_resolution((0, 100), false));
+		assert_eq!(12, get_auto_axis_resolution((0, 30), false));
+	}
+	#[test]
+	fn auto_resolution_paired_uses_half_range() {
+		assert_eq!(5, get_auto_axis_resolution((-10, 10), true));
+		assert_eq!(11, get_auto_axis_resolution((-11, 11), true));
+	}
+	#[test]
+	fn auto_resolution_falls_back_to_rounded_step() {
+		assert_eq!(6, get_auto_axis_resolution((0, 1237), false));
+	}
+	#[test]
+	fn auto_resolution_zero_range() {
+		assert_eq!(1, get_auto_axis_resolution((3, 3), false));
+	}
+	#[test]
+	fn nice_steps() {
+		assert_eq!(10.0, get_nice_step(95.0, 10));
+		assert_eq!(20.0, get_nice_step(200.0, 10));
+		assert_eq!(0.5, get_nice_step(4.0, 10));
+		assert_eq!(5000.0, get_nice_step(41000.0, 10));
+		assert_eq!(1.0, get_nice_step(0.0, 10));
+	}
+	#[test]
+	fn nice_limits_round_outward() {
+		// data bounds of 0 to 87 buffered to 95
+		assert_eq!(((0, 100), 10), get_nice_axis_limits((0, 95)));
+		assert_eq!(((0, 12), 6), get_nice_axis_limits((0, 11)));
+		assert_eq!(((1200, 2600), 7), get_nice_axis_limits((1213, 2533)));
+		assert_eq!(((-30, 0), 6), get_nice_axis_limits((-28, 0)));
+		assert_eq!(((0, 4), 8), get_nice_axis_limits((0, 4)));
+		assert_eq!(((3, 3), 1), get_nice_axis_limits((3, 3)));
+	}
+	#[test]
+	fn nice_limits_of_paired_axes_are_symmetric() {
+		assert_eq!(((-40, 40), 4), get_nice_axis_limits((-13, 37)));
+		assert_eq!(((-10, 10), 5), get_nice_axis_limits((-10, 7)));
+	}
+	const ALL_QUADRANTS: [Quadrants; 9] = [
+		Quadrants::RightPair,
+		Quadrants::LeftPair,
+		Quadrants::TopPair,
+		Quadrants::BottomPair,
+		Quadrants::AllQuadrants,
+		Quadrants::TopRight,
+		Quadrants::TopLeft,
+		Quadrants::BottomRight,
+		Quadrants::BottomLeft,
+	];
+	#[test]
+	fn fitted_axes_are_centred_in_every_quadrant() {
+		// (top, right, bottom, left) space consumed around the axes
+		let margins = [(10, 10, 10, 10), (53, 17, 91, 120), (40, 211, 37, 64)];
+		for quadrants in ALL_QUADRANTS.iter() {
+			for (top, right, bottom, left) in margins {
+				for (x_resolution, y_resolution) in [(11, 7), (10, 10), (3, 13)] {
+					let canvas_size = (840, 600);
+					let ((x_min, y_max), (x_max, y_min)) = get_xy_axis_pixel_min_max(
+						quadrants,
+						top,
+						right,
+						bottom,
+						left,
+						canvas_size,
+						x_resolution,
+						y_resolution,
+						None,
+					);
+					// the lengths which fit are this many pixels apart, each half of a paired axis is an even
+					// multiple of the resolution
+					let period = |resolution: u32, is_paired: bool| {
+						if !is_paired {
+							resolution
+						} else if resolution % 2 == 0 {
+							2 * resolution
+						} else {
+							4 * resolution
+						}
+					};
+					let context = format!(
+						"{:?} margins {:?} resolutions {:?}",
+						quadrants,
+						(top, right, bottom, left),
+						(x_resolution, y_resolution)
+					);
+					// the space either side of the plot differs by no more than one pixel
+					let (gap_left, gap_right) = (x_min - left, canvas_size.0 - right - x_max);
+					let (gap_top, gap_bottom) = (y_min - top, canvas_size.1 - bottom - y_max);
+					assert!(gap_right - gap_left <= 1, "{}", context);
+					assert!(gap_bottom - gap_top <= 1, "{}", context);
+					// and fewer pixels were removed than reach the next shorter length which fits
+					assert!(
+						gap_left + gap_right < period(x_resolution, quadrants.is_x_paired()),
+						"{}",
+						context
+					);
+					assert!(
+						gap_top + gap_bottom < period(y_resolution, quadrants.is_y_paired()),
+						"{}",
+						context
+					);
+				}
+			}
+		}
+	}
+	#[test]
+	fn mirrored_quadrants_fit_identically() {
+		let fit = |quadrants: &Quadrants| {
+			let ((x_min, y_max), (x_max, y_min)) =
+				get_xy_axis_pixel_min_max(quadrants, 53, 17, 91, 120, (840, 600), 11, 7, None);
+			(x_max - x_min, y_max - y_min, x_min, y_min)
+		};
+		assert_eq!(fit(&Quadrants::TopRight), fit(&Quadrants::TopLeft));
+		assert_eq!(fit(&Quadrants::TopRight), fit(&Quadrants::BottomRight));
+		assert_eq!(fit(&Quadrants::TopRight), fit(&Quadrants::BottomLeft));
+		assert_eq!(fit(&Quadrants::RightPair), fit(&Quadrants::LeftPair));
+		assert_eq!(fit(&Quadrants::TopPair), fit(&Quadrants::BottomPair));
+	}
+	#[test]
+	fn margins_wider_than_the_canvas_collapse_the_axes() {
+		// more is taken from the right and bottom than the canvas has
+		let ((x_min, y_max), (x_max, y_min)) = get_xy_axis_pixel_min_max(
+			&Quadrants::AllQuadrants,
+			20,
+			80,
+			90,
+			30,
+			(60, 40),
+			4,
+			4,
+			None,
+		);
+		assert_eq!((30, 30), (x_min, x_max));
+		assert_eq!((20, 20), (y_min, y_max));
+	}
+	#[test]
+	fn fit_removes_fewest_pixels() {
+		// 703 pixels available, 700 is the nearest multiple of 10
+		assert_eq!((101, 801), fit_axis_to_resolution(100, 803, 10, false));
+		assert_eq!((100, 800), fit_axis_to_resolution(100, 800, 10, false));
+		// halves of 160 are the nearest to 163 that are both even and divisible by 5
+		assert_eq!((101, 261), fit_axis_to_resolution(100, 263, 5, true));
+		// too short to divide so collapses to the centre
+		assert_eq!((6, 6), fit_axis_to_resolution(5, 7, 10, false));
+	}
+	#[test]
+	fn minor_ticks_divide_the_subdivision() {
+		assert_eq!((9, 5), get_minor_ticks(50, None));
+		assert_eq!((4, 3), get_minor_ticks(15, None));
+		assert_eq!((0, 0), get_minor_ticks(7, None));
+		assert_eq!((0, 0), get_minor_ticks(50, Some(0)));
+		assert_eq!((3, 12), get_minor_ticks(50, Some(3)));
+		assert_eq!((0, 0), get_minor_ticks(3, Some(4)));
+	}
+	#[test]
+	fn grid_patterns_start_at_the_axis() {
+		let drawn = |style: AxisStyle, scale: u32| -> Vec<bool> {
+			(0..12).map(|d| style.is_grid_drawn(d, scale)).collect()
+		};
+		assert!(drawn(AxisStyle::default(), 1)
+			.iter()
+			.all(|is_drawn| *is_drawn));
+		let dashed = AxisStyle {
+			grid_style: GridStyle::Dashed,
+			grid_dash_length: 3,
+			..AxisStyle::default()
+		};
+		let pattern = [true, true, true, false, false, false];
+		assert_eq!(pattern.repeat(2), drawn(dashed, 1));
+		// a supersampled canvas stretches the pattern
+		assert_eq!(vec![true; 6], drawn(dashed, 2)[..6].to_vec());
+		let dotted = AxisStyle {
+			grid_style: GridStyle::Dotted,
+			..AxisStyle::default()
+		};
+		assert_eq!([true, false, false].repeat(4), drawn(dotted, 1));
+	}
+	#[test]
+	fn overlapping_labels_are_skipped() {
+		// labels 30 pixels wide with a gap of 5 fit between markers 40 apart
+		assert_eq!(1, get_tick_label_every(None, 30, 5, 40));
+		assert_eq!(2, get_tick_label_every(None, 30, 5, 20));
+		assert_eq!(2, get_tick_label_every(None, 35, 5, 20));
+		assert_eq!(3, get_tick_label_every(None, 36, 5, 20));
+		assert_eq!(1, get_tick_label_every(None, 30, 5, 0));
+		// an explicit choice is kept even when labels overlap
+		assert_eq!(1, get_tick_label_every(Some(1), 30, 5, 20));
+		assert_eq!(4, get_tick_label_every(Some(4), 30, 5, 40));
+	}
+	#[test]
+	fn custom_ticks_within_the_axis_are_labelled() {
+		let ticks: Vec<CustomTick> =
+			ron::from_str(r#"[0.0, (2.5, "half"), 4.75, -1.0, 9.5]"#).unwrap();
+		assert_eq!(
+			vec![
+				(0.0, String::from("0")),
+				(2.5, String::from("half")),
+				// written to the precision of the most precise value
+				(4.75, String::from("4.75")),
+			],
+			get_custom_ticks("x", &ticks, (0, 7), &TickFormat::default())
+		);
+		let ticks = [CustomTick::Value(1.0), CustomTick::Value(2.125)];
+		assert_eq!(
+			vec![(1.0, String::from("1")), (2.125, String::from("2.125"))],
+			get_custom_ticks("y", &ticks, (0, 5), &TickFormat::default())
+		);
+	}
+	#[test]
+	fn reversed_values_mirror_about_the_middle() {
+		assert_eq!(70.0, get_reversed_value(30.0, (0, 100), true));
+		assert_eq!(30.0, get_reversed_value(30.0, (0, 100), false));
+		assert_eq!(25.0, get_reversed_value(35.0, (20, 40), true));
+		// paired axes are symmetric about zero
+		assert_eq!(-4.0, get_reversed_value(4.0, (-10, 10), true));
+	}
+	#[test]
+	fn aligned_limits_share_the_divisions() {
+		assert_eq!((0, 100), get_aligned_axis_limits((3.0, 96.0), 10));
+		assert_eq!((20, 70), get_aligned_axis_limits((21.0, 68.0), 5));
+		// a range which overhangs the first nice step takes a larger one
+		assert_eq!((0, 200), get_aligned_axis_limits((5.0, 104.0), 10));
+		assert_eq!((0, 4), get_aligned_axis_limits((0.2, 0.9), 4));
+	}
 }
@@ -1,20 +1,35 @@
 //! Based on a type of BestFit this module will calculate the valid data points for the given axes/canvas size
 
 use std::{
-	collections::HashMap,
+	collections::BTreeMap,
 	f32::consts::{E, PI},
 };
 
-use serde::Deserialize;
-use tracing::{error, trace};
+use image::{ImageBuffer, Rgba};
+use rusttype::{Font, PositionedGlyph};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, trace, warn};
 
 use crate::{
-	canvas::plot::{DataPoint, DataSymbol},
+	canvas::{
+		axes::get_line_extents,
+		format::{format_value, FormatHint},
+		glyphs::{create_glyphs, draw_glyphs, get_maximum_height_of_glyphs, get_width_of_glyphs},
+		legend::PixelBounds,
+		line::{find_antialiased_line_pixels, find_line_pixels},
+		plot::{DataPoint, DataSymbol},
+		put_pixel_block, PixelStamp,
+	},
 	colours::Colour,
+	error::PlotError,
 };
 
+/// Highest degree of polynomial a `PolynomialRegression` fits, the normal equations of higher degrees are
+/// too poorly conditioned to solve reliably
+pub const MAX_REGRESSION_DEGREE: u32 = 8;
+
 /// Types of curve that can be fitted to a graph
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub enum BestFit {
 	/// Equation of a straight line, `y = mx + c`
 	Linear {
@@ -51,7 +66,8 @@ pub enum BestFit {
 	},
 	/// Equation of form `y = a + bx + cx^2 + dx^3....n`
 	///
-	/// Each `HashMap<u32, f32>` key corresponds to an `nth` order power while the value is the coefficient.
+	/// Each `BTreeMap<u32, f32>` key corresponds to an `nth` order power while the value is the coefficient.
+	/// Terms are summed in ascending order of power so the curve is identical between runs.
 	/// ```txt
 	/// let mut y = 0.0;
 	/// for (k, v) in coefficients.iter() {
@@ -63,7 +79,54 @@ pub enum BestFit {
 	/// `Some(GenericPolynomial(coefficients: {0: 1.0, 1: 1.0, 2: 1.0, 3: 1.0, 4: -1.0}, colour: Black))`
 	GenericPolynomial {
 		/// Keys are powers `x` will be raised by and values are the coefficient
-		coefficients: HashMap<u32, f32>,
+		coefficients: BTreeMap<u32, f32>,
+		/// The colour of the best fit curve
+		colour: Colour,
+	},
+	/// A polynomial of the given degree fitted to the points of its data set by least squares, drawn as a
+	/// `GenericPolynomial` of the fitted coefficients
+	///
+	/// For instance a cubic trend could be found with `Some(PolynomialRegression(degree: 3, colour: Black))`
+	PolynomialRegression {
+		/// Highest power of `x` in the fitted polynomial, at most [`MAX_REGRESSION_DEGREE`]
+		degree: u32,
+		/// The colour of the best fit curve
+		colour: Colour,
+	},
+	/// Equation of form `y = a * ln(x) + b`, the curve is only drawn where `x > 0`
+	///
+	/// For instance `Some(Logarithmic(a: 2.0, b: 1.0, colour: Black))`
+	Logarithmic {
+		/// Coefficient of the natural logarithm of `x`
+		a: f32,
+		/// Vertical offset, the value of `y` at `x = 1`
+		b: f32,
+		/// The colour of the best fit curve
+		colour: Colour,
+	},
+	/// A `Logarithmic` curve fitted to the points of its data set with `x > 0` by least squares
+	///
+	/// For instance `Some(LogarithmicRegression(colour: Black))`
+	LogarithmicRegression {
+		/// The colour of the best fit curve
+		colour: Colour,
+	},
+	/// Equation of form `y = a * x^b`, a negative exponent leaves the curve undefined at `x = 0`
+	///
+	/// For instance `Some(PowerLaw(constant: 3.0, exponent: -0.5, colour: Black))`
+	PowerLaw {
+		/// Coefficient/size of the power law
+		constant: f32,
+		/// The power `x` is raised by
+		exponent: f32,
+		/// The colour of the best fit curve
+		colour: Colour,
+	},
+	/// A `PowerLaw` curve fitted to the points of its data set with positive `x` and `y` by least squares
+	/// of `ln(y)` against `ln(x)`
+	///
+	/// For instance `Some(PowerLawRegression(colour: Black))`
+	PowerLawRegression {
 		/// The colour of the best fit curve
 		colour: Colour,
 	},
@@ -80,25 +143,85 @@ pub enum BestFit {
 		/// The colour of the best fit curve
 		colour: Colour,
 	},
-	/// Probability distribution of the form `y = (o*sqrt(2pi))^-1 * e^(-(x -u)^2/2o^2)`
+	/// An `Exponential` curve of base `e` without a vertical shift fitted to the points of its data set by
+	/// least squares of `ln(y)` against `x`, every `y` must be positive
 	///
-	/// `y = (variance * (2.0 * PI).sqrt()).powf(-1.0) * E.powf(-(x - expected_value).powf(2.0) / (2.0 * variance.powf(2.0)))`
+	/// For instance `Some(ExponentialRegression(colour: Black))`
+	ExponentialRegression {
+		/// The colour of the best fit curve
+		colour: Colour,
+	},
+	/// Equation of form `y = l / (1 + e^(-k(x - x0)))`, an S shaped curve levelling off at zero and `l` such as
+	/// a dose-response curve
+	///
+	/// For instance `Some(Logistic(l: 10.0, k: 1.5, x0: 4.0, colour: Black))`
+	Logistic {
+		/// The value the curve levels off at as `x` grows, for a positive `k`
+		l: f32,
+		/// Steepness of the curve, a negative steepness falls from `l` to zero
+		k: f32,
+		/// The midpoint, where `y = l / 2`
+		x0: f32,
+		/// The colour of the best fit curve
+		colour: Colour,
+	},
+	/// A `Logistic` curve fitted to the points of its data set by least squares, starting from the best of
+	/// a grid of steepnesses and midpoints and refined with Gauss-Newton steps
+	///
+	/// For instance `Some(LogisticRegression(colour: Black))`
+	LogisticRegression {
+		/// The colour of the best fit curve
+		colour: Colour,
+	},
+	/// Bell curve of the form `y = a * e^(-(x - u)^2/2o^2)`, without an amplitude `a` the curve is the
+	/// probability distribution `y = (o*sqrt(2pi))^-1 * e^(-(x -u)^2/2o^2)`
+	///
+	/// For instance `Some(Gaussian(amplitude: 4.0, mean: 0.0, std_dev: 0.3, colour: Black))`. The mean and
+	/// standard deviation may also be written `expected_value` and `variance`
 	Gaussian {
-		/// Weighted average
-		expected_value: f32,
-		/// Deviation
-		variance: f32,
+		/// Optional, height of the peak, defaults to `(std_dev * (2.0 * PI).sqrt()).powf(-1.0)` so that the
+		/// area under the curve is one
+		#[serde(
+			default,
+			deserialize_with = "crate::deserialize_some",
+			serialize_with = "crate::serialize_some",
+			skip_serializing_if = "Option::is_none"
+		)]
+		amplitude: Option<f32>,
+		/// Centre of the peak, the weighted average
+		#[serde(alias = "expected_value")]
+		mean: f32,
+		/// Standard deviation, the width of the peak, must be greater than zero
+		#[serde(alias = "variance")]
+		std_dev: f32,
+		/// The colour of the best fit curve
+		colour: Colour,
+	},
+	/// A `Gaussian` whose mean and standard deviation are those of the `x` values of the points of its data
+	/// set weighted by their `y` values, such as a histogram, with the amplitude fitted by least squares.
+	/// Points with a negative `y` are given no weight
+	///
+	/// For instance `Some(GaussianRegression(colour: Black))`
+	GaussianRegression {
+		/// The colour of the best fit curve
+		colour: Colour,
+	},
+	/// Equation of form `y = a(1 - n^(-bx)) + c`, a curve rising from `c` at `x = 0` and levelling off
+	/// towards `a + c` such as the charge of a capacitor
+	///
+	/// For instance `Some(ExponentialApproach(constant: 2.0, base: 2.7, power: 0.5, vertical_shift: 1.0, colour: Black))`
+	ExponentialApproach {
+		/// The distance the curve rises from its start to its limit
+		constant: f32,
+		/// The base
+		base: f32,
+		/// How quickly the curve approaches its limit
+		power: f32,
+		/// Vertical offset from the origin, the value of `y` at `x = 0`
+		vertical_shift: f32,
 		/// The colour of the best fit curve
 		colour: Colour,
 	},
-	// /// Equation of form `y = a(1 - n^(-bx)) + c`
-	// ExponentialApproach {
-	// 	constant: f32,
-	// 	base: f32,
-	// 	power: f32,
-	// 	vertical_shift: f32,
-	// 	colour: Colour,
-	// },
 	/// Equation of form `y = a * sin(bx + c) + d`
 	///
 	/// `y = amplitude * sin( period * x + phase_shift) + vertical_shift`
@@ -129,267 +252,2076 @@ pub enum BestFit {
 		/// The colour of the best fit curve
 		colour: Colour,
 	},
+	/// A horizontal reference line at a constant `y` across the whole x-axis, such as a specification limit
+	///
+	/// For instance `Some(HorizontalLine(y: 4.5, colour: Red))`
+	HorizontalLine {
+		/// The value of `y` the line is drawn at
+		y: f32,
+		/// The colour of the best fit curve
+		colour: Colour,
+	},
+	/// A vertical reference line at a constant `x` up the whole y-axis, such as a deadline
+	///
+	/// For instance `Some(VerticalLine(x: 12.0, colour: Red))`
+	VerticalLine {
+		/// The value of `x` the line is drawn at
+		x: f32,
+		/// The colour of the best fit curve
+		colour: Colour,
+	},
+	/// The rolling mean of the points of its data set taken in order of `x`, each mean of `window`
+	/// consecutive points is placed at the mean of their `x` values and the means are joined by straight
+	/// lines. Smooths a noisy series rather than fitting an equation to it
+	///
+	/// For instance `Some(MovingAverage(window: 5, colour: Black))`
+	MovingAverage {
+		/// How many consecutive points each mean is taken over, at least 1 and at most the number of points
+		window: usize,
+		/// The colour of the best fit curve
+		colour: Colour,
+	},
+	/// A smooth curve passing exactly through every point of its data set, such as a sparse calibration
+	/// table. The points are taken in order of `x`, points sharing an `x` are averaged, and joined by a
+	/// monotone cubic spline which does not overshoot between points. Two points are joined by a straight
+	/// line
+	///
+	/// For instance `Some(Interpolate(colour: Black))`
+	Interpolate {
+		/// The colour of the best fit curve
+		colour: Colour,
+	},
+	/// Cubic pieces joining points in ascending order of `x`, the curve an `Interpolate` is drawn as
+	#[serde(skip)]
+	Spline {
+		/// The `(x, y)` knots of the curve in ascending order of `x`
+		points: Vec<(f32, f32)>,
+		/// The gradient of the curve at each knot
+		slopes: Vec<f32>,
+		/// The colour of the best fit curve
+		colour: Colour,
+	},
+	/// Straight lines joining points in ascending order of `x`, the curve a `MovingAverage` is drawn as
+	#[serde(skip)]
+	Polyline {
+		/// The `(x, y)` corners of the line in ascending order of `x`
+		points: Vec<(f32, f32)>,
+		/// The colour of the best fit curve
+		colour: Colour,
+	},
 }
 
 impl BestFit {
-	/// Based on the type of `BestFit` curve generate its coordinates within the given bounds and a scale factor is used to create a seamless curve, i.e a large number of tightly knit points to create the illusion of a line
-	pub fn find_coordinates(
-		&self,
-		x_min: i32,
-		x_max: i32,
-		y_min: i32,
-		y_max: i32,
-		scale_factor: i32,
-	) -> Vec<DataPoint> {
+	/// The colour the curve should be drawn with
+	pub fn colour(&self) -> Colour {
+		match self {
+			BestFit::Linear { colour, .. }
+			| BestFit::Quadratic { colour, .. }
+			| BestFit::Cubic { colour, .. }
+			| BestFit::GenericPolynomial { colour, .. }
+			| BestFit::PolynomialRegression { colour, .. }
+			| BestFit::Logarithmic { colour, .. }
+			| BestFit::LogarithmicRegression { colour }
+			| BestFit::PowerLaw { colour, .. }
+			| BestFit::PowerLawRegression { colour }
+			| BestFit::Exponential { colour, .. }
+			| BestFit::ExponentialApproach { colour, .. }
+			| BestFit::Logistic { colour, .. }
+			| BestFit::LogisticRegression { colour }
+			| BestFit::ExponentialRegression { colour }
+			| BestFit::MovingAverage { colour, .. }
+			| BestFit::Polyline { colour, .. }
+			| BestFit::Interpolate { colour }
+			| BestFit::Spline { colour, .. }
+			| BestFit::HorizontalLine { colour, .. }
+			| BestFit::VerticalLine { colour, .. }
+			| BestFit::Gaussian { colour, .. }
+			| BestFit::GaussianRegression { colour }
+			| BestFit::Sine { colour, .. }
+			| BestFit::Cosine { colour, .. } => *colour,
+		}
+	}
+	/// Ensure the parameters of the curve can be evaluated
+	pub fn validate(&self) -> Result<(), PlotError> {
+		match self {
+			BestFit::Exponential { base, .. } if *base <= 0.0 => Err(PlotError::Config(format!(
+				"The base used in an exponential best fit must be greater than zero, you specified {}",
+				base
+			))),
+			BestFit::ExponentialApproach { base, .. } if *base <= 0.0 => {
+				Err(PlotError::Config(format!(
+					"The base used in an exponential approach best fit must be greater than zero, you specified {}",
+					base
+				)))
+			}
+			BestFit::PolynomialRegression { degree, .. } if *degree > MAX_REGRESSION_DEGREE => {
+				Err(PlotError::Config(format!(
+					"A polynomial regression of degree {} is too poorly conditioned to fit reliably, the degree must be at most {}",
+					degree, MAX_REGRESSION_DEGREE
+				)))
+			}
+			BestFit::HorizontalLine { y, .. } if !y.is_finite() => Err(PlotError::Config(format!(
+				"The y value of a horizontal line must be finite, you specified {}",
+				y
+			))),
+			BestFit::VerticalLine { x, .. } if !x.is_finite() => Err(PlotError::Config(format!(
+				"The x value of a vertical line must be finite, you specified {}",
+				x
+			))),
+			BestFit::MovingAverage { window: 0, .. } => Err(PlotError::Config(
+				"The window of a moving average must be at least 1 point".to_owned(),
+			)),
+			// prevvent dividing by zero
+			BestFit::Gaussian { std_dev, .. } if !std_dev.is_normal() || *std_dev < 0.0 => {
+				Err(PlotError::Config(format!(
+					"The std_dev of a gaussian best fit must be greater than zero and finite, you specified {}",
+					std_dev
+				)))
+			}
+			BestFit::Gaussian {
+				amplitude: Some(amplitude),
+				..
+			} if !amplitude.is_finite() => Err(PlotError::Config(format!(
+				"The amplitude of a gaussian best fit must be finite, you specified {}",
+				amplitude
+			))),
+			_ => Ok(()),
+		}
+	}
+	/// Find the `y` value of the curve at `x`
+	pub fn evaluate(&self, x: f32) -> f32 {
 		match self {
 			BestFit::Linear {
 				gradient,
 				y_intercept,
-				colour,
-			} => {
-				trace!("Finding coordinates for Linear best fit line with gradient {}, y_intercept {} and between ({}, {}) and ({}, {})", gradient, y_intercept, x_min, y_min, x_max, y_max);
-				let mut points: Vec<DataPoint> = Vec::new();
-				for scaled_x in (x_min * scale_factor)..=(x_max * scale_factor) {
-					let x = scaled_x as f32 / scale_factor as f32;
-					let y = (*gradient * x) + *y_intercept;
-					if y > y_min as f32 && y < y_max as f32 {
-						points.push(DataPoint {
-							x,
-							ux: None,
-							y,
-							uy: None,
-							colour: *colour,
-							symbol: DataSymbol::Point,
-							symbol_radius: 1,
-							symbol_thickness: 1,
-						});
-					}
-				}
-				points
-			}
+				..
+			} => (*gradient * x) + *y_intercept,
 			BestFit::Quadratic {
 				intercept,
 				linear_coeff,
 				quadratic_coeff,
-				colour,
-			} => {
-				trace!("Finding coordinates for Quadratic best fit line with intercept {}, linear coefficient {} and quadratic coefficient {}", intercept, linear_coeff, quadratic_coeff);
-				let mut points: Vec<DataPoint> = Vec::new();
-				for scaled_x in (x_min * scale_factor)..=(x_max * scale_factor) {
-					let x = scaled_x as f32 / scale_factor as f32;
-					let y = intercept + (linear_coeff * x) + (quadratic_coeff * x.powf(2.0));
-					if y > y_min as f32 && y < y_max as f32 {
-						points.push(DataPoint {
-							x,
-							ux: None,
-							y,
-							uy: None,
-							colour: *colour,
-							symbol: DataSymbol::Point,
-							symbol_radius: 1,
-							symbol_thickness: 1,
-						});
-					}
-				}
-				points
-			}
+				..
+			} => intercept + (linear_coeff * x) + (quadratic_coeff * x.powf(2.0)),
 			BestFit::Cubic {
 				intercept,
 				linear_coeff,
 				quadratic_coeff,
 				cubic_coeff,
-				colour,
+				..
 			} => {
-				trace!("Finding coordinates for Cubic best fit line with intercept {}, linear coefficient {}, quadratic coefficient {} and cubic coefficient {}", intercept, linear_coeff, quadratic_coeff, cubic_coeff);
-				let mut points: Vec<DataPoint> = Vec::new();
-				for scaled_x in (x_min * scale_factor)..=(x_max * scale_factor) {
-					let x = scaled_x as f32 / scale_factor as f32;
-					let y = intercept
-						+ (linear_coeff * x) + (quadratic_coeff * x.powf(2.0))
-						+ (cubic_coeff * x.powf(3.0));
-					if y > y_min as f32 && y < y_max as f32 {
-						points.push(DataPoint {
-							x,
-							ux: None,
-							y,
-							uy: None,
-							colour: *colour,
-							symbol: DataSymbol::Point,
-							symbol_radius: 1,
-							symbol_thickness: 1,
-						});
-					}
+				intercept
+					+ (linear_coeff * x)
+					+ (quadratic_coeff * x.powf(2.0))
+					+ (cubic_coeff * x.powf(3.0))
+			}
+			BestFit::GenericPolynomial { coefficients, .. } => {
+				let mut y = 0.0;
+				for (k, v) in coefficients.iter() {
+					y += v * x.powf(*k as f32);
 				}
-				points
+				y
 			}
-			BestFit::GenericPolynomial {
-				coefficients,
-				colour,
-			} => {
-				trace!("Finding coordinates for GenericPolynomial best fit line");
-				let mut points: Vec<DataPoint> = Vec::new();
-				for scaled_x in (x_min * scale_factor)..=(x_max * scale_factor) {
-					let x = scaled_x as f32 / scale_factor as f32;
-					let mut y = 0.0;
-					for (k, v) in coefficients.iter() {
-						y += v * x.powf(*k as f32);
-					}
-					if y > y_min as f32 && y < y_max as f32 {
-						points.push(DataPoint {
-							x,
-							ux: None,
-							y,
-							uy: None,
-							colour: *colour,
-							symbol: DataSymbol::Point,
-							symbol_radius: 1,
-							symbol_thickness: 1,
-						});
+			// the logarithm is undefined at and below zero
+			BestFit::Logarithmic { a, b, .. } if x > 0.0 => a * x.ln() + b,
+			BestFit::Logarithmic { .. } => f32::NAN,
+			// zero raised to a negative power is infinite
+			BestFit::PowerLaw { exponent, .. } if x == 0.0 && *exponent < 0.0 => f32::NAN,
+			BestFit::PowerLaw {
+				constant, exponent, ..
+			} => constant * x.powf(*exponent),
+			// a regression has no coefficients until it is fitted to its data, see `BestFit::fit`
+			BestFit::PolynomialRegression { .. }
+			| BestFit::LogarithmicRegression { .. }
+			| BestFit::PowerLawRegression { .. }
+			| BestFit::ExponentialRegression { .. }
+			| BestFit::LogisticRegression { .. }
+			| BestFit::GaussianRegression { .. }
+			| BestFit::Interpolate { .. }
+			| BestFit::MovingAverage { .. } => f32::NAN,
+			BestFit::HorizontalLine { y, .. } => *y,
+			// a vertical line has no single value of `y`, see `BestFit::find_coordinates`
+			BestFit::VerticalLine { .. } => f32::NAN,
+			BestFit::Polyline { points, .. } => {
+				// the first corner at or beyond `x`, the line has no value beyond its ends
+				let after = points.partition_point(|(corner_x, _)| *corner_x < x);
+				match (after.checked_sub(1).map(|i| points[i]), points.get(after)) {
+					(_, Some((x1, y1))) if *x1 == x => *y1,
+					(Some((x0, y0)), Some((x1, y1))) => y0 + (y1 - y0) * (x - x0) / (x1 - x0),
+					_ => f32::NAN,
+				}
+			}
+			BestFit::Spline { points, slopes, .. } => {
+				// the knot at or beyond `x`, the curve has no value beyond its ends
+				let after = points.partition_point(|(knot_x, _)| *knot_x < x);
+				match (after.checked_sub(1), points.get(after)) {
+					(_, Some((x1, y1))) if *x1 == x => *y1,
+					(Some(before), Some((x1, y1))) => {
+						let (x0, y0) = points[before];
+						let width = x1 - x0;
+						let t = (x - x0) / width;
+						// cubic Hermite basis
+						(2.0 * t.powi(3) - 3.0 * t.powi(2) + 1.0) * y0
+							+ (t.powi(3) - 2.0 * t.powi(2) + t) * width * slopes[before]
+							+ (-2.0 * t.powi(3) + 3.0 * t.powi(2)) * y1
+							+ (t.powi(3) - t.powi(2)) * width * slopes[after]
 					}
+					_ => f32::NAN,
 				}
-				points
 			}
 			BestFit::Exponential {
 				constant,
 				base,
 				power,
 				vertical_shift,
-				colour,
+				..
+			} => (constant * base.powf(power * x)) + vertical_shift,
+			BestFit::Gaussian {
+				amplitude,
+				mean,
+				std_dev,
+				..
 			} => {
-				trace!("Finding coordinates for Exponential best fit line with constant {}, base {}, power {} and vertica shift {}", constant, base, power, vertical_shift);
-				if *base <= 0.0 {
-					error!("The base used in an exponential best fit must be greater than zero, you specified {}", base);
-					std::process::exit(1);
+				amplitude.unwrap_or_else(|| (std_dev * (2.0 * PI).sqrt()).powf(-1.0))
+					* E.powf(-(x - mean).powf(2.0) / (2.0 * std_dev.powf(2.0)))
+			}
+			BestFit::ExponentialApproach {
+				constant,
+				base,
+				power,
+				vertical_shift,
+				..
+			} => constant * (1.0 - base.powf(-power * x)) + vertical_shift,
+			BestFit::Logistic { l, k, x0, .. } => l / (1.0 + (-k * (x - x0)).exp()),
+			BestFit::Sine {
+				amplitude,
+				period,
+				phase_shift,
+				vertical_shift,
+				..
+			} => amplitude * ((period * x) + phase_shift).sin() + vertical_shift,
+			BestFit::Cosine {
+				amplitude,
+				period,
+				phase_shift,
+				vertical_shift,
+				..
+			} => amplitude * ((period * x) + phase_shift).cos() + vertical_shift,
+		}
+	}
+	/// Replace a regression with the curve it fits to the `(x, y)` `points` of its data set, any other curve
+	/// already has its parameters and is returned as it is
+	pub fn fit(&self, points: &[(f32, f32)]) -> Result<BestFit, PlotError> {
+		self.validate()?;
+		match self {
+			BestFit::PolynomialRegression { degree, colour } => {
+				let coefficients = fit_polynomial(*degree, points)?;
+				info!(
+					"Fitted a degree {} polynomial to {} points with coefficients {:?}",
+					degree,
+					points.len(),
+					coefficients
+				);
+				Ok(BestFit::GenericPolynomial {
+					coefficients: coefficients
+						.iter()
+						.enumerate()
+						.map(|(power, coefficient)| (power as u32, *coefficient as f32))
+						.collect(),
+					colour: *colour,
+				})
+			}
+			BestFit::LogarithmicRegression { colour } => {
+				// a straight line through `(ln(x), y)`
+				let logarithmic: Vec<(f32, f32)> = points
+					.iter()
+					.filter(|(x, _)| *x > 0.0)
+					.map(|(x, y)| (x.ln(), *y))
+					.collect();
+				let coefficients = fit_polynomial(1, &logarithmic).map_err(|_| {
+					PlotError::Config(format!(
+						"A logarithmic regression needs at least 2 points with different positive x values, found {} points with positive x",
+						logarithmic.len()
+					))
+				})?;
+				info!(
+					"Fitted a logarithm to {} points with coefficients {:?}",
+					logarithmic.len(),
+					coefficients
+				);
+				Ok(BestFit::Logarithmic {
+					a: coefficients[1] as f32,
+					b: coefficients[0] as f32,
+					colour: *colour,
+				})
+			}
+			BestFit::PowerLawRegression { colour } => {
+				// a straight line through `(ln(x), ln(y))`
+				let log_log: Vec<(f32, f32)> = points
+					.iter()
+					.filter(|(x, y)| *x > 0.0 && *y > 0.0)
+					.map(|(x, y)| (x.ln(), y.ln()))
+					.collect();
+				let coefficients = fit_polynomial(1, &log_log).map_err(|_| {
+					PlotError::Config(format!(
+						"A power law regression needs at least 2 points with different positive x values and positive y values, found {} points with positive x and y",
+						log_log.len()
+					))
+				})?;
+				info!(
+					"Fitted a power law to {} points with coefficients {:?}",
+					log_log.len(),
+					coefficients
+				);
+				Ok(BestFit::PowerLaw {
+					constant: coefficients[0].exp() as f32,
+					exponent: coefficients[1] as f32,
+					colour: *colour,
+				})
+			}
+			BestFit::ExponentialRegression { colour } => {
+				if let Some((x, y)) = points.iter().find(|(_, y)| *y <= 0.0) {
+					return Err(PlotError::Config(format!(
+						"An exponential regression needs every y value to be positive to take its logarithm, found {} at x = {}",
+						y, x
+					)));
 				}
-				let mut points: Vec<DataPoint> = Vec::new();
-				for scaled_x in (x_min * scale_factor)..=(x_max * scale_factor) {
-					let x = scaled_x as f32 / scale_factor as f32;
-					let y = (constant * base.powf(power * x)) + vertical_shift;
-					if y > y_min as f32 && y < y_max as f32 {
-						points.push(DataPoint {
-							x,
-							ux: None,
-							y,
-							uy: None,
-							colour: *colour,
-							symbol: DataSymbol::Point,
-							symbol_radius: 1,
-							symbol_thickness: 1,
-						});
-					}
+				// a straight line through `(x, ln(y))`
+				let logarithmic: Vec<(f32, f32)> =
+					points.iter().map(|(x, y)| (*x, y.ln())).collect();
+				let coefficients = fit_polynomial(1, &logarithmic).map_err(|_| {
+					PlotError::Config(format!(
+						"An exponential regression needs at least 2 points with different x values, found {} points",
+						points.len()
+					))
+				})?;
+				let curve = BestFit::Exponential {
+					constant: coefficients[0].exp() as f32,
+					base: E,
+					power: coefficients[1] as f32,
+					vertical_shift: 0.0,
+					colour: *colour,
+				};
+				info!(
+					"Fitted an exponential to {} points, {}",
+					points.len(),
+					curve.equation().unwrap_or_default()
+				);
+				Ok(curve)
+			}
+			BestFit::LogisticRegression { colour } => {
+				let (l, k, x0) = fit_logistic(points)?;
+				let curve = BestFit::Logistic {
+					l: l as f32,
+					k: k as f32,
+					x0: x0 as f32,
+					colour: *colour,
+				};
+				info!(
+					"Fitted a logistic curve to {} points, {}",
+					points.len(),
+					curve.equation().unwrap_or_default()
+				);
+				Ok(curve)
+			}
+			BestFit::GaussianRegression { colour } => {
+				let (amplitude, mean, std_dev) = fit_gaussian(points)?;
+				let curve = BestFit::Gaussian {
+					amplitude: Some(amplitude as f32),
+					mean: mean as f32,
+					std_dev: std_dev as f32,
+					colour: *colour,
+				};
+				info!(
+					"Fitted a gaussian to {} points, {}",
+					points.len(),
+					curve.equation().unwrap_or_default()
+				);
+				Ok(curve)
+			}
+			BestFit::MovingAverage { window, colour } => {
+				if *window > points.len() {
+					return Err(PlotError::Config(format!(
+						"The window of a moving average cannot be larger than its data set, found a window of {} over {} points",
+						window,
+						points.len()
+					)));
 				}
-				points
+				let mut sorted = points.to_vec();
+				sorted.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+				let points: Vec<(f32, f32)> = sorted
+					.windows(*window)
+					.map(|values| {
+						let count = values.len() as f64;
+						let (x_sum, y_sum) =
+							values.iter().fold((0.0, 0.0), |(x_sum, y_sum), (x, y)| {
+								(x_sum + *x as f64, y_sum + *y as f64)
+							});
+						((x_sum / count) as f32, (y_sum / count) as f32)
+					})
+					.collect();
+				info!(
+					"Found a moving average of {} points over {} points",
+					window,
+					sorted.len()
+				);
+				Ok(BestFit::Polyline {
+					points,
+					colour: *colour,
+				})
 			}
-			BestFit::Gaussian {
-				expected_value,
-				variance,
-				colour,
+			BestFit::Interpolate { colour } => fit_monotone_spline(points, *colour),
+			curve => Ok(curve.clone()),
+		}
+	}
+	/// The equation of the curve with its parameters rounded to three significant figures, e.g
+	/// `y = 2.31·e^(0.42x)`. `None` for a regression which has not been fitted to its data yet or a curve
+	/// without an equation such as a `MovingAverage`
+	pub fn equation(&self) -> Option<String> {
+		let expression = match self {
+			BestFit::Linear {
+				gradient,
+				y_intercept,
+				..
+			} => format_sum(&[(*gradient, "x"), (*y_intercept, "")]),
+			BestFit::Quadratic {
+				intercept,
+				linear_coeff,
+				quadratic_coeff,
+				..
+			} => format_sum(&[
+				(*quadratic_coeff, "x^2"),
+				(*linear_coeff, "x"),
+				(*intercept, ""),
+			]),
+			BestFit::Cubic {
+				intercept,
+				linear_coeff,
+				quadratic_coeff,
+				cubic_coeff,
+				..
+			} => format_sum(&[
+				(*cubic_coeff, "x^3"),
+				(*quadratic_coeff, "x^2"),
+				(*linear_coeff, "x"),
+				(*intercept, ""),
+			]),
+			BestFit::GenericPolynomial { coefficients, .. } => {
+				let powers: Vec<String> = coefficients
+					.keys()
+					.map(|power| match power {
+						0 => String::new(),
+						1 => String::from("x"),
+						power => format!("x^{}", power),
+					})
+					.collect();
+				// highest power first
+				let terms: Vec<(f32, &str)> = coefficients
+					.values()
+					.cloned()
+					.zip(powers.iter().map(String::as_str))
+					.rev()
+					.collect();
+				format_sum(&terms)
+			}
+			BestFit::Logarithmic { a, b, .. } => format_sum(&[(*a, "ln(x)"), (*b, "")]),
+			BestFit::PowerLaw {
+				constant, exponent, ..
 			} => {
-				trace!("Finding coordinates for Gaussian best fit line with expected_value {} and varience {}", expected_value, variance);
-				// prevvent dividing by zero
-				if !variance.is_normal() {
-					error!("Variance cannot be zero, infinite, subnormal or NaN");
-					std::process::exit(1)
-				}
-				let mut points: Vec<DataPoint> = Vec::new();
-				for scaled_x in (x_min * scale_factor)..=(x_max * scale_factor) {
-					let x = scaled_x as f32 / scale_factor as f32;
-					let y = (variance * (2.0 * PI).sqrt()).powf(-1.0)
-						* E.powf(-(x - expected_value).powf(2.0) / (2.0 * variance.powf(2.0)));
-					if y > y_min as f32 && y < y_max as f32 {
-						points.push(DataPoint {
-							x,
-							ux: None,
-							y,
-							uy: None,
-							colour: *colour,
-							symbol: DataSymbol::Point,
-							symbol_radius: 1,
-							symbol_thickness: 1,
-						});
-					}
-				}
-				points
-			}
-			// BestFit::ExponentialApproach { constant, base, power, vertical_shift, colour } => {
-			// 	trace!("Finding coordinates for ExponentialApproach best fit line with constant {}, base {}, power {} and vertica shift {}", constant, base, power, vertical_shift);
-			// 	if *base <= 0.0 {
-			// 		error!("The base used in an exponential best fit must be greater than zero, you specified {}", base);
-			// 		std::process::exit(1);
-			// 	}
-			// 	let mut points: Vec<DataPoint> = Vec::new();
-			// 	for scaled_x in x_min..=(x_max * scale_factor) {
-			// 		let x = scaled_x as f32 / scale_factor as f32;
-			// 		let y = constant * (1.0 - base.powf(-power * x)) + vertical_shift;
-			// 		if y > y_min as f32 && y < y_max as f32 {
-			// 			points.push(DataPoint {
-			// 				x: x,
-			// 				ux: None,
-			// 				y: y,
-			// 				uy: None,
-			// 				colour: *colour,
-			// 				symbol: DataSymbol::Point,
-			// 				symbol_radius: 1,
-			// 				symbol_thickness: 1,
-			// 			});
-			// 		}
-			// 	}
-			// 	return points
-			// },
+				let exponent = format_parameter(*exponent);
+				let power = if exponent.chars().all(|c| c.is_ascii_digit()) {
+					format!("x^{}", exponent)
+				} else {
+					format!("x^({})", exponent)
+				};
+				format_sum(&[(*constant, &power)])
+			}
+			BestFit::Exponential {
+				constant,
+				base,
+				power,
+				vertical_shift,
+				..
+			} => {
+				let base = if (base - E).abs() < f32::EPSILON {
+					String::from("e")
+				} else {
+					format_parameter(*base)
+				};
+				let exponential = format!("{}^({})", base, format_sum(&[(*power, "x")]));
+				format_sum(&[(*constant, &exponential), (*vertical_shift, "")])
+			}
+			BestFit::ExponentialApproach {
+				constant,
+				base,
+				power,
+				vertical_shift,
+				..
+			} => {
+				let base = if (base - E).abs() < f32::EPSILON {
+					String::from("e")
+				} else {
+					format_parameter(*base)
+				};
+				let approach = format!("(1 - {}^({}))", base, format_sum(&[(-power, "x")]));
+				format_sum(&[(*constant, &approach), (*vertical_shift, "")])
+			}
+			BestFit::HorizontalLine { y, .. } => format_parameter(*y),
+			BestFit::VerticalLine { x, .. } => {
+				return Some(format!("x = {}", format_parameter(*x)))
+			}
+			BestFit::Logistic { l, k, x0, .. } => {
+				let shifted = if *x0 == 0.0 {
+					String::from("x")
+				} else {
+					format!("({})", format_sum(&[(1.0, "x"), (-x0, "")]))
+				};
+				format!(
+					"{} / (1 + e^({}))",
+					format_parameter(*l),
+					format_sum(&[(-k, &shifted)])
+				)
+			}
+			BestFit::Gaussian {
+				amplitude: None,
+				mean,
+				std_dev,
+				..
+			} => format!(
+				"gaussian(μ = {}, σ = {})",
+				format_parameter(*mean),
+				format_parameter(*std_dev)
+			),
+			BestFit::Gaussian {
+				amplitude: Some(amplitude),
+				mean,
+				std_dev,
+				..
+			} => format!(
+				"{}, μ = {}, σ = {}",
+				format_sum(&[(*amplitude, "e^(-(x - μ)^2 / 2σ^2)")]),
+				format_parameter(*mean),
+				format_parameter(*std_dev)
+			),
 			BestFit::Sine {
 				amplitude,
 				period,
 				phase_shift,
 				vertical_shift,
-				colour,
+				..
 			} => {
-				trace!("Finding coordinates for Sinusoidal best fit line with amplitude {}, period {}, phase shift {} and vertical shift {}", amplitude, period, phase_shift, vertical_shift);
-				let mut points: Vec<DataPoint> = Vec::new();
-				for scaled_x in (x_min * scale_factor)..=(x_max * scale_factor) {
-					let x = scaled_x as f32 / scale_factor as f32;
-					let y = amplitude * ((period * x) + phase_shift).sin() + vertical_shift;
-					if y > y_min as f32 && y < y_max as f32 {
-						points.push(DataPoint {
-							x,
-							ux: None,
-							y,
-							uy: None,
-							colour: *colour,
-							symbol: DataSymbol::Point,
-							symbol_radius: 1,
-							symbol_thickness: 1,
-						});
-					}
-				}
-				points
+				let sine = format!("sin({})", format_sum(&[(*period, "x"), (*phase_shift, "")]));
+				format_sum(&[(*amplitude, &sine), (*vertical_shift, "")])
 			}
 			BestFit::Cosine {
 				amplitude,
 				period,
 				phase_shift,
 				vertical_shift,
+				..
+			} => {
+				let cosine = format!("cos({})", format_sum(&[(*period, "x"), (*phase_shift, "")]));
+				format_sum(&[(*amplitude, &cosine), (*vertical_shift, "")])
+			}
+			BestFit::PolynomialRegression { .. }
+			| BestFit::LogarithmicRegression { .. }
+			| BestFit::PowerLawRegression { .. }
+			| BestFit::ExponentialRegression { .. }
+			| BestFit::LogisticRegression { .. }
+			| BestFit::GaussianRegression { .. }
+			| BestFit::MovingAverage { .. }
+			| BestFit::Interpolate { .. }
+			| BestFit::Spline { .. }
+			| BestFit::Polyline { .. } => return None,
+		};
+		Some(format!("y = {}", expression))
+	}
+	/// The parameters of the curve worth naming in its legend entry, e.g `μ = 1.5, σ = 0.3` for a
+	/// `Gaussian`. `None` for the other curves, whose parameters are better read from their equation
+	pub fn summary(&self) -> Option<String> {
+		match self {
+			BestFit::Gaussian { mean, std_dev, .. } => Some(format!(
+				"μ = {}, σ = {}",
+				format_parameter(*mean),
+				format_parameter(*std_dev)
+			)),
+			_ => None,
+		}
+	}
+	/// Based on the type of `BestFit` curve generate its coordinates within the given bounds, sampled once
+	/// for each of the `columns` pixel columns the x-axis spans. Consecutive samples are joined by straight
+	/// lines when drawn, so the curve is split into runs wherever it is undefined, e.g a logarithm at
+	/// `x <= 0`, or leaves the y bounds. A run that leaves the y bounds is clipped to the edge it crosses
+	///
+	/// An x `domain` restricts the curve to part of the axis, it starts and stops exactly at the ends of the
+	/// domain which lie within the axis
+	pub fn find_coordinates(
+		&self,
+		x_min: i32,
+		x_max: i32,
+		y_min: i32,
+		y_max: i32,
+		columns: u32,
+		domain: Option<(f32, f32)>,
+	) -> Result<Vec<Vec<DataPoint>>, PlotError> {
+		trace!(
+			"Finding coordinates for best fit line {:?} between ({}, {}) and ({}, {})",
+			self,
+			x_min,
+			y_min,
+			x_max,
+			y_max
+		);
+		self.validate()?;
+		let colour = self.colour();
+		let (start, end) = match domain {
+			Some((start, end)) => (start.max(x_min as f32), end.min(x_max as f32)),
+			None => (x_min as f32, x_max as f32),
+		};
+		if start > end {
+			warn!(
+				"The domain {:?} of best fit line {:?} lies outside of the x-axis, no curve is drawn",
+				domain, self
+			);
+			return Ok(Vec::new());
+		}
+		// a vertical line runs up the whole y-axis from a single `x`, sampled in `y` instead
+		if let BestFit::VerticalLine { x, .. } = self {
+			if *x < start || *x > end {
+				warn!(
+					"The vertical line at x = {} lies outside of the x-axis, it is not drawn",
+					x
+				);
+				return Ok(Vec::new());
+			}
+			let columns = columns.max(1);
+			let step = (y_max - y_min) as f32 / columns as f32;
+			return Ok(vec![(0..=columns)
+				.map(|row| DataPoint {
+					x: *x,
+					ux: None,
+					y: y_min as f32 + row as f32 * step,
+					uy: None,
+					colour,
+					symbol: DataSymbol::Point,
+					symbol_radius: 1,
+					symbol_thickness: 1,
+					line_thickness: 1,
+					antialias: false,
+				})
+				.collect()]);
+		}
+		// one sample per pixel column of the whole axis so that the samples of every curve line up,
+		// bracketed by the exact ends of the domain
+		let columns = columns.max(1);
+		let step = (x_max - x_min) as f32 / columns as f32;
+		let samples = std::iter::once(start)
+			.chain((0..=columns).map(|column| x_min as f32 + column as f32 * step))
+			.chain(std::iter::once(end))
+			.filter(|x| *x >= start && *x <= end);
+		let point = |x: f32, y: f32| DataPoint {
+			x,
+			ux: None,
+			y,
+			uy: None,
+			colour,
+			symbol: DataSymbol::Point,
+			symbol_radius: 1,
+			symbol_thickness: 1,
+			line_thickness: 1,
+			antialias: false,
+		};
+		let (low, high) = (y_min as f32, y_max as f32);
+		let mut runs: Vec<Vec<DataPoint>> = Vec::new();
+		let mut run: Vec<DataPoint> = Vec::new();
+		let mut previous = f32::NEG_INFINITY;
+		let mut previous_y = f32::NAN;
+		for x in samples {
+			if x <= previous {
+				continue;
+			}
+			let y = self.evaluate(x);
+			let is_inside = y.is_finite() && y > low && y < high;
+			// a steep curve is clipped where it crosses the edge of the axis rather than stopping at the
+			// last sample within it, up to a whole column short
+			if is_inside && run.is_empty() && previous_y.is_finite() {
+				let edge = if previous_y >= high { high } else { low };
+				run.push(point(self.find_crossing(x, previous, edge), edge));
+			}
+			if is_inside {
+				run.push(point(x, y));
+			} else if !run.is_empty() {
+				if y.is_finite() {
+					let edge = if y >= high { high } else { low };
+					run.push(point(self.find_crossing(previous, x, edge), edge));
+				}
+				runs.push(std::mem::take(&mut run));
+			}
+			previous = x;
+			previous_y = y;
+		}
+		if !run.is_empty() {
+			runs.push(run);
+		}
+		Ok(runs)
+	}
+	/// Bisect between an x `inside` the y bounds and one `outside` of them for where the curve reaches
+	/// the `edge` of the bounds it leaves by
+	fn find_crossing(&self, inside: f32, outside: f32, edge: f32) -> f32 {
+		let is_beyond = |y: f32| (y >= edge) == (self.evaluate(outside) >= edge);
+		let (mut inside, mut outside) = (inside, outside);
+		for _ in 0..CROSSING_BISECTIONS {
+			let middle = (inside + outside) / 2.0;
+			if is_beyond(self.evaluate(middle)) {
+				outside = middle;
+			} else {
+				inside = middle;
+			}
+		}
+		(inside + outside) / 2.0
+	}
+}
+
+/// How many times the gap between two samples is halved to find where a curve crosses the edge of the
+/// y-axis, enough to land within a pixel on any canvas
+const CROSSING_BISECTIONS: u32 = 24;
+/// How many dashes of a `LineStyle::Dashed` curve fit along the x-axis
+const DASHES_PER_AXIS: f32 = 40.0;
+/// How many dots of a `LineStyle::Dotted` curve fit along the x-axis
+const DOTS_PER_AXIS: f32 = 120.0;
+
+/// The pattern a best fit curve is drawn with
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Default)]
+pub enum LineStyle {
+	/// An unbroken curve
+	#[default]
+	Solid,
+	/// Dashes separated by gaps of the same width in `x`
+	Dashed,
+	/// Short dots separated by gaps three times their width in `x`
+	Dotted,
+}
+
+impl LineStyle {
+	/// Whether the curve is drawn at `x` on an x-axis spanning `x_limits`. The pattern is measured in `x`
+	/// from the start of the axis so that dashes are equally wide whatever the slope of the curve and line
+	/// up between curves
+	pub fn is_drawn(self, x: f32, x_limits: (i32, i32)) -> bool {
+		let span = (x_limits.1 - x_limits.0).max(1) as f32;
+		let (count, fraction_drawn) = match self {
+			LineStyle::Solid => return true,
+			LineStyle::Dashed => (DASHES_PER_AXIS, 0.5),
+			LineStyle::Dotted => (DOTS_PER_AXIS, 0.25),
+		};
+		let period = span / count;
+		((x - x_limits.0 as f32) / period).rem_euclid(1.0) < fraction_drawn
+	}
+}
+
+/// Canvas width in pixels each pixel of the default thickness of a best fit curve is given
+pub const BEST_FIT_WIDTH_PER_THICKNESS: u32 = 1000;
+
+/// The thickness of a best fit curve drawn without one configured, one pixel for every
+/// `BEST_FIT_WIDTH_PER_THICKNESS` pixels of the width of the canvas so that the curve stays visible on
+/// large canvases
+pub fn get_default_best_fit_thickness(canvas_width: u32) -> u32 {
+	(canvas_width / BEST_FIT_WIDTH_PER_THICKNESS).max(1)
+}
+
+/// Draw a best fit curve joining consecutive `pixels` with straight lines of blocks `thickness` pixels
+/// across, on a canvas supersampled by `scale`. With `antialias` the lines are smoothed by partly covering
+/// the pixels along their edges instead
+pub fn draw_curve(
+	canvas: &mut ImageBuffer<Rgba<u8>, Vec<u8>>,
+	pixels: &[(u32, u32)],
+	thickness: u32,
+	scale: u32,
+	colour: [u8; 4],
+	antialias: bool,
+) {
+	let (before, _) = get_line_extents(thickness, scale);
+	// a single pixel is drawn as a dot
+	let segments = pixels.windows(2).map(|pair| (pair[0], pair[1])).chain(
+		pixels
+			.first()
+			.filter(|_| pixels.len() == 1)
+			.map(|p| (*p, *p)),
+	);
+	if antialias {
+		let width = (thickness * scale) as f32;
+		// centred on the middle of the blocks a hard edged curve is drawn with
+		let offset = (width - 1.0) / 2.0 - before as f32;
+		let mut stamp = PixelStamp::new(canvas);
+		for (start, end) in segments {
+			for (px, py, coverage) in find_antialiased_line_pixels(
+				(start.0 as f32 + offset, start.1 as f32 + offset),
+				(end.0 as f32 + offset, end.1 as f32 + offset),
+				width,
+			) {
+				if !stamp.cover(px, py, coverage) {
+					warn!(
+						"Cannot draw best fit curve at pixel position ({}, {})",
+						px, py
+					);
+				}
+			}
+		}
+		stamp.draw(canvas, colour);
+		return;
+	}
+	for (start, end) in segments {
+		for (px, py) in find_line_pixels(start, end) {
+			if !put_pixel_block(
+				canvas,
+				px.saturating_sub(before),
+				py.saturating_sub(before),
+				thickness * scale,
 				colour,
+			) {
+				warn!(
+					"Cannot draw best fit curve at pixel position ({}, {})",
+					px, py
+				);
+			}
+		}
+	}
+}
+
+/// How closely a curve follows the points of its data set
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct FitStatistics {
+	/// Coefficient of determination, the fraction of the variation of `y` the curve accounts for
+	pub r_squared: f32,
+	/// Root mean square error, the typical distance in `y` between a point and the curve
+	pub rmse: f32,
+}
+
+impl FitStatistics {
+	/// The statistics as they are written beside the curve
+	pub fn label(&self) -> String {
+		format!("R² = {:.3}, RMSE = {:.3}", self.r_squared, self.rmse)
+	}
+}
+
+/// Find how closely `curve` follows the `(x, y)` `points`, ignoring any point where the curve has no
+/// value. `None` when fewer than two points remain or their `y` values are all equal so that R² is undefined
+pub fn get_fit_statistics(curve: &BestFit, points: &[(f32, f32)]) -> Option<FitStatistics> {
+	let pairs: Vec<(f64, f64)> = points
+		.iter()
+		.map(|(x, y)| (*y as f64, curve.evaluate(*x) as f64))
+		.filter(|(y, fitted)| y.is_finite() && fitted.is_finite())
+		.collect();
+	if pairs.len() < 2 {
+		return None;
+	}
+	let count = pairs.len() as f64;
+	let mean = pairs.iter().map(|(y, _)| y).sum::<f64>() / count;
+	let total: f64 = pairs.iter().map(|(y, _)| (y - mean).powi(2)).sum();
+	let residual: f64 = pairs.iter().map(|(y, fitted)| (y - fitted).powi(2)).sum();
+	if total == 0.0 {
+		return None;
+	}
+	Some(FitStatistics {
+		r_squared: (1.0 - residual / total) as f32,
+		rmse: (residual / count).sqrt() as f32,
+	})
+}
+
+/// Write the `lines` describing a curve, such as its equation and statistics, one above the other in its
+/// `colour` beside `anchor`, the pixel the curve ends at, keeping the text within the axes spanning
+/// `axis_min_pixel` to `axis_max_pixel`. The text sits above the end of the curve unless there is no room,
+/// when it sits below
+#[allow(clippy::too_many_arguments)]
+pub fn draw_fit_statistics(
+	canvas: &mut ImageBuffer<Rgba<u8>, Vec<u8>>,
+	lines: &[String],
+	colour: [u8; 4],
+	anchor: (u32, u32),
+	axis_min_pixel: (u32, u32),
+	axis_max_pixel: (u32, u32),
+	font: &Font,
+	font_size: f32,
+) -> Result<(), PlotError> {
+	let glyphs: Vec<Vec<PositionedGlyph>> = lines
+		.iter()
+		.map(|line| create_glyphs(font_size, line, font))
+		.collect();
+	let width = glyphs
+		.iter()
+		.map(|line| get_width_of_glyphs(line))
+		.max()
+		.unwrap_or(0);
+	let gap = (font_size / 2.0) as u32;
+	// every line takes the height of the tallest so that they are evenly spaced
+	let line_height = glyphs
+		.iter()
+		.map(|line| get_maximum_height_of_glyphs(line))
+		.max()
+		.unwrap_or(0)
+		+ gap / 2;
+	let height = line_height * lines.len() as u32;
+	let x = anchor
+		.0
+		.min(axis_max_pixel.0)
+		.saturating_sub(width)
+		.max(axis_min_pixel.0);
+	let y = match anchor.1.checked_sub(height + gap) {
+		Some(y) if y >= axis_max_pixel.1 => y,
+		_ => anchor.1 + gap,
+	};
+	debug!("Writing fit statistics {:?} at ({}, {})", lines, x, y);
+	for (i, line) in glyphs.into_iter().enumerate() {
+		draw_glyphs(canvas, colour, line, (x, y + i as u32 * line_height));
+	}
+	Ok(())
+}
+
+/// A corner of the area within the axes
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Default)]
+pub enum Corner {
+	/// Beside the top of the y-axis
+	#[default]
+	TopLeft,
+	/// Opposite the origin of a graph in the top right quadrant
+	TopRight,
+	/// Beside the origin of a graph in the top right quadrant
+	BottomLeft,
+	/// Beside the end of the x-axis
+	BottomRight,
+}
+
+impl Corner {
+	/// The corner diagonally across the axes
+	pub fn opposite(self) -> Corner {
+		match self {
+			Corner::TopLeft => Corner::BottomRight,
+			Corner::TopRight => Corner::BottomLeft,
+			Corner::BottomLeft => Corner::TopRight,
+			Corner::BottomRight => Corner::TopLeft,
+		}
+	}
+}
+
+/// Whether the rectangles spanning the top left and bottom right pixels `a` and `b` share any pixel
+fn overlaps(a: PixelBounds, b: PixelBounds) -> bool {
+	a.0 .0 <= b.1 .0 && b.0 .0 <= a.1 .0 && a.0 .1 <= b.1 .1 && b.0 .1 <= a.1 .1
+}
+
+/// Write the `equations` of curves, each in the colour of its curve, one above the other in `corner` of
+/// the axes spanning `axis_min_pixel` to `axis_max_pixel`. When the text would overlap the `avoid`
+/// rectangle, such as the legend, it is written in the opposite corner instead
+#[allow(clippy::too_many_arguments)]
+pub fn draw_equations(
+	canvas: &mut ImageBuffer<Rgba<u8>, Vec<u8>>,
+	equations: &[(String, [u8; 4])],
+	corner: Corner,
+	axis_min_pixel: (u32, u32),
+	axis_max_pixel: (u32, u32),
+	avoid: Option<PixelBounds>,
+	font: &Font,
+	font_size: f32,
+) -> Result<(), PlotError> {
+	let glyphs: Vec<Vec<PositionedGlyph>> = equations
+		.iter()
+		.map(|(equation, _)| create_glyphs(font_size, equation, font))
+		.collect();
+	let width = glyphs
+		.iter()
+		.map(|line| get_width_of_glyphs(line))
+		.max()
+		.unwrap_or(0);
+	let gap = (font_size / 2.0) as u32;
+	// every line takes the height of the tallest so that they are evenly spaced
+	let line_height = glyphs
+		.iter()
+		.map(|line| get_maximum_height_of_glyphs(line))
+		.max()
+		.unwrap_or(0)
+		+ gap / 2;
+	let height = line_height * equations.len() as u32;
+	// the top left pixel of the text in a corner, inset from the axes by the gap
+	let position = |corner: Corner| -> (u32, u32) {
+		let x = match corner {
+			Corner::TopLeft | Corner::BottomLeft => axis_min_pixel.0 + gap,
+			Corner::TopRight | Corner::BottomRight => axis_max_pixel.0.saturating_sub(width + gap),
+		};
+		let y = match corner {
+			Corner::TopLeft | Corner::TopRight => axis_max_pixel.1 + gap,
+			Corner::BottomLeft | Corner::BottomRight => {
+				axis_min_pixel.1.saturating_sub(height + gap)
+			}
+		};
+		(x, y)
+	};
+	let mut chosen = corner;
+	let (x, y) = position(corner);
+	if let Some(avoid) = avoid {
+		if overlaps(((x, y), (x + width, y + height)), avoid) {
+			debug!(
+				"Equations in the {:?} corner would overlap the legend, writing them in the {:?} corner",
+				corner,
+				corner.opposite()
+			);
+			chosen = corner.opposite();
+		}
+	}
+	let (x, y) = position(chosen);
+	debug!("Writing equations {:?} at ({}, {})", equations, x, y);
+	for (i, (line, (_, colour))) in glyphs.into_iter().zip(equations.iter()).enumerate() {
+		draw_glyphs(canvas, *colour, line, (x, y + i as u32 * line_height));
+	}
+	Ok(())
+}
+
+/// Significant figures the parameters of a curve are written to in its equation
+const EQUATION_SIGNIFICANT_FIGURES: i32 = 3;
+
+/// Write a parameter of a curve rounded to `EQUATION_SIGNIFICANT_FIGURES` without trailing zeros, larger
+/// values keep every digit of their integer part
+fn format_parameter(value: f32) -> String {
+	let magnitude = if value == 0.0 {
+		0
+	} else {
+		(value.abs() as f64).log10().floor() as i32
+	};
+	format_value(
+		value as f64,
+		FormatHint {
+			decimal_places: Some((EQUATION_SIGNIFICANT_FIGURES - 1 - magnitude).max(0) as usize),
+			..FormatHint::default()
+		},
+	)
+}
+
+/// Write the sum of `(coefficient, term)` pairs, e.g `2x - 1`. Terms with a zero coefficient are left
+/// out, a coefficient of one is left off its term and a term which is not a power of `x` is joined to its
+/// coefficient by `·`. An empty term stands for a constant
+fn format_sum(terms: &[(f32, &str)]) -> String {
+	let mut expression = String::new();
+	for (coefficient, term) in terms {
+		let text = format_parameter(coefficient.abs());
+		if text == "0" {
+			continue;
+		}
+		let product = match (text.as_str(), *term) {
+			(_, "") => text,
+			("1", term) => term.to_owned(),
+			(text, term) if term.starts_with('x') => format!("{}{}", text, term),
+			(text, term) => format!("{}·{}", text, term),
+		};
+		let is_negative = *coefficient < 0.0;
+		expression = match (expression.is_empty(), is_negative) {
+			(true, false) => product,
+			(true, true) => format!("-{}", product),
+			(false, false) => format!("{} + {}", expression, product),
+			(false, true) => format!("{} - {}", expression, product),
+		};
+	}
+	if expression.is_empty() {
+		String::from("0")
+	} else {
+		expression
+	}
+}
+
+/// Find the coefficients, in ascending order of power, of the polynomial of `degree` which best fits
+/// `points` by solving the normal equations of least squares. `x` is divided by its largest magnitude while
+/// solving so that its powers stay close to one
+fn fit_polynomial(degree: u32, points: &[(f32, f32)]) -> Result<Vec<f64>, PlotError> {
+	let terms = degree as usize + 1;
+	let mut distinct_x: Vec<f32> = points.iter().map(|(x, _)| *x).collect();
+	distinct_x.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+	distinct_x.dedup();
+	if distinct_x.len() < terms {
+		return Err(PlotError::Config(format!(
+			"A polynomial regression of degree {} needs at least {} points with different x values, found {}",
+			degree,
+			terms,
+			distinct_x.len()
+		)));
+	}
+	let x_scale = points
+		.iter()
+		.map(|(x, _)| (*x as f64).abs())
+		.fold(0.0, f64::max)
+		.max(f64::MIN_POSITIVE);
+	// augmented matrix of the normal equations, each row holds the sums of `x^(row + column)` followed by
+	// the sum of `y * x^row`
+	let mut matrix = vec![vec![0.0_f64; terms + 1]; terms];
+	for (x, y) in points {
+		let x = *x as f64 / x_scale;
+		let powers: Vec<f64> = (0..(2 * terms)).map(|k| x.powi(k as i32)).collect();
+		for (row, sums) in matrix.iter_mut().enumerate() {
+			for (column, sum) in sums.iter_mut().take(terms).enumerate() {
+				*sum += powers[row + column];
+			}
+			sums[terms] += *y as f64 * powers[row];
+		}
+	}
+	let coefficients = solve_linear(matrix).ok_or_else(|| {
+		PlotError::Config(format!(
+			"The points are too poorly conditioned to fit a polynomial regression of degree {}, try a lower degree",
+			degree
+		))
+	})?;
+	// undo the scaling of x
+	Ok(coefficients
+		.iter()
+		.enumerate()
+		.map(|(power, coefficient)| coefficient / x_scale.powi(power as i32))
+		.collect())
+}
+
+/// Find `(amplitude, mean, std_dev)` of the gaussian which follows `points`, the mean and standard
+/// deviation are the moments of `x` weighted by `y` and the amplitude is then fitted by least squares.
+/// Points with a negative `y` are given no weight
+fn fit_gaussian(points: &[(f32, f32)]) -> Result<(f64, f64, f64), PlotError> {
+	let weighted: Vec<(f64, f64)> = points
+		.iter()
+		.map(|(x, y)| (*x as f64, (*y as f64).max(0.0)))
+		.collect();
+	let total: f64 = weighted.iter().map(|(_, weight)| weight).sum();
+	if total <= 0.0 {
+		return Err(PlotError::Config(
+			"A gaussian regression needs points with a positive y value to weight x by".to_owned(),
+		));
+	}
+	let mean = weighted.iter().map(|(x, weight)| x * weight).sum::<f64>() / total;
+	let std_dev = (weighted
+		.iter()
+		.map(|(x, weight)| (x - mean).powi(2) * weight)
+		.sum::<f64>()
+		/ total)
+		.sqrt();
+	if std_dev <= 0.0 || !std_dev.is_finite() {
+		return Err(PlotError::Config(
+			"A gaussian regression needs points with a positive y value at 2 or more different x values"
+				.to_owned(),
+		));
+	}
+	// the amplitude is linear in the curve so the best is found directly
+	let (product, square) = points.iter().fold((0.0, 0.0), |(product, square), (x, y)| {
+		let shape = (-(*x as f64 - mean).powi(2) / (2.0 * std_dev.powi(2))).exp();
+		(product + *y as f64 * shape, square + shape * shape)
+	});
+	Ok((product / square, mean, std_dev))
+}
+
+/// Find the monotone cubic spline of `colour` through `points`, sorted by `x` with the `y` values of
+/// points sharing an `x` averaged. The gradients follow Fritsch and Butland so that the
+/// curve never overshoots between knots, a knot where the data turns is flat and the ends take the
+/// gradient of the straight line to their neighbour
+fn fit_monotone_spline(points: &[(f32, f32)], colour: Colour) -> Result<BestFit, PlotError> {
+	let mut sorted: Vec<(f32, f32)> = points
+		.iter()
+		.filter(|(x, y)| x.is_finite() && y.is_finite())
+		.cloned()
+		.collect();
+	sorted.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+	// `(x, sum of y, count)` of each distinct x
+	let mut sums: Vec<(f32, f64, u32)> = Vec::new();
+	for (x, y) in sorted {
+		match sums.last_mut() {
+			Some((last_x, sum, count)) if *last_x == x => {
+				*sum += y as f64;
+				*count += 1;
+			}
+			_ => sums.push((x, y as f64, 1)),
+		}
+	}
+	let knots: Vec<(f32, f32)> = sums
+		.iter()
+		.map(|(x, sum, count)| (*x, (sum / *count as f64) as f32))
+		.collect();
+	if knots.len() < 2 {
+		return Err(PlotError::Config(format!(
+			"An interpolation needs at least 2 points with different x values, found {}",
+			knots.len()
+		)));
+	}
+	let widths: Vec<f32> = knots.windows(2).map(|pair| pair[1].0 - pair[0].0).collect();
+	let gradients: Vec<f32> = knots
+		.windows(2)
+		.zip(widths.iter())
+		.map(|(pair, width)| (pair[1].1 - pair[0].1) / width)
+		.collect();
+	let mut slopes = Vec::with_capacity(knots.len());
+	slopes.push(gradients[0]);
+	for k in 1..knots.len() - 1 {
+		let (before, after) = (gradients[k - 1], gradients[k]);
+		if before * after <= 0.0 {
+			slopes.push(0.0);
+		} else {
+			let weight_before = 2.0 * widths[k] + widths[k - 1];
+			let weight_after = widths[k] + 2.0 * widths[k - 1];
+			slopes.push(
+				(weight_before + weight_after) / (weight_before / before + weight_after / after),
+			);
+		}
+	}
+	slopes.push(gradients[gradients.len() - 1]);
+	info!("Interpolated a spline through {} points", knots.len());
+	Ok(BestFit::Spline {
+		points: knots,
+		slopes,
+		colour,
+	})
+}
+
+/// Steepnesses of the starting grid of a logistic regression, as multiples of one over the span of `x`
+const LOGISTIC_STEEPNESS_GRID: [f64; 7] = [1.0, 2.0, 4.0, 8.0, 16.0, 32.0, 64.0];
+/// Midpoints of the starting grid of a logistic regression, evenly spread across the span of `x`
+const LOGISTIC_MIDPOINT_GRID: usize = 21;
+/// Most Gauss-Newton steps a logistic regression refines its starting point with
+const LOGISTIC_MAX_ITERATIONS: usize = 200;
+
+/// The value of the logistic curve rising from zero to one of steepness `k` and midpoint `x0` at `x`
+fn sigmoid(k: f64, x0: f64, x: f64) -> f64 {
+	1.0 / (1.0 + (-k * (x - x0)).exp())
+}
+
+/// The sum of the squared residuals of `points` against the logistic curve of `(l, k, x0)`
+fn logistic_error(points: &[(f64, f64)], (l, k, x0): (f64, f64, f64)) -> f64 {
+	points
+		.iter()
+		.map(|(x, y)| (y - l * sigmoid(k, x0, *x)).powi(2))
+		.sum()
+}
+
+/// Find `(l, k, x0)` of the logistic curve which best fits `points` by least squares. Every pair of
+/// steepness and midpoint of a grid spanning the points is tried with the limit `l` which suits it best,
+/// then the best of them is refined by Gauss-Newton steps damped Levenberg-Marquardt style so that the
+/// error never grows
+fn fit_logistic(points: &[(f32, f32)]) -> Result<(f64, f64, f64), PlotError> {
+	let points: Vec<(f64, f64)> = points.iter().map(|(x, y)| (*x as f64, *y as f64)).collect();
+	let mut distinct_x: Vec<f64> = points.iter().map(|(x, _)| *x).collect();
+	distinct_x.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+	distinct_x.dedup();
+	if distinct_x.len() < 3 {
+		return Err(PlotError::Config(format!(
+			"A logistic regression needs at least 3 points with different x values, found {}",
+			distinct_x.len()
+		)));
+	}
+	let (x_min, x_max) = (distinct_x[0], distinct_x[distinct_x.len() - 1]);
+	let span = x_max - x_min;
+	// the limit is linear in the curve so the best for a steepness and midpoint is found directly
+	let best_limit = |k: f64, x0: f64| -> f64 {
+		let (product, square) = points.iter().fold((0.0, 0.0), |(product, square), (x, y)| {
+			let s = sigmoid(k, x0, *x);
+			(product + y * s, square + s * s)
+		});
+		if square > 0.0 {
+			product / square
+		} else {
+			0.0
+		}
+	};
+	let mut parameters = (0.0, 0.0, 0.0);
+	let mut error = f64::INFINITY;
+	for steepness in LOGISTIC_STEEPNESS_GRID.iter() {
+		for k in [steepness / span, -steepness / span] {
+			for step in 0..LOGISTIC_MIDPOINT_GRID {
+				let x0 = x_min + span * step as f64 / (LOGISTIC_MIDPOINT_GRID - 1) as f64;
+				let candidate = (best_limit(k, x0), k, x0);
+				let candidate_error = logistic_error(&points, candidate);
+				if candidate_error < error {
+					parameters = candidate;
+					error = candidate_error;
+				}
+			}
+		}
+	}
+	let mut damping = 1e-3;
+	for _ in 0..LOGISTIC_MAX_ITERATIONS {
+		let (l, k, x0) = parameters;
+		// normal equations of the linearised residuals, augmented with the gradient
+		let mut matrix = vec![vec![0.0_f64; 4]; 3];
+		for (x, y) in points.iter() {
+			let s = sigmoid(k, x0, *x);
+			let slope = l * s * (1.0 - s);
+			let jacobian = [s, slope * (x - x0), -slope * k];
+			let residual = y - l * s;
+			for (row, sums) in matrix.iter_mut().enumerate() {
+				for (column, sum) in sums.iter_mut().take(3).enumerate() {
+					*sum += jacobian[row] * jacobian[column];
+				}
+				sums[3] += jacobian[row] * residual;
+			}
+		}
+		for (i, row) in matrix.iter_mut().enumerate() {
+			row[i] *= 1.0 + damping;
+		}
+		let step = match solve_linear(matrix) {
+			Some(step) => step,
+			None => break,
+		};
+		let candidate = (l + step[0], k + step[1], x0 + step[2]);
+		let candidate_error = logistic_error(&points, candidate);
+		if candidate_error.is_finite() && candidate_error <= error {
+			let converged = error - candidate_error <= 1e-12 * error.max(f64::MIN_POSITIVE);
+			parameters = candidate;
+			error = candidate_error;
+			damping /= 10.0;
+			if converged {
+				break;
+			}
+		} else {
+			damping *= 10.0;
+		}
+	}
+	if !(parameters.0.is_finite() && parameters.1.is_finite() && parameters.2.is_finite()) {
+		return Err(PlotError::Config(
+			"A logistic regression could not be fitted to the points".to_owned(),
+		));
+	}
+	Ok(parameters)
+}
+
+/// Solve the square system of linear equations held in the augmented `matrix`, each row holding the
+/// coefficients of the unknowns followed by the constant, by Gaussian elimination with partial pivoting.
+/// `None` when the system is too poorly conditioned to have a single solution
+fn solve_linear(mut matrix: Vec<Vec<f64>>) -> Option<Vec<f64>> {
+	let terms = matrix.len();
+	for column in 0..terms {
+		let pivot = (column..terms)
+			.max_by(|a, b| {
+				matrix[*a][column]
+					.abs()
+					.partial_cmp(&matrix[*b][column].abs())
+					.unwrap_or(std::cmp::Ordering::Equal)
+			})
+			.unwrap_or(column);
+		matrix.swap(column, pivot);
+		if matrix[column][column].abs() < 1e-12 {
+			return None;
+		}
+		let pivot_row = matrix[column].clone();
+		for row in matrix.iter_mut().skip(column + 1) {
+			let factor = row[column] / pivot_row[column];
+			for (value, pivot_value) in row.iter_mut().zip(pivot_row.iter()).skip(column) {
+				*value -= factor * pivot_value;
+			}
+		}
+	}
+	// back substitution
+	let mut solution = vec![0.0_f64; terms];
+	for row in (0..terms).rev() {
+		let known: f64 = ((row + 1)..terms)
+			.map(|k| matrix[row][k] * solution[k])
+			.sum();
+		solution[row] = (matrix[row][terms] - known) / matrix[row][row];
+	}
+	Some(solution)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Points along `y = sum(coefficients[k] * x^k)` with a small deterministic wobble standing in for noise
+	fn noisy_points(coefficients: &[f32], xs: impl Iterator<Item = f32>) -> Vec<(f32, f32)> {
+		xs.map(|x| {
+			let y: f32 = coefficients
+				.iter()
+				.enumerate()
+				.map(|(k, c)| c * x.powi(k as i32))
+				.sum();
+			(x, y + 0.01 * (7.0 * x).sin())
+		})
+		.collect()
+	}
+	/// The coefficients of a fitted regression in ascending order of power
+	fn fitted(degree: u32, points: &[(f32, f32)]) -> Vec<f32> {
+		match (BestFit::PolynomialRegression {
+			degree,
+			colour: Colour::Black,
+		})
+		.fit(points)
+		.unwrap()
+		{
+			BestFit::GenericPolynomial { coefficients, .. } => {
+				coefficients.values().cloned().collect()
+			}
+			other => panic!("expected a polynomial, got {:?}", other),
+		}
+	}
+
+	#[test]
+	fn regression_recovers_known_polynomials() {
+		let cases: [(&[f32], Vec<f32>); 3] = [
+			(&[1.5, -2.0], (0..40).map(|i| i as f32 * 0.25).collect()),
+			(
+				&[2.0, -3.0, 0.5],
+				(-20..=20).map(|i| i as f32 * 0.5).collect(),
+			),
+			(
+				&[-4.0, 1.0, 0.25, -0.05],
+				(0..=60).map(|i| i as f32 * 0.25).collect(),
+			),
+		];
+		for (coefficients, xs) in cases {
+			let points = noisy_points(coefficients, xs.into_iter());
+			let fit = fitted(coefficients.len() as u32 - 1, &points);
+			assert_eq!(coefficients.len(), fit.len());
+			for (expected, actual) in coefficients.iter().zip(fit.iter()) {
+				assert!(
+					(expected - actual).abs() < 0.02,
+					"expected {:?}, fitted {:?}",
+					coefficients,
+					fit
+				);
+			}
+		}
+	}
+	#[test]
+	fn fitted_regression_passes_through_its_points() {
+		let points = noisy_points(
+			&[1.0, 0.0, -1.0, 0.0, 0.1],
+			(-12..=12).map(|i| i as f32 * 0.25),
+		);
+		let curve = (BestFit::PolynomialRegression {
+			degree: 4,
+			colour: Colour::Black,
+		})
+		.fit(&points)
+		.unwrap();
+		for (x, y) in points {
+			assert!((curve.evaluate(x) - y).abs() < 0.05, "at x = {}", x);
+		}
+	}
+	#[test]
+	fn regression_needs_a_sensible_degree_and_enough_points() {
+		let regression = |degree: u32| BestFit::PolynomialRegression {
+			degree,
+			colour: Colour::Black,
+		};
+		let points = noisy_points(&[1.0, 1.0], (0..20).map(|i| i as f32));
+		assert!(regression(MAX_REGRESSION_DEGREE).fit(&points).is_ok());
+		assert!(regression(MAX_REGRESSION_DEGREE + 1).validate().is_err());
+		assert!(regression(MAX_REGRESSION_DEGREE + 1).fit(&points).is_err());
+		// three distinct x values cannot pin down a cubic
+		let repeated = [(1.0, 1.0), (1.0, 2.0), (2.0, 1.0), (3.0, 0.0)];
+		assert!(regression(3).fit(&repeated).is_err());
+		assert!(regression(2).fit(&repeated).is_ok());
+	}
+	#[test]
+	fn curves_with_parameters_are_not_refitted() {
+		let line = BestFit::Linear {
+			gradient: 2.0,
+			y_intercept: 1.0,
+			colour: Colour::Black,
+		};
+		assert_eq!(line, line.fit(&[(0.0, 5.0), (1.0, -3.0)]).unwrap());
+	}
+	#[test]
+	fn statistics_measure_how_closely_a_curve_follows_its_points() {
+		let line = BestFit::Linear {
+			gradient: 2.0,
+			y_intercept: 1.0,
+			colour: Colour::Black,
+		};
+		let exact: Vec<(f32, f32)> = (0..10).map(|i| (i as f32, 2.0 * i as f32 + 1.0)).collect();
+		assert_eq!(
+			Some(FitStatistics {
+				r_squared: 1.0,
+				rmse: 0.0
+			}),
+			get_fit_statistics(&line, &exact)
+		);
+		// every point is 1 away from the line while y varies by a total of 88 about its mean of 4
+		let offset = [
+			(0.0, 2.0),
+			(1.0, 2.0),
+			(2.0, 6.0),
+			(3.0, 6.0),
+			(-1.0, -2.0),
+			(4.0, 10.0),
+		];
+		let statistics = get_fit_statistics(&line, &offset).unwrap();
+		assert!((statistics.rmse - 1.0).abs() < 1e-6);
+		assert!((statistics.r_squared - (1.0 - 6.0 / 88.0)).abs() < 1e-6);
+		assert_eq!("R² = 0.932, RMSE = 1.000", statistics.label());
+	}
+	#[test]
+	fn statistics_are_undefined_without_variation() {
+		let line = BestFit::Linear {
+			gradient: 0.0,
+			y_intercept: 1.0,
+			colour: Colour::Black,
+		};
+		assert_eq!(None, get_fit_statistics(&line, &[(0.0, 1.0)]));
+		assert_eq!(None, get_fit_statistics(&line, &[(0.0, 3.0), (1.0, 3.0)]));
+	}
+	#[test]
+	fn steep_curve_is_clipped_to_the_edges_of_the_axis() {
+		let curve = BestFit::Cubic {
+			intercept: 0.0,
+			linear_coeff: 0.0,
+			quadratic_coeff: 0.0,
+			cubic_coeff: 1.0,
+			colour: Colour::Black,
+		};
+		// samples a tenth apart jump from 97.3 to 103.8 either side of the top of the axis
+		let runs = curve.find_coordinates(-5, 5, -100, 100, 100, None).unwrap();
+		assert_eq!(1, runs.len());
+		let (first, last) = (runs[0].first().unwrap(), runs[0].last().unwrap());
+		let edge = 100.0_f32.cbrt();
+		assert_eq!(-100.0, first.y);
+		assert!((first.x + edge).abs() < 1e-4, "{}", first.x);
+		assert_eq!(100.0, last.y);
+		assert!((last.x - edge).abs() < 1e-4, "{}", last.x);
+		// a curve leaving and re-entering the axis is clipped at each crossing
+		let wave = BestFit::Cubic {
+			intercept: 0.0,
+			linear_coeff: -20.0,
+			quadratic_coeff: 0.0,
+			cubic_coeff: 1.0,
+			colour: Colour::Black,
+		};
+		let runs = wave.find_coordinates(-10, 10, -20, 20, 200, None).unwrap();
+		assert!(runs.len() > 1);
+		for run in runs.iter() {
+			for end in [run.first().unwrap(), run.last().unwrap()] {
+				if end.x > -10.0 && end.x < 10.0 {
+					assert!(end.y == 20.0 || end.y == -20.0, "{:?}", end);
+					assert!((wave.evaluate(end.x) - end.y).abs() < 0.01, "{:?}", end);
+				}
+			}
+		}
+	}
+	#[test]
+	fn logarithmic_curve_is_only_drawn_for_positive_x() {
+		let curve = BestFit::Logarithmic {
+			a: 2.0,
+			b: 1.0,
+			colour: Colour::Black,
+		};
+		assert!((curve.evaluate(1.0) - 1.0).abs() < 1e-6);
+		assert!((curve.evaluate(E) - 3.0).abs() < 1e-6);
+		assert!(curve.evaluate(0.0).is_nan());
+		assert!(curve.evaluate(-1.0).is_nan());
+		let points = curve.find_coordinates(-5, 5, -100, 100, 100, None).unwrap();
+		assert_eq!(1, points.len());
+		let points = &points[0];
+		assert!(points.iter().all(|p| p.x > 0.0 && p.y.is_finite()));
+	}
+	#[test]
+	fn logarithmic_regression_recovers_known_curve() {
+		// points at x <= 0 cannot lie on a logarithm and are left out of the fit
+		let mut points: Vec<(f32, f32)> = (1..40)
+			.map(|i| {
+				let x = i as f32 * 0.5;
+				(x, -1.5 * x.ln() + 4.0 + 0.01 * (7.0 * x).sin())
+			})
+			.collect();
+		points.push((0.0, 100.0));
+		points.push((-2.0, -100.0));
+		let fitted = (BestFit::LogarithmicRegression {
+			colour: Colour::Black,
+		})
+		.fit(&points)
+		.unwrap();
+		match fitted {
+			BestFit::Logarithmic { a, b, .. } => {
+				assert!((a + 1.5).abs() < 0.01, "a = {}", a);
+				assert!((b - 4.0).abs() < 0.01, "b = {}", b);
+			}
+			other => panic!("expected a logarithm, got {:?}", other),
+		}
+		let statistics = get_fit_statistics(&fitted, &points).unwrap();
+		assert!(statistics.r_squared > 0.99);
+		assert!((BestFit::LogarithmicRegression {
+			colour: Colour::Black
+		})
+		.fit(&[(-1.0, 1.0), (2.0, 2.0)])
+		.is_err());
+	}
+	#[test]
+	fn power_law_skips_zero_with_a_negative_exponent() {
+		let curve = BestFit::PowerLaw {
+			constant: 3.0,
+			exponent: -0.5,
+			colour: Colour::Black,
+		};
+		assert!((curve.evaluate(4.0) - 1.5).abs() < 1e-6);
+		assert!(curve.evaluate(0.0).is_nan());
+		let points = curve.find_coordinates(0, 10, 0, 100, 100, None).unwrap();
+		assert_eq!(1, points.len());
+		let points = &points[0];
+		assert!(points.iter().all(|p| p.x > 0.0 && p.y.is_finite()));
+		// a positive exponent passes through the origin
+		let square = BestFit::PowerLaw {
+			constant: 1.0,
+			exponent: 2.0,
+			colour: Colour::Black,
+		};
+		assert_eq!(0.0, square.evaluate(0.0));
+	}
+	#[test]
+	fn power_law_regression_recovers_known_curve() {
+		let points: Vec<(f32, f32)> = (1..40)
+			.map(|i| {
+				let x = i as f32 * 0.5;
+				(x, 2.5 * x.powf(1.7) * (1.0 + 0.001 * (7.0 * x).sin()))
+			})
+			.chain([(0.0, 0.0), (-1.0, 2.0)])
+			.collect();
+		match (BestFit::PowerLawRegression {
+			colour: Colour::Black,
+		})
+		.fit(&points)
+		.unwrap()
+		{
+			BestFit::PowerLaw {
+				constant, exponent, ..
 			} => {
-				trace!("Finding coordinates for Cosinusoidal best fit line with amplitude {}, period {}, phase shift {} and vertical shift {}", amplitude, period, phase_shift, vertical_shift);
-				let mut points: Vec<DataPoint> = Vec::new();
-				for scaled_x in (x_min * scale_factor)..=(x_max * scale_factor) {
-					let x = scaled_x as f32 / scale_factor as f32;
-					let y = amplitude * ((period * x) + phase_shift).cos() + vertical_shift;
-					if y > y_min as f32 && y < y_max as f32 {
-						points.push(DataPoint {
-							x,
-							ux: None,
-							y,
-							uy: None,
-							colour: *colour,
-							symbol: DataSymbol::Point,
-							symbol_radius: 1,
-							symbol_thickness: 1,
-						});
-					}
+				assert!((constant - 2.5).abs() < 0.01, "constant = {}", constant);
+				assert!((exponent - 1.7).abs() < 0.01, "exponent = {}", exponent);
+			}
+			other => panic!("expected a power law, got {:?}", other),
+		}
+		assert!((BestFit::PowerLawRegression {
+			colour: Colour::Black
+		})
+		.fit(&[(1.0, -1.0), (2.0, 2.0), (-3.0, 3.0)])
+		.is_err());
+	}
+	#[test]
+	fn exponential_regression_recovers_known_curve() {
+		let points: Vec<(f32, f32)> = (0..40)
+			.map(|i| {
+				let x = i as f32 * 0.25;
+				(x, 2.31 * (0.42 * x).exp() * (1.0 + 0.001 * (7.0 * x).sin()))
+			})
+			.collect();
+		let fitted = (BestFit::ExponentialRegression {
+			colour: Colour::Black,
+		})
+		.fit(&points)
+		.unwrap();
+		match fitted {
+			BestFit::Exponential {
+				constant,
+				base,
+				power,
+				vertical_shift,
+				..
+			} => {
+				assert!((constant - 2.31).abs() < 0.01, "constant = {}", constant);
+				assert_eq!(E, base);
+				assert!((power - 0.42).abs() < 0.01, "power = {}", power);
+				assert_eq!(0.0, vertical_shift);
+			}
+			ref other => panic!("expected an exponential, got {:?}", other),
+		}
+		assert_eq!(Some("y = 2.31·e^(0.42x)".to_owned()), fitted.equation());
+		// the logarithm of a non-positive y is undefined
+		let error = (BestFit::ExponentialRegression {
+			colour: Colour::Black,
+		})
+		.fit(&[(0.0, 1.0), (1.0, 0.0), (2.0, 4.0)])
+		.unwrap_err();
+		assert!(error.to_string().contains("positive"), "{}", error);
+	}
+	#[test]
+	fn equations_are_written_with_their_parameters() {
+		let equation = |curve: BestFit| curve.equation().unwrap();
+		let colour = Colour::Black;
+		assert_eq!(
+			"y = 1.93x + 0.41",
+			equation(BestFit::Linear {
+				gradient: 1.93,
+				y_intercept: 0.41,
+				colour
+			})
+		);
+		assert_eq!(
+			"y = -x^2 - 0.5",
+			equation(BestFit::Quadratic {
+				intercept: -0.5,
+				linear_coeff: 0.0,
+				quadratic_coeff: -1.0,
+				colour
+			})
+		);
+		assert_eq!(
+			"y = -x^4 + x^3 + 12345x + 0.000123",
+			equation(BestFit::GenericPolynomial {
+				coefficients: BTreeMap::from([(0, 0.000123), (1, 12345.0), (3, 1.0), (4, -1.0)]),
+				colour
+			})
+		);
+		assert_eq!(
+			"y = 3x^(-0.5)",
+			equation(BestFit::PowerLaw {
+				constant: 3.0,
+				exponent: -0.5,
+				colour
+			})
+		);
+		assert_eq!(
+			"y = 2·3^(-x) + 1",
+			equation(BestFit::Exponential {
+				constant: 2.0,
+				base: 3.0,
+				power: -1.0,
+				vertical_shift: 1.0,
+				colour
+			})
+		);
+		assert_eq!(
+			"y = 2·(1 - e^(-0.5x)) + 1",
+			equation(BestFit::ExponentialApproach {
+				constant: 2.0,
+				base: E,
+				power: 0.5,
+				vertical_shift: 1.0,
+				colour
+			})
+		);
+		assert_eq!(
+			"y = 0.5·sin(2x + 1.25)",
+			equation(BestFit::Sine {
+				amplitude: 0.5,
+				period: 2.0,
+				phase_shift: 1.25,
+				vertical_shift: 0.0,
+				colour
+			})
+		);
+		assert_eq!(
+			None,
+			(BestFit::PolynomialRegression { degree: 2, colour }).equation()
+		);
+	}
+	#[test]
+	fn moving_average_is_centred_for_odd_and_even_windows() {
+		// deliberately out of order, the average is taken in order of x
+		let points = [(3.0, 9.0), (0.0, 0.0), (2.0, 4.0), (1.0, 1.0), (4.0, 16.0)];
+		let average = |window: usize| match (BestFit::MovingAverage {
+			window,
+			colour: Colour::Black,
+		})
+		.fit(&points)
+		.unwrap()
+		{
+			BestFit::Polyline { points, .. } => points,
+			other => panic!("expected a polyline, got {:?}", other),
+		};
+		assert_eq!(
+			vec![(1.0, 5.0 / 3.0), (2.0, 14.0 / 3.0), (3.0, 29.0 / 3.0)],
+			average(3)
+		);
+		// an even window sits between the middle two points of each window
+		assert_eq!(vec![(1.5, 3.5), (2.5, 7.5)], average(4));
+		assert_eq!(vec![(2.0, 6.0)], average(5));
+		// a window of one point is the series itself
+		assert_eq!(
+			vec![(0.0, 0.0), (1.0, 1.0), (2.0, 4.0), (3.0, 9.0), (4.0, 16.0)],
+			average(1)
+		);
+	}
+	#[test]
+	fn moving_average_window_must_fit_the_data() {
+		let moving_average = |window: usize| BestFit::MovingAverage {
+			window,
+			colour: Colour::Black,
+		};
+		let points = [(0.0, 0.0), (1.0, 1.0), (2.0, 4.0)];
+		assert!(moving_average(0).validate().is_err());
+		assert!(moving_average(0).fit(&points).is_err());
+		assert!(moving_average(4).fit(&points).is_err());
+		assert!(moving_average(3).fit(&points).is_ok());
+	}
+	#[test]
+	fn polyline_joins_its_points_with_straight_lines() {
+		let line = BestFit::Polyline {
+			points: vec![(0.0, 0.0), (2.0, 4.0), (3.0, 1.0)],
+			colour: Colour::Black,
+		};
+		assert_eq!(2.0, line.evaluate(1.0));
+		assert_eq!(4.0, line.evaluate(2.0));
+		assert_eq!(2.5, line.evaluate(2.5));
+		assert!(line.evaluate(-0.5).is_nan());
+		assert!(line.evaluate(3.5).is_nan());
+		assert_eq!(None, line.equation());
+	}
+	#[test]
+	fn default_thickness_grows_with_the_canvas() {
+		assert_eq!(1, get_default_best_fit_thickness(400));
+		assert_eq!(1, get_default_best_fit_thickness(1920));
+		assert_eq!(2, get_default_best_fit_thickness(2000));
+		assert_eq!(4, get_default_best_fit_thickness(4000));
+	}
+	#[test]
+	fn line_styles_break_the_curve_evenly_in_x() {
+		let limits = (0, 40);
+		assert!((0..400).all(|i| LineStyle::Solid.is_drawn(i as f32 * 0.1, limits)));
+		assert!(LineStyle::Dashed.is_drawn(0.25, limits));
+		assert!(!LineStyle::Dashed.is_drawn(0.75, limits));
+		assert!(LineStyle::Dashed.is_drawn(1.25, limits));
+		assert!(LineStyle::Dotted.is_drawn(0.05, limits));
+		assert!(!LineStyle::Dotted.is_drawn(0.2, limits));
+	}
+	#[test]
+	fn domain_restricts_the_curve_to_its_intersection_with_the_axis() {
+		let curve = BestFit::Linear {
+			gradient: 1.0,
+			y_intercept: 0.0,
+			colour: Colour::Black,
+		};
+		let points = curve
+			.find_coordinates(0, 10, -100, 100, 100, Some((2.25, 4.0)))
+			.unwrap()
+			.remove(0);
+		assert_eq!(2.25, points.first().unwrap().x);
+		assert_eq!(4.0, points.last().unwrap().x);
+		assert!(points.windows(2).all(|pair| pair[0].x < pair[1].x));
+		let points = curve
+			.find_coordinates(0, 10, -100, 100, 100, Some((-5.0, 20.0)))
+			.unwrap()
+			.remove(0);
+		assert_eq!(0.0, points.first().unwrap().x);
+		assert_eq!(10.0, points.last().unwrap().x);
+		assert_eq!(101, points.len());
+		assert!(curve
+			.find_coordinates(0, 10, -100, 100, 100, Some((12.0, 20.0)))
+			.unwrap()
+			.is_empty());
+	}
+	#[test]
+	fn opposite_corners_are_diagonally_across() {
+		assert_eq!(Corner::BottomLeft, Corner::TopRight.opposite());
+		assert_eq!(Corner::TopLeft, Corner::BottomRight.opposite());
+		assert!(overlaps(((0, 0), (10, 10)), ((10, 5), (20, 20))));
+		assert!(!overlaps(((0, 0), (10, 10)), ((11, 0), (20, 10))));
+	}
+	#[test]
+	fn exponential_approach_rises_towards_its_limit() {
+		let curve = BestFit::ExponentialApproach {
+			constant: 2.0,
+			base: E,
+			power: 0.5,
+			vertical_shift: 1.0,
+			colour: Colour::Black,
+		};
+		assert!(curve.validate().is_ok());
+		assert_eq!(1.0, curve.evaluate(0.0));
+		let ys: Vec<f32> = (0..=40).map(|i| curve.evaluate(i as f32 * 0.5)).collect();
+		assert!(ys.windows(2).all(|pair| pair[0] < pair[1]));
+		assert!(ys.iter().all(|y| *y < 3.0));
+		assert!((3.0 - ys[40]).abs() < 1e-3);
+		assert!((BestFit::ExponentialApproach {
+			constant: 2.0,
+			base: 0.0,
+			power: 0.5,
+			vertical_shift: 1.0,
+			colour: Colour::Black,
+		})
+		.validate()
+		.is_err());
+	}
+	#[test]
+	fn logistic_regression_recovers_noisy_sigmoid() {
+		for (l, k, x0) in [(10.0, 1.5, 4.0), (3.0, -0.8, 2.0)] {
+			let points: Vec<(f32, f32)> = (0..=80)
+				.map(|i| {
+					let x = i as f32 * 0.1;
+					(
+						x,
+						l / (1.0 + (-k * (x - x0)).exp()) + 0.05 * (7.0 * x).sin(),
+					)
+				})
+				.collect();
+			let fitted = (BestFit::LogisticRegression {
+				colour: Colour::Black,
+			})
+			.fit(&points)
+			.unwrap();
+			match fitted {
+				BestFit::Logistic {
+					l: fitted_l,
+					k: fitted_k,
+					x0: fitted_x0,
+					..
+				} => {
+					assert!((fitted_l - l).abs() < 0.05 * l, "l = {}", fitted_l);
+					assert!((fitted_k - k).abs() < 0.05 * k.abs(), "k = {}", fitted_k);
+					assert!((fitted_x0 - x0).abs() < 0.05, "x0 = {}", fitted_x0);
 				}
-				points
+				ref other => panic!("expected a logistic curve, got {:?}", other),
+			}
+			assert!(get_fit_statistics(&fitted, &points).unwrap().r_squared > 0.99);
+		}
+		assert!((BestFit::LogisticRegression {
+			colour: Colour::Black
+		})
+		.fit(&[(0.0, 0.0), (1.0, 1.0), (1.0, 2.0)])
+		.is_err());
+	}
+	#[test]
+	fn logistic_curve_passes_half_its_limit_at_the_midpoint() {
+		let curve = BestFit::Logistic {
+			l: 10.0,
+			k: 2.0,
+			x0: 5.0,
+			colour: Colour::Black,
+		};
+		assert_eq!(5.0, curve.evaluate(5.0));
+		assert!(curve.evaluate(20.0) > 9.99);
+		assert!(curve.evaluate(-10.0) < 0.01);
+		assert_eq!(
+			Some("y = 10 / (1 + e^(-2·(x - 5)))".to_owned()),
+			curve.equation()
+		);
+	}
+	#[test]
+	fn gaussian_regression_finds_weighted_mean_and_deviation() {
+		let (amplitude, mean, std_dev) = (4.0_f32, 3.0_f32, 0.5_f32);
+		let points: Vec<(f32, f32)> = (0..=120)
+			.map(|i| {
+				let x = i as f32 * 0.05;
+				(
+					x,
+					amplitude * (-(x - mean).powi(2) / (2.0 * std_dev.powi(2))).exp(),
+				)
+			})
+			.collect();
+		let fitted = (BestFit::GaussianRegression {
+			colour: Colour::Black,
+		})
+		.fit(&points)
+		.unwrap();
+		match fitted {
+			BestFit::Gaussian {
+				amplitude: Some(a),
+				mean: u,
+				std_dev: o,
+				..
+			} => {
+				assert!((a - amplitude).abs() < 0.05, "amplitude = {}", a);
+				assert!((u - mean).abs() < 0.01, "mean = {}", u);
+				assert!((o - std_dev).abs() < 0.01, "std_dev = {}", o);
 			}
+			ref other => panic!("expected a gaussian, got {:?}", other),
+		}
+		assert_eq!(Some("μ = 3, σ = 0.5".to_owned()), fitted.summary());
+		assert_eq!(
+			Some("y = 4·e^(-(x - μ)^2 / 2σ^2), μ = 3, σ = 0.5".to_owned()),
+			fitted.equation()
+		);
+		assert!((BestFit::GaussianRegression {
+			colour: Colour::Black
+		})
+		.fit(&[(0.0, -1.0), (1.0, 0.0)])
+		.is_err());
+	}
+	#[test]
+	fn gaussian_needs_a_positive_deviation() {
+		let gaussian = |std_dev: f32| BestFit::Gaussian {
+			amplitude: None,
+			mean: 0.0,
+			std_dev,
+			colour: Colour::Black,
+		};
+		assert!(gaussian(0.3).validate().is_ok());
+		assert!(gaussian(0.0).validate().is_err());
+		assert!(gaussian(-0.3).validate().is_err());
+		assert!(gaussian(f32::NAN).validate().is_err());
+		// without an amplitude the area under the curve is one
+		assert!((gaussian(0.5).evaluate(0.0) - 0.797_884_6).abs() < 1e-5);
+		let legacy: BestFit =
+			ron::from_str("Gaussian(expected_value: 1.0, variance: 0.3, colour: Black)").unwrap();
+		assert_eq!(
+			BestFit::Gaussian {
+				amplitude: None,
+				mean: 1.0,
+				std_dev: 0.3,
+				colour: Colour::Black
+			},
+			legacy
+		);
+		let scaled: BestFit =
+			ron::from_str("Gaussian(amplitude: 2.0, mean: 1.0, std_dev: 0.3, colour: Black)")
+				.unwrap();
+		assert_eq!(2.0, scaled.evaluate(1.0));
+	}
+	#[test]
+	fn reference_lines_span_their_axis() {
+		let horizontal = BestFit::HorizontalLine {
+			y: 4.5,
+			colour: Colour::Red,
+		};
+		assert_eq!(4.5, horizontal.evaluate(-100.0));
+		assert_eq!(Some("y = 4.5".to_owned()), horizontal.equation());
+		let runs = horizontal
+			.find_coordinates(0, 10, 0, 10, 100, None)
+			.unwrap();
+		assert_eq!((0.0, 10.0), (runs[0][0].x, runs[0][100].x));
+		let vertical = BestFit::VerticalLine {
+			x: 3.0,
+			colour: Colour::Red,
+		};
+		assert!(vertical.evaluate(3.0).is_nan());
+		assert_eq!(Some("x = 3".to_owned()), vertical.equation());
+		let runs = vertical.find_coordinates(0, 10, -5, 5, 100, None).unwrap();
+		assert_eq!(1, runs.len());
+		assert!(runs[0].iter().all(|p| p.x == 3.0));
+		assert_eq!((-5.0, 5.0), (runs[0][0].y, runs[0][100].y));
+		assert!(vertical
+			.find_coordinates(0, 10, -5, 5, 100, Some((5.0, 8.0)))
+			.unwrap()
+			.is_empty());
+		assert!((BestFit::VerticalLine {
+			x: f32::INFINITY,
+			colour: Colour::Red
+		})
+		.validate()
+		.is_err());
+	}
+	#[test]
+	fn interpolation_passes_through_every_point_without_overshooting() {
+		// a calibration table which rises steeply then levels off
+		let points = [
+			(0.0, 0.0),
+			(1.0, 0.1),
+			(2.0, 5.0),
+			(3.0, 5.1),
+			(4.0, 5.1),
+			(6.0, 9.0),
+		];
+		let curve = (BestFit::Interpolate {
+			colour: Colour::Black,
+		})
+		.fit(&points)
+		.unwrap();
+		for (x, y) in points.iter() {
+			assert!((curve.evaluate(*x) - y).abs() < 1e-5, "y({}) = {}", x, y);
+		}
+		let samples: Vec<f32> = (0..=600).map(|i| curve.evaluate(i as f32 * 0.01)).collect();
+		assert!(samples.windows(2).all(|pair| pair[0] <= pair[1] + 1e-6));
+		// the flat stretch stays flat
+		assert!((curve.evaluate(3.5) - 5.1).abs() < 1e-5);
+		assert!(curve.evaluate(-0.5).is_nan());
+		assert!(curve.evaluate(6.5).is_nan());
+		assert_eq!(None, curve.equation());
+	}
+	#[test]
+	fn interpolation_of_two_points_is_a_straight_line() {
+		// duplicates of an x are averaged
+		let curve = (BestFit::Interpolate {
+			colour: Colour::Black,
+		})
+		.fit(&[(2.0, 3.0), (0.0, 0.0), (2.0, 5.0)])
+		.unwrap();
+		for i in 0..=20 {
+			let x = i as f32 * 0.1;
+			assert!((curve.evaluate(x) - 2.0 * x).abs() < 1e-5);
 		}
+		assert!((BestFit::Interpolate {
+			colour: Colour::Black
+		})
+		.fit(&[(1.0, 1.0), (1.0, 2.0)])
+		.is_err());
 	}
 }
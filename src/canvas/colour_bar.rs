@@ -0,0 +1,157 @@
+//! Draws a colour bar beside the axes showing how values are mapped along a colormap
+
+use image::{ImageBuffer, Rgba};
+use rusttype::Font;
+use tracing::debug;
+
+use crate::{
+	canvas::{
+		format::{format_value, FormatHint},
+		glyphs::{create_glyphs, draw_glyphs, get_width_of_glyphs},
+		put_pixel_block, VHConsumedCanvasSpace,
+	},
+	colours::{Colormap, Colour, BLACK},
+	error::PlotError,
+};
+
+/// Width of the gradient of a colour bar in pixels before scaling
+const COLOUR_BAR_WIDTH: u32 = 16;
+
+/// The labels written beside the top and bottom of a colour bar, the largest and smallest values
+fn get_colour_bar_labels(colour_range: (f32, f32)) -> (String, String) {
+	(
+		format_value(colour_range.1 as f64, FormatHint::default()),
+		format_value(colour_range.0 as f64, FormatHint::default()),
+	)
+}
+
+/// The horizontal space taken by a colour bar, a gap of `font_size` from whatever is to its left followed
+/// by the gradient and its labels
+pub fn get_colour_bar_space(
+	colour_range: (f32, f32),
+	font: &Font,
+	font_size: f32,
+	scale: u32,
+) -> Result<VHConsumedCanvasSpace, PlotError> {
+	let (max_label, min_label) = get_colour_bar_labels(colour_range);
+	let label_width = [max_label, min_label]
+		.iter()
+		.map(|label| get_width_of_glyphs(&create_glyphs(font_size, label, font)))
+		.max()
+		.unwrap_or_default();
+	Ok(VHConsumedCanvasSpace {
+		v_space_from_top: 0,
+		h_space_from_right: font_size as u32
+			+ COLOUR_BAR_WIDTH * scale
+			+ get_colour_bar_label_gap(font_size)
+			+ label_width,
+		v_space_from_bottom: 0,
+		h_space_from_left: 0,
+	})
+}
+
+/// The gap between the gradient of a colour bar and its labels
+fn get_colour_bar_label_gap(font_size: f32) -> u32 {
+	(font_size / 4.0) as u32
+}
+
+/// Draw a colour bar into the space reserved by [`get_colour_bar_space`] starting at the column `left`. The
+/// gradient runs from the smallest value of `colour_range` at `bottom` to the largest at `top`, outlined in
+/// black with each end labelled with its value
+#[allow(clippy::too_many_arguments)]
+pub fn build_colour_bar(
+	canvas: &mut ImageBuffer<Rgba<u8>, Vec<u8>>,
+	left: u32,
+	top: u32,
+	bottom: u32,
+	colormap: Colormap,
+	colour_range: (f32, f32),
+	font: &Font,
+	font_size: f32,
+	scale: u32,
+) -> Result<(), PlotError> {
+	debug!(
+		"Drawing a {:?} colour bar spanning {:?}",
+		colormap, colour_range
+	);
+	let bar_left = left + font_size as u32;
+	let bar_right = bar_left + COLOUR_BAR_WIDTH * scale;
+	let height = bottom.saturating_sub(top).max(1);
+	for y in top..=bottom {
+		let rgba = Colour::get_pixel_colour(colormap.sample((bottom - y) as f32 / height as f32));
+		for x in bar_left..bar_right {
+			put_pixel_block(canvas, x, y, 1, rgba);
+		}
+	}
+	// an outline as thick as the axes sets the lightest colours apart from the background
+	for x in bar_left..bar_right {
+		put_pixel_block(canvas, x, top, scale, BLACK);
+		put_pixel_block(canvas, x, bottom.saturating_sub(scale - 1), scale, BLACK);
+	}
+	for y in top..=bottom {
+		put_pixel_block(canvas, bar_left, y, scale, BLACK);
+		put_pixel_block(canvas, bar_right.saturating_sub(scale), y, scale, BLACK);
+	}
+	let (max_label, min_label) = get_colour_bar_labels(colour_range);
+	let label_left = bar_right + get_colour_bar_label_gap(font_size);
+	draw_glyphs(
+		canvas,
+		BLACK,
+		create_glyphs(font_size, &max_label, font),
+		(label_left, top),
+	);
+	draw_glyphs(
+		canvas,
+		BLACK,
+		create_glyphs(font_size, &min_label, font),
+		(label_left, bottom.saturating_sub(font_size as u32)),
+	);
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		canvas::{
+			colour_bar::{build_colour_bar, get_colour_bar_space},
+			draw_base_canvas,
+		},
+		colours::{Colormap, Colour, WHITE},
+		get_system_font,
+	};
+	#[test]
+	fn colour_bar_runs_from_the_smallest_value_at_the_bottom() {
+		let font = get_system_font().unwrap();
+		let mut canvas = draw_base_canvas((200, 200));
+		let space = get_colour_bar_space((0.0, 100.0), &font, 12.0, 1).unwrap();
+		build_colour_bar(
+			&mut canvas,
+			200 - space.h_space_from_right,
+			20,
+			180,
+			Colormap::Viridis,
+			(0.0, 100.0),
+			&font,
+			12.0,
+			1,
+		)
+		.unwrap();
+		// sampled down the middle of the gradient just inside of its outline, 160 pixels tall
+		let x = 200 - space.h_space_from_right + 12 + 8;
+		assert_eq!(
+			Colour::get_pixel_colour(Colormap::Viridis.sample(159.0 / 160.0)),
+			canvas.get_pixel(x, 21).0
+		);
+		assert_eq!(
+			Colour::get_pixel_colour(Colormap::Viridis.sample(1.0 / 160.0)),
+			canvas.get_pixel(x, 179).0
+		);
+		// nothing is drawn beyond the reserved space
+		for y in 0..200 {
+			assert_eq!(
+				WHITE,
+				canvas.get_pixel(200 - space.h_space_from_right - 1, y).0
+			);
+		}
+	}
+}
@@ -0,0 +1,250 @@
+//! Writes epoch timestamps as calendar dates and times so that an x-axis of timestamps can be labelled
+//! with `12:30` or `Mar 4` rather than `1709555400`, and picks steps between labels which fall on whole
+//! minutes, hours and days. Timestamps are seconds since 1970-01-01 in UTC
+
+/// Abbreviated month names written by `%b`
+const MONTHS: [&str; 12] = [
+	"Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+/// Seconds in a minute
+const MINUTE: f64 = 60.0;
+/// Seconds in an hour
+const HOUR: f64 = 3600.0;
+/// Seconds in a day
+const DAY: f64 = 86400.0;
+/// Steps between labels which land on whole seconds, minutes, hours and days, in seconds
+const CALENDAR_STEPS: [f64; 25] = [
+	1.0,
+	2.0,
+	5.0,
+	10.0,
+	15.0,
+	30.0,
+	MINUTE,
+	2.0 * MINUTE,
+	5.0 * MINUTE,
+	10.0 * MINUTE,
+	15.0 * MINUTE,
+	30.0 * MINUTE,
+	HOUR,
+	2.0 * HOUR,
+	3.0 * HOUR,
+	6.0 * HOUR,
+	12.0 * HOUR,
+	DAY,
+	2.0 * DAY,
+	7.0 * DAY,
+	14.0 * DAY,
+	28.0 * DAY,
+	91.0 * DAY,
+	182.0 * DAY,
+	364.0 * DAY,
+];
+
+/// How the tick labels of an axis of timestamps are written
+#[derive(Debug, Clone, PartialEq)]
+pub enum DateTimeFormat {
+	/// Pick `%H:%M:%S`, `%H:%M`, `%b %-d` or `%Y-%m` from the span of the axis
+	Auto,
+	/// A strftime style pattern, see [`format_datetime`] for the supported specifiers
+	Pattern(String),
+}
+
+impl DateTimeFormat {
+	/// Read the `x_tick_datetime_format` of a config, where `auto` picks a pattern from the span of the axis
+	pub fn parse(text: &str) -> DateTimeFormat {
+		if text.eq_ignore_ascii_case("auto") {
+			DateTimeFormat::Auto
+		} else {
+			DateTimeFormat::Pattern(text.to_owned())
+		}
+	}
+	/// The pattern used on an axis spanning `span` seconds
+	pub fn pattern(&self, span: f64) -> &str {
+		match self {
+			DateTimeFormat::Auto => auto_pattern(span),
+			DateTimeFormat::Pattern(pattern) => pattern,
+		}
+	}
+}
+
+/// The coarsest pattern which still tells apart the labels of an axis spanning `span` seconds
+fn auto_pattern(span: f64) -> &'static str {
+	let span = span.abs();
+	if span < 10.0 * MINUTE {
+		"%H:%M:%S"
+	} else if span < 2.0 * DAY {
+		"%H:%M"
+	} else if span < 548.0 * DAY {
+		"%b %-d"
+	} else {
+		"%Y-%m"
+	}
+}
+
+/// The year, month, day, hour, minute and second of a timestamp in UTC
+fn civil_from_epoch(seconds: i64) -> (i64, u32, u32, u32, u32, u32) {
+	let days = seconds.div_euclid(86400);
+	let of_day = seconds.rem_euclid(86400) as u32;
+	// Howard Hinnant's `civil_from_days`, counting eras of 400 years from 0000-03-01
+	let z = days + 719468;
+	let era = z.div_euclid(146097);
+	let day_of_era = z.rem_euclid(146097);
+	let year_of_era =
+		(day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+	let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+	let shifted_month = (5 * day_of_year + 2) / 153;
+	let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+	let month = if shifted_month < 10 {
+		shifted_month + 3
+	} else {
+		shifted_month - 9
+	} as u32;
+	let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+	(
+		year,
+		month,
+		day,
+		of_day / 3600,
+		of_day % 3600 / 60,
+		of_day % 60,
+	)
+}
+
+/// Write a timestamp with a strftime style pattern. The supported specifiers are `%Y`, `%y`, `%m`, `%b`,
+/// `%d`, `%H`, `%M`, `%S` and `%%`, a `-` after the `%` drops the zero padding of a number, e.g `%-d`.
+/// Any other specifier is written as it is. The timestamp is rounded to the nearest second
+pub fn format_datetime(seconds: f64, pattern: &str) -> String {
+	let (year, month, day, hour, minute, second) = civil_from_epoch(seconds.round() as i64);
+	let mut text = String::with_capacity(pattern.len() + 8);
+	let mut chars = pattern.chars().peekable();
+	while let Some(c) = chars.next() {
+		if c != '%' {
+			text.push(c);
+			continue;
+		}
+		let is_padded = chars.peek() != Some(&'-');
+		if !is_padded {
+			chars.next();
+		}
+		let number = |value: u32| {
+			if is_padded {
+				format!("{:02}", value)
+			} else {
+				value.to_string()
+			}
+		};
+		match chars.next() {
+			Some('Y') => text.push_str(&year.to_string()),
+			Some('y') => text.push_str(&number(year.rem_euclid(100) as u32)),
+			Some('m') => text.push_str(&number(month)),
+			Some('b') => text.push_str(MONTHS[month as usize - 1]),
+			Some('d') => text.push_str(&number(day)),
+			Some('H') => text.push_str(&number(hour)),
+			Some('M') => text.push_str(&number(minute)),
+			Some('S') => text.push_str(&number(second)),
+			Some('%') => text.push('%'),
+			Some(other) => {
+				text.push('%');
+				if !is_padded {
+					text.push('-');
+				}
+				text.push(other);
+			}
+			None => text.push('%'),
+		}
+	}
+	text
+}
+
+/// The smallest calendar step, in seconds, which divides `span` seconds into no more than `target` parts.
+/// Beyond the largest step whole multiples of a 364 day year are used
+pub fn calendar_step(span: f64, target: u32) -> f64 {
+	let raw_step = span.abs() / target.max(1) as f64;
+	match CALENDAR_STEPS.iter().find(|step| **step >= raw_step) {
+		Some(step) => *step,
+		None => {
+			let year = CALENDAR_STEPS[CALENDAR_STEPS.len() - 1];
+			let years = raw_step / year;
+			let magnitude = 10_f64.powf(years.log10().floor());
+			let nice = [1.0, 2.0, 5.0, 10.0]
+				.iter()
+				.map(|nice| nice * magnitude)
+				.find(|nice| *nice >= years)
+				.unwrap_or(10.0 * magnitude);
+			nice.ceil() * year
+		}
+	}
+}
+
+/// Widen the bounds of an axis of timestamps by a margin and round them outward to a calendar step, giving
+/// the whole number limits of the axis and the resolution dividing it into steps of whole minutes, hours
+/// or days
+pub fn calendar_limits(min: f64, max: f64, target: u32) -> ((i32, i32), u32) {
+	// a lone timestamp is given a minute either side
+	let span = (max - min).max(MINUTE);
+	let margin = span * 0.05;
+	let step = calendar_step(span + 2.0 * margin, target);
+	let low = ((min - margin) / step).floor() * step;
+	let high = ((max + margin) / step).ceil() * step;
+	let resolution = ((high - low) / step).round().max(1.0) as u32;
+	((low as i32, high as i32), resolution)
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::canvas::datetime::{
+		calendar_limits, calendar_step, format_datetime, DateTimeFormat,
+	};
+	#[test]
+	fn dates_and_times() {
+		assert_eq!(
+			"1970-01-01 00:00:00",
+			format_datetime(0.0, "%Y-%m-%d %H:%M:%S")
+		);
+		// 2024-02-29T13:05:09Z, a leap day
+		assert_eq!(
+			"2024-02-29 13:05:09",
+			format_datetime(1709211909.0, "%Y-%m-%d %H:%M:%S")
+		);
+		assert_eq!("Feb 29", format_datetime(1709211909.0, "%b %-d"));
+		assert_eq!("29/02/24", format_datetime(1709211909.0, "%d/%m/%y"));
+		assert_eq!(
+			"1:05 100%",
+			format_datetime(1709211909.0 - 12.0 * 3600.0, "%-H:%M 100%%")
+		);
+		// 1969-12-31T23:59:59Z
+		assert_eq!(
+			"1969-12-31 23:59:59",
+			format_datetime(-1.0, "%Y-%m-%d %H:%M:%S")
+		);
+		assert_eq!("%q %-q", format_datetime(0.0, "%q %-q"));
+		assert_eq!("00:00:01", format_datetime(0.6, "%H:%M:%S"));
+	}
+	#[test]
+	fn auto_patterns_follow_the_span() {
+		let auto = DateTimeFormat::parse("Auto");
+		assert_eq!(DateTimeFormat::Auto, auto);
+		assert_eq!("%H:%M:%S", auto.pattern(300.0));
+		assert_eq!("%H:%M", auto.pattern(6.0 * 3600.0));
+		assert_eq!("%b %-d", auto.pattern(30.0 * 86400.0));
+		assert_eq!("%Y-%m", auto.pattern(3.0 * 365.0 * 86400.0));
+		assert_eq!("%d", DateTimeFormat::parse("%d").pattern(300.0));
+	}
+	#[test]
+	fn steps_land_on_whole_units() {
+		assert_eq!(60.0, calendar_step(500.0, 10));
+		assert_eq!(3600.0, calendar_step(8.0 * 3600.0, 10));
+		assert_eq!(86400.0, calendar_step(9.0 * 86400.0, 10));
+		assert_eq!(
+			2.0 * 364.0 * 86400.0,
+			calendar_step(15.0 * 364.0 * 86400.0, 10)
+		);
+		// 2024-03-04T09:12:30Z to 2024-03-04T17:40:00Z gives hourly labels
+		let ((low, high), resolution) = calendar_limits(1709543550.0, 1709574000.0, 10);
+		assert_eq!(0, low % 3600);
+		assert_eq!(0, high % 3600);
+		assert_eq!(((high - low) / 3600) as u32, resolution);
+		assert!(low < 1709543550 && high > 1709574000);
+	}
+}
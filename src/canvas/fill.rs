@@ -0,0 +1,196 @@
+//! Fills regions of the canvas by blending a colour over what has already been drawn
+
+use image::{ImageBuffer, Rgba};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::{colours::Colour, error::PlotError};
+
+/// Fills the area between a series and the x-axis with its colour, fading from `from_opacity` at the
+/// series to `to_opacity` at the axis. Opacities range from `0.0`, transparent, to `1.0`, solid
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq)]
+pub struct FillGradient {
+	/// Opacity of the fill where it meets the series
+	pub from_opacity: f32,
+	/// Opacity of the fill where it meets the x-axis
+	pub to_opacity: f32,
+}
+
+impl FillGradient {
+	/// Ensure both opacities lie between `0.0` and `1.0`, the error names the data set if not
+	pub fn validate(&self, set_name: &str) -> Result<(), PlotError> {
+		for opacity in [self.from_opacity, self.to_opacity] {
+			if !(0.0..=1.0).contains(&opacity) {
+				return Err(PlotError::Config(format!(
+					"The fill_gradient opacities of data set {} must be between 0.0 and 1.0, found {}",
+					set_name, opacity
+				)));
+			}
+		}
+		Ok(())
+	}
+	/// The opacity a fraction `t` of the way from the series, `0.0`, to the axis, `1.0`
+	fn opacity_at(&self, t: f32) -> f32 {
+		self.from_opacity + (self.to_opacity - self.from_opacity) * t
+	}
+}
+
+/// Blend a colour over the existing pixel at `(x, y)` with the given opacity
+pub fn blend_pixel(
+	canvas: &mut ImageBuffer<Rgba<u8>, Vec<u8>>,
+	x: u32,
+	y: u32,
+	colour: [u8; 4],
+	opacity: f32,
+) {
+	if let Some(pixel) = canvas.get_pixel_mut_checked(x, y) {
+		let opacity = opacity.clamp(0.0, 1.0);
+		for (below, above) in pixel.0.iter_mut().zip(colour.iter()) {
+			*below = (*below as f32 + (*above as f32 - *below as f32) * opacity).round() as u8;
+		}
+	}
+}
+
+/// Fill the area between a series of points, joined in order of increasing x, and the x-axis with a
+/// vertical gradient. Each pixel column fades from the series towards the axis so that a series crossing
+/// zero fades towards the axis from both sides. Nothing is drawn outside of the plot area bounded by
+/// `axis_min_pixel` and `axis_max_pixel`
+#[allow(clippy::too_many_arguments)]
+pub fn fill_gradient_under(
+	canvas: &mut ImageBuffer<Rgba<u8>, Vec<u8>>,
+	values: &[(f32, f32)],
+	colour: Colour,
+	gradient: FillGradient,
+	x_scale_factor: f32,
+	y_scale_factor: f32,
+	axis_origin_pixel: (u32, u32),
+	axis_min_pixel: (u32, u32),
+	axis_max_pixel: (u32, u32),
+) {
+	debug!("Filling area under series with {:?}", gradient);
+	let rgba = Colour::get_pixel_colour(colour);
+	let mut pixels: Vec<(f32, f32)> = values
+		.iter()
+		.map(|(x, y)| {
+			(
+				axis_origin_pixel.0 as f32 + x * x_scale_factor,
+				axis_origin_pixel.1 as f32 - y * y_scale_factor,
+			)
+		})
+		.collect();
+	pixels.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+	// canvas y increases downwards so the top of the plot area is the smaller value
+	let (left, right) = (axis_min_pixel.0 as f32, axis_max_pixel.0 as f32);
+	let (top, bottom) = (axis_max_pixel.1 as f32, axis_min_pixel.1 as f32);
+	let axis_y = axis_origin_pixel.1 as f32;
+	let segments = pixels.len().saturating_sub(1);
+	for (i, pair) in pixels.windows(2).enumerate() {
+		let (start, end) = (pair[0], pair[1]);
+		// a column on the boundary of two segments belongs to the later one, the final segment keeps
+		// its last column
+		let is_last = i + 1 == segments;
+		let mut column = start.0.ceil().max(left);
+		while column <= end.0.min(right) && (is_last || column < end.0) {
+			// linearly interpolate the series at the column
+			let curve_y = if end.0 > start.0 {
+				start.1 + (end.1 - start.1) * ((column - start.0) / (end.0 - start.0))
+			} else {
+				start.1
+			};
+			let distance = axis_y - curve_y;
+			let mut row = curve_y.min(axis_y).max(top).round();
+			while row <= curve_y.max(axis_y).min(bottom) {
+				let t = if distance == 0.0 {
+					1.0
+				} else {
+					((row - curve_y) / distance).abs().min(1.0)
+				};
+				blend_pixel(
+					canvas,
+					column as u32,
+					row as u32,
+					rgba,
+					gradient.opacity_at(t),
+				);
+				row += 1.0;
+			}
+			column += 1.0;
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		canvas::{
+			draw_base_canvas,
+			fill::{blend_pixel, fill_gradient_under, FillGradient},
+		},
+		colours::{Colour, WHITE},
+	};
+	#[test]
+	fn blending_interpolates_channels() {
+		let mut canvas = draw_base_canvas((2, 2));
+		blend_pixel(&mut canvas, 0, 0, [0, 0, 255, 255], 0.5);
+		assert_eq!([128, 128, 255, 255], canvas.get_pixel(0, 0).0);
+		blend_pixel(&mut canvas, 1, 1, [0, 0, 0, 255], 0.0);
+		assert_eq!(WHITE, canvas.get_pixel(1, 1).0);
+		// outside of the canvas is ignored
+		blend_pixel(&mut canvas, 5, 5, [0, 0, 0, 255], 1.0);
+	}
+	#[test]
+	fn gradient_fades_towards_the_axis_from_both_sides() {
+		// a 101x101 plot area with the x-axis through the middle and one pixel per unit
+		let mut canvas = draw_base_canvas((101, 101));
+		let gradient = FillGradient {
+			from_opacity: 1.0,
+			to_opacity: 0.0,
+		};
+		fill_gradient_under(
+			&mut canvas,
+			&[(0.0, 40.0), (50.0, 40.0), (100.0, -40.0)],
+			Colour::Black,
+			gradient,
+			1.0,
+			1.0,
+			(0, 50),
+			(0, 100),
+			(100, 0),
+		);
+		// solid at the series, white at the axis and fading in between
+		assert_eq!(0, canvas.get_pixel(20, 10).0[0]);
+		assert_eq!(255, canvas.get_pixel(20, 50).0[0]);
+		assert_eq!(128, canvas.get_pixel(20, 30).0[0]);
+		// nothing above the series
+		assert_eq!(WHITE, canvas.get_pixel(20, 9).0);
+		// the series crosses zero at x = 75, beneath the axis the fill runs upwards from the series
+		assert_eq!(0, canvas.get_pixel(100, 90).0[0]);
+		assert_eq!(WHITE, canvas.get_pixel(100, 91).0);
+		assert!(canvas.get_pixel(100, 70).0[0] > canvas.get_pixel(100, 80).0[0]);
+		assert_eq!(WHITE, canvas.get_pixel(100, 40).0);
+	}
+	#[test]
+	fn gradient_is_clipped_to_the_plot_area() {
+		let mut canvas = draw_base_canvas((101, 101));
+		let gradient = FillGradient {
+			from_opacity: 1.0,
+			to_opacity: 1.0,
+		};
+		// the plot area spans pixels 20 to 80 while the series runs beyond it in every direction
+		fill_gradient_under(
+			&mut canvas,
+			&[(-100.0, 100.0), (100.0, 100.0)],
+			Colour::Black,
+			gradient,
+			1.0,
+			1.0,
+			(50, 80),
+			(20, 80),
+			(80, 20),
+		);
+		for (x, y, pixel) in canvas.enumerate_pixels() {
+			let inside = (20..=80).contains(&x) && (20..=80).contains(&y);
+			assert_eq!(inside, pixel.0 != WHITE, "({}, {})", x, y);
+		}
+	}
+}
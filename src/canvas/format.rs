@@ -0,0 +1,701 @@
+//! Formats numbers for display so that tick labels, annotations and reports share the same precision rules
+
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use super::datetime::{format_datetime, DateTimeFormat};
+
+/// When a value should be written in scientific notation
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Serialize, Default)]
+pub enum Notation {
+	/// Scientific for very large or very small magnitudes, plain otherwise
+	#[default]
+	Auto,
+	/// Always plain, e.g `1000000000`
+	Plain,
+	/// Always scientific, e.g `1e9`
+	Scientific,
+	/// Plain values multiplied by one hundred with a `%` appended, e.g `0.25` is written as `25%`
+	Percent,
+}
+
+/// Describes how a value should be formatted by `format_value`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct FormatHint {
+	/// Optional, the maximum number of decimal places to round a value to, trailing zeros are trimmed unless
+	/// `is_fixed`. When `None` the shortest representation which reads back as the same value is used
+	pub decimal_places: Option<usize>,
+	/// Keep the trailing zeros of `decimal_places` so that every value has exactly that many, e.g `1.50`
+	pub is_fixed: bool,
+	/// When scientific notation is used
+	pub notation: Notation,
+	/// Optional character used to group the integer digits of plain values in thousands, takes precedence
+	/// over the grouping of a `locale`
+	pub thousands_separator: Option<char>,
+	/// Optional locale whose decimal and grouping separators are used for plain values
+	pub locale: Option<Locale>,
+}
+
+impl Default for FormatHint {
+	fn default() -> Self {
+		FormatHint {
+			decimal_places: None,
+			is_fixed: false,
+			notation: Notation::Auto,
+			thousands_separator: None,
+			locale: None,
+		}
+	}
+}
+
+impl FormatHint {
+	/// Derive a hint from the step between consecutive values, such as the value of an axis subdivision,
+	/// so that every value is written with no more decimal places than the step requires. The step is
+	/// rounded to 4 significant figures first so that a step of a third, `33.333332`, gives `33.33`
+	pub fn from_step(step: f64) -> FormatHint {
+		let step = step.abs();
+		let decimal_places = if step.is_normal() {
+			let magnitude = step.log10().floor() as i32;
+			let places = (STEP_SIGNIFICANT_FIGURES - 1 - magnitude).max(0) as usize;
+			match trim_zeros(format!("{:.*}", places, step)).split_once('.') {
+				Some((_, decimals)) => decimals.len(),
+				None => 0,
+			}
+		} else {
+			0
+		};
+		FormatHint {
+			decimal_places: Some(decimal_places),
+			..FormatHint::default()
+		}
+	}
+}
+
+/// How the scale labels along an axis are written
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TickFormat {
+	/// Optional, the exact number of decimal places of every label, trailing zeros included. When `None`
+	/// labels have no more decimal places than the step between them requires
+	pub decimal_places: Option<u8>,
+	/// Whether labels are written in scientific notation, see [`TickFormat::for_span`] for how an axis
+	/// settles `Auto`
+	pub notation: Notation,
+	/// Optional locale whose decimal and grouping separators are used
+	pub locale: Option<Locale>,
+	/// Optional, write the labels as the dates and times of epoch timestamps in seconds, which takes the place
+	/// of the notation, decimal places and locale
+	pub datetime: Option<DateTimeFormat>,
+	/// Optional text appended to every label, e.g a unit of `" ms"`
+	pub suffix: Option<String>,
+	/// Optional, write a label at every this many scale markers counted from the origin. When `None` as few
+	/// labels are skipped as stop neighbouring labels overlapping
+	pub label_every: Option<u32>,
+}
+
+impl TickFormat {
+	/// Settle `Notation::Auto` for an axis whose labels cover `span`, so that every label of the axis shares
+	/// one notation. Scientific is used when the magnitude of the span is beyond ±4, e.g a span of `200000`.
+	/// An automatic date and time format is settled in the same way
+	pub fn for_span(&self, span: f64) -> TickFormat {
+		if let Some(datetime) = &self.datetime {
+			return TickFormat {
+				datetime: Some(DateTimeFormat::Pattern(datetime.pattern(span).to_owned())),
+				..self.clone()
+			};
+		}
+		if self.notation != Notation::Auto {
+			return self.clone();
+		}
+		let span = span.abs();
+		let notation = if span.is_normal() && span.log10().floor().abs() > TICK_SCIENTIFIC_MAGNITUDE
+		{
+			Notation::Scientific
+		} else {
+			Notation::Plain
+		};
+		TickFormat {
+			notation,
+			..self.clone()
+		}
+	}
+}
+
+/// Format the value of a scale label on an axis whose labels are `step` apart, every label of an axis is
+/// written through here so that they share the same precision. A date and time format which has not been
+/// settled by [`TickFormat::for_span`] picks its pattern from the step alone
+pub fn format_tick_value(value: f64, step: f64, format: &TickFormat) -> String {
+	let text = format_tick_number(value, step, format);
+	match &format.suffix {
+		Some(suffix) => text + suffix,
+		None => text,
+	}
+}
+
+/// The value of a scale label written in the notation, precision and locale of `format`, without its suffix
+fn format_tick_number(value: f64, step: f64, format: &TickFormat) -> String {
+	if let Some(datetime) = &format.datetime {
+		return format_datetime(value, datetime.pattern(step));
+	}
+	let is_scientific = format.notation == Notation::Scientific;
+	// an origin label of `0e0` reads worse than the plain zero it stands for
+	if is_scientific && snap_to_integer(value) == 0.0 {
+		return String::from("0");
+	}
+	let hint = match format.decimal_places {
+		Some(places) => FormatHint {
+			decimal_places: Some(places as usize),
+			is_fixed: true,
+			..FormatHint::default()
+		},
+		None if is_scientific => FormatHint {
+			decimal_places: Some(mantissa_places(value, step)),
+			..FormatHint::default()
+		},
+		// a percentage needs the decimal places of the step between percentages
+		None if format.notation == Notation::Percent => FormatHint::from_step(step * 100.0),
+		None => FormatHint::from_step(step),
+	};
+	format_value(
+		value,
+		FormatHint {
+			notation: format.notation,
+			locale: format.locale,
+			..hint
+		},
+	)
+}
+
+/// Decimal places the mantissa of `value` needs in scientific notation to show the digits which change
+/// between values `step` apart, e.g `1.2e9` for a step of `2e8`
+fn mantissa_places(value: f64, step: f64) -> usize {
+	let (value, step) = (value.abs(), step.abs());
+	if !value.is_normal() || !step.is_normal() {
+		return 0;
+	}
+	// the exponent of the last significant digit of the step, rounded as in `FormatHint::from_step`
+	let step_text = format!("{:.*e}", (STEP_SIGNIFICANT_FIGURES - 1) as usize, step);
+	let (mantissa, exponent) = step_text.split_once('e').unwrap_or((&step_text, "0"));
+	let step_decimals = match trim_zeros(mantissa.to_owned()).split_once('.') {
+		Some((_, decimals)) => decimals.len() as i32,
+		None => 0,
+	};
+	let last_digit = exponent.parse::<i32>().unwrap_or(0) - step_decimals;
+	(value.log10().floor() as i32 - last_digit).max(0) as usize
+}
+
+/// The order in which the parts of a date are written
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum DateOrder {
+	/// e.g `06/01/2024`
+	MonthDayYear,
+	/// e.g `01.06.2024`
+	DayMonthYear,
+}
+
+/// A small built-in table of the conventions used to write numbers and dates in a region
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Locale {
+	/// English, `1,234.5` and `06/01/2024`
+	En,
+	/// German, `1.234,5` and `01.06.2024`
+	De,
+	/// French, `1 234,5` and `01/06/2024`
+	Fr,
+}
+
+impl FromStr for Locale {
+	type Err = String;
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.to_lowercase().as_str() {
+			"en" => Ok(Locale::En),
+			"de" => Ok(Locale::De),
+			"fr" => Ok(Locale::Fr),
+			_ => Err(format!(
+				"Unknown locale {}, supported locales are en, de and fr",
+				s
+			)),
+		}
+	}
+}
+
+impl Locale {
+	/// Character separating the integer and fractional parts of a number
+	pub fn decimal_separator(self) -> char {
+		match self {
+			Locale::En => '.',
+			Locale::De | Locale::Fr => ',',
+		}
+	}
+	/// Character separating each group of three integer digits
+	pub fn grouping_separator(self) -> char {
+		match self {
+			Locale::En => ',',
+			Locale::De => '.',
+			Locale::Fr => ' ',
+		}
+	}
+	/// The order of the day, month and year of a date
+	pub fn date_order(self) -> DateOrder {
+		match self {
+			Locale::En => DateOrder::MonthDayYear,
+			Locale::De | Locale::Fr => DateOrder::DayMonthYear,
+		}
+	}
+	/// Character separating the parts of a date
+	fn date_separator(self) -> char {
+		match self {
+			Locale::En | Locale::Fr => '/',
+			Locale::De => '.',
+		}
+	}
+	/// Write a calendar date in the order and with the separator of the locale
+	pub fn format_date(self, year: i32, month: u32, day: u32) -> String {
+		let separator = self.date_separator();
+		match self.date_order() {
+			DateOrder::MonthDayYear => {
+				format!("{:02}{}{:02}{}{}", month, separator, day, separator, year)
+			}
+			DateOrder::DayMonthYear => {
+				format!("{:02}{}{:02}{}{}", day, separator, month, separator, year)
+			}
+		}
+	}
+}
+
+/// Magnitude at and above which `Notation::Auto` switches to scientific notation
+const SCIENTIFIC_UPPER: f64 = 1e9;
+/// Magnitude below which `Notation::Auto` switches to scientific notation
+const SCIENTIFIC_LOWER: f64 = 1e-4;
+/// Magnitude of an axis span beyond which `TickFormat::for_span` settles on scientific notation
+const TICK_SCIENTIFIC_MAGNITUDE: f64 = 4.0;
+/// Significant figures of a step kept when deriving the decimal places of the values it separates
+const STEP_SIGNIFICANT_FIGURES: i32 = 4;
+
+/// Format a value according to a hint:
+///
+/// * Values within a single `f32` rounding error of an integer are snapped to that integer
+/// * Without a `decimal_places` hint the shortest text which reads back as the same number is used,
+///   values which can be represented as an `f32` are written with `f32` precision to avoid noise digits
+/// * With a `decimal_places` hint the value is rounded and trailing zeros are trimmed, unless `is_fixed`
+/// * Negative zero is written as `0`
+/// * Plain values use the separators of a `locale`, scientific notation ignores it
+/// * Percentages are rounded to `decimal_places` after being multiplied by one hundred
+pub fn format_value(value: f64, hint: FormatHint) -> String {
+	if value.is_nan() {
+		return String::from("NaN");
+	}
+	if value.is_infinite() {
+		return String::from(if value > 0.0 { "inf" } else { "-inf" });
+	}
+	if hint.notation == Notation::Percent {
+		let text = format_value(
+			value * 100.0,
+			FormatHint {
+				notation: Notation::Plain,
+				..hint
+			},
+		);
+		return format!("{}%", text);
+	}
+	let value = snap_to_integer(value);
+	let magnitude = value.abs();
+	let is_scientific = match hint.notation {
+		Notation::Auto => {
+			magnitude >= SCIENTIFIC_UPPER || (magnitude > 0.0 && magnitude < SCIENTIFIC_LOWER)
+		}
+		Notation::Plain | Notation::Percent => false,
+		Notation::Scientific => true,
+	};
+	let text = if is_scientific {
+		format_scientific(value, hint.decimal_places, hint.is_fixed)
+	} else {
+		match hint.decimal_places {
+			Some(places) if hint.is_fixed => format!("{:.*}", places, value),
+			Some(places) => trim_zeros(format!("{:.*}", places, value)),
+			None => shortest(value),
+		}
+	};
+	// rounding may leave a negative zero such as `-0` or `-0.0`
+	let text = if text.starts_with('-') && text[1..].chars().all(|c| c == '0' || c == '.') {
+		text[1..].to_owned()
+	} else {
+		text
+	};
+	if is_scientific {
+		return text;
+	}
+	let grouping = hint
+		.thousands_separator
+		.or_else(|| hint.locale.map(Locale::grouping_separator));
+	let decimal_separator = hint.locale.map_or('.', Locale::decimal_separator);
+	match grouping {
+		Some(separator) => group_thousands(&text, separator, decimal_separator),
+		None => text.replace('.', &decimal_separator.to_string()),
+	}
+}
+
+/// Snap a value which is within an `f32` rounding error of an integer to that integer
+fn snap_to_integer(value: f64) -> f64 {
+	let rounded = value.round();
+	if (value - rounded).abs() <= value.abs() * f32::EPSILON as f64 {
+		rounded
+	} else {
+		value
+	}
+}
+
+/// The shortest plain text which reads back as `value`, using `f32` precision when the value is an `f32`
+fn shortest(value: f64) -> String {
+	if value as f32 as f64 == value {
+		(value as f32).to_string()
+	} else {
+		value.to_string()
+	}
+}
+
+/// Write a value as a mantissa and exponent, e.g `1.5e-7`, the mantissa keeps its trailing zeros when
+/// `is_fixed`
+fn format_scientific(value: f64, decimal_places: Option<usize>, is_fixed: bool) -> String {
+	let text = match decimal_places {
+		Some(places) => format!("{:.*e}", places, value),
+		None if value as f32 as f64 == value => format!("{:e}", value as f32),
+		None => format!("{:e}", value),
+	};
+	match text.split_once('e') {
+		Some((mantissa, exponent)) if !is_fixed => {
+			format!("{}e{}", trim_zeros(mantissa.to_owned()), exponent)
+		}
+		_ => text,
+	}
+}
+
+/// Remove trailing zeros after a decimal point, along with the point itself if nothing follows it
+fn trim_zeros(text: String) -> String {
+	if !text.contains('.') {
+		return text;
+	}
+	text.trim_end_matches('0').trim_end_matches('.').to_owned()
+}
+
+/// Insert `separator` between each group of three integer digits, writing `decimal_separator` before
+/// any fractional digits
+fn group_thousands(text: &str, separator: char, decimal_separator: char) -> String {
+	let (sign, unsigned) = match text.strip_prefix('-') {
+		Some(rest) => ("-", rest),
+		None => ("", text),
+	};
+	let (integer, fraction) = match unsigned.split_once('.') {
+		Some((i, f)) => (i, Some(f)),
+		None => (unsigned, None),
+	};
+	let mut grouped = String::with_capacity(text.len() + integer.len() / 3);
+	for (i, digit) in integer.chars().enumerate() {
+		if i > 0 && (integer.len() - i) % 3 == 0 {
+			grouped.push(separator);
+		}
+		grouped.push(digit);
+	}
+	match fraction {
+		Some(f) => format!("{}{}{}{}", sign, grouped, decimal_separator, f),
+		None => format!("{}{}", sign, grouped),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::canvas::format::{
+		format_tick_value, format_value, FormatHint, Locale, Notation, TickFormat,
+	};
+	#[test]
+	fn zero() {
+		assert_eq!("0", format_value(0.0, FormatHint::default()));
+		assert_eq!("0", format_value(-0.0, FormatHint::default()));
+		assert_eq!("0", format_value(-0.0001, FormatHint::from_step(0.5)));
+	}
+	#[test]
+	fn non_finite() {
+		assert_eq!("NaN", format_value(f64::NAN, FormatHint::default()));
+		assert_eq!("inf", format_value(f64::INFINITY, FormatHint::default()));
+		assert_eq!(
+			"-inf",
+			format_value(f64::NEG_INFINITY, FormatHint::default())
+		);
+	}
+	#[test]
+	fn f32_noise_is_hidden() {
+		assert_eq!("0.3", format_value(0.3_f32 as f64, FormatHint::default()));
+		assert_eq!(
+			"2.5",
+			format_value(2.5000002_f32 as f64, FormatHint::from_step(0.5))
+		);
+		assert_eq!("0.1", format_value(0.1, FormatHint::default()));
+	}
+	#[test]
+	fn integers_are_snapped() {
+		assert_eq!("11", format_value(10.999999, FormatHint::default()));
+		assert_eq!("-11", format_value(-11.000001, FormatHint::default()));
+		assert_eq!(
+			"11",
+			format_value((11.0_f32 / 11.0 * 11.0) as f64, FormatHint::default())
+		);
+	}
+	#[test]
+	fn every_magnitude_round_trips() {
+		for exponent in -9..=12 {
+			for mantissa in [1.0, 2.5, 7.125] {
+				for sign in [1.0, -1.0] {
+					let value: f64 = sign * mantissa * 10_f64.powi(exponent);
+					let text = format_value(value, FormatHint::default());
+					let parsed: f64 = text.parse().unwrap();
+					assert!(
+						((parsed - value) / value).abs() < 1e-6,
+						"{} formatted as {}",
+						value,
+						text
+					);
+				}
+			}
+		}
+	}
+	#[test]
+	fn auto_notation_thresholds() {
+		assert_eq!("1e-9", format_value(1e-9, FormatHint::default()));
+		assert_eq!("-2.5e-5", format_value(-2.5e-5, FormatHint::default()));
+		assert_eq!("0.0001", format_value(1e-4, FormatHint::default()));
+		assert_eq!("123456", format_value(123456.0, FormatHint::default()));
+		assert_eq!("100000000", format_value(1e8, FormatHint::default()));
+		assert_eq!("1e9", format_value(1e9, FormatHint::default()));
+		assert_eq!("1e12", format_value(1e12, FormatHint::default()));
+		assert_eq!("-7.125e11", format_value(-7.125e11, FormatHint::default()));
+	}
+	#[test]
+	fn explicit_notation() {
+		let plain = FormatHint {
+			notation: Notation::Plain,
+			..FormatHint::default()
+		};
+		assert_eq!("1000000000000", format_value(1e12, plain));
+		assert_eq!("0.000000001", format_value(1e-9, plain));
+		let scientific = FormatHint {
+			notation: Notation::Scientific,
+			decimal_places: Some(2),
+			..FormatHint::default()
+		};
+		assert_eq!("1.23e3", format_value(1234.0, scientific));
+		assert_eq!("1e0", format_value(1.0, scientific));
+		assert_eq!("-5e-3", format_value(-0.005, scientific));
+	}
+	#[test]
+	fn percentages() {
+		let percent = FormatHint {
+			notation: Notation::Percent,
+			..FormatHint::default()
+		};
+		assert_eq!("25%", format_value(0.25, percent));
+		assert_eq!("-150%", format_value(-1.5, percent));
+		assert_eq!("30%", format_value(0.3_f32 as f64, percent));
+		let percent = TickFormat {
+			notation: Notation::Percent,
+			..TickFormat::default()
+		};
+		assert_eq!("0%", format_tick_value(0.0, 0.1, &percent));
+		assert_eq!(
+			"70%",
+			format_tick_value((0.1_f32 * 7.0) as f64, 0.1, &percent)
+		);
+		assert_eq!("12.5%", format_tick_value(0.125, 0.025, &percent));
+		let fixed = TickFormat {
+			decimal_places: Some(1),
+			notation: Notation::Percent,
+			locale: Some(Locale::De),
+			..TickFormat::default()
+		};
+		assert_eq!("50,0%", format_tick_value(0.5, 0.1, &fixed));
+		// the notation of a percentage is not settled by its span
+		assert_eq!(Notation::Percent, percent.for_span(1e-6).notation);
+	}
+	#[test]
+	fn decimal_places_from_step() {
+		assert_eq!(Some(0), FormatHint::from_step(1.0).decimal_places);
+		assert_eq!(Some(0), FormatHint::from_step(20.0).decimal_places);
+		assert_eq!(Some(1), FormatHint::from_step(0.5).decimal_places);
+		assert_eq!(Some(2), FormatHint::from_step(-0.25).decimal_places);
+		let hint = FormatHint::from_step(0.25);
+		assert_eq!("0.75", format_value(0.75, hint));
+		assert_eq!("1.5", format_value(1.5, hint));
+		assert_eq!("2", format_value(2.0000002, hint));
+	}
+	#[test]
+	fn noisy_steps_are_rounded() {
+		assert_eq!(
+			Some(2),
+			FormatHint::from_step(100.0_f32 as f64 / 3.0).decimal_places
+		);
+		assert_eq!(
+			Some(1),
+			FormatHint::from_step(0.3_f32 as f64).decimal_places
+		);
+		assert_eq!(Some(6), FormatHint::from_step(0.000125).decimal_places);
+		assert_eq!(Some(0), FormatHint::from_step(0.0).decimal_places);
+		assert_eq!(Some(0), FormatHint::from_step(f64::NAN).decimal_places);
+	}
+	#[test]
+	fn tick_values() {
+		let auto = TickFormat::default();
+		let third = (100.0_f32 / 3.0) as f64;
+		assert_eq!("33.33", format_tick_value(third, third, &auto));
+		assert_eq!("-66.67", format_tick_value(-2.0 * third, third, &auto));
+		assert_eq!("0.3", format_tick_value(0.3_f32 as f64, 0.1, &auto));
+		assert_eq!("0.9", format_tick_value((0.3_f32 * 3.0) as f64, 0.3, &auto));
+		assert_eq!("-1.5", format_tick_value(-1.5, 0.5, &auto));
+		assert_eq!("1.25e-5", format_tick_value(0.0000125, 0.0000125, &auto));
+		assert_eq!("0", format_tick_value(-0.0001, 0.5, &auto));
+		let fixed = TickFormat {
+			decimal_places: Some(2),
+			..TickFormat::default()
+		};
+		assert_eq!("0.30", format_tick_value(0.3_f32 as f64, 0.1, &fixed));
+		assert_eq!("-1.50", format_tick_value(-1.5, 0.5, &fixed));
+		assert_eq!("10.00", format_tick_value(10.0, 5.0, &fixed));
+		assert_eq!("0.00", format_tick_value(-0.001, 0.5, &fixed));
+		assert_eq!("1.00e-5", format_tick_value(0.00001, 0.00001, &fixed));
+		let whole = TickFormat {
+			decimal_places: Some(0),
+			locale: Some(Locale::De),
+			..TickFormat::default()
+		};
+		assert_eq!("-1.235", format_tick_value(-1234.6, 0.5, &whole));
+	}
+	#[test]
+	fn scientific_tick_values() {
+		let scientific = TickFormat {
+			notation: Notation::Scientific,
+			..TickFormat::default()
+		};
+		assert_eq!("1.5e9", format_tick_value(1.5e9, 5e8, &scientific));
+		assert_eq!(
+			"1.2e9",
+			format_tick_value((2e8_f32 * 6.0) as f64, 2e8, &scientific)
+		);
+		assert_eq!("1e9", format_tick_value(1e9, 2e8, &scientific));
+		assert_eq!("-2.5e5", format_tick_value(-2.5e5, 5e4, &scientific));
+		assert_eq!("1.25e3", format_tick_value(1250.0, 250.0, &scientific));
+		assert_eq!("5e-5", format_tick_value(0.00005, 0.00005, &scientific));
+		assert_eq!("0", format_tick_value(0.0, 5e8, &scientific));
+		let locale = TickFormat {
+			locale: Some(Locale::De),
+			..scientific.clone()
+		};
+		assert_eq!("1.5e9", format_tick_value(1.5e9, 5e8, &locale));
+		let fixed = TickFormat {
+			decimal_places: Some(2),
+			..scientific.clone()
+		};
+		assert_eq!("2.00e9", format_tick_value(2e9, 5e8, &fixed));
+	}
+	#[test]
+	fn suffix_follows_every_label() {
+		let ms = TickFormat {
+			suffix: Some(String::from(" ms")),
+			..TickFormat::default()
+		};
+		assert_eq!("10 ms", format_tick_value(10.0, 10.0, &ms));
+		assert_eq!("-2.5 ms", format_tick_value(-2.5, 0.5, &ms));
+		let scientific = TickFormat {
+			notation: Notation::Scientific,
+			..ms.clone()
+		};
+		assert_eq!("0 ms", format_tick_value(0.0, 5e8, &scientific));
+		assert_eq!("1.5e9 ms", format_tick_value(1.5e9, 5e8, &scientific));
+	}
+	#[test]
+	fn tick_notation_follows_the_span() {
+		let auto = TickFormat::default();
+		assert_eq!(Notation::Plain, auto.for_span(100.0).notation);
+		assert_eq!(Notation::Plain, auto.for_span(99999.0).notation);
+		assert_eq!(Notation::Scientific, auto.for_span(200000.0).notation);
+		assert_eq!(Notation::Scientific, auto.for_span(2e9).notation);
+		assert_eq!(Notation::Scientific, auto.for_span(0.00001).notation);
+		assert_eq!(Notation::Plain, auto.for_span(0.0).notation);
+		let plain = TickFormat {
+			notation: Notation::Plain,
+			..auto.clone()
+		};
+		assert_eq!(Notation::Plain, plain.for_span(2e9).notation);
+	}
+	#[test]
+	fn thousands_separator() {
+		let hint = FormatHint {
+			thousands_separator: Some(','),
+			..FormatHint::default()
+		};
+		assert_eq!("1,234,567", format_value(1234567.0, hint));
+		assert_eq!("-1,234.5", format_value(-1234.5, hint));
+		assert_eq!("999", format_value(999.0, hint));
+		assert_eq!("100,000", format_value(100000.0, hint));
+		assert_eq!("0.5", format_value(0.5, hint));
+		let hint = FormatHint {
+			thousands_separator: Some(' '),
+			decimal_places: Some(2),
+			..FormatHint::default()
+		};
+		assert_eq!("12 345.68", format_value(12345.678, hint));
+	}
+	#[test]
+	fn locale_separators() {
+		let hint = |locale: Locale| FormatHint {
+			locale: Some(locale),
+			..FormatHint::default()
+		};
+		assert_eq!("1.234,5", format_value(1234.5, hint(Locale::De)));
+		assert_eq!("-1.234.567,5", format_value(-1234567.5, hint(Locale::De)));
+		assert_eq!("-1,234,567.5", format_value(-1234567.5, hint(Locale::En)));
+		assert_eq!("-1 234 567,5", format_value(-1234567.5, hint(Locale::Fr)));
+		assert_eq!("-0,5", format_value(-0.5, hint(Locale::De)));
+		assert_eq!(
+			"0",
+			format_value(
+				-0.0001,
+				FormatHint {
+					locale: Some(Locale::De),
+					..FormatHint::from_step(0.5)
+				}
+			)
+		);
+		// an explicit separator takes precedence over the locale's grouping
+		let hint = FormatHint {
+			thousands_separator: Some(' '),
+			locale: Some(Locale::De),
+			..FormatHint::default()
+		};
+		assert_eq!("-12 345,5", format_value(-12345.5, hint));
+	}
+	#[test]
+	fn locale_is_ignored_by_scientific_notation() {
+		let hint = FormatHint {
+			locale: Some(Locale::De),
+			..FormatHint::default()
+		};
+		assert_eq!("-2.5e-5", format_value(-2.5e-5, hint));
+		assert_eq!("1.5e12", format_value(1.5e12, hint));
+		let hint = FormatHint {
+			notation: Notation::Scientific,
+			decimal_places: Some(2),
+			locale: Some(Locale::Fr),
+			..FormatHint::default()
+		};
+		assert_eq!("-1.23e3", format_value(-1234.0, hint));
+	}
+	#[test]
+	fn locale_dates() {
+		assert_eq!("06/01/2024", Locale::En.format_date(2024, 6, 1));
+		assert_eq!("01.06.2024", Locale::De.format_date(2024, 6, 1));
+		assert_eq!("01/06/2024", Locale::Fr.format_date(2024, 6, 1));
+	}
+	#[test]
+	fn locale_from_str() {
+		assert_eq!(Ok(Locale::De), "DE".parse::<Locale>());
+		let e = "xx".parse::<Locale>().unwrap_err();
+		assert!(e.contains("en, de and fr"), "{}", e);
+	}
+}
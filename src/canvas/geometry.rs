@@ -0,0 +1,140 @@
+//! Describes where the plot area ended up on the canvas so that other programs compositing the image can locate the axes,
+//! or so that separately rendered graphs can line their axes up with one another
+
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+use tracing::info;
+
+use crate::error::PlotError;
+
+/// Pixel positions of the axes along with the data limits they represent. Note that pixel positions use the
+/// top left corner of the canvas as the origin `(0, 0)`
+#[derive(Debug, Serialize)]
+pub struct PlotGeometry {
+	/// Size of the whole image
+	pub canvas_pixel_size: (u32, u32),
+	/// Bottom left corner of the plot area
+	pub axis_min_pixel: (u32, u32),
+	/// Top right corner of the plot area
+	pub axis_max_pixel: (u32, u32),
+	/// Pixel where the x and y axes intersect
+	pub axis_origin_pixel: (u32, u32),
+	/// The minimum and maximum x values spanned by the x-axis
+	pub x_data_min_max_limits: (i32, i32),
+	/// The minimum and maximum y values spanned by the y-axis
+	pub y_data_min_max_limits: (i32, i32),
+	/// The minimum and maximum values spanned by the secondary y-axis, when one is drawn
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub y2_data_min_max_limits: Option<(i32, i32)>,
+}
+
+impl PlotGeometry {
+	/// Write the geometry to disk as `.ron`
+	pub fn write(&self, path: &str) -> Result<(), PlotError> {
+		write_ron(self, path, "plot geometry")
+	}
+}
+
+/// The horizontal extent of a plot area, recorded by the first of several graphs rendered with the same
+/// `--align-file` so that the rest can place their x-axes at the same pixel columns
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct PlotAlignment {
+	/// Width of the whole image
+	pub canvas_width: u32,
+	/// Pixel column of the left end of the x-axis
+	pub axis_min_pixel_column: u32,
+	/// Pixel column of the right end of the x-axis
+	pub axis_max_pixel_column: u32,
+}
+
+impl PlotAlignment {
+	/// Read the alignment recorded by a previous graph, `None` if there isn't one yet
+	pub fn read(path: &str) -> Result<Option<PlotAlignment>, PlotError> {
+		if !Path::new(path).exists() {
+			return Ok(None);
+		}
+		let text = fs::read_to_string(path).map_err(|e| PlotError::Io {
+			context: format!("Unable to read plot alignment from {}", path),
+			source: e,
+		})?;
+		ron::from_str(&text).map(Some).map_err(|e| {
+			PlotError::Config(format!("Unable to parse plot alignment in {}: {}", path, e))
+		})
+	}
+	/// Write the alignment to disk as `.ron`
+	pub fn write(&self, path: &str) -> Result<(), PlotError> {
+		write_ron(self, path, "plot alignment")
+	}
+	/// Check that a graph on a canvas `canvas_width` wide, whose labels and legend consume
+	/// `h_space_from_left` and `h_space_from_right` pixels, can place its x-axis at the recorded columns.
+	/// The error describes how many more pixels are needed
+	pub fn check_fits(
+		&self,
+		canvas_width: u32,
+		h_space_from_left: u32,
+		h_space_from_right: u32,
+	) -> Result<(), String> {
+		if canvas_width != self.canvas_width {
+			return Err(format!(
+				"the canvas is {} pixels wide but the aligned graphs are {} pixels wide",
+				canvas_width, self.canvas_width
+			));
+		}
+		if h_space_from_left > self.axis_min_pixel_column {
+			return Err(format!(
+				"the graph needs {} more pixels left of the x-axis than the {} recorded",
+				h_space_from_left - self.axis_min_pixel_column,
+				self.axis_min_pixel_column
+			));
+		}
+		let space_right = canvas_width.saturating_sub(self.axis_max_pixel_column);
+		if h_space_from_right > space_right {
+			return Err(format!(
+				"the graph needs {} more pixels right of the x-axis than the {} recorded",
+				h_space_from_right - space_right,
+				space_right
+			));
+		}
+		Ok(())
+	}
+}
+
+/// Serialise a value to `.ron` and write it to `path`, `description` names it in log messages
+fn write_ron<T: Serialize>(value: &T, path: &str, description: &str) -> Result<(), PlotError> {
+	let ron = ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())
+		.map_err(|e| PlotError::Render(format!("Unable to serialise {}: {}", description, e)))?;
+	fs::write(path, ron).map_err(|e| PlotError::Io {
+		context: format!("Unable to write {} to {}", description, path),
+		source: e,
+	})?;
+	info!("Written {} to {}", description, path);
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::canvas::geometry::PlotAlignment;
+	#[test]
+	fn alignment_reports_the_missing_space() {
+		let alignment = PlotAlignment {
+			canvas_width: 800,
+			axis_min_pixel_column: 100,
+			axis_max_pixel_column: 750,
+		};
+		assert_eq!(Ok(()), alignment.check_fits(800, 100, 50));
+		assert_eq!(
+			Err(
+				"the graph needs 12 more pixels left of the x-axis than the 100 recorded"
+					.to_owned()
+			),
+			alignment.check_fits(800, 112, 50)
+		);
+		assert_eq!(
+			Err(
+				"the graph needs 5 more pixels right of the x-axis than the 50 recorded".to_owned()
+			),
+			alignment.check_fits(800, 80, 55)
+		);
+		assert!(alignment.check_fits(640, 80, 50).is_err());
+	}
+}
@@ -1,10 +1,10 @@
 //! Calculation of font sizes, vectors of glyphs, drawing of glyphs and helper methods to find glyph height and width to assist in positioning text on a canvas
 
 use image::{ImageBuffer, Rgba};
-use rusttype::{point, Font, PositionedGlyph, Scale};
+use rusttype::{point, Font, PositionedGlyph, Rect, Scale};
 use tracing::{debug, warn};
 
-use crate::colours::*;
+use crate::{canvas::fill::blend_pixel, colours::*};
 
 /// Font sizes for the different elements of a graph
 pub struct FontSizes {
@@ -44,6 +44,16 @@ impl FontSizes {
 			legend_font_size,
 		}
 	}
+	/// Multiply each font size by `factor` to draw the same text on a canvas `factor` times larger
+	pub fn scale(self, factor: u32) -> FontSizes {
+		let factor = factor as f32;
+		FontSizes {
+			title_font_size: self.title_font_size * factor,
+			axis_font_size: self.axis_font_size * factor,
+			axis_unit_font_size: self.axis_unit_font_size * factor,
+			legend_font_size: self.legend_font_size * factor,
+		}
+	}
 }
 
 /// Creates a vector of gyphs running left to right
@@ -77,44 +87,198 @@ pub fn draw_glyphs(
 				let b = colour[2];
 				let a = (v * 255.0) as u8;
 				// select pixels with an offset
-				let px = x + position.0 + bounding_box.min.x as u32;
-				let py = y + position.1 + bounding_box.min.y as u32;
+				let (px, py) = offset_glyph_pixel((x, y), position, (bounding_box.min.x, bounding_box.min.y));
+				// a pixel with an alpha channel of 0 is the empty space around a character which is filled
+				// with the white background, only within the glyph so that data drawn elsewhere is untouched
 				match canvas.get_pixel_mut_checked(px, py) {
+					Some(pixel) if a == 0 => *pixel = Rgba(WHITE),
 					Some(pixel) => *pixel = Rgba([r, g, b, a]),
 					None => warn!("Cannot draw text outside of canvas at ({}, {}), shorter title/labels required or increase the canvas size", px, py),
 				}
 			});
 		}
 	}
-	// drawing glyphs creates a pixel with an alpha channel of 0 surrounding its edges,
-	// these indicate the empty space around a character and we fill them in with white background pixels
-	for pixel in canvas.pixels_mut() {
-		if pixel.0[3] == 0 {
-			*pixel = Rgba(WHITE);
+}
+/// Draws glyphs by blending their coverage over what is already on the canvas, so that text keeps the
+/// colour beneath it rather than leaving white around each character
+pub fn blend_glyphs(
+	canvas: &mut ImageBuffer<Rgba<u8>, Vec<u8>>,
+	colour: [u8; 4],
+	glyphs: Vec<PositionedGlyph>,
+	position: (u32, u32),
+) {
+	for glyph in glyphs {
+		if let Some(bounding_box) = glyph.pixel_bounding_box() {
+			glyph.draw(|x, y, v| {
+				let (px, py) =
+					offset_glyph_pixel((x, y), position, (bounding_box.min.x, bounding_box.min.y));
+				blend_pixel(canvas, px, py, colour, v);
+			});
 		}
 	}
 }
-/// From a vector of glyphs find the maximum glyph height
+/// Draws glyphs turned a quarter anticlockwise so that they read from bottom to top, e.g alongside a
+/// y-axis. The glyphs are drawn onto a temporary buffer which is rotated and copied onto the canvas, the
+/// position is taken to be the top left corner of the turned text, see [`get_vertical_size_of_glyphs`]
+pub fn draw_glyphs_vertically(
+	canvas: &mut ImageBuffer<Rgba<u8>, Vec<u8>>,
+	colour: [u8; 4],
+	glyphs: Vec<PositionedGlyph>,
+	position: (u32, u32),
+) {
+	let bounds = match glyph_bounds(&glyphs) {
+		Some(bounds) => bounds,
+		None => return,
+	};
+	// glyphs cannot be drawn before the buffer edge so any which start left of or above their origin
+	// push the rest along
+	let shift_x = (-bounds.min.x).max(0);
+	let shift_y = (-bounds.min.y).max(0);
+	let mut buffer = ImageBuffer::new(
+		(bounds.max.x + shift_x) as u32,
+		(bounds.max.y + shift_y) as u32,
+	);
+	draw_glyphs(
+		&mut buffer,
+		colour,
+		glyphs,
+		(shift_x as u32, shift_y as u32),
+	);
+	let text = image::imageops::crop_imm(
+		&buffer,
+		(bounds.min.x + shift_x) as u32,
+		(bounds.min.y + shift_y) as u32,
+		bounds.width() as u32,
+		bounds.height() as u32,
+	)
+	.to_image();
+	for (x, y, pixel) in image::imageops::rotate270(&text).enumerate_pixels() {
+		let (px, py) = (position.0 + x, position.1 + y);
+		match canvas.get_pixel_mut_checked(px, py) {
+			Some(canvas_pixel) => *canvas_pixel = *pixel,
+			None => warn!("Cannot draw text outside of canvas at ({}, {}), shorter title/labels required or increase the canvas size", px, py),
+		}
+	}
+}
+/// Find the canvas pixel a glyph pixel should be drawn to. A glyph's bounding box can start left of or
+/// above its origin, e.g `j` or italic characters, so the offset is found in `i64` and any pixel which
+/// would fall before the canvas edge is clamped onto it rather than wrapping around
+fn offset_glyph_pixel(
+	glyph_pixel: (u32, u32),
+	position: (u32, u32),
+	bounding_box_min: (i32, i32),
+) -> (u32, u32) {
+	let offset = |pixel: u32, position: u32, min: i32| -> u32 {
+		let value = pixel as i64 + position as i64 + min as i64;
+		value.clamp(0, u32::MAX as i64) as u32
+	};
+	(
+		offset(glyph_pixel.0, position.0, bounding_box_min.0),
+		offset(glyph_pixel.1, position.1, bounding_box_min.1),
+	)
+}
+/// From a vector of glyphs find the maximum glyph height. Whitespace has no bounding box so it is skipped at
+/// either end, text without any visible glyphs has no height
 pub fn get_maximum_height_of_glyphs(glyphs: &[PositionedGlyph]) -> u32 {
-	let min_y = glyphs
-		.first()
-		.map(|g| g.pixel_bounding_box().unwrap().min.y)
-		.unwrap();
-	let max_y = glyphs
-		.last()
-		.map(|g| g.pixel_bounding_box().unwrap().max.y)
-		.unwrap();
-	(max_y - min_y) as u32
+	match visible_extents(glyphs) {
+		Some((first, last)) => (last.max.y - first.min.y) as u32,
+		None => 0,
+	}
 }
-/// From a vector of glyphs find the total width
+/// From a vector of glyphs find the total width, text without any visible glyphs has no width
 pub fn get_width_of_glyphs(glyphs: &[PositionedGlyph]) -> u32 {
-	let min_x = glyphs
-		.first()
-		.map(|g| g.pixel_bounding_box().unwrap().min.x)
-		.unwrap();
-	let max_x = glyphs
-		.last()
-		.map(|g| g.pixel_bounding_box().unwrap().max.x)
-		.unwrap();
-	(max_x - min_x) as u32
+	match visible_extents(glyphs) {
+		Some((first, last)) => (last.max.x - first.min.x) as u32,
+		None => 0,
+	}
+}
+/// The `(width, height)` of glyphs drawn with [`draw_glyphs_vertically`], as wide as the tallest glyph
+/// reaches and as tall as the text is long
+pub fn get_vertical_size_of_glyphs(glyphs: &[PositionedGlyph]) -> (u32, u32) {
+	match glyph_bounds(glyphs) {
+		Some(bounds) => (bounds.height() as u32, bounds.width() as u32),
+		None => (0, 0),
+	}
+}
+/// The pixel bounding box enclosing every visible glyph
+fn glyph_bounds(glyphs: &[PositionedGlyph]) -> Option<Rect<i32>> {
+	glyphs
+		.iter()
+		.filter_map(|g| g.pixel_bounding_box())
+		.reduce(|bounds, glyph| Rect {
+			min: point(bounds.min.x.min(glyph.min.x), bounds.min.y.min(glyph.min.y)),
+			max: point(bounds.max.x.max(glyph.max.x), bounds.max.y.max(glyph.max.y)),
+		})
+}
+/// The pixel bounding boxes of the first and last glyphs which have one
+fn visible_extents(glyphs: &[PositionedGlyph]) -> Option<(Rect<i32>, Rect<i32>)> {
+	let first = glyphs.iter().find_map(|g| g.pixel_bounding_box())?;
+	let last = glyphs.iter().rev().find_map(|g| g.pixel_bounding_box())?;
+	Some((first, last))
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		canvas::{
+			draw_base_canvas,
+			glyphs::{
+				create_glyphs, draw_glyphs, draw_glyphs_vertically, get_vertical_size_of_glyphs,
+				offset_glyph_pixel,
+			},
+		},
+		colours::{BLACK, WHITE},
+		get_system_font,
+	};
+	#[test]
+	fn negative_bounding_box_is_clamped() {
+		assert_eq!((0, 0), offset_glyph_pixel((0, 0), (0, 0), (-2, -1)));
+		assert_eq!((1, 3), offset_glyph_pixel((3, 4), (0, 0), (-2, -1)));
+		assert_eq!((13, 9), offset_glyph_pixel((3, 4), (10, 5), (0, 0)));
+	}
+	#[test]
+	fn text_flush_with_canvas_origin_is_drawn() {
+		let font = get_system_font().unwrap();
+		for text in ["j", "/"] {
+			let glyphs = create_glyphs(20.0, text, &font);
+			// every pixel of the glyph must land on the canvas so nothing is skipped with a warning
+			for glyph in glyphs.iter() {
+				if let Some(bounding_box) = glyph.pixel_bounding_box() {
+					glyph.draw(|x, y, _| {
+						let (px, py) = offset_glyph_pixel(
+							(x, y),
+							(0, 0),
+							(bounding_box.min.x, bounding_box.min.y),
+						);
+						assert!(px < 40 && py < 40, "{} drawn at ({}, {})", text, px, py);
+					});
+				}
+			}
+			let mut canvas = draw_base_canvas((40, 40));
+			draw_glyphs(&mut canvas, BLACK, glyphs, (0, 0));
+			let drawn = canvas
+				.enumerate_pixels()
+				.filter(|(x, y, pixel)| *x < 20 && *y < 30 && pixel.0 != WHITE)
+				.count();
+			assert!(drawn > 0, "{} was not drawn", text);
+		}
+	}
+	#[test]
+	fn vertical_text_is_drawn_within_its_turned_size() {
+		let font = get_system_font().unwrap();
+		let glyphs = create_glyphs(20.0, "Energy (kJ)", &font);
+		let (width, height) = get_vertical_size_of_glyphs(&glyphs);
+		assert!(height > 2 * width);
+		let mut canvas = draw_base_canvas((100, 200));
+		draw_glyphs_vertically(&mut canvas, BLACK, glyphs, (10, 20));
+		let drawn: Vec<(u32, u32)> = canvas
+			.enumerate_pixels()
+			.filter(|(_, _, pixel)| pixel.0 != WHITE)
+			.map(|(x, y, _)| (x, y))
+			.collect();
+		assert!(!drawn.is_empty());
+		for (x, y) in drawn {
+			assert!((10..10 + width).contains(&x) && (20..20 + height).contains(&y));
+		}
+	}
 }
@@ -1,24 +1,95 @@
 //! Controls drawing a legend onto a canvas
 
 use image::{ImageBuffer, Rgba};
+use rusttype::{Font, PositionedGlyph};
+use serde::{Deserialize, Serialize};
 use tracing::{debug, trace, warn};
 
 use crate::{
 	canvas::{
-		glyphs::{create_glyphs, draw_glyphs, get_maximum_height_of_glyphs},
-		VHConsumedCanvasSpace,
+		axes::get_line_extents,
+		best_fit::{Corner, LineStyle},
+		glyphs::{
+			blend_glyphs, create_glyphs, draw_glyphs, get_maximum_height_of_glyphs,
+			get_width_of_glyphs,
+		},
+		PixelStamp, VHConsumedCanvasSpace,
 	},
-	colours::{Colour, BLACK},
-	get_system_font,
+	colours::{Colour, BLACK, WHITE},
+	error::PlotError,
 };
 
 use super::plot::DataSymbol;
+/// The top left and bottom right pixels of a rectangle on the canvas
+pub type PixelBounds = ((u32, u32), (u32, u32));
+/// What is drawn beside the name of a legend entry
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum LegendSwatch {
+	/// The symbol the points of a data set are drawn with
+	Symbol(DataSymbol),
+	/// A short horizontal line `thickness` pixels thick, representing a curve
+	Line {
+		/// Pixel thickness of the line
+		thickness: u32,
+		/// The pattern of the curve so that dashed and dotted curves can be told apart
+		style: LineStyle,
+	},
+}
+/// Where a legend is drawn
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Default)]
+pub enum LegendPosition {
+	/// Inside the axes, beside the top of the y-axis
+	TopLeft,
+	/// Inside the axes, opposite the origin of a graph in the top right quadrant
+	TopRight,
+	/// Inside the axes, beside the origin of a graph in the top right quadrant
+	BottomLeft,
+	/// Inside the axes, beside the end of the x-axis
+	BottomRight,
+	/// To the right of the axes in space reserved from the canvas
+	#[default]
+	Outside,
+}
+
+impl LegendPosition {
+	/// The corner of the axes the legend is drawn in, `None` when it is drawn outside of them
+	pub fn corner(self) -> Option<Corner> {
+		match self {
+			LegendPosition::TopLeft => Some(Corner::TopLeft),
+			LegendPosition::TopRight => Some(Corner::TopRight),
+			LegendPosition::BottomLeft => Some(Corner::BottomLeft),
+			LegendPosition::BottomRight => Some(Corner::BottomRight),
+			LegendPosition::Outside => None,
+		}
+	}
+}
+impl From<Corner> for LegendPosition {
+	fn from(corner: Corner) -> Self {
+		match corner {
+			Corner::TopLeft => LegendPosition::TopLeft,
+			Corner::TopRight => LegendPosition::TopRight,
+			Corner::BottomLeft => LegendPosition::BottomLeft,
+			Corner::BottomRight => LegendPosition::BottomRight,
+		}
+	}
+}
+/// How a legend is placed and decorated
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct LegendStyle {
+	/// Where the legend is drawn
+	pub position: LegendPosition,
+	/// Should a border be drawn around the legend
+	pub frame: bool,
+	/// The colour filling the legend behind its entries, a legend inside the axes defaults to white so
+	/// that it stays legible over the grid and data
+	pub background: Option<Colour>,
+}
 /// Representation of a legend entry (row)
 #[derive(Debug)]
 pub struct LegendField {
-	/// The symbol that represents the data set
-	pub symbol: DataSymbol,
-	/// The size of the symbol
+	/// The symbol or line that represents the data set
+	pub swatch: LegendSwatch,
+	/// The size of the symbol, zero for a line
 	pub symbol_radius: u32,
 	/// The thickness of the smbol
 	pub symbol_thickness: u32,
@@ -27,55 +98,720 @@ pub struct LegendField {
 	/// The name of the data set
 	pub name: String,
 }
-/// From a given `origin` point create a series of rows containing the symbol and name of each data set
+/// The entries of a legend with an optional title written above them
+#[derive(Debug)]
+pub struct Legend {
+	/// Written above the entries a little larger than their names
+	pub title: Option<String>,
+	/// One row per entry
+	pub fields: Vec<LegendField>,
+	/// The distance between the tops of consecutive rows as a multiple of the height of the tallest name
+	pub row_spacing: f32,
+	/// Whether the edges of symbols are smoothed as they are on the plot
+	pub antialias: bool,
+}
+/// How many line swatches side by side span the x-axis the pattern of a curve is measured against, so
+/// that a dashed swatch shows two dashes
+const SWATCH_PATTERN_SPAN: u32 = 20;
+/// How much larger than the names of a legend its title is written
+const LEGEND_TITLE_FONT_SCALE: f32 = 1.25;
+/// The glyphs of a legend and the sizes needed to place them
+struct LegendLayout<'a> {
+	/// The radius given to every symbol, as symbols have different radii the largest is used so that
+	/// the names line up
+	max_radius: u32,
+	/// The glyphs of each name
+	glyphs: Vec<Vec<PositionedGlyph<'a>>>,
+	/// The height of the tallest name
+	height: u32,
+	/// The distance between the tops of consecutive rows
+	row_pitch: u32,
+	/// The glyphs of the title
+	title: Option<Vec<PositionedGlyph<'a>>>,
+	/// The height of the title and the space below it, zero without a title
+	title_height: u32,
+}
+/// Create the glyphs of a legend and measure them
+fn get_legend_layout<'a>(
+	legend: &'a Legend,
+	font_size: f32,
+	scale: u32,
+	font: &'a Font,
+) -> LegendLayout<'a> {
+	let max_radius = legend
+		.fields
+		.iter()
+		.map(|field| field.symbol_radius)
+		.max()
+		.unwrap_or(0)
+		+ 2 * scale;
+	let glyphs: Vec<_> = legend
+		.fields
+		.iter()
+		.map(|field| create_glyphs(font_size, &field.name, font))
+		.collect();
+	let height = glyphs
+		.iter()
+		.map(|glyphs| get_maximum_height_of_glyphs(glyphs))
+		.max()
+		.unwrap_or(0);
+	let row_pitch = (height as f32 * legend.row_spacing).round() as u32;
+	let title = legend
+		.title
+		.as_ref()
+		.map(|title| create_glyphs(font_size * LEGEND_TITLE_FONT_SCALE, title, font));
+	let title_height = title
+		.as_ref()
+		.map_or(0, |title| get_maximum_height_of_glyphs(title) + height / 2);
+	LegendLayout {
+		max_radius,
+		glyphs,
+		height,
+		row_pitch,
+		title,
+		title_height,
+	}
+}
+/// The width of a legend, the height above the centre of its first row and the height below it
+fn get_legend_extents(
+	legend: &Legend,
+	font: &Font,
+	font_size: f32,
+	scale: u32,
+) -> Result<(u32, u32, u32), PlotError> {
+	let layout = get_legend_layout(legend, font_size, scale, font);
+	let width = layout
+		.glyphs
+		.iter()
+		.map(|glyphs| get_width_of_glyphs(glyphs))
+		.max()
+		.unwrap_or(0);
+	let title_width = layout
+		.title
+		.as_ref()
+		.map_or(0, |title| get_width_of_glyphs(title));
+	let rows = legend.fields.len().saturating_sub(1) as u32;
+	Ok((
+		((layout.max_radius + scale) * 3 + width).max(title_width),
+		layout.max_radius + layout.title_height,
+		rows * layout.row_pitch + layout.height.max(layout.max_radius),
+	))
+}
+/// The space between the entries of a legend and the edge of its background
+fn get_legend_padding(font_size: f32) -> u32 {
+	(font_size / 4.0) as u32
+}
+/// The top left and bottom right pixels of the legend [`build_legend`] draws at `origin`, including the
+/// padding its background and border surround the entries with, so that other text can keep clear of it
+pub fn get_legend_bounds(
+	origin: (u32, u32),
+	legend: &Legend,
+	font: &Font,
+	font_size: f32,
+	scale: u32,
+) -> Result<PixelBounds, PlotError> {
+	let (width, above, below) = get_legend_extents(legend, font, font_size, scale)?;
+	let padding = get_legend_padding(font_size);
+	Ok((
+		(
+			origin.0.saturating_sub(padding),
+			origin.1.saturating_sub(above + padding),
+		),
+		(origin.0 + width + padding, origin.1 + below + padding),
+	))
+}
+/// The gap left between a legend outside the axes and whatever is drawn to its left
+fn get_outside_legend_gap(font_size: f32) -> u32 {
+	font_size as u32
+}
+/// The origin to give [`build_legend`] so that a legend outside the axes ends, background and border
+/// included, at the `right` pixel column with its first row at `y`. The legend is as wide as its longest
+/// name so that it never runs off the canvas
+pub fn get_outside_legend_origin(
+	right: u32,
+	y: u32,
+	legend: &Legend,
+	font: &Font,
+	font_size: f32,
+	scale: u32,
+) -> Result<(u32, u32), PlotError> {
+	let (width, _, _) = get_legend_extents(legend, font, font_size, scale)?;
+	let padding = get_legend_padding(font_size);
+	Ok((right.saturating_sub(width + padding), y))
+}
+/// The canvas space a legend at `position` consumes, measured before it is drawn so that the axes can be
+/// placed clear of it. A legend outside the axes takes its width and a gap from the right of the canvas
+/// while one inside them takes nothing
+pub fn get_legend_space(
+	legend: &Legend,
+	font: &Font,
+	font_size: f32,
+	scale: u32,
+	position: LegendPosition,
+) -> Result<VHConsumedCanvasSpace, PlotError> {
+	let h_space_from_right = match position {
+		LegendPosition::Outside => {
+			let (width, _, _) = get_legend_extents(legend, font, font_size, scale)?;
+			width + 2 * get_legend_padding(font_size) + 1 + get_outside_legend_gap(font_size)
+		}
+		_ => 0,
+	};
+	Ok(VHConsumedCanvasSpace {
+		v_space_from_top: 0,
+		h_space_from_right,
+		v_space_from_bottom: 0,
+		h_space_from_left: 0,
+	})
+}
+/// The origin to give [`build_legend`] so that the legend hugs `corner` of the axes spanning
+/// `axis_min_pixel` to `axis_max_pixel`, inset from them by half the font size
+pub fn get_legend_origin(
+	corner: Corner,
+	axis_min_pixel: (u32, u32),
+	axis_max_pixel: (u32, u32),
+	legend: &Legend,
+	font: &Font,
+	font_size: f32,
+	scale: u32,
+) -> Result<(u32, u32), PlotError> {
+	let (width, above, below) = get_legend_extents(legend, font, font_size, scale)?;
+	// the background and border are kept clear of the axes, not just the entries
+	let gap = (font_size / 2.0) as u32 + get_legend_padding(font_size);
+	let x = match corner {
+		Corner::TopLeft | Corner::BottomLeft => axis_min_pixel.0 + gap,
+		Corner::TopRight | Corner::BottomRight => axis_max_pixel.0.saturating_sub(width + gap),
+	};
+	let y = match corner {
+		Corner::TopLeft | Corner::TopRight => axis_max_pixel.1 + gap + above,
+		Corner::BottomLeft | Corner::BottomRight => axis_min_pixel.1.saturating_sub(below + gap),
+	};
+	Ok((x, y))
+}
+/// The corner of the axes where a legend would cover the fewest pixels already drawn, so that an overlaid
+/// legend hides as little of the data as possible. Equally busy corners are preferred in the order top
+/// right, top left, bottom right then bottom left
+pub fn get_quietest_corner(
+	canvas: &ImageBuffer<Rgba<u8>, Vec<u8>>,
+	axis_min_pixel: (u32, u32),
+	axis_max_pixel: (u32, u32),
+	legend: &Legend,
+	font: &Font,
+	font_size: f32,
+	scale: u32,
+) -> Result<Corner, PlotError> {
+	let mut quietest = (Corner::TopRight, usize::MAX);
+	for corner in [
+		Corner::TopRight,
+		Corner::TopLeft,
+		Corner::BottomRight,
+		Corner::BottomLeft,
+	] {
+		let origin = get_legend_origin(
+			corner,
+			axis_min_pixel,
+			axis_max_pixel,
+			legend,
+			font,
+			font_size,
+			scale,
+		)?;
+		let ((min_x, min_y), (max_x, max_y)) =
+			get_legend_bounds(origin, legend, font, font_size, scale)?;
+		let drawn = (min_x..=max_x)
+			.flat_map(|x| (min_y..=max_y).map(move |y| (x, y)))
+			.filter(|(x, y)| {
+				canvas
+					.get_pixel_checked(*x, *y)
+					.is_some_and(|pixel| pixel.0 != WHITE)
+			})
+			.count();
+		trace!(
+			"A legend in the {:?} corner covers {} pixels",
+			corner,
+			drawn
+		);
+		if drawn < quietest.1 {
+			quietest = (corner, drawn);
+		}
+	}
+	Ok(quietest.0)
+}
+/// From a given `origin` point create a series of rows containing the symbol or line and name of each data
+/// set, the rows are evenly spaced by `row_spacing` times the tallest name and any title is written above them. The legend is measured before anything is drawn
+/// so that its background and border can be laid beneath the entries. The space it consumes is that of
+/// [`get_legend_space`]
 pub fn build_legend(
 	canvas: &mut ImageBuffer<Rgba<u8>, Vec<u8>>,
 	origin: (u32, u32),
-	fields: Vec<LegendField>,
+	legend: Legend,
+	font: &Font,
 	font_size: f32,
-) -> VHConsumedCanvasSpace {
-	debug!("Building legend at {:?}...", origin);
-	let font = get_system_font();
-	// As symbols have different radii we want to find the maximum so we can space out the legend elements
-	// with the same offset
-	let max_radius: u32 = fields
-		.iter()
-		.max_by(|a, b| a.symbol_radius.cmp(&b.symbol_radius))
-		.unwrap()
-		.symbol_radius
-		+ 2;
-	for (i, field) in fields.iter().enumerate() {
+	scale: u32,
+	style: LegendStyle,
+) -> Result<VHConsumedCanvasSpace, PlotError> {
+	debug!("Building legend {:?} at {:?}...", style, origin);
+	let ((min_x, min_y), (max_x, max_y)) =
+		get_legend_bounds(origin, &legend, font, font_size, scale)?;
+	let (canvas_width, canvas_height) = canvas.dimensions();
+	let (max_x, max_y) = (
+		max_x.min(canvas_width.saturating_sub(1)),
+		max_y.min(canvas_height.saturating_sub(1)),
+	);
+	let background = match (style.background, style.position) {
+		(None, LegendPosition::Outside) => None,
+		(background, _) => Some(background.unwrap_or(Colour::White)),
+	};
+	if let Some(background) = background {
+		// clear gridlines and curves from behind the legend so that it stays legible
+		let pixel = Rgba(Colour::get_pixel_colour(background));
+		for x in min_x..=max_x {
+			for y in min_y..=max_y {
+				canvas.put_pixel(x, y, pixel);
+			}
+		}
+	}
+	if style.frame {
+		// the border is a pixel thick once the canvas is downsampled
+		for x in min_x..=max_x {
+			for y in min_y..=max_y {
+				if x < min_x + scale || x + scale > max_x || y < min_y + scale || y + scale > max_y
+				{
+					canvas.put_pixel(x, y, Rgba(BLACK));
+				}
+			}
+		}
+	}
+	let LegendLayout {
+		max_radius,
+		glyphs,
+		row_pitch,
+		title,
+		title_height,
+		..
+	} = get_legend_layout(&legend, font_size, scale, font);
+	if let Some(title) = title {
+		let title_position = (origin.0, origin.1.saturating_sub(max_radius + title_height));
+		match background {
+			Some(_) => blend_glyphs(canvas, BLACK, title, title_position),
+			None => draw_glyphs(canvas, BLACK, title, title_position),
+		}
+	}
+	for (i, (field, glyphs)) in legend.fields.iter().zip(glyphs).enumerate() {
 		trace!("Legend field {:?}", field);
-		let glyphs = create_glyphs(font_size, &field.name, &font);
-		// height is used to write legend fields on new rows
-		let height = get_maximum_height_of_glyphs(&glyphs);
 		let symbol_position = (
-			origin.0 + (max_radius + 1),
-			origin.1 + (i as u32 * height * 2),
+			origin.0 + (max_radius + scale),
+			origin.1 + i as u32 * row_pitch,
 		);
-		let pixels =
-			field
-				.symbol
-				.find_pixels(symbol_position, field.symbol_thickness, field.symbol_radius);
-		for (px, py) in pixels.iter() {
-			match canvas.get_pixel_mut_checked(*px, *py) {
-				Some(pixel) => *pixel = Rgba(Colour::get_pixel_colour(field.colour)),
-				None => warn!(
+		let (pixels, block_size): (Vec<(u32, u32, f32)>, u32) = match field.swatch {
+			// a point has no edges to smooth
+			LegendSwatch::Symbol(symbol) if legend.antialias && symbol != DataSymbol::Point => (
+				symbol.find_coverage(symbol_position, field.symbol_thickness, field.symbol_radius),
+				1,
+			),
+			// the other symbols are sized by the caller, a point is a single block
+			LegendSwatch::Symbol(symbol) => (
+				symbol
+					.find_pixels(symbol_position, field.symbol_thickness, field.symbol_radius)
+					.into_iter()
+					.map(|(x, y)| (x, y, 1.0))
+					.collect(),
+				if matches!(symbol, DataSymbol::Point) {
+					scale
+				} else {
+					1
+				},
+			),
+			// spans the width given to a symbol, centred on its row
+			LegendSwatch::Line { thickness, style } => {
+				let (before, _) = get_line_extents(thickness, 1);
+				let y = symbol_position.1.saturating_sub(before);
+				let width = 2 * (max_radius + scale);
+				let pattern_limits = (0, (width * SWATCH_PATTERN_SPAN) as i32);
+				(
+					(0..=width)
+						.filter(|dx| style.is_drawn(*dx as f32, pattern_limits))
+						.map(|dx| (origin.0 + dx, y, 1.0))
+						.collect(),
+					thickness,
+				)
+			}
+		};
+		let mut stamp = PixelStamp::new(canvas);
+		for (px, py, coverage) in pixels.iter() {
+			let is_inside = if *coverage < 1.0 {
+				stamp.cover(*px, *py, *coverage)
+			} else {
+				stamp.block(*px, *py, block_size)
+			};
+			if !is_inside {
+				warn!(
 					"Cannot plot legend point with symbol pixel position ({}, {})",
 					px, py
-				),
+				);
 			}
 		}
+		stamp.draw(canvas, Colour::get_pixel_colour(field.colour));
 		let text_position = (
-			origin.0 + (max_radius + 1) * 3,
-			origin.1 + (i as u32 * height * 2),
+			origin.0 + (max_radius + scale) * 3,
+			origin.1 + i as u32 * row_pitch,
 		);
-		draw_glyphs(canvas, BLACK, glyphs, text_position);
+		// text over a background keeps its colour rather than leaving white around each character
+		match background {
+			Some(_) => blend_glyphs(canvas, BLACK, glyphs, text_position),
+			None => draw_glyphs(canvas, BLACK, glyphs, text_position),
+		}
 	}
-	VHConsumedCanvasSpace {
-		v_space_from_top: 0,
-		h_space_from_right: (canvas.dimensions().0 - (origin.0))/2,
-		v_space_from_bottom: 0,
-		h_space_from_left: 0,
+	get_legend_space(&legend, font, font_size, scale, style.position)
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		canvas::{
+			best_fit::{Corner, LineStyle},
+			draw_base_canvas,
+			legend::{
+				build_legend, get_legend_bounds, get_legend_origin, get_outside_legend_origin,
+				get_quietest_corner, Legend, LegendField, LegendPosition, LegendStyle,
+				LegendSwatch,
+			},
+			plot::DataSymbol,
+		},
+		colours::{Colour, BLACK},
+		get_system_font,
+	};
+	fn legend() -> Legend {
+		Legend {
+			title: None,
+			row_spacing: 2.0,
+			antialias: false,
+			fields: vec![
+				LegendField {
+					swatch: LegendSwatch::Symbol(DataSymbol::Circle),
+					symbol_radius: 4,
+					symbol_thickness: 1,
+					colour: Colour::Red,
+					name: String::from("Temperature"),
+				},
+				LegendField {
+					swatch: LegendSwatch::Line {
+						thickness: 2,
+						style: LineStyle::Solid,
+					},
+					symbol_radius: 0,
+					symbol_thickness: 0,
+					colour: Colour::Blue,
+					name: String::from("Temperature (fit)"),
+				},
+			],
+		}
+	}
+	#[test]
+	fn legend_hugs_each_corner_inside_the_axes() {
+		let font = get_system_font().unwrap();
+		let (axis_min, axis_max) = ((100, 500), (700, 50));
+		let gap = 7;
+		for corner in [
+			Corner::TopLeft,
+			Corner::TopRight,
+			Corner::BottomLeft,
+			Corner::BottomRight,
+		] {
+			let origin =
+				get_legend_origin(corner, axis_min, axis_max, &legend(), &font, 14.0, 1).unwrap();
+			let ((left, top), (right, bottom)) =
+				get_legend_bounds(origin, &legend(), &font, 14.0, 1).unwrap();
+			match corner {
+				Corner::TopLeft | Corner::BottomLeft => assert_eq!(axis_min.0 + gap, left),
+				Corner::TopRight | Corner::BottomRight => assert_eq!(axis_max.0 - gap, right),
+			}
+			match corner {
+				Corner::TopLeft | Corner::TopRight => assert_eq!(axis_max.1 + gap, top),
+				Corner::BottomLeft | Corner::BottomRight => assert_eq!(axis_min.1 - gap, bottom),
+			}
+		}
+	}
+	#[test]
+	fn only_an_outside_legend_consumes_canvas_space() {
+		let font = get_system_font().unwrap();
+		let mut canvas = draw_base_canvas((800, 600));
+		let outside = build_legend(
+			&mut canvas,
+			(700, 150),
+			legend(),
+			&font,
+			14.0,
+			1,
+			LegendStyle {
+				position: LegendPosition::Outside,
+				frame: false,
+				background: None,
+			},
+		)
+		.unwrap();
+		let ((left, _), (right, _)) =
+			get_legend_bounds((700, 150), &legend(), &font, 14.0, 1).unwrap();
+		assert_eq!(right + 1 - left + 14, outside.h_space_from_right);
+		let inside = build_legend(
+			&mut canvas,
+			(100, 150),
+			legend(),
+			&font,
+			14.0,
+			1,
+			LegendStyle {
+				position: LegendPosition::TopLeft,
+				frame: false,
+				background: None,
+			},
+		)
+		.unwrap();
+		assert_eq!(0, inside.h_space_from_right);
+		assert_eq!(None, LegendPosition::Outside.corner());
+		assert_eq!(
+			Some(Corner::BottomRight),
+			LegendPosition::BottomRight.corner()
+		);
+	}
+	#[test]
+	fn legend_is_framed_and_filled_behind_its_entries() {
+		let font = get_system_font().unwrap();
+		let mut canvas = draw_base_canvas((400, 300));
+		let origin = (100, 100);
+		build_legend(
+			&mut canvas,
+			origin,
+			legend(),
+			&font,
+			14.0,
+			2,
+			LegendStyle {
+				position: LegendPosition::Outside,
+				frame: true,
+				background: Some(Colour::Orange),
+			},
+		)
+		.unwrap();
+		let ((min_x, min_y), (max_x, max_y)) =
+			get_legend_bounds(origin, &legend(), &font, 14.0, 2).unwrap();
+		let black = Colour::get_pixel_colour(Colour::Black);
+		let orange = Colour::get_pixel_colour(Colour::Orange);
+		// the border is as thick as the supersampling scale
+		for (x, y) in [
+			(min_x, min_y),
+			(min_x + 1, max_y),
+			(max_x, min_y + 10),
+			(max_x - 1, max_y - 1),
+		] {
+			assert_eq!(black, canvas.get_pixel(x, y).0, "({}, {})", x, y);
+		}
+		assert_eq!(orange, canvas.get_pixel(min_x + 2, min_y + 2).0);
+		assert_eq!(orange, canvas.get_pixel(max_x - 2, max_y - 2).0);
+		// nothing is drawn beyond the border
+		let white = Colour::get_pixel_colour(Colour::White);
+		assert_eq!(white, canvas.get_pixel(min_x - 1, min_y).0);
+		assert_eq!(white, canvas.get_pixel(max_x + 1, max_y).0);
+	}
+	#[test]
+	fn long_names_widen_the_legend_without_leaving_the_canvas() {
+		let font = get_system_font().unwrap();
+		let mut long_legend = legend();
+		long_legend.fields[0].name = String::from("Ambient temperature at the north station");
+		assert_eq!(40, long_legend.fields[0].name.len());
+		let style = LegendStyle {
+			position: LegendPosition::Outside,
+			frame: false,
+			background: None,
+		};
+		let right = 790;
+		let mut space = Vec::new();
+		for legend in [legend(), long_legend] {
+			let origin = get_outside_legend_origin(right, 150, &legend, &font, 14.0, 1).unwrap();
+			let ((left, _), (legend_right, _)) =
+				get_legend_bounds(origin, &legend, &font, 14.0, 1).unwrap();
+			assert_eq!(right, legend_right);
+			let mut canvas = draw_base_canvas((800, 600));
+			let consumed =
+				build_legend(&mut canvas, origin, legend, &font, 14.0, 1, style).unwrap();
+			assert_eq!(right + 1 - left + 14, consumed.h_space_from_right);
+			space.push(consumed.h_space_from_right);
+		}
+		// the space reserved follows the text rather than the size of the canvas
+		assert!(space[1] > space[0] + 100, "{:?}", space);
+		assert!(space[1] < 800 / 2, "{:?}", space);
+	}
+	#[test]
+	fn overlaid_legend_avoids_drawn_corners() {
+		let font = get_system_font().unwrap();
+		let mut canvas = draw_base_canvas((800, 600));
+		let (axis_min, axis_max) = ((100, 500), (700, 50));
+		assert_eq!(
+			Corner::TopRight,
+			get_quietest_corner(&canvas, axis_min, axis_max, &legend(), &font, 14.0, 1).unwrap()
+		);
+		// data crowding the top half pushes the legend to the bottom right
+		for x in 100..700 {
+			for y in 50..275 {
+				canvas.put_pixel(x, y, image::Rgba(BLACK));
+			}
+		}
+		assert_eq!(
+			Corner::BottomRight,
+			get_quietest_corner(&canvas, axis_min, axis_max, &legend(), &font, 14.0, 1).unwrap()
+		);
+		assert_eq!(
+			LegendPosition::BottomLeft,
+			LegendPosition::from(Corner::BottomLeft)
+		);
+	}
+	#[test]
+	fn title_is_measured_above_the_entries() {
+		let font = get_system_font().unwrap();
+		let untitled = get_legend_bounds((200, 200), &legend(), &font, 14.0, 1).unwrap();
+		let mut titled_legend = legend();
+		titled_legend.title = Some(String::from("Sensors"));
+		let titled = get_legend_bounds((200, 200), &titled_legend, &font, 14.0, 1).unwrap();
+		// the entries stay where they were while the block grows upwards to fit the title
+		assert_eq!(untitled.1, titled.1);
+		assert!(
+			titled.0 .1 + 14 < untitled.0 .1,
+			"{:?} {:?}",
+			titled,
+			untitled
+		);
+		// a long title widens the block
+		titled_legend.title = Some(String::from("Readings taken across every sensor"));
+		let wide = get_legend_bounds((200, 200), &titled_legend, &font, 14.0, 1).unwrap();
+		assert!(wide.1 .0 > untitled.1 .0);
+		// placed in a corner the title is kept inside the axes
+		let origin = get_legend_origin(
+			Corner::TopLeft,
+			(100, 500),
+			(700, 50),
+			&titled_legend,
+			&font,
+			14.0,
+			1,
+		)
+		.unwrap();
+		let ((_, top), _) = get_legend_bounds(origin, &titled_legend, &font, 14.0, 1).unwrap();
+		assert_eq!(57, top);
+	}
+	#[test]
+	fn row_spacing_sets_the_distance_between_rows() {
+		let font = get_system_font().unwrap();
+		let height = |row_spacing: f32| {
+			let mut spaced = legend();
+			spaced.row_spacing = row_spacing;
+			let ((_, top), (_, bottom)) =
+				get_legend_bounds((200, 200), &spaced, &font, 14.0, 1).unwrap();
+			bottom - top
+		};
+		// two rows are a single pitch apart
+		let pitch = height(2.0) - height(1.0);
+		assert!(pitch > 5, "{}", pitch);
+		assert_eq!(pitch * 2, height(3.0) - height(1.0));
+	}
+	/// The mean height of the pixels drawn in `colour`
+	fn mean_y(canvas: &image::ImageBuffer<image::Rgba<u8>, Vec<u8>>, colour: Colour) -> f32 {
+		let pixel = Colour::get_pixel_colour(colour);
+		let ys: Vec<u32> = canvas
+			.enumerate_pixels()
+			.filter(|(_, _, p)| p.0 == pixel)
+			.map(|(_, y, _)| y)
+			.collect();
+		assert!(!ys.is_empty(), "{:?} is not drawn", colour);
+		ys.iter().sum::<u32>() as f32 / ys.len() as f32
+	}
+	#[test]
+	fn symbol_and_line_rows_are_evenly_spaced() {
+		let font = get_system_font().unwrap();
+		let field = |swatch, colour| LegendField {
+			swatch,
+			symbol_radius: 4,
+			symbol_thickness: 1,
+			colour,
+			name: String::from("Temperature"),
+		};
+		let line = LegendSwatch::Line {
+			thickness: 1,
+			style: LineStyle::Solid,
+		};
+		let colours = [Colour::Red, Colour::Blue, Colour::Green, Colour::Orange];
+		let mixed = Legend {
+			fields: vec![
+				field(LegendSwatch::Symbol(DataSymbol::Circle), colours[0]),
+				field(line, colours[1]),
+				field(line, colours[2]),
+				field(LegendSwatch::Symbol(DataSymbol::Circle), colours[3]),
+			],
+			..legend()
+		};
+		let mut canvas = draw_base_canvas((400, 300));
+		build_legend(
+			&mut canvas,
+			(100, 100),
+			mixed,
+			&font,
+			14.0,
+			1,
+			LegendStyle {
+				position: LegendPosition::Outside,
+				frame: false,
+				background: None,
+			},
+		)
+		.unwrap();
+		let rows: Vec<f32> = colours.iter().map(|c| mean_y(&canvas, *c)).collect();
+		let pitch = rows[1] - rows[0];
+		assert!(pitch > 5.0, "{:?}", rows);
+		for pair in rows.windows(2) {
+			assert!((pair[1] - pair[0] - pitch).abs() < 0.01, "{:?}", rows);
+		}
+	}
+	#[test]
+	fn line_swatch_is_drawn_in_the_style_of_its_curve() {
+		let font = get_system_font().unwrap();
+		let swatch = |style| {
+			let mut canvas = draw_base_canvas((400, 300));
+			let styled = Legend {
+				fields: vec![LegendField {
+					swatch: LegendSwatch::Line {
+						thickness: 1,
+						style,
+					},
+					symbol_radius: 8,
+					symbol_thickness: 1,
+					colour: Colour::Blue,
+					name: String::from("Temperature (fit)"),
+				}],
+				..legend()
+			};
+			build_legend(
+				&mut canvas,
+				(100, 100),
+				styled,
+				&font,
+				14.0,
+				1,
+				LegendStyle {
+					position: LegendPosition::Outside,
+					frame: false,
+					background: None,
+				},
+			)
+			.unwrap();
+			let blue = Colour::get_pixel_colour(Colour::Blue);
+			canvas
+				.enumerate_pixels()
+				.filter(|(_, _, p)| p.0 == blue)
+				.count()
+		};
+		let solid = swatch(LineStyle::Solid);
+		let dashed = swatch(LineStyle::Dashed);
+		let dotted = swatch(LineStyle::Dotted);
+		// dashes cover half of the swatch and dots a quarter
+		assert!(dashed > 0 && dashed < solid, "{} {}", dashed, solid);
+		assert!(dotted > 0 && dotted < dashed, "{} {}", dotted, dashed);
 	}
 }
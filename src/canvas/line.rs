@@ -0,0 +1,149 @@
+//! Rasterises straight lines between two pixels, or between two points with smoothed edges
+
+/// Find the pixels joining `start` to `end` (both inclusive) using integer Bresenham, producing exactly one pixel per step along the major axis
+pub fn find_line_pixels(start: (u32, u32), end: (u32, u32)) -> Vec<(u32, u32)> {
+	let (mut x, mut y) = (start.0 as i64, start.1 as i64);
+	let (x_end, y_end) = (end.0 as i64, end.1 as i64);
+	let dx = (x_end - x).abs();
+	let dy = -(y_end - y).abs();
+	let step_x = if x < x_end { 1 } else { -1 };
+	let step_y = if y < y_end { 1 } else { -1 };
+	let mut error = dx + dy;
+	let mut pixels = Vec::with_capacity((dx.max(-dy) + 1) as usize);
+	loop {
+		pixels.push((x as u32, y as u32));
+		if x == x_end && y == y_end {
+			break;
+		}
+		let doubled_error = 2 * error;
+		if doubled_error >= dy {
+			error += dy;
+			x += step_x;
+		}
+		if doubled_error <= dx {
+			error += dx;
+			y += step_y;
+		}
+	}
+	pixels
+}
+
+/// Find the pixels a line `width` pixels across covers between the points `start` and `end`, along with
+/// the fraction of each pixel covered. A generalisation of Wu's algorithm, at each step along the major axis
+/// the line spans `width` across the minor axis and every pixel it overlaps is covered by the length of the
+/// overlap, so a line one pixel wide is shared between the two pixels nearest to it. Pixel centres lie on
+/// whole coordinates
+pub fn find_antialiased_line_pixels(
+	start: (f32, f32),
+	end: (f32, f32),
+	width: f32,
+) -> Vec<(u32, u32, f32)> {
+	// a steep line is walked down its rows, swapping x and y so that the major axis is always first
+	let is_steep = (end.1 - start.1).abs() > (end.0 - start.0).abs();
+	let (a, b) = if is_steep {
+		((start.1, start.0), (end.1, end.0))
+	} else {
+		(start, end)
+	};
+	let (a, b) = if a.0 > b.0 { (b, a) } else { (a, b) };
+	let gradient = if b.0 == a.0 {
+		0.0
+	} else {
+		(b.1 - a.1) / (b.0 - a.0)
+	};
+	let half_width = width / 2.0;
+	let mut pixels = Vec::new();
+	for major in a.0.round() as i64..=b.0.round() as i64 {
+		let centre = a.1 + gradient * (major as f32 - a.0);
+		let (low, high) = (centre - half_width, centre + half_width);
+		for minor in (low - 0.5).floor() as i64..=(high + 0.5).ceil() as i64 {
+			let coverage = high.min(minor as f32 + 0.5) - low.max(minor as f32 - 0.5);
+			if coverage <= 0.0 || major < 0 || minor < 0 {
+				continue;
+			}
+			let (x, y) = if is_steep {
+				(minor, major)
+			} else {
+				(major, minor)
+			};
+			pixels.push((x as u32, y as u32, coverage.min(1.0)));
+		}
+	}
+	pixels
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::canvas::line::{find_antialiased_line_pixels, find_line_pixels};
+	#[test]
+	fn single_pixel() {
+		assert_eq!(vec![(4, 4)], find_line_pixels((4, 4), (4, 4)));
+	}
+	#[test]
+	fn horizontal() {
+		assert_eq!(
+			vec![(1, 2), (2, 2), (3, 2), (4, 2)],
+			find_line_pixels((1, 2), (4, 2))
+		);
+	}
+	#[test]
+	fn vertical_descending() {
+		assert_eq!(
+			vec![(3, 5), (3, 4), (3, 3)],
+			find_line_pixels((3, 5), (3, 3))
+		);
+	}
+	#[test]
+	fn diagonal() {
+		assert_eq!(
+			vec![(0, 3), (1, 2), (2, 1), (3, 0)],
+			find_line_pixels((0, 3), (3, 0))
+		);
+	}
+	#[test]
+	fn steep_line_is_contiguous() {
+		let pixels = find_line_pixels((10, 10), (13, 30));
+		assert_eq!(21, pixels.len());
+		for pair in pixels.windows(2) {
+			let dx = (pair[1].0 as i64 - pair[0].0 as i64).abs();
+			let dy = (pair[1].1 as i64 - pair[0].1 as i64).abs();
+			assert!(dx <= 1 && dy == 1);
+		}
+	}
+	#[test]
+	fn antialiased_line_between_pixel_centres_is_solid() {
+		assert_eq!(
+			vec![(1, 2, 1.0), (2, 2, 1.0), (3, 2, 1.0)],
+			find_antialiased_line_pixels((1.0, 2.0), (3.0, 2.0), 1.0)
+		);
+		assert_eq!(
+			vec![(3, 3, 1.0), (3, 4, 1.0)],
+			find_antialiased_line_pixels((3.0, 4.0), (3.0, 3.0), 1.0)
+		);
+	}
+	#[test]
+	fn antialiased_line_is_shared_between_the_nearest_pixels() {
+		let pixels = find_antialiased_line_pixels((0.0, 0.0), (8.0, 2.0), 1.0);
+		for x in 0..=8 {
+			let column: Vec<&(u32, u32, f32)> = pixels.iter().filter(|p| p.0 == x).collect();
+			// a line one pixel wide covers a pixel's worth of each column
+			let total: f32 = column.iter().map(|p| p.2).sum();
+			assert!((total - 1.0).abs() < 1e-5, "column {} covers {}", x, total);
+			assert!(column.len() <= 2);
+		}
+		// halfway between two rows each is half covered
+		assert!(pixels.contains(&(2, 0, 0.5)));
+		assert!(pixels.contains(&(2, 1, 0.5)));
+	}
+	#[test]
+	fn wide_antialiased_line_is_solid_with_smooth_edges() {
+		let pixels = find_antialiased_line_pixels((10.0, 10.0), (20.0, 40.0), 4.0);
+		for y in 10..=40 {
+			let row: Vec<&(u32, u32, f32)> = pixels.iter().filter(|p| p.1 == y).collect();
+			let total: f32 = row.iter().map(|p| p.2).sum();
+			assert!((total - 4.0).abs() < 1e-4, "row {} covers {}", y, total);
+			// only the two pixels at either edge are partly covered
+			assert!(row.iter().filter(|p| p.2 < 1.0).count() <= 2);
+		}
+	}
+}
@@ -0,0 +1,86 @@
+//! Provenance embedded into saved png images so that the config and data which produced a graph can be
+//! identified later
+
+use std::path::{Path, PathBuf};
+
+/// A keyword and text pair written to a png as a text chunk
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextChunk {
+	/// Keyword identifying the text, 1 to 79 Latin-1 characters
+	pub keyword: String,
+	/// The text itself
+	pub text: String,
+}
+
+impl TextChunk {
+	/// Create a chunk from a keyword and its text
+	fn new(keyword: &str, text: String) -> TextChunk {
+		TextChunk {
+			keyword: keyword.to_owned(),
+			text,
+		}
+	}
+}
+
+/// Describe which version of plotrs drew a graph from which config and data files. The config is identified
+/// by its path and a hash of its contents so that a later edit to the file can be spotted
+pub fn provenance_chunks(
+	config_path: &Path,
+	config_contents: &[u8],
+	data_paths: &[PathBuf],
+) -> Vec<TextChunk> {
+	let data = data_paths
+		.iter()
+		.map(|path| path.display().to_string())
+		.collect::<Vec<String>>()
+		.join("\n");
+	vec![
+		TextChunk::new("Software", format!("plotrs {}", env!("CARGO_PKG_VERSION"))),
+		TextChunk::new("plotrs:config", config_path.display().to_string()),
+		TextChunk::new(
+			"plotrs:config-hash",
+			format!("fnv1a-64:{:016x}", fnv1a_64(config_contents)),
+		),
+		TextChunk::new("plotrs:data", data),
+	]
+}
+
+/// The 64 bit FNV-1a hash of some bytes. Unlike the hashers of the standard library its output is fixed so
+/// a hash embedded today can be compared against one calculated by a later release
+fn fnv1a_64(bytes: &[u8]) -> u64 {
+	bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+		(hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::canvas::metadata::{fnv1a_64, provenance_chunks};
+	use std::path::{Path, PathBuf};
+	#[test]
+	fn fnv_matches_reference_values() {
+		assert_eq!(0xcbf29ce484222325, fnv1a_64(b""));
+		assert_eq!(0xaf63dc4c8601ec8c, fnv1a_64(b"a"));
+		assert_eq!(0x85944171f73967e8, fnv1a_64(b"foobar"));
+	}
+	#[test]
+	fn provenance_names_config_and_data() {
+		let chunks = provenance_chunks(
+			Path::new("graphs/cpu.ron"),
+			b"a",
+			&[PathBuf::from("cpu_1.csv"), PathBuf::from("cpu_2.csv")],
+		);
+		let text = |keyword: &str| {
+			chunks
+				.iter()
+				.find(|chunk| chunk.keyword == keyword)
+				.unwrap()
+				.text
+				.clone()
+		};
+		assert!(text("Software").starts_with("plotrs "));
+		assert_eq!("graphs/cpu.ron", text("plotrs:config"));
+		assert_eq!("fnv1a-64:af63dc4c8601ec8c", text("plotrs:config-hash"));
+		assert_eq!("cpu_1.csv\ncpu_2.csv", text("plotrs:data"));
+	}
+}
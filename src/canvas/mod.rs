@@ -1,15 +1,36 @@
 //! Methods for drawing onto a canvas, generating glyphs and saving images
 
 use crate::colours::*;
-use image::{ImageBuffer, Rgba, RgbaImage};
+use image::{
+	codecs::jpeg::JpegEncoder, ImageBuffer, ImageError, ImageFormat, ImageResult, Rgb, RgbImage,
+	Rgba, RgbaImage,
+};
+use metadata::TextChunk;
 use regex::Regex;
-use tracing::{error, info};
+use serde::{Deserialize, Serialize};
+use std::{
+	cmp::Ordering,
+	fmt,
+	fs::File,
+	io::{self, BufWriter, Cursor, Write},
+	path::{Path, PathBuf},
+	str::FromStr,
+};
+use tracing::{info, warn};
 
 pub mod axes;
 pub mod best_fit;
+pub mod colour_bar;
+pub mod datetime;
+pub mod fill;
+pub mod format;
+pub mod geometry;
 pub mod glyphs;
 pub mod legend;
+pub mod line;
+pub mod metadata;
 pub mod plot;
+pub mod progress;
 pub mod quadrants;
 pub mod title;
 /// Vertical and horizontal pixel border used for spacing elements
@@ -30,11 +51,15 @@ pub struct VHConsumedCanvasSpace {
 impl VHConsumedCanvasSpace {
 	/// Instantiate a new `VHConsumedCanvasSpace` with default values of `CANVAS_BORDER_PIXELS`
 	pub fn new() -> VHConsumedCanvasSpace {
+		VHConsumedCanvasSpace::with_border(CANVAS_BORDER_PIXELS)
+	}
+	/// Instantiate a new `VHConsumedCanvasSpace` with `border` pixels consumed from each edge
+	pub fn with_border(border: u32) -> VHConsumedCanvasSpace {
 		VHConsumedCanvasSpace {
-			v_space_from_top: CANVAS_BORDER_PIXELS,
-			h_space_from_right: CANVAS_BORDER_PIXELS,
-			v_space_from_bottom: CANVAS_BORDER_PIXELS,
-			h_space_from_left: CANVAS_BORDER_PIXELS,
+			v_space_from_top: border,
+			h_space_from_right: border,
+			v_space_from_bottom: border,
+			h_space_from_left: border,
 		}
 	}
 	/// Adds `VHConsumedCanvasSpace` to the calling `VHConsumedCanvasSpace`
@@ -46,6 +71,24 @@ impl VHConsumedCanvasSpace {
 	}
 }
 
+impl Default for VHConsumedCanvasSpace {
+	fn default() -> Self {
+		VHConsumedCanvasSpace::new()
+	}
+}
+
+/// How much of the graph surrounding the plot area should be drawn
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Default)]
+pub enum Chrome {
+	/// Title, axis labels, legend, axes, scale markings and data
+	#[default]
+	Full,
+	/// Skips the title, axis labels and legend. Useful when compositing graphs into something that supplies its own titles
+	PlotOnly,
+	/// As `PlotOnly` while also skipping the text of the axis scale markings
+	BareAxes,
+}
+
 /// Create a blank canvas which can be mutated with content. By default all pixels are coloured white
 pub fn draw_base_canvas(canvas_pixel_size: (u32, u32)) -> ImageBuffer<Rgba<u8>, Vec<u8>> {
 	// create a new image buffer based on `canvas_pixel_size`
@@ -58,20 +101,541 @@ pub fn draw_base_canvas(canvas_pixel_size: (u32, u32)) -> ImageBuffer<Rgba<u8>,
 	imgbuf
 }
 
-/// Save finished image to disk where the file name is taken from the title of the graph
-pub fn save_image(imgbuf: ImageBuffer<Rgba<u8>, Vec<u8>>, output_path: &str, title: String) {
-	let re = Regex::new(r"\s|\W").unwrap();
-	let file_name = re.replace_all(title.as_str(), "_").to_lowercase();
-	let output = output_path.to_owned() + "/" + file_name.as_str() + ".png";
-	info!("Saving image to {}", output);
-	match imgbuf.save(output) {
+/// Convert text such as a graph title into a lowercase file name with each run of whitespace and symbols
+/// replaced by a single `_`, e.g `CPU % vs time` becomes `cpu_vs_time`
+pub fn to_file_name(text: &str) -> String {
+	let re = Regex::new(r"[\s\W]+").unwrap();
+	re.replace_all(text, "_").to_lowercase()
+}
+/// Image formats a graph can be saved as
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub enum OutputFormat {
+	/// Lossless with transparency, the default
+	#[default]
+	Png,
+	/// Lossy, the canvas is flattened onto a white background as there is no alpha channel
+	Jpeg,
+	/// Uncompressed bitmap
+	Bmp,
+	/// Lossless WebP
+	Webp,
+	/// Tagged Image File Format
+	Tiff,
+}
+
+impl FromStr for OutputFormat {
+	type Err = String;
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.to_lowercase().as_str() {
+			"png" => Ok(OutputFormat::Png),
+			"jpeg" | "jpg" => Ok(OutputFormat::Jpeg),
+			"bmp" => Ok(OutputFormat::Bmp),
+			"webp" => Ok(OutputFormat::Webp),
+			"tiff" | "tif" => Ok(OutputFormat::Tiff),
+			_ => Err(format!(
+				"Unknown image format {}, valid formats are png, jpeg, bmp, webp and tiff",
+				s
+			)),
+		}
+	}
+}
+
+impl OutputFormat {
+	/// The format used by the `image` crate to encode the graph
+	fn image_format(&self) -> ImageFormat {
+		match self {
+			OutputFormat::Png => ImageFormat::Png,
+			OutputFormat::Jpeg => ImageFormat::Jpeg,
+			OutputFormat::Bmp => ImageFormat::Bmp,
+			OutputFormat::Webp => ImageFormat::WebP,
+			OutputFormat::Tiff => ImageFormat::Tiff,
+		}
+	}
+	/// File extension of the format
+	pub fn extension(&self) -> &'static str {
+		match self {
+			OutputFormat::Png => "png",
+			OutputFormat::Jpeg => "jpg",
+			OutputFormat::Bmp => "bmp",
+			OutputFormat::Webp => "webp",
+			OutputFormat::Tiff => "tiff",
+		}
+	}
+}
+
+/// What to do when the file an image is saved to already exists
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub enum ExistingOutput {
+	/// Leave the existing file alone and fail, the default
+	#[default]
+	Refuse,
+	/// Replace the existing file
+	Overwrite,
+	/// Save to the file name with the first free `_1`, `_2`, ... suffix appended
+	AutoSuffix,
+}
+
+/// Describes why an image could not be written
+#[derive(Debug)]
+pub struct ImageSaveError {
+	/// Where the image was being written, `-` for stdout
+	pub path: PathBuf,
+	/// The underlying encoding or io error
+	pub source: ImageError,
+}
+
+impl fmt::Display for ImageSaveError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"Unable to save image to {:?}: {}",
+			self.path, self.source
+		)
+	}
+}
+
+impl std::error::Error for ImageSaveError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		Some(&self.source)
+	}
+}
+
+/// The path an image is saved to, `<output_path>/<file_name>.<extension>` where the extension is taken
+/// from the `format`, or `-` for stdout when `output_path` is `-`. An existing file is handled according
+/// to `existing`. Nothing is written so the path can be checked before a graph is drawn
+pub fn get_output_path(
+	output_path: &str,
+	file_name: &str,
+	format: OutputFormat,
+	existing: ExistingOutput,
+) -> Result<PathBuf, ImageSaveError> {
+	if output_path == "-" {
+		return Ok(PathBuf::from("-"));
+	}
+	let path_with_suffix = |suffix: String| {
+		PathBuf::from(output_path).join(format!("{}{}.{}", file_name, suffix, format.extension()))
+	};
+	let output = path_with_suffix(String::new());
+	// a graph without a title would otherwise be saved as a hidden `.png`
+	if file_name.is_empty() {
+		return Err(ImageSaveError {
+			path: output,
+			source: ImageError::IoError(io::Error::new(
+				io::ErrorKind::InvalidInput,
+				"the file name is empty, give the graph a title or an output name",
+			)),
+		});
+	}
+	if !output.exists() {
+		return Ok(output);
+	}
+	match existing {
+		ExistingOutput::Refuse => Err(ImageSaveError {
+			path: output,
+			source: ImageError::IoError(io::Error::new(
+				io::ErrorKind::AlreadyExists,
+				"the file already exists, pass --force to overwrite it or --auto-suffix to save alongside it",
+			)),
+		}),
+		ExistingOutput::Overwrite => {
+			info!("Overwriting {}", output.display());
+			Ok(output)
+		}
+		ExistingOutput::AutoSuffix => Ok((1..)
+			.map(|n| path_with_suffix(format!("_{}", n)))
+			.find(|path| !path.exists())
+			.unwrap_or(output)),
+	}
+}
+
+/// Save finished image to disk as `<output_path>/<file_name>.<extension>` where the extension is taken
+/// from the `format`, or write it to stdout when `output_path` is `-`. `quality` ranges from 1 to 100
+/// and is only used by `Jpeg`. An existing file is handled according to `existing`. Any `metadata` is
+/// embedded as text chunks of a `Png`, other formats skip it. Returns the path written to, which is `-`
+/// for stdout
+pub fn save_image(
+	imgbuf: ImageBuffer<Rgba<u8>, Vec<u8>>,
+	output_path: &str,
+	file_name: &str,
+	format: OutputFormat,
+	quality: u8,
+	existing: ExistingOutput,
+	metadata: &[TextChunk],
+) -> Result<PathBuf, ImageSaveError> {
+	let output = get_output_path(output_path, file_name, format, existing)?;
+	write_image(imgbuf, output, format, quality, metadata)
+}
+
+/// Write finished image to the `output` found by [`get_output_path`], replacing any file already there,
+/// or to stdout when `output` is `-`. Returns the path written to
+pub fn write_image(
+	imgbuf: ImageBuffer<Rgba<u8>, Vec<u8>>,
+	output: PathBuf,
+	format: OutputFormat,
+	quality: u8,
+	metadata: &[TextChunk],
+) -> Result<PathBuf, ImageSaveError> {
+	if !metadata.is_empty() && format != OutputFormat::Png {
+		warn!(
+			"Metadata can only be embedded in png images, it is not written to {}",
+			format.extension()
+		);
+	}
+	let result = if output == Path::new("-") {
+		info!("Writing image to stdout");
+		write_to_stdout(&imgbuf, format, quality, metadata)
+	} else {
+		info!("Saving image to {}", output.display());
+		match format {
+			OutputFormat::Jpeg => {
+				File::create(&output)
+					.map_err(ImageError::IoError)
+					.and_then(|file| {
+						JpegEncoder::new_with_quality(BufWriter::new(file), quality)
+							.encode_image(&flatten(&imgbuf))
+					})
+			}
+			OutputFormat::Png => File::create(&output)
+				.map_err(ImageError::IoError)
+				.and_then(|file| write_png(BufWriter::new(file), &imgbuf, metadata)),
+			_ => imgbuf.save_with_format(&output, format.image_format()),
+		}
+	};
+	match result {
 		Ok(_) => {
 			info!("Image saved");
-			std::process::exit(0);
+			Ok(output)
+		}
+		Err(source) => Err(ImageSaveError {
+			path: output,
+			source,
+		}),
+	}
+}
+
+/// Encode the image in memory and write it to stdout in one go so that it can be piped into other tools
+fn write_to_stdout(
+	imgbuf: &ImageBuffer<Rgba<u8>, Vec<u8>>,
+	format: OutputFormat,
+	quality: u8,
+	metadata: &[TextChunk],
+) -> ImageResult<()> {
+	let mut bytes = Cursor::new(Vec::new());
+	match format {
+		OutputFormat::Jpeg => {
+			JpegEncoder::new_with_quality(&mut bytes, quality).encode_image(&flatten(imgbuf))?
+		}
+		OutputFormat::Png => write_png(&mut bytes, imgbuf, metadata)?,
+		_ => imgbuf.write_to(&mut bytes, format.image_format())?,
+	}
+	let stdout = std::io::stdout();
+	let mut handle = stdout.lock();
+	handle.write_all(bytes.get_ref())?;
+	handle.flush()?;
+	Ok(())
+}
+
+/// Encode the image as a png with the same settings the `image` crate uses, adding each of `metadata`
+/// as a `tEXt` chunk, or an `iTXt` chunk when it isn't plain ascii
+fn write_png<W: Write>(
+	writer: W,
+	imgbuf: &ImageBuffer<Rgba<u8>, Vec<u8>>,
+	metadata: &[TextChunk],
+) -> ImageResult<()> {
+	let to_image_error = |e: png::EncodingError| ImageError::IoError(e.into());
+	let mut encoder = png::Encoder::new(writer, imgbuf.width(), imgbuf.height());
+	encoder.set_color(png::ColorType::Rgba);
+	encoder.set_depth(png::BitDepth::Eight);
+	encoder.set_compression(png::Compression::Fast);
+	encoder.set_filter(png::FilterType::Sub);
+	encoder.set_adaptive_filter(png::AdaptiveFilterType::Adaptive);
+	for chunk in metadata {
+		let (keyword, text) = (chunk.keyword.clone(), chunk.text.clone());
+		if chunk.text.is_ascii() {
+			encoder.add_text_chunk(keyword, text)
+		} else {
+			encoder.add_itxt_chunk(keyword, text)
+		}
+		.map_err(to_image_error)?;
+	}
+	let mut writer = encoder.write_header().map_err(to_image_error)?;
+	writer
+		.write_image_data(imgbuf.as_raw())
+		.map_err(to_image_error)?;
+	writer.finish().map_err(to_image_error)
+}
+
+/// Colour a `size` by `size` block of pixels with its top left corner at `(x, y)`, lines drawn with blocks
+/// are `size` pixels thick. Pixels outside of the canvas are skipped, returns whether the whole block was
+/// drawn
+pub fn put_pixel_block(
+	canvas: &mut ImageBuffer<Rgba<u8>, Vec<u8>>,
+	x: u32,
+	y: u32,
+	size: u32,
+	colour: [u8; 4],
+) -> bool {
+	let mut is_inside = true;
+	for px in x..x.saturating_add(size) {
+		for py in y..y.saturating_add(size) {
+			match canvas.get_pixel_mut_checked(px, py) {
+				Some(pixel) => *pixel = Rgba(colour),
+				None => is_inside = false,
+			}
+		}
+	}
+	is_inside
+}
+
+/// Composite `colour` over `pixel` according to the alpha of both, an opaque colour replaces the pixel
+pub fn composite_pixel(pixel: &mut Rgba<u8>, colour: [u8; 4]) {
+	let above = colour[3] as f32 / 255.0;
+	let below = pixel.0[3] as f32 / 255.0 * (1.0 - above);
+	let alpha = above + below;
+	if alpha <= 0.0 {
+		*pixel = Rgba([0, 0, 0, 0]);
+		return;
+	}
+	for (channel, over) in pixel.0.iter_mut().zip(colour.iter()).take(3) {
+		*channel = ((*over as f32 * above + *channel as f32 * below) / alpha).round() as u8;
+	}
+	pixel.0[3] = (alpha * 255.0).round() as u8;
+}
+
+/// The pixels covered by a shape built from overlapping blocks, gathered so that each pixel is composited
+/// once and a translucent shape is evenly coloured where its strokes overlap
+pub struct PixelStamp {
+	/// Every pixel marked along with the fraction of it covered, possibly more than once
+	pixels: Vec<(u32, u32, f32)>,
+	/// Width of the canvas being drawn on
+	width: u32,
+	/// Height of the canvas being drawn on
+	height: u32,
+}
+
+impl PixelStamp {
+	/// An empty stamp for drawing onto `canvas`
+	pub fn new(canvas: &ImageBuffer<Rgba<u8>, Vec<u8>>) -> Self {
+		PixelStamp {
+			pixels: Vec::new(),
+			width: canvas.width(),
+			height: canvas.height(),
+		}
+	}
+	/// Mark a `size` by `size` block of pixels with its top left corner at `(x, y)`, as in
+	/// [`put_pixel_block`] pixels outside of the canvas are skipped and whether the whole block is on the
+	/// canvas is returned
+	pub fn block(&mut self, x: u32, y: u32, size: u32) -> bool {
+		let mut is_inside = true;
+		for px in x..x.saturating_add(size) {
+			for py in y..y.saturating_add(size) {
+				if px < self.width && py < self.height {
+					self.pixels.push((px, py, 1.0));
+				} else {
+					is_inside = false;
+				}
+			}
+		}
+		is_inside
+	}
+	/// Mark the pixel at `(x, y)` as partly covered, `coverage` running from 0 to 1, to smooth the edge of
+	/// a shape. A pixel outside of the canvas is skipped and whether it is on the canvas is returned
+	pub fn cover(&mut self, x: u32, y: u32, coverage: f32) -> bool {
+		if x < self.width && y < self.height {
+			self.pixels.push((x, y, coverage.clamp(0.0, 1.0)));
+			true
+		} else {
+			false
 		}
-		Err(e) => {
-			error!("Unable to save image: {:?}", e);
-			std::process::exit(1);
+	}
+	/// Composite `colour` over every marked pixel once, its opacity scaled by the largest coverage the
+	/// pixel was marked with
+	pub fn draw(mut self, canvas: &mut ImageBuffer<Rgba<u8>, Vec<u8>>, colour: [u8; 4]) {
+		// the most covered mark of each pixel sorts first and is the one kept
+		self.pixels.sort_unstable_by(|a, b| {
+			(a.0, a.1)
+				.cmp(&(b.0, b.1))
+				.then(b.2.partial_cmp(&a.2).unwrap_or(Ordering::Equal))
+		});
+		self.pixels.dedup_by(|a, b| (a.0, a.1) == (b.0, b.1));
+		for (x, y, coverage) in self.pixels {
+			let alpha = (colour[3] as f32 * coverage).round() as u8;
+			if alpha > 0 {
+				composite_pixel(
+					canvas.get_pixel_mut(x, y),
+					[colour[0], colour[1], colour[2], alpha],
+				);
+			}
 		}
 	}
 }
+
+/// Shrink a canvas rendered at `factor` times its intended size by averaging each `factor` by `factor`
+/// block of pixels into one, smoothing the edges of everything drawn
+pub fn downsample(
+	imgbuf: ImageBuffer<Rgba<u8>, Vec<u8>>,
+	factor: u32,
+) -> ImageBuffer<Rgba<u8>, Vec<u8>> {
+	if factor <= 1 {
+		return imgbuf;
+	}
+	let (width, height) = (imgbuf.width() / factor, imgbuf.height() / factor);
+	let samples = factor * factor;
+	ImageBuffer::from_fn(width, height, |x, y| {
+		let mut sum = [0_u32; 4];
+		for py in (y * factor)..((y + 1) * factor) {
+			for px in (x * factor)..((x + 1) * factor) {
+				for (total, channel) in sum.iter_mut().zip(imgbuf.get_pixel(px, py).0.iter()) {
+					*total += *channel as u32;
+				}
+			}
+		}
+		// round to the nearest value
+		Rgba(sum.map(|total| ((total + samples / 2) / samples) as u8))
+	})
+}
+
+/// Blend the canvas onto a white background, removing its alpha channel
+fn flatten(imgbuf: &ImageBuffer<Rgba<u8>, Vec<u8>>) -> RgbImage {
+	RgbImage::from_fn(imgbuf.width(), imgbuf.height(), |x, y| {
+		let Rgba([r, g, b, a]) = *imgbuf.get_pixel(x, y);
+		let blend = |channel: u8, background: u8| -> u8 {
+			((channel as u32 * a as u32 + background as u32 * (255 - a as u32)) / 255) as u8
+		};
+		Rgb([blend(r, WHITE[0]), blend(g, WHITE[1]), blend(b, WHITE[2])])
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		canvas::{
+			composite_pixel, downsample, draw_base_canvas, flatten, get_output_path,
+			put_pixel_block, save_image, to_file_name, ExistingOutput, OutputFormat, PixelStamp,
+		},
+		colours::WHITE,
+	};
+	use image::{Rgb, Rgba};
+	use std::path::PathBuf;
+	#[test]
+	fn flatten_blends_onto_white() {
+		let mut canvas = draw_base_canvas((2, 1));
+		canvas.put_pixel(0, 0, Rgba([0, 0, 0, 255]));
+		canvas.put_pixel(1, 0, Rgba([0, 0, 255, 51]));
+		let flat = flatten(&canvas);
+		assert_eq!(&Rgb([0, 0, 0]), flat.get_pixel(0, 0));
+		assert_eq!(&Rgb([204, 204, 255]), flat.get_pixel(1, 0));
+	}
+	#[test]
+	fn composite_pixel_lets_what_is_beneath_show_through() {
+		let mut pixel = Rgba(WHITE);
+		composite_pixel(&mut pixel, [0, 0, 255, 51]);
+		assert_eq!(Rgba([204, 204, 255, 255]), pixel);
+		let mut pixel = Rgba([0, 0, 0, 0]);
+		composite_pixel(&mut pixel, [0, 0, 255, 51]);
+		assert_eq!(Rgba([0, 0, 255, 51]), pixel);
+		composite_pixel(&mut pixel, [255, 0, 0, 255]);
+		assert_eq!(Rgba([255, 0, 0, 255]), pixel);
+	}
+	#[test]
+	fn stamp_blends_partly_covered_pixels_once() {
+		let mut canvas = draw_base_canvas((3, 1));
+		let mut stamp = PixelStamp::new(&canvas);
+		assert!(stamp.cover(0, 0, 0.5));
+		// the larger coverage of a pixel marked twice wins rather than the two adding up
+		assert!(stamp.cover(1, 0, 0.25));
+		assert!(stamp.cover(1, 0, 0.5));
+		assert!(stamp.block(2, 0, 1));
+		assert!(stamp.cover(2, 0, 0.5));
+		assert!(!stamp.cover(3, 0, 1.0));
+		stamp.draw(&mut canvas, [0, 0, 0, 255]);
+		assert_eq!(Rgba([127, 127, 127, 255]), *canvas.get_pixel(0, 0));
+		assert_eq!(Rgba([127, 127, 127, 255]), *canvas.get_pixel(1, 0));
+		assert_eq!(Rgba([0, 0, 0, 255]), *canvas.get_pixel(2, 0));
+	}
+	#[test]
+	fn file_names_collapse_replaced_characters() {
+		assert_eq!("cpu_vs_time", to_file_name("CPU % vs time"));
+		assert_eq!(
+			"oh_wow_multiple_data_sets",
+			to_file_name("Oh Wow, Multiple Data Sets")
+		);
+		assert_eq!("already_snake", to_file_name("already_snake"));
+	}
+	#[test]
+	fn output_formats_parse_case_insensitively() {
+		assert_eq!(Ok(OutputFormat::Jpeg), "JPG".parse());
+		assert_eq!(Ok(OutputFormat::Webp), "webp".parse());
+		assert!("gif".parse::<OutputFormat>().is_err());
+	}
+	#[test]
+	fn downsample_averages_blocks() {
+		let mut canvas = draw_base_canvas((4, 2));
+		// a black line 1 pixel wide fills a quarter of the left block
+		canvas.put_pixel(0, 0, Rgba([0, 0, 0, 255]));
+		canvas.put_pixel(0, 1, Rgba([0, 0, 0, 255]));
+		// the right block is entirely blue
+		assert!(put_pixel_block(&mut canvas, 2, 0, 2, [0, 0, 255, 255]));
+		let small = downsample(canvas, 2);
+		assert_eq!((2, 1), small.dimensions());
+		assert_eq!([128, 128, 128, 255], small.get_pixel(0, 0).0);
+		assert_eq!([0, 0, 255, 255], small.get_pixel(1, 0).0);
+	}
+	#[test]
+	fn pixel_blocks_are_clipped_to_the_canvas() {
+		let mut canvas = draw_base_canvas((3, 3));
+		assert!(!put_pixel_block(&mut canvas, 2, 2, 2, [0, 0, 0, 255]));
+		assert_eq!(8, canvas.pixels().filter(|p| p.0 == WHITE).count());
+	}
+	#[test]
+	fn existing_files_are_refused_overwritten_or_suffixed() {
+		let dir = std::env::temp_dir().join(format!("plotrs_save_{}", std::process::id()));
+		std::fs::create_dir_all(&dir).unwrap();
+		let output = dir.to_str().unwrap();
+		let canvas = draw_base_canvas((2, 2));
+		let save = |existing| {
+			save_image(
+				canvas.clone(),
+				output,
+				"graph",
+				OutputFormat::Png,
+				90,
+				existing,
+				&[],
+			)
+		};
+		let path = save(ExistingOutput::Refuse).unwrap();
+		assert_eq!(dir.join("graph.png"), path);
+		assert!(path.exists());
+		// an existing file is only replaced when asked
+		let error = save(ExistingOutput::Refuse).unwrap_err();
+		assert_eq!(dir.join("graph.png"), error.path);
+		assert_eq!(
+			dir.join("graph.png"),
+			save(ExistingOutput::Overwrite).unwrap()
+		);
+		assert_eq!(
+			dir.join("graph_1.png"),
+			save(ExistingOutput::AutoSuffix).unwrap()
+		);
+		assert_eq!(
+			dir.join("graph_2.png"),
+			save(ExistingOutput::AutoSuffix).unwrap()
+		);
+		std::fs::remove_dir_all(&dir).unwrap();
+		// the directory no longer exists so writing fails
+		let error = save(ExistingOutput::Overwrite).unwrap_err();
+		assert_eq!(dir.join("graph.png"), error.path);
+	}
+	#[test]
+	fn empty_file_names_are_refused() {
+		let error =
+			get_output_path(".", "", OutputFormat::Png, ExistingOutput::Overwrite).unwrap_err();
+		assert_eq!(PathBuf::from(".").join(".png"), error.path);
+		// stdout has no file name to check
+		assert_eq!(
+			PathBuf::from("-"),
+			get_output_path("-", "", OutputFormat::Png, ExistingOutput::Refuse).unwrap()
+		);
+	}
+}
@@ -1,23 +1,77 @@
 //! Draws data points and optional uncertainty/error bars onto a canvas with given symbols and colours
 
 use image::{ImageBuffer, Rgba};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use tracing::{trace, warn};
 
+use crate::canvas::{
+	line::{find_antialiased_line_pixels, find_line_pixels},
+	PixelStamp,
+};
 use crate::colours::Colour;
 
 /// The shape a plotted data point should take
-#[derive(Debug, Deserialize, Copy, Clone)]
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq)]
 #[allow(clippy::missing_docs_in_private_items)]
 pub enum DataSymbol {
 	Cross,
 	Circle,
+	/// An equilateral triangle with a corner pointing up
 	Triangle,
 	Square,
 	Point,
+	/// An equilateral triangle with a corner pointing down
+	TriangleDown,
+	/// A square rotated to stand on one corner
+	Diamond,
+	/// An axis aligned cross whose arms reach exactly `radius` pixels, where `Cross` rounds its arms up
+	/// to an even length
+	Plus,
+	/// The outline of a five-pointed star with a point facing up
+	Star,
+	/// A cross with its arms along the diagonals
+	X,
 }
 
+/// Symbols handed out in turn to data sets which need to be told apart without colour
+pub const SYMBOL_CYCLE: [DataSymbol; 9] = [
+	DataSymbol::Cross,
+	DataSymbol::Circle,
+	DataSymbol::Triangle,
+	DataSymbol::Square,
+	DataSymbol::Point,
+	DataSymbol::Diamond,
+	DataSymbol::Star,
+	DataSymbol::X,
+	DataSymbol::TriangleDown,
+];
+
+/// Every symbol along with the name it is written as in a config
+const NAMED_SYMBOLS: [(&str, DataSymbol); 10] = [
+	("Cross", DataSymbol::Cross),
+	("Circle", DataSymbol::Circle),
+	("Triangle", DataSymbol::Triangle),
+	("Square", DataSymbol::Square),
+	("Point", DataSymbol::Point),
+	("TriangleDown", DataSymbol::TriangleDown),
+	("Diamond", DataSymbol::Diamond),
+	("Plus", DataSymbol::Plus),
+	("Star", DataSymbol::Star),
+	("X", DataSymbol::X),
+];
+
+/// Ratio of the inner to the outer radius of a regular five-pointed star, `sin(18°) / sin(54°)`
+const STAR_INNER_RATIO: f32 = 0.381_966;
+
 impl DataSymbol {
+	/// Look up a symbol by the name it is written as in a config ignoring case, e.g `circle`
+	pub fn from_name(name: &str) -> Option<DataSymbol> {
+		NAMED_SYMBOLS
+			.iter()
+			.find(|(known, _)| known.eq_ignore_ascii_case(name))
+			.map(|(_, symbol)| *symbol)
+	}
 	/// Based on the `DataSymbol` type find the pixels that make up its shape
 	pub fn find_pixels(self, origin: (u32, u32), thickness: u32, radius: u32) -> Vec<(u32, u32)> {
 		let mut pixel_coords: Vec<(u32, u32)> = Vec::new();
@@ -59,107 +113,29 @@ impl DataSymbol {
 			}
 			DataSymbol::Circle => {
 				pixel_coords.push(origin);
-				for n in 0..=thickness {
-					// Ensure even radius of circle
-					let r = if (radius + 1) & 1 == 1 {
-						radius + 2 + n
-					} else {
-						radius + 1 + n
-					};
-					let scale_factor: u32 = 1000;
-					for angle_deg in 0..(360 * scale_factor) {
-						let y =
-							(angle_deg as f32 / scale_factor as f32).to_radians().sin() * r as f32;
-						let x =
-							(angle_deg as f32 / scale_factor as f32).to_radians().cos() * r as f32;
-						let delta_x = origin.0 as f32 + x;
-						let delta_y = origin.1 as f32 + y;
-						pixel_coords.push((delta_x as u32, delta_y as u32));
-					}
-				}
+				// Ensure even radius of circle
+				let r = if (radius + 1) & 1 == 1 {
+					radius + 2
+				} else {
+					radius + 1
+				};
+				pixel_coords.extend(find_circle_pixels(origin, r, r + thickness));
 			}
-			DataSymbol::Triangle => {
+			DataSymbol::Triangle | DataSymbol::TriangleDown => {
 				pixel_coords.push(origin);
+				// To draw an equilateral triangle with a corner facing up or down we can find the coords
+				// of each corner and rasterise a straight line between each pair of them.
 				for n in 0..=thickness {
-					let float_n = n as f32;
-					// To draw an equilateral triangle with a corner facing northwards we can
-					// find the coords of each corner, find equations of a straight line to
-					// join them up and iterate over the known `x` coord to place the pixels
-					// along each line.
-					//
-					// Find the 3 corner coord from our origin:
-					//
-					// Incircle radius of an equilateral triangle is twice the area divided by the perimiter
-					// area = (side_length * height)/ 2
-					// height = side_length * (3.sqrt() / 2)
-					// perimiter = 3 x side_length
-					// ir = side_length / (2 * 3.sqrt())
-					//
-					// Note our side length is the radius argument
-					// Ensure side_length is even otherwise the triangle will have an offset
-					// Initial (radius + 1) ensures that if a user sets radius to zero something will still be drawn
-					let side_length: f32 = if (radius + 1) & 1 == 1 {
-						(radius + 2) as f32 + float_n
-					} else {
-						(radius + 1) as f32 + float_n
-					};
-					let height: f32 = side_length * (3.0_f32.sqrt() / 2.0);
-					let incircle_radius: f32 = side_length / (2.0 * 3.0_f32.sqrt());
-
-					// The 3 corners
-					// The northwards facing corner is simply the origin plus the (height - incircle_radius)
-					let top: (f32, f32) =
-						(origin.0 as f32, origin.1 as f32 + height - incircle_radius);
-					// The bottom left corner is simply the origin moved left by half the side length and moved down by incircle
-					let left: (f32, f32) = (
-						origin.0 as f32 - (side_length / 2.0),
-						origin.1 as f32 - incircle_radius,
-					);
-					// The bottom right corner is simply the origin moved right by half the side length and moved down by incircle
-					let right: (f32, f32) = (
-						origin.0 as f32 + (side_length / 2.0),
-						origin.1 as f32 - incircle_radius,
+					let [apex, left, right] = find_triangle_corners(
+						origin,
+						triangle_side_length(radius, n),
+						self.triangle_direction(),
 					);
-					// Ensure corners are drawn
-					pixel_coords.push((top.0 as u32, top.1 as u32));
-					pixel_coords.push((left.0 as u32, left.1 as u32));
-					pixel_coords.push((right.0 as u32, right.1 as u32));
-
-					// Joining left and right is easy as they are vertically aligned
-					let x_distance_between_left_right = (right.0 - left.0) as u32;
-					for i in 0..x_distance_between_left_right {
-						pixel_coords.push((left.0 as u32 + i, left.1 as u32));
-					}
-
-					// Finding the points to connect left to top we can create an equation of a striahgt line, y = mx + c
-					let gradient_l_t = (top.1 - left.1) / (top.0 - left.0);
-					let intercept_l_t = left.1 - (gradient_l_t * left.0);
-					// Iterate over each X from left to top giving us the Y
-					let left_to_top_delta_x = (top.0 - left.0) as u32;
-					// We modify the actual range to iterate by a scale factor as we cannot iterate over a float
-					// which would allow us to find a contiguous line of points. Without a modifier the f32 conversion
-					// to u32 produces a dotted line
-					let scale_factor = 10000;
-					for i in 0..(left_to_top_delta_x * scale_factor) {
-						let x = left.0 + (i as f32 / scale_factor as f32);
-						let y = (gradient_l_t * x) + intercept_l_t;
-						pixel_coords.push((x as u32, y as u32));
-					}
-
-					// Finding the points to connect top to right we can create an equation of a striahgt line, y = mx + c
-					let gradient_t_r = (top.1 - right.1) / (top.0 - right.0);
-					let intercept_t_r = right.1 - (gradient_t_r * right.0);
-					// Iterate over each X from top to right giving us the Y
-					let top_to_right_delta_x = (right.0 - top.0) as u32;
-					// We modify the actual range to iterate by a scale factor as we cannot iterate over a float
-					// which would allow us to find a contiguous line of points. Without a modifier the f32 conversion
-					// to u32 produces a dotted line
-					let scale_factor = 10000;
-					for i in 0..(top_to_right_delta_x * scale_factor) {
-						let x = top.0 + (i as f32 / scale_factor as f32);
-						let y = (gradient_t_r * x) + intercept_t_r;
-						pixel_coords.push((x as u32, y as u32));
-					}
+					// Join the corners with straight lines, Bresenham yields one pixel per step so edges
+					// are contiguous without generating duplicate coordinates
+					pixel_coords.extend(find_line_pixels(left, right));
+					pixel_coords.extend(find_line_pixels(left, apex));
+					pixel_coords.extend(find_line_pixels(apex, right));
 				}
 			}
 			DataSymbol::Square => {
@@ -195,9 +171,398 @@ impl DataSymbol {
 				// it's just the original pixel
 				pixel_coords.push(origin);
 			}
+			DataSymbol::Diamond => {
+				pixel_coords.push(origin);
+				for n in 0..=thickness {
+					// the corners sit on the axes through the origin, as far out as the sides of a square
+					let reach = radius.max(1) + n;
+					let top = (origin.0, origin.1 - reach);
+					let right = (origin.0 + reach, origin.1);
+					let bottom = (origin.0, origin.1 + reach);
+					let left = (origin.0 - reach, origin.1);
+					pixel_coords.extend(find_line_pixels(top, right));
+					pixel_coords.extend(find_line_pixels(right, bottom));
+					pixel_coords.extend(find_line_pixels(bottom, left));
+					pixel_coords.extend(find_line_pixels(left, top));
+				}
+			}
+			DataSymbol::Plus => {
+				pixel_coords.push(origin);
+				for i in 1..=radius.max(1) {
+					for n in 0..=thickness {
+						// horizontal arms
+						pixel_coords.push((origin.0 + i, origin.1 + n));
+						pixel_coords.push((origin.0 + i, origin.1 - n));
+						pixel_coords.push((origin.0 - i, origin.1 + n));
+						pixel_coords.push((origin.0 - i, origin.1 - n));
+						// vertical arms
+						pixel_coords.push((origin.0 + n, origin.1 + i));
+						pixel_coords.push((origin.0 - n, origin.1 + i));
+						pixel_coords.push((origin.0 + n, origin.1 - i));
+						pixel_coords.push((origin.0 - n, origin.1 - i));
+					}
+				}
+			}
+			DataSymbol::Star => {
+				pixel_coords.push(origin);
+				for n in 0..=thickness {
+					let corners: Vec<(u32, u32)> =
+						find_star_corners(origin, (radius.max(1) + n) as f32)
+							.iter()
+							.map(|(x, y)| (x.round() as u32, y.round() as u32))
+							.collect();
+					// only the right half is rasterised and then mirrored so the star is symmetric
+					for pair in corners.windows(2) {
+						for (x, y) in find_line_pixels(pair[0], pair[1]) {
+							pixel_coords.push((x, y));
+							pixel_coords.push((2 * origin.0 - x, y));
+						}
+					}
+				}
+			}
+			DataSymbol::X => {
+				pixel_coords.push(origin);
+				for i in 1..=radius.max(1) {
+					// each arm is widened sideways so the thickness reads the same as a `Plus`
+					for n in 0..=thickness {
+						pixel_coords.push((origin.0 + i + n, origin.1 + i));
+						pixel_coords.push((origin.0 + i - n, origin.1 + i));
+						pixel_coords.push((origin.0 - i + n, origin.1 + i));
+						pixel_coords.push((origin.0 - i - n, origin.1 + i));
+						pixel_coords.push((origin.0 + i + n, origin.1 - i));
+						pixel_coords.push((origin.0 + i - n, origin.1 - i));
+						pixel_coords.push((origin.0 - i + n, origin.1 - i));
+						pixel_coords.push((origin.0 - i - n, origin.1 - i));
+					}
+				}
+				// widen the centre to match the arms
+				for n in 1..=thickness {
+					pixel_coords.push((origin.0 + n, origin.1));
+					pixel_coords.push((origin.0 - n, origin.1));
+				}
+			}
 		}
 		pixel_coords
 	}
+	/// Like [`DataSymbol::find_pixels`] but with the fraction of each pixel the shape covers, so that the
+	/// curved and sloping edges of circles, triangles and stars are smoothed by partly covering the pixels
+	/// either side of them. The other symbols are made of horizontal, vertical and diagonal strokes which
+	/// land exactly on pixels so they are fully covered
+	pub fn find_coverage(
+		self,
+		origin: (u32, u32),
+		thickness: u32,
+		radius: u32,
+	) -> Vec<(u32, u32, f32)> {
+		let mut pixel_coords: Vec<(u32, u32, f32)> = vec![(origin.0, origin.1, 1.0)];
+		let float_origin = (origin.0 as f32, origin.1 as f32);
+		match self {
+			DataSymbol::Circle => {
+				// Ensure even radius of circle
+				let r = if (radius + 1) & 1 == 1 {
+					radius + 2
+				} else {
+					radius + 1
+				};
+				pixel_coords.extend(find_antialiased_circle_pixels(origin, r, r + thickness));
+			}
+			DataSymbol::Triangle | DataSymbol::TriangleDown => {
+				for n in 0..=thickness {
+					let [apex, left, right] = find_exact_triangle_corners(
+						float_origin,
+						triangle_side_length(radius, n),
+						self.triangle_direction(),
+					);
+					for (start, end) in [(left, right), (left, apex), (apex, right)] {
+						pixel_coords.extend(find_antialiased_line_pixels(start, end, 1.0));
+					}
+				}
+			}
+			DataSymbol::Star => {
+				for n in 0..=thickness {
+					let corners = find_star_corners(origin, (radius.max(1) + n) as f32);
+					// only the right half is rasterised and then mirrored so the star is symmetric
+					for pair in corners.windows(2) {
+						for (x, y, coverage) in find_antialiased_line_pixels(pair[0], pair[1], 1.0)
+						{
+							pixel_coords.push((x, y, coverage));
+							if x <= 2 * origin.0 {
+								pixel_coords.push((2 * origin.0 - x, y, coverage));
+							}
+						}
+					}
+				}
+			}
+			_ => {
+				return self
+					.find_pixels(origin, thickness, radius)
+					.into_iter()
+					.map(|(x, y)| (x, y, 1.0))
+					.collect()
+			}
+		}
+		pixel_coords
+	}
+	/// The unit vector a `Triangle` or `TriangleDown` points along. Canvas rows grow downwards so a
+	/// triangle pointing up faces towards smaller rows
+	fn triangle_direction(self) -> (f32, f32) {
+		if self == DataSymbol::TriangleDown {
+			(0.0, 1.0)
+		} else {
+			(0.0, -1.0)
+		}
+	}
+	/// The number of distinct canvas pixels the symbol covers for a given `radius` and `thickness`
+	pub fn pixel_count(self, radius: u32, thickness: u32) -> u64 {
+		// Symbols are always drawn with an even length, see `find_pixels`
+		let length = if (radius + 1) & 1 == 1 {
+			radius as u64 + 2
+		} else {
+			radius as u64 + 1
+		};
+		let thickness = thickness as u64;
+		match self {
+			DataSymbol::Cross => {
+				// two bars of (2 * length - 1) by (2 * thickness + 1) pixels overlapping at the origin
+				let long = 2 * length - 1;
+				let short = 2 * thickness + 1;
+				2 * long * short - long.min(short).pow(2)
+			}
+			DataSymbol::Circle
+			| DataSymbol::Triangle
+			| DataSymbol::TriangleDown
+			| DataSymbol::Square
+			| DataSymbol::Diamond
+			| DataSymbol::Plus
+			| DataSymbol::Star
+			| DataSymbol::X => {
+				// far enough from the canvas corner that no pixel of the shape is clamped
+				let offset = 4 * (radius + thickness as u32) + 8;
+				let pixels: HashSet<(u32, u32)> = self
+					.find_pixels((offset, offset), thickness as u32, radius)
+					.into_iter()
+					.collect();
+				pixels.len() as u64
+			}
+			DataSymbol::Point => 1,
+		}
+	}
+}
+
+/// Find the pixels of a ring centred on `origin` from radius `inner` out to radius `outer`. Each radius is
+/// rasterised with the midpoint circle algorithm, walking one octant in integer steps and choosing the pixel
+/// nearest the true circle at each, so every ring is gap free. Concentric rings leave holes between them so
+/// each row is filled from the innermost ring to the outermost, and each pixel is only generated once
+pub fn find_circle_pixels(origin: (u32, u32), inner: u32, outer: u32) -> Vec<(u32, u32)> {
+	let (cx, cy) = (origin.0 as i64, origin.1 as i64);
+	let outer = outer.max(inner) as i64;
+	// the smallest and largest column offset, at or right of the centre, covered in each row from the top
+	let mut spans: Vec<Option<(i64, i64)>> = vec![None; 2 * outer as usize + 1];
+	let mut cover = |dx: i64, dy: i64| {
+		let span = &mut spans[(dy + outer) as usize];
+		*span = match span {
+			Some((lo, hi)) => Some(((*lo).min(dx), (*hi).max(dx))),
+			None => Some((dx, dx)),
+		};
+	};
+	for r in inner as i64..=outer {
+		let mut x: i64 = 0;
+		let mut y: i64 = r;
+		// the decision variable tracks whether the midpoint between the two candidate pixels of the next
+		// step lies inside or outside the circle
+		let mut d: i64 = 1 - r;
+		while x <= y {
+			// the right half of the ring, the left half is its mirror image
+			cover(x, y);
+			cover(x, -y);
+			cover(y, x);
+			cover(y, -x);
+			if d < 0 {
+				d += 2 * x + 3;
+			} else {
+				d += 2 * (x - y) + 5;
+				y -= 1;
+			}
+			x += 1;
+		}
+	}
+	let mut pixel_coords = Vec::new();
+	for (row, span) in spans.iter().enumerate() {
+		if let Some((lo, hi)) = span {
+			let dy = row as i64 - outer;
+			for dx in *lo..=*hi {
+				pixel_coords.push(((cx + dx) as u32, (cy + dy) as u32));
+				if dx > 0 {
+					pixel_coords.push(((cx - dx) as u32, (cy + dy) as u32));
+				}
+			}
+		}
+	}
+	pixel_coords
+}
+
+/// Find the pixels of a ring centred on `origin` from radius `inner` out to radius `outer` along with the
+/// fraction of each covered. As a line is in [`find_antialiased_line_pixels`], the ring is a band one pixel
+/// wider than the distance between its radii and each pixel is covered by the length of its overlap with
+/// the band along the line from the centre, so a ring of a single radius is shared between the two pixels
+/// nearest to it like Wu's circles
+pub fn find_antialiased_circle_pixels(
+	origin: (u32, u32),
+	inner: u32,
+	outer: u32,
+) -> Vec<(u32, u32, f32)> {
+	let (cx, cy) = (origin.0 as i64, origin.1 as i64);
+	let (low, high) = (inner as f32 - 0.5, outer.max(inner) as f32 + 0.5);
+	let reach = high.ceil() as i64;
+	let mut pixel_coords = Vec::new();
+	for dy in -reach..=reach {
+		// only the columns of each row between the circles bounding the band are visited
+		let width = |radius: f32| (radius * radius - (dy * dy) as f32).max(0.0).sqrt();
+		let (near, far) = (width(low - 1.0).floor() as i64, width(high).ceil() as i64);
+		for dx in near..=far {
+			let distance = ((dx * dx + dy * dy) as f32).sqrt();
+			let coverage = (distance + 0.5).min(high) - (distance - 0.5).max(low);
+			if coverage <= 0.0 {
+				continue;
+			}
+			let y = cy + dy;
+			// the left half of the ring mirrors the right
+			if y >= 0 && cx + dx >= 0 {
+				pixel_coords.push(((cx + dx) as u32, y as u32, coverage.min(1.0)));
+			}
+			if y >= 0 && dx > 0 && cx - dx >= 0 {
+				pixel_coords.push(((cx - dx) as u32, y as u32, coverage.min(1.0)));
+			}
+		}
+	}
+	pixel_coords
+}
+
+/// The side length of the `n`th outline of a triangle symbol drawn `radius` large
+///
+/// Incircle radius of an equilateral triangle is twice the area divided by the perimiter
+/// area = (side_length * height)/ 2
+/// height = side_length * (3.sqrt() / 2)
+/// perimiter = 3 x side_length
+/// ir = side_length / (2 * 3.sqrt())
+///
+/// Note our side length is the radius argument
+fn triangle_side_length(radius: u32, n: u32) -> f32 {
+	// Ensure side_length is even otherwise the triangle will have an offset
+	// Initial (radius + 1) ensures that if a user sets radius to zero something will still be drawn
+	if (radius + 1) & 1 == 1 {
+		(radius + 2) as f32 + n as f32
+	} else {
+		(radius + 1) as f32 + n as f32
+	}
+}
+
+/// The corners of the right half of a five-pointed star with its points `outer` from `origin`. They
+/// alternate between the points and the notches between them, clockwise from the point facing up round to
+/// the point facing down. Canvas rows grow downwards so up is a smaller row
+fn find_star_corners(origin: (u32, u32), outer: f32) -> Vec<(f32, f32)> {
+	(0..=5)
+		.map(|k| {
+			let reach = if k % 2 == 0 {
+				outer
+			} else {
+				outer * STAR_INNER_RATIO
+			};
+			let angle = (k as f32 * 36.0).to_radians();
+			(
+				origin.0 as f32 + reach * angle.sin(),
+				origin.1 as f32 - reach * angle.cos(),
+			)
+		})
+		.collect()
+}
+
+/// Find the corners of an equilateral triangle with sides `side_length` long centred on `origin`, returned
+/// as the corner facing along the unit vector `direction` followed by the two corners of the base opposite it
+pub fn find_triangle_corners(
+	origin: (u32, u32),
+	side_length: f32,
+	direction: (f32, f32),
+) -> [(u32, u32); 3] {
+	find_exact_triangle_corners((origin.0 as f32, origin.1 as f32), side_length, direction)
+		.map(|(x, y)| (x as u32, y as u32))
+}
+
+/// As [`find_triangle_corners`] without truncating the corners to pixels
+fn find_exact_triangle_corners(
+	origin: (f32, f32),
+	side_length: f32,
+	direction: (f32, f32),
+) -> [(f32, f32); 3] {
+	let height: f32 = side_length * (3.0_f32.sqrt() / 2.0);
+	let incircle_radius: f32 = side_length / (2.0 * 3.0_f32.sqrt());
+	// The base runs perpendicular to the direction the triangle faces
+	let across = (-direction.1, direction.0);
+	// The facing corner is simply the origin moved along the direction by (height - incircle_radius)
+	let apex: (f32, f32) = (
+		origin.0 + direction.0 * height - direction.0 * incircle_radius,
+		origin.1 + direction.1 * height - direction.1 * incircle_radius,
+	);
+	// The base corners are the origin moved back by the incircle radius and across by half the side length
+	let left: (f32, f32) = (
+		origin.0 - direction.0 * incircle_radius + across.0 * (side_length / 2.0),
+		origin.1 - direction.1 * incircle_radius + across.1 * (side_length / 2.0),
+	);
+	let right: (f32, f32) = (
+		origin.0 - direction.0 * incircle_radius - across.0 * (side_length / 2.0),
+		origin.1 - direction.1 * incircle_radius - across.1 * (side_length / 2.0),
+	);
+	[apex, left, right]
+}
+
+/// Find the pixels covered by the triangle with the given `corners`, including its edges
+pub fn find_filled_triangle_pixels(corners: [(u32, u32); 3]) -> Vec<(u32, u32)> {
+	let [a, b, c] = corners.map(|(x, y)| (x as i64, y as i64));
+	// Which side of the edge from `start` to `end` a pixel lies on, zero when on the edge itself
+	let side = |start: (i64, i64), end: (i64, i64), p: (i64, i64)| {
+		(end.0 - start.0) * (p.1 - start.1) - (end.1 - start.1) * (p.0 - start.0)
+	};
+	// Rasterise the edges as the outlined symbols do so that the filled shape covers them
+	let mut pixel_coords = find_line_pixels(corners[1], corners[2]);
+	pixel_coords.extend(find_line_pixels(corners[1], corners[0]));
+	pixel_coords.extend(find_line_pixels(corners[0], corners[2]));
+	for x in a.0.min(b.0).min(c.0)..=a.0.max(b.0).max(c.0) {
+		for y in a.1.min(b.1).min(c.1)..=a.1.max(b.1).max(c.1) {
+			let sides = [side(a, b, (x, y)), side(b, c, (x, y)), side(c, a, (x, y))];
+			// A pixel is inside when it is on the same side of all three edges
+			if sides.iter().all(|s| *s >= 0) || sides.iter().all(|s| *s <= 0) {
+				pixel_coords.push((x as u32, y as u32));
+			}
+		}
+	}
+	pixel_coords
+}
+
+/// The pixel column an `x` value, relative to the value at the origin of the axes, is drawn at. The value
+/// must lie within the axes
+pub fn get_x_pixel(x: f32, x_scale_factor: f32, axes_origin_x: u32) -> u32 {
+	if x > 0.0 {
+		axes_origin_x + (x * x_scale_factor) as u32
+	} else {
+		axes_origin_x - (-x * x_scale_factor) as u32
+	}
+}
+
+/// The pixel row a `y` value, relative to the value at the origin of the axes, is drawn at. The value must
+/// lie within the axes
+pub fn get_y_pixel(y: f32, y_scale_factor: f32, axes_origin_y: u32) -> u32 {
+	// note pixel postions use an origin based from top-left corner so to draw them in the human-like axis_origin we flip the signs for y
+	if y > 0.0 {
+		axes_origin_y - (y * y_scale_factor) as u32
+	} else {
+		axes_origin_y + (-y * y_scale_factor) as u32
+	}
+}
+
+/// Scale a symbol radius or thickness, which are drawn as `1 + size` pixels, so that the symbol covers
+/// the same area of a canvas drawn `scale` times larger
+pub fn scale_symbol_size(size: u32, scale: u32) -> u32 {
+	(size + 1) * scale - 1
 }
 
 /// Representation of a point to be drawn on a graph
@@ -219,6 +584,10 @@ pub struct DataPoint {
 	pub symbol_radius: u32,
 	/// The thinkness of a drawn symbol in (1 + symbol_thickness) pixels
 	pub symbol_thickness: u32,
+	/// The width in pixels of uncertainty bars and of a `Point` symbol
+	pub line_thickness: u32,
+	/// Whether the edges of the symbol are smoothed by partly covering the pixels along them
+	pub antialias: bool,
 }
 impl DataPoint {
 	/// Draws a data point onto the canvas with a given symbol and scales its size against the number of pixels available
@@ -231,17 +600,8 @@ impl DataPoint {
 	) {
 		trace!("Drawing point {:?}", self);
 		let rgba = Colour::get_pixel_colour(self.colour);
-		let x_pixel_corrected_pos = if self.x > 0.0 {
-			axes_origin.0 + (self.x * x_scale_factor) as u32
-		} else {
-			axes_origin.0 - (-self.x * x_scale_factor) as u32
-		};
-		// note pixel postions use an origin based from top-left corner so to draw them in the human-like axis_origin we flip the signs for y
-		let y_pixel_corrected_pos = if self.y > 0.0 {
-			axes_origin.1 - (self.y * y_scale_factor) as u32
-		} else {
-			axes_origin.1 + (-self.y * y_scale_factor) as u32
-		};
+		let x_pixel_corrected_pos = get_x_pixel(self.x, x_scale_factor, axes_origin.0);
+		let y_pixel_corrected_pos = get_y_pixel(self.y, y_scale_factor, axes_origin.1);
 		trace!(
 			"Plotting data point ({}, {}) with pixel position ({}, {})",
 			self.x,
@@ -249,147 +609,695 @@ impl DataPoint {
 			x_pixel_corrected_pos,
 			y_pixel_corrected_pos
 		);
-		// find the pixels that corrpespond to the symbol shape
-		let pixels_in_shape = self.symbol.find_pixels(
-			(x_pixel_corrected_pos, y_pixel_corrected_pos),
-			self.symbol_thickness,
-			self.symbol_radius,
-		);
-		// Draw the symbol for a data point
-		for (px, py) in pixels_in_shape.iter() {
-			match canvas.get_pixel_mut_checked(*px, *py) {
-				Some(pixel) => *pixel = Rgba(rgba),
-				None => warn!(
-					"Cannot plot data point ({}, {}) with symbol pixel position ({}, {})",
-					self.x, self.y, x_pixel_corrected_pos, y_pixel_corrected_pos
-				),
+		let origin = (x_pixel_corrected_pos, y_pixel_corrected_pos);
+		// every pixel of the point is gathered first so that a translucent colour is composited evenly
+		let mut stamp = PixelStamp::new(canvas);
+		// Draw the symbol for a data point, a point is a square block which has no edges to smooth
+		if self.antialias && self.symbol != DataSymbol::Point {
+			for (px, py, coverage) in self
+				.symbol
+				.find_coverage(origin, self.symbol_thickness, self.symbol_radius)
+				.into_iter()
+			{
+				if !stamp.cover(px, py, coverage) {
+					warn!(
+						"Cannot plot data point ({}, {}) with symbol pixel position ({}, {})",
+						self.x, self.y, x_pixel_corrected_pos, y_pixel_corrected_pos
+					);
+				}
+			}
+		} else {
+			// find the pixels that corrpespond to the symbol shape
+			let pixels_in_shape =
+				self.symbol
+					.find_pixels(origin, self.symbol_thickness, self.symbol_radius);
+			// the other symbols have their thickness scaled, a point is a single block
+			let block_size = if matches!(self.symbol, DataSymbol::Point) {
+				self.line_thickness
+			} else {
+				1
+			};
+			for (px, py) in pixels_in_shape.iter() {
+				if !stamp.block(*px, *py, block_size) {
+					warn!(
+						"Cannot plot data point ({}, {}) with symbol pixel position ({}, {})",
+						self.x, self.y, x_pixel_corrected_pos, y_pixel_corrected_pos
+					);
+				}
 			}
 		}
 		// Draw uncertainty bars
-		match self.ux {
-			Some(value) => {
-				trace!("Drawing x uncertainty with size {}", value);
-				// furthest pixel to the right
-				let upper_limit_pixel = axes_origin.0 + ((self.x + value) * x_scale_factor) as u32;
-				// furthest pixel to the left
-				let lower_limit_pixel = axes_origin.0 + ((self.x - value) * x_scale_factor) as u32;
-				// draw line from left to right
-				for px in lower_limit_pixel..=upper_limit_pixel {
-					match canvas.get_pixel_mut_checked(px, y_pixel_corrected_pos) {
-						Some(pixel) => *pixel = Rgba(rgba),
-						None => warn!(
-							"Cannot plot error bar point ({}, {}) with pixel position ({}, {})",
-							self.x, self.y, px, y_pixel_corrected_pos
-						),
-					}
+		if let Some(value) = self.ux {
+			trace!("Drawing x uncertainty with size {}", value);
+			// furthest pixel to the right
+			let upper_limit_pixel = axes_origin.0 + ((self.x + value) * x_scale_factor) as u32;
+			// furthest pixel to the left
+			let lower_limit_pixel = axes_origin.0 + ((self.x - value) * x_scale_factor) as u32;
+			// draw line from left to right
+			for px in lower_limit_pixel..=upper_limit_pixel {
+				if !stamp.block(px, y_pixel_corrected_pos, self.line_thickness) {
+					warn!(
+						"Cannot plot error bar point ({}, {}) with pixel position ({}, {})",
+						self.x, self.y, px, y_pixel_corrected_pos
+					);
 				}
-				let error_bar_length = (upper_limit_pixel - lower_limit_pixel) / 4;
-				// draws 'wings' either side of the limits
-				for py in 0..=error_bar_length {
-					// down
-					match canvas
-						.get_pixel_mut_checked(upper_limit_pixel, y_pixel_corrected_pos + py)
-					{
-						Some(pixel) => *pixel = Rgba(rgba),
-						None => warn!(
-							"Cannot plot error bar wing for point ({}, {}) with pixel position ({}, {})",
-							self.x, self.y, upper_limit_pixel, y_pixel_corrected_pos + py
-						),
-					}
-					match canvas
-						.get_pixel_mut_checked(lower_limit_pixel, y_pixel_corrected_pos + py)
-					{
-						Some(pixel) => *pixel = Rgba(rgba),
-						None => warn!(
-							"Cannot plot error bar wing for point ({}, {}) with pixel position ({}, {})",
-							self.x, self.y, lower_limit_pixel, y_pixel_corrected_pos - py
-						),
-					}
-					// up
-					match canvas
-						.get_pixel_mut_checked(upper_limit_pixel, y_pixel_corrected_pos - py)
-					{
-						Some(pixel) => *pixel = Rgba(rgba),
-						None => warn!(
-							"Cannot plot error bar wing for point ({}, {}) with pixel position ({}, {})",
-							self.x, self.y, upper_limit_pixel, y_pixel_corrected_pos + py
-						),
-					}
-					match canvas
-						.get_pixel_mut_checked(lower_limit_pixel, y_pixel_corrected_pos - py)
-					{
-						Some(pixel) => *pixel = Rgba(rgba),
-						None => warn!(
-							"Cannot plot error bar wing for point ({}, {}) with pixel position ({}, {})",
-							self.x, self.y, lower_limit_pixel, y_pixel_corrected_pos - py
-						),
-					}
+			}
+			let error_bar_length = (upper_limit_pixel - lower_limit_pixel) / 4;
+			// draws 'wings' either side of the limits
+			for py in 0..=error_bar_length {
+				// down
+				if !stamp.block(
+					upper_limit_pixel,
+					y_pixel_corrected_pos + py,
+					self.line_thickness,
+				) {
+					warn!(
+						"Cannot plot error bar wing for point ({}, {}) with pixel position ({}, {})",
+						self.x, self.y, upper_limit_pixel, y_pixel_corrected_pos + py
+					);
+				}
+				if !stamp.block(
+					lower_limit_pixel,
+					y_pixel_corrected_pos + py,
+					self.line_thickness,
+				) {
+					warn!(
+						"Cannot plot error bar wing for point ({}, {}) with pixel position ({}, {})",
+						self.x, self.y, lower_limit_pixel, y_pixel_corrected_pos - py
+					);
+				}
+				// up
+				if !stamp.block(
+					upper_limit_pixel,
+					y_pixel_corrected_pos - py,
+					self.line_thickness,
+				) {
+					warn!(
+						"Cannot plot error bar wing for point ({}, {}) with pixel position ({}, {})",
+						self.x, self.y, upper_limit_pixel, y_pixel_corrected_pos + py
+					);
+				}
+				if !stamp.block(
+					lower_limit_pixel,
+					y_pixel_corrected_pos - py,
+					self.line_thickness,
+				) {
+					warn!(
+						"Cannot plot error bar wing for point ({}, {}) with pixel position ({}, {})",
+						self.x, self.y, lower_limit_pixel, y_pixel_corrected_pos - py
+					);
 				}
 			}
-			None => {}
 		}
 		// Draw uncertainty bars
-		match self.uy {
-			Some(value) => {
-				trace!("Drawing y uncertainty with size {}", value);
-				// furthest pixel above
-				let upper_limit_pixel = axes_origin.1 - ((self.y - value) * y_scale_factor) as u32;
-				// furthest pixel below
-				let lower_limit_pixel = axes_origin.1 - ((self.y + value) * y_scale_factor) as u32;
-				// draw line from above to below
-				for py in lower_limit_pixel..=upper_limit_pixel {
-					match canvas.get_pixel_mut_checked(x_pixel_corrected_pos, py) {
-						Some(pixel) => *pixel = Rgba(rgba),
-						None => warn!(
-							"Cannot plot error bar point ({}, {}) with pixel position ({}, {})",
-							self.x, self.y, x_pixel_corrected_pos, py
-						),
-					}
+		if let Some(value) = self.uy {
+			trace!("Drawing y uncertainty with size {}", value);
+			// furthest pixel above
+			let upper_limit_pixel = axes_origin.1 - ((self.y - value) * y_scale_factor) as u32;
+			// furthest pixel below
+			let lower_limit_pixel = axes_origin.1 - ((self.y + value) * y_scale_factor) as u32;
+			// draw line from above to below
+			for py in lower_limit_pixel..=upper_limit_pixel {
+				if !stamp.block(x_pixel_corrected_pos, py, self.line_thickness) {
+					warn!(
+						"Cannot plot error bar point ({}, {}) with pixel position ({}, {})",
+						self.x, self.y, x_pixel_corrected_pos, py
+					);
 				}
-				let error_bar_length = (upper_limit_pixel - lower_limit_pixel) / 4;
-				// draws 'wings' either side of the limits
-				for px in 0..=error_bar_length {
-					// to the left
-					match canvas
-						.get_pixel_mut_checked(x_pixel_corrected_pos - px, upper_limit_pixel)
-					{
-						Some(pixel) => *pixel = Rgba(rgba),
-						None => warn!(
-							"Cannot plot error bar wing for point ({}, {}) with pixel position ({}, {})",
-							self.x, self.y, x_pixel_corrected_pos - px, upper_limit_pixel
-						),
-					}
-					match canvas
-						.get_pixel_mut_checked(x_pixel_corrected_pos - px, lower_limit_pixel)
-					{
-						Some(pixel) => *pixel = Rgba(rgba),
-						None => warn!(
-							"Cannot plot error bar wing for point ({}, {}) with pixel position ({}, {})",
-							self.x, self.y, x_pixel_corrected_pos - px, lower_limit_pixel
-						),
-					}
-					// to the right
-					match canvas
-						.get_pixel_mut_checked(x_pixel_corrected_pos + px, upper_limit_pixel)
-					{
-						Some(pixel) => *pixel = Rgba(rgba),
-						None => warn!(
-							"Cannot plot error bar wing for point ({}, {}) with pixel position ({}, {})",
-							self.x, self.y, x_pixel_corrected_pos + px, upper_limit_pixel
-						),
-					}
-					match canvas
-						.get_pixel_mut_checked(x_pixel_corrected_pos + px, lower_limit_pixel)
-					{
-						Some(pixel) => *pixel = Rgba(rgba),
-						None => warn!(
-							"Cannot plot error bar wing for point ({}, {}) with pixel position ({}, {})",
-							self.x, self.y, x_pixel_corrected_pos + px, lower_limit_pixel
-						),
+			}
+			let error_bar_length = (upper_limit_pixel - lower_limit_pixel) / 4;
+			// draws 'wings' either side of the limits
+			for px in 0..=error_bar_length {
+				// to the left
+				if !stamp.block(
+					x_pixel_corrected_pos - px,
+					upper_limit_pixel,
+					self.line_thickness,
+				) {
+					warn!(
+						"Cannot plot error bar wing for point ({}, {}) with pixel position ({}, {})",
+						self.x, self.y, x_pixel_corrected_pos - px, upper_limit_pixel
+					);
+				}
+				if !stamp.block(
+					x_pixel_corrected_pos - px,
+					lower_limit_pixel,
+					self.line_thickness,
+				) {
+					warn!(
+						"Cannot plot error bar wing for point ({}, {}) with pixel position ({}, {})",
+						self.x, self.y, x_pixel_corrected_pos - px, lower_limit_pixel
+					);
+				}
+				// to the right
+				if !stamp.block(
+					x_pixel_corrected_pos + px,
+					upper_limit_pixel,
+					self.line_thickness,
+				) {
+					warn!(
+						"Cannot plot error bar wing for point ({}, {}) with pixel position ({}, {})",
+						self.x, self.y, x_pixel_corrected_pos + px, upper_limit_pixel
+					);
+				}
+				if !stamp.block(
+					x_pixel_corrected_pos + px,
+					lower_limit_pixel,
+					self.line_thickness,
+				) {
+					warn!(
+						"Cannot plot error bar wing for point ({}, {}) with pixel position ({}, {})",
+						self.x, self.y, x_pixel_corrected_pos + px, lower_limit_pixel
+					);
+				}
+			}
+		}
+		stamp.draw(canvas, rgba);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		canvas::{
+			draw_base_canvas,
+			plot::{
+				find_antialiased_circle_pixels, find_circle_pixels, find_filled_triangle_pixels,
+				find_triangle_corners, DataPoint, DataSymbol,
+			},
+		},
+		colours::{Colour, WHITE},
+	};
+	use std::collections::HashSet;
+
+	/// The edges of a triangle pointing down as they were generated before switching to Bresenham, by
+	/// stepping along `y = mx + c` in 1/10000th pixel increments
+	fn legacy_triangle_pixels(origin: (u32, u32), thickness: u32, radius: u32) -> Vec<(u32, u32)> {
+		let mut pixel_coords = vec![origin];
+		for n in 0..=thickness {
+			let side_length: f32 = if (radius + 1) & 1 == 1 {
+				(radius + 2) as f32 + n as f32
+			} else {
+				(radius + 1) as f32 + n as f32
+			};
+			let height: f32 = side_length * (3.0_f32.sqrt() / 2.0);
+			let incircle_radius: f32 = side_length / (2.0 * 3.0_f32.sqrt());
+			let top = (origin.0 as f32, origin.1 as f32 + height - incircle_radius);
+			let left = (
+				origin.0 as f32 - (side_length / 2.0),
+				origin.1 as f32 - incircle_radius,
+			);
+			let right = (
+				origin.0 as f32 + (side_length / 2.0),
+				origin.1 as f32 - incircle_radius,
+			);
+			pixel_coords.push((top.0 as u32, top.1 as u32));
+			pixel_coords.push((left.0 as u32, left.1 as u32));
+			pixel_coords.push((right.0 as u32, right.1 as u32));
+			for i in 0..(right.0 - left.0) as u32 {
+				pixel_coords.push((left.0 as u32 + i, left.1 as u32));
+			}
+			let scale_factor = 10000;
+			let gradient = (top.1 - left.1) / (top.0 - left.0);
+			let intercept = left.1 - (gradient * left.0);
+			for i in 0..((top.0 - left.0) as u32 * scale_factor) {
+				let x = left.0 + (i as f32 / scale_factor as f32);
+				pixel_coords.push((x as u32, ((gradient * x) + intercept) as u32));
+			}
+			let gradient = (top.1 - right.1) / (top.0 - right.0);
+			let intercept = right.1 - (gradient * right.0);
+			for i in 0..((right.0 - top.0) as u32 * scale_factor) {
+				let x = top.0 + (i as f32 / scale_factor as f32);
+				pixel_coords.push((x as u32, ((gradient * x) + intercept) as u32));
+			}
+		}
+		pixel_coords
+	}
+	#[test]
+	fn triangle_matches_legacy_silhouette() {
+		for radius in 2..=10 {
+			let new: HashSet<(u32, u32)> = DataSymbol::TriangleDown
+				.find_pixels((50, 50), 0, radius)
+				.into_iter()
+				.collect();
+			let old: HashSet<(u32, u32)> = legacy_triangle_pixels((50, 50), 0, radius)
+				.into_iter()
+				.collect();
+			// Bresenham gives 8-connected edges where the legacy stepping produced a 4-connected
+			// staircase, so allow a pixel of leeway either side while requiring the same extent
+			let bounds = |pixels: &HashSet<(u32, u32)>| {
+				(
+					pixels.iter().map(|p| p.0).min(),
+					pixels.iter().map(|p| p.0).max(),
+					pixels.iter().map(|p| p.1).min(),
+					pixels.iter().map(|p| p.1).max(),
+				)
+			};
+			assert_eq!(bounds(&old), bounds(&new), "radius {}", radius);
+			let is_near = |p: &(u32, u32), pixels: &HashSet<(u32, u32)>| {
+				pixels.iter().any(|q| {
+					(p.0 as i64 - q.0 as i64).abs() <= 1 && (p.1 as i64 - q.1 as i64).abs() <= 1
+				})
+			};
+			assert!(new.iter().all(|p| is_near(p, &old)), "radius {}", radius);
+			assert!(old.iter().all(|p| is_near(p, &new)), "radius {}", radius);
+		}
+	}
+	#[test]
+	fn triangle_generates_far_fewer_coordinates() {
+		for radius in 2..=10 {
+			let new = DataSymbol::TriangleDown
+				.find_pixels((50, 50), 0, radius)
+				.len();
+			let old = legacy_triangle_pixels((50, 50), 0, radius).len();
+			assert!(old > new * 100, "radius {}: {} vs {}", radius, old, new);
+		}
+	}
+	/// The distinct pixels `find_pixels` generates for a symbol
+	fn distinct_pixels(symbol: DataSymbol, radius: u32, thickness: u32) -> u64 {
+		let pixels: HashSet<(u32, u32)> = symbol
+			.find_pixels((100, 100), thickness, radius)
+			.into_iter()
+			.collect();
+		pixels.len() as u64
+	}
+	#[test]
+	fn pixel_count_is_exact_for_simple_shapes() {
+		for symbol in [
+			DataSymbol::Cross,
+			DataSymbol::Circle,
+			DataSymbol::Triangle,
+			DataSymbol::TriangleDown,
+			DataSymbol::Square,
+			DataSymbol::Point,
+			DataSymbol::Diamond,
+			DataSymbol::Plus,
+			DataSymbol::Star,
+			DataSymbol::X,
+		] {
+			for radius in 0..=12 {
+				for thickness in 0..=4 {
+					assert_eq!(
+						distinct_pixels(symbol, radius, thickness),
+						symbol.pixel_count(radius, thickness),
+						"{:?} radius {} thickness {}",
+						symbol,
+						radius,
+						thickness
+					);
+				}
+			}
+		}
+	}
+	/// The pixels a ring must cover to be drawn without gaps, for every column and row it crosses the pixel
+	/// nearest the true circle
+	fn required_ring_pixels(origin: (u32, u32), r: u32) -> HashSet<(u32, u32)> {
+		let mut pixels = HashSet::new();
+		let (cx, cy) = (origin.0 as i64, origin.1 as i64);
+		let r = r as i64;
+		for along in -r..=r {
+			let across = ((r * r - along * along) as f64).sqrt().round() as i64;
+			for (dx, dy) in [
+				(along, across),
+				(along, -across),
+				(across, along),
+				(-across, along),
+			] {
+				pixels.insert(((cx + dx) as u32, (cy + dy) as u32));
+			}
+		}
+		pixels
+	}
+	#[test]
+	fn circle_covers_the_required_ring() {
+		for r in 1..=50 {
+			let ring: Vec<(u32, u32)> = find_circle_pixels((100, 100), r, r);
+			let distinct: HashSet<(u32, u32)> = ring.iter().copied().collect();
+			assert_eq!(ring.len(), distinct.len(), "radius {} repeats pixels", r);
+			let required = required_ring_pixels((100, 100), r);
+			assert!(
+				required.is_subset(&distinct),
+				"radius {} misses {:?}",
+				r,
+				required.difference(&distinct)
+			);
+			// each pixel of the closed ring touches at least two others
+			for (x, y) in distinct.iter() {
+				let neighbours = distinct
+					.iter()
+					.filter(|(nx, ny)| {
+						(nx, ny) != (x, y)
+							&& (*nx as i64 - *x as i64).abs() <= 1
+							&& (*ny as i64 - *y as i64).abs() <= 1
+					})
+					.count();
+				assert!(neighbours >= 2, "radius {} gap at ({}, {})", r, x, y);
+			}
+		}
+	}
+	#[test]
+	fn thick_circle_is_solid_between_its_rings() {
+		for (inner, outer) in [(2, 4), (6, 9), (20, 30)] {
+			let ring: Vec<(u32, u32)> = find_circle_pixels((100, 100), inner, outer);
+			let distinct: HashSet<(u32, u32)> = ring.iter().copied().collect();
+			assert_eq!(ring.len(), distinct.len());
+			// every pixel whose centre lies between the inner and outer circles is covered
+			for x in 100 - outer..=100 + outer {
+				for y in 100 - outer..=100 + outer {
+					let d2 = (x as i64 - 100).pow(2) + (y as i64 - 100).pow(2);
+					if (inner * inner) as i64 <= d2 && d2 <= (outer * outer) as i64 {
+						assert!(
+							distinct.contains(&(x, y)),
+							"{}-{} misses ({}, {})",
+							inner,
+							outer,
+							x,
+							y
+						);
 					}
 				}
 			}
-			None => {}
+			assert!(required_ring_pixels((100, 100), inner).is_subset(&distinct));
+			assert!(required_ring_pixels((100, 100), outer).is_subset(&distinct));
+		}
+	}
+	#[test]
+	fn circle_matches_legacy_silhouette() {
+		for radius in 0..=10 {
+			let new: HashSet<(u32, u32)> = DataSymbol::Circle
+				.find_pixels((50, 50), 0, radius)
+				.into_iter()
+				.collect();
+			// each ring used to be sampled at every thousandth of a degree and truncated to a pixel
+			let r = if (radius + 1) & 1 == 1 {
+				radius + 2
+			} else {
+				radius + 1
+			};
+			let old: HashSet<(u32, u32)> = (0..360_000)
+				.map(|angle| {
+					let angle = (angle as f32 / 1000.0).to_radians();
+					(
+						(50.0 + angle.cos() * r as f32) as u32,
+						(50.0 + angle.sin() * r as f32) as u32,
+					)
+				})
+				.collect();
+			// truncation pulled the old ring towards the origin, so allow a pixel of leeway
+			let is_near = |p: &(u32, u32), pixels: &HashSet<(u32, u32)>| {
+				pixels.iter().any(|q| {
+					(p.0 as i64 - q.0 as i64).abs() <= 1 && (p.1 as i64 - q.1 as i64).abs() <= 1
+				})
+			};
+			assert!(
+				new.iter()
+					.filter(|p| **p != (50, 50))
+					.all(|p| is_near(p, &old)),
+				"radius {}",
+				radius
+			);
+			assert!(old.iter().all(|p| is_near(p, &new)), "radius {}", radius);
+		}
+	}
+	#[test]
+	fn filled_triangle_covers_its_outline() {
+		for direction in [(0.0, 1.0), (1.0, 0.0), (0.0, -1.0)] {
+			let corners = find_triangle_corners((50, 50), 12.0, direction);
+			let filled: HashSet<(u32, u32)> =
+				find_filled_triangle_pixels(corners).into_iter().collect();
+			let outline: HashSet<(u32, u32)> = DataSymbol::Triangle
+				.find_pixels((50, 50), 0, 11)
+				.into_iter()
+				.collect();
+			assert!(filled.contains(&(50, 50)));
+			assert!(corners.iter().all(|corner| filled.contains(corner)));
+			// the outline of the symbol pointing up is the same triangle as the filled one facing that way
+			if direction == (0.0, -1.0) {
+				assert!(
+					outline.is_subset(&filled),
+					"{:?}",
+					outline.difference(&filled)
+				);
+			}
 		}
 	}
+	#[test]
+	fn translucent_point_is_composited_once_per_pixel() {
+		let mut canvas = draw_base_canvas((100, 100));
+		let point = DataPoint {
+			x: 0.0,
+			ux: Some(10.0),
+			y: 0.0,
+			uy: Some(10.0),
+			colour: Colour::Rgba(255, 0, 0, 128),
+			symbol: DataSymbol::Cross,
+			symbol_radius: 4,
+			symbol_thickness: 1,
+			line_thickness: 2,
+			antialias: false,
+		};
+		point.draw_point(&mut canvas, 1.0, 1.0, (50, 50));
+		let coloured: Vec<_> = canvas.pixels().filter(|pixel| pixel.0 != WHITE).collect();
+		assert!(!coloured.is_empty());
+		// the cross and both error bars overlap at the centre, which must be no darker than elsewhere
+		for pixel in coloured {
+			assert_eq!([255, 127, 127, 255], pixel.0);
+		}
+	}
+	/// The extent of a set of pixels as `(min x, max x, min y, max y)`
+	type Bounds = (u32, u32, u32, u32);
+	/// The distinct pixels of a symbol centred on (50, 50) along with their bounds
+	fn coverage(symbol: DataSymbol, radius: u32) -> (HashSet<(u32, u32)>, Bounds) {
+		let pixels: HashSet<(u32, u32)> = symbol
+			.find_pixels((50, 50), 0, radius)
+			.into_iter()
+			.collect();
+		let bounds = (
+			pixels.iter().map(|p| p.0).min().unwrap(),
+			pixels.iter().map(|p| p.0).max().unwrap(),
+			pixels.iter().map(|p| p.1).min().unwrap(),
+			pixels.iter().map(|p| p.1).max().unwrap(),
+		);
+		(pixels, bounds)
+	}
+	/// Whether the pixels are unchanged by reflecting them left to right about the centre
+	fn is_left_right_symmetric(pixels: &HashSet<(u32, u32)>) -> bool {
+		pixels.iter().all(|(x, y)| pixels.contains(&(100 - x, *y)))
+	}
+	#[test]
+	fn diamond_coverage() {
+		for (radius, count) in [(3, 13), (7, 29)] {
+			let (pixels, bounds) = coverage(DataSymbol::Diamond, radius);
+			assert_eq!(count, pixels.len(), "radius {}", radius);
+			assert_eq!((50 - radius, 50 + radius, 50 - radius, 50 + radius), bounds);
+			// the corners lie on the axes through the centre
+			for corner in [
+				(50, 50 - radius),
+				(50 + radius, 50),
+				(50, 50 + radius),
+				(50 - radius, 50),
+			] {
+				assert!(pixels.contains(&corner), "radius {} {:?}", radius, corner);
+			}
+			// an outline, nothing between the centre and the corners is drawn
+			assert!(!pixels.contains(&(51, 50)));
+			assert!(is_left_right_symmetric(&pixels));
+		}
+	}
+	#[test]
+	fn plus_coverage() {
+		for (radius, count) in [(3, 13), (7, 29)] {
+			let (pixels, bounds) = coverage(DataSymbol::Plus, radius);
+			assert_eq!(count, pixels.len(), "radius {}", radius);
+			assert_eq!((50 - radius, 50 + radius, 50 - radius, 50 + radius), bounds);
+			// every pixel lies on one of the axes through the centre
+			assert!(pixels.iter().all(|(x, y)| *x == 50 || *y == 50));
+		}
+		// unlike a cross the arms are not rounded up to an even length
+		assert_eq!((48, 52, 48, 52), coverage(DataSymbol::Plus, 2).1,);
+		assert_eq!((47, 53, 47, 53), coverage(DataSymbol::Cross, 2).1);
+	}
+	#[test]
+	fn star_coverage() {
+		for (radius, count, lowest) in [(3, 21, 52), (7, 46, 56)] {
+			let (pixels, bounds) = coverage(DataSymbol::Star, radius);
+			assert_eq!(count, pixels.len(), "radius {}", radius);
+			// the top point reaches the full radius while the lower points sit 54° below horizontal
+			assert_eq!((50 - radius, 50 + radius, 50 - radius, lowest), bounds);
+			assert!(pixels.contains(&(50, 50 - radius)));
+			assert!(is_left_right_symmetric(&pixels));
+		}
+	}
+	#[test]
+	fn x_coverage() {
+		for (radius, count) in [(3, 13), (7, 29)] {
+			let (pixels, bounds) = coverage(DataSymbol::X, radius);
+			assert_eq!(count, pixels.len(), "radius {}", radius);
+			assert_eq!((50 - radius, 50 + radius, 50 - radius, 50 + radius), bounds);
+			// every pixel lies on one of the diagonals through the centre
+			assert!(pixels
+				.iter()
+				.all(|(x, y)| (*x as i64 - 50).abs() == (*y as i64 - 50).abs()));
+		}
+	}
+	/// Draw the pixels of a symbol centred on (50, 50) as rows of text, `#` for a covered pixel, across
+	/// the 13 pixel square around the centre
+	fn symbol_as_text(symbol: DataSymbol, radius: u32) -> Vec<String> {
+		let pixels: HashSet<(u32, u32)> = symbol
+			.find_pixels((50, 50), 0, radius)
+			.into_iter()
+			.collect();
+		(44..=56)
+			.map(|y| {
+				(44..=56)
+					.map(|x| if pixels.contains(&(x, y)) { '#' } else { '.' })
+					.collect()
+			})
+			.collect()
+	}
+	#[test]
+	fn triangles_point_up_and_down() {
+		assert_eq!(
+			vec![
+				".............",
+				"......#......",
+				".....#.#.....",
+				".....#.#.....",
+				"....#...#....",
+				"....#...#....",
+				"...#..#..#...",
+				"...#.....#...",
+				"..#########..",
+				".............",
+				".............",
+				".............",
+				".............",
+			],
+			symbol_as_text(DataSymbol::Triangle, 6)
+		);
+		assert_eq!(
+			vec![
+				".............",
+				".............",
+				".............",
+				"..#########..",
+				"...#.....#...",
+				"...#.....#...",
+				"....#.#.#....",
+				"....#...#....",
+				".....#.#.....",
+				".....#.#.....",
+				"......#......",
+				".............",
+				".............",
+			],
+			symbol_as_text(DataSymbol::TriangleDown, 6)
+		);
+	}
+	#[test]
+	fn antialiased_circle_is_shared_between_the_nearest_pixels() {
+		for r in [3, 8, 20] {
+			let pixels = find_antialiased_circle_pixels((100, 100), r, r);
+			let distinct: HashSet<(u32, u32)> = pixels.iter().map(|(x, y, _)| (*x, *y)).collect();
+			assert_eq!(pixels.len(), distinct.len(), "radius {} repeats pixels", r);
+			for (x, y, coverage) in pixels.iter() {
+				let distance = ((*x as f32 - 100.0).powi(2) + (*y as f32 - 100.0).powi(2)).sqrt();
+				assert!(*coverage > 0.0 && *coverage <= 1.0);
+				// a ring of a single radius covers pixels in proportion to how near they are to it
+				assert!(
+					(coverage - (1.0 - (distance - r as f32).abs())).abs() < 1e-4,
+					"radius {} at ({}, {})",
+					r,
+					x,
+					y
+				);
+			}
+			// the pixels on the axes sit exactly on the ring
+			assert!(pixels.contains(&(100 + r, 100, 1.0)));
+			assert!(pixels.contains(&(100, 100 - r, 1.0)));
+		}
+		// a thick ring is solid between its radii
+		let thick = find_antialiased_circle_pixels((100, 100), 6, 9);
+		for (x, y) in find_circle_pixels((100, 100), 7, 8) {
+			assert!(
+				thick.iter().any(|p| (p.0, p.1) == (x, y) && p.2 > 0.999),
+				"({}, {})",
+				x,
+				y
+			);
+		}
+	}
+	#[test]
+	fn antialiasing_only_softens_curved_and_sloping_symbols() {
+		for symbol in [
+			DataSymbol::Cross,
+			DataSymbol::Square,
+			DataSymbol::Point,
+			DataSymbol::Diamond,
+			DataSymbol::Plus,
+			DataSymbol::X,
+		] {
+			let hard: Vec<(u32, u32, f32)> = symbol
+				.find_pixels((50, 50), 1, 5)
+				.into_iter()
+				.map(|(x, y)| (x, y, 1.0))
+				.collect();
+			assert_eq!(hard, symbol.find_coverage((50, 50), 1, 5), "{:?}", symbol);
+		}
+		for symbol in [
+			DataSymbol::Circle,
+			DataSymbol::Triangle,
+			DataSymbol::TriangleDown,
+			DataSymbol::Star,
+		] {
+			let soft = symbol.find_coverage((50, 50), 0, 7);
+			assert!(
+				soft.iter().any(|(_, _, coverage)| *coverage < 1.0),
+				"{:?}",
+				symbol
+			);
+			// the smoothed outline lies over the hard edged one
+			let hard: HashSet<(u32, u32)> =
+				symbol.find_pixels((50, 50), 0, 7).into_iter().collect();
+			let is_near = |p: &(u32, u32, f32)| {
+				hard.iter().any(|q| {
+					(p.0 as i64 - q.0 as i64).abs() <= 1 && (p.1 as i64 - q.1 as i64).abs() <= 1
+				})
+			};
+			assert!(soft.iter().all(is_near), "{:?}", symbol);
+		}
+	}
+	#[test]
+	fn antialiased_point_blends_into_the_canvas() {
+		let draw = |antialias| {
+			let mut canvas = draw_base_canvas((40, 40));
+			DataPoint {
+				x: 0.0,
+				ux: None,
+				y: 0.0,
+				uy: None,
+				colour: Colour::Black,
+				symbol: DataSymbol::Circle,
+				symbol_radius: 8,
+				symbol_thickness: 0,
+				line_thickness: 1,
+				antialias,
+			}
+			.draw_point(&mut canvas, 1.0, 1.0, (20, 20));
+			canvas
+		};
+		let is_grey = |pixel: &&image::Rgba<u8>| pixel.0 != WHITE && pixel.0 != [0, 0, 0, 255];
+		assert_eq!(0, draw(false).pixels().filter(is_grey).count());
+		assert!(draw(true).pixels().filter(is_grey).count() > 0);
+	}
+	#[test]
+	fn symbols_are_found_by_name_ignoring_case() {
+		assert_eq!(Some(DataSymbol::Circle), DataSymbol::from_name("Circle"));
+		assert_eq!(
+			Some(DataSymbol::TriangleDown),
+			DataSymbol::from_name("triangledown")
+		);
+		assert_eq!(Some(DataSymbol::X), DataSymbol::from_name("x"));
+		assert_eq!(None, DataSymbol::from_name("pass"));
+	}
 }
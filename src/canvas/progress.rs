@@ -0,0 +1,53 @@
+//! Hooks reporting how far through drawing a graph is, so that long renders of large canvases or csv files
+//! can show feedback
+
+use std::fmt;
+
+/// A step of drawing and saving a graph, reported as it begins
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum RenderStage {
+	/// Reading the values of every data set
+	LoadingData,
+	/// Finding the axis ranges and resolutions from the values
+	ComputingBounds,
+	/// Drawing the axes, scale markings and grid
+	DrawingAxes,
+	/// Plotting the zero indexed data set `index` of `count`
+	PlottingSet {
+		/// Zero indexed position of the set being plotted
+		index: usize,
+		/// Number of data sets in the graph
+		count: usize,
+	},
+	/// Encoding the image and writing it out
+	Saving,
+}
+
+/// Receives each [`RenderStage`] as drawing reaches it. Any `Fn(RenderStage)` closure is a `RenderProgress`,
+/// e.g
+///
+/// ```
+/// use plotrs::{canvas::progress::RenderStage, scatter::RenderOptions};
+/// use std::sync::Arc;
+///
+/// let options = RenderOptions {
+///     progress: Some(Arc::new(|stage: RenderStage| eprintln!("{:?}", stage))),
+///     ..RenderOptions::default()
+/// };
+/// ```
+pub trait RenderProgress {
+	/// Called as drawing moves on to `stage`
+	fn stage(&self, stage: RenderStage);
+}
+
+impl<F: Fn(RenderStage)> RenderProgress for F {
+	fn stage(&self, stage: RenderStage) {
+		self(stage)
+	}
+}
+
+impl fmt::Debug for dyn RenderProgress + Send + Sync {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str("RenderProgress")
+	}
+}
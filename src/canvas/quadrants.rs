@@ -1,7 +1,11 @@
 //! Calculates which cartesian quadrants need to be drawn based on the x-y range of supplied data sets
 
+use std::cmp::Ordering::{Equal, Greater, Less};
+
+use crate::error::PlotError;
+
 /// Quadrant combinations
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Copy, Clone)]
 #[allow(clippy::enum_variant_names)]
 pub enum Quadrants {
 	/// Top right and bottom right quadrants, x is only positive, y is pos and neg
@@ -23,138 +27,142 @@ pub enum Quadrants {
 	/// Bottom left quadrant, x is negative, y is negative
 	BottomLeft,
 }
-/// Based on the minimum and maximum possible values in the data sets identify which quadrants need to be drawn
-pub fn get_quadrants(min_xy: (i32, i32), max_xy: (i32, i32)) -> Quadrants {
-	match min_xy.0.signum() {
+impl Quadrants {
+	/// Whether the x-axis spans negative and positive values, bisected by the y-axis
+	pub fn is_x_paired(&self) -> bool {
+		matches!(
+			self,
+			Quadrants::AllQuadrants | Quadrants::TopPair | Quadrants::BottomPair
+		)
+	}
+	/// Whether the y-axis spans negative and positive values, bisected by the x-axis
+	pub fn is_y_paired(&self) -> bool {
+		matches!(
+			self,
+			Quadrants::AllQuadrants | Quadrants::LeftPair | Quadrants::RightPair
+		)
+	}
+	/// The data values at the origin of the axes. An axis of only positive values is labelled from its
+	/// minimum rather than zero, so values are plotted from there too
+	pub fn origin_values(
+		&self,
+		x_data_min_max_limits: (i32, i32),
+		y_data_min_max_limits: (i32, i32),
+	) -> (f32, f32) {
+		let x = match self {
+			Quadrants::TopRight | Quadrants::BottomRight | Quadrants::RightPair => {
+				x_data_min_max_limits.0 as f32
+			}
+			_ => 0.0,
+		};
+		let y = match self {
+			Quadrants::TopRight | Quadrants::TopLeft | Quadrants::TopPair => {
+				y_data_min_max_limits.0 as f32
+			}
+			_ => 0.0,
+		};
+		(x, y)
+	}
+}
+/// Based on the minimum and maximum possible values in the data sets identify which quadrants need to be drawn.
+/// Bounds which give the y-axis no extent, e.g every y value is zero, cannot be placed in a quadrant
+pub fn get_quadrants(min_xy: (i32, i32), max_xy: (i32, i32)) -> Result<Quadrants, PlotError> {
+	let quadrants = match min_xy.0.cmp(&0) {
 		// x minimum is positive there x maximum is positive
-		1 => {
-			match min_xy.1.signum() {
+		Greater => {
+			match min_xy.1.cmp(&0) {
 				// y minimum is positive
-				1 => Quadrants::TopRight,
+				Greater => Quadrants::TopRight,
 				// y minimum is zero
-				0 => Quadrants::TopRight,
+				Equal => Quadrants::TopRight,
 				// y minimum is negative therefore bottom right or right pair
-				-1 => match max_xy.1.signum() {
-					1 => Quadrants::RightPair,
-					0 => Quadrants::BottomRight,
-					-1 => Quadrants::BottomRight,
-					_ => {
-						panic!("Invalid `signum()`?!")
-					}
+				Less => match max_xy.1.cmp(&0) {
+					Greater => Quadrants::RightPair,
+					Equal => Quadrants::BottomRight,
+					Less => Quadrants::BottomRight,
 				},
-				_ => {
-					panic!("Invalid `signum()`?!")
-				}
 			}
 		}
 		// x minimum is zero therefore x maximum is positive
-		0 => {
-			match min_xy.1.signum() {
+		Equal => {
+			match min_xy.1.cmp(&0) {
 				// y minimum is positive
-				1 => Quadrants::TopRight,
+				Greater => Quadrants::TopRight,
 				// y minimum is zero therefore y maximum could be positive or negative
-				0 => {
-					match max_xy.1.signum() {
-						1 => Quadrants::TopRight,
-						0 => {
-							panic!("Quadrant bounds: invalid data, x is positive while y is exactly zero")
-						}
-						-1 => {
-							panic!("Quadrant bounds: invalid data, x is positive y min cannot be zero with positive y max ")
-						}
-						_ => {
-							panic!("Invalid `signum()`?!")
-						}
+				Equal => match max_xy.1.cmp(&0) {
+					Greater => Quadrants::TopRight,
+					Equal => {
+						return Err(PlotError::Render(
+							"Quadrant bounds: invalid data, x is positive while y is exactly zero"
+								.to_owned(),
+						))
 					}
-				}
-				// y minimum is negative therefore could be right pair or bottom right
-				-1 => match max_xy.1.signum() {
-					1 => Quadrants::RightPair,
-					0 => Quadrants::BottomRight,
-					-1 => Quadrants::BottomRight,
-					_ => {
-						panic!("Invalid `signum()`?!")
+					Less => {
+						return Err(PlotError::Render(
+							"Quadrant bounds: invalid data, x is positive y min cannot be zero with negative y max"
+								.to_owned(),
+						))
 					}
 				},
-				_ => {
-					panic!("Invalid `signum()`?!")
-				}
+				// y minimum is negative therefore could be right pair or bottom right
+				Less => match max_xy.1.cmp(&0) {
+					Greater => Quadrants::RightPair,
+					Equal => Quadrants::BottomRight,
+					Less => Quadrants::BottomRight,
+				},
 			}
 		}
 		// x minimum is negative
-		-1 => {
-			match max_xy.0.signum() {
+		Less => {
+			match max_xy.0.cmp(&0) {
 				// x maximum is positive so could be all quadrants or top/bottom pair
-				1 => {
-					match min_xy.1.signum() {
+				Greater => {
+					match min_xy.1.cmp(&0) {
 						// y minimum is positive therefore must be top pair
-						1 => Quadrants::TopPair,
+						Greater => Quadrants::TopPair,
 						// y minimum is zero therefore must be top pair
-						0 => Quadrants::TopPair,
+						Equal => Quadrants::TopPair,
 						// y minimum is negative so could be bottom pair or all quadrants
-						-1 => match max_xy.1.signum() {
-							1 => Quadrants::AllQuadrants,
-							0 => Quadrants::BottomPair,
-							-1 => Quadrants::BottomPair,
-							_ => {
-								panic!("Invalid `signum()`?!")
-							}
+						Less => match max_xy.1.cmp(&0) {
+							Greater => Quadrants::AllQuadrants,
+							Equal => Quadrants::BottomPair,
+							Less => Quadrants::BottomPair,
 						},
-						_ => {
-							panic!("Invalid `signum()`?!")
-						}
 					}
 				}
 				// x maximum is zero therefore could be top left, bottom left or left pair
-				0 => {
-					match min_xy.1.signum() {
+				Equal => {
+					match min_xy.1.cmp(&0) {
 						// y minimum is positive therefore can only be top left
-						1 => Quadrants::TopLeft,
+						Greater => Quadrants::TopLeft,
 						// y minimum is zero so max must be positive
-						0 => Quadrants::TopLeft,
+						Equal => Quadrants::TopLeft,
 						// y minimum is negative so y maximum must be zero or positive
-						-1 => {
-							match max_xy.1.signum() {
+						Less => {
+							match max_xy.1.cmp(&0) {
 								// y maximum is positive therefore left pair
-								1 => Quadrants::LeftPair,
+								Greater => Quadrants::LeftPair,
 								// y maximum is zero therefore must be bottom left
-								0 => Quadrants::BottomLeft,
-								-1 => Quadrants::BottomLeft,
-								_ => {
-									panic!("Invalid `signum()`?!")
-								}
+								Equal => Quadrants::BottomLeft,
+								Less => Quadrants::BottomLeft,
 							}
 						}
-						_ => {
-							panic!("Invalid `signum()`?!")
-						}
 					}
 				}
 				// x min-max is negative so must be left top/bottom/pair
-				-1 => match min_xy.1.signum() {
-					1 => Quadrants::TopLeft,
-					0 => Quadrants::TopLeft,
-					-1 => match max_xy.1.signum() {
-						1 => Quadrants::LeftPair,
-						0 => Quadrants::BottomLeft,
-						-1 => Quadrants::BottomLeft,
-						_ => {
-							panic!("Invalid `signum()`?!")
-						}
+				Less => match min_xy.1.cmp(&0) {
+					Greater => Quadrants::TopLeft,
+					Equal => Quadrants::TopLeft,
+					Less => match max_xy.1.cmp(&0) {
+						Greater => Quadrants::LeftPair,
+						Equal => Quadrants::BottomLeft,
+						Less => Quadrants::BottomLeft,
 					},
-					_ => {
-						panic!("Invalid `signum()`?!")
-					}
 				},
-				_ => {
-					panic!("Invalid `signum()`?!")
-				}
 			}
 		}
-		_ => {
-			panic!("Invalid `signum()`?!")
-		}
-	}
+	};
+	Ok(quadrants)
 }
 
 #[cfg(test)]
@@ -164,175 +172,181 @@ mod tests {
 	fn all_quadrants() {
 		let min_xy: (i32, i32) = (-1, -1);
 		let max_xy: (i32, i32) = (1, 1);
-		let q = get_quadrants(min_xy, max_xy);
+		let q = get_quadrants(min_xy, max_xy).unwrap();
 		assert_eq!(Quadrants::AllQuadrants, q);
 	}
 	#[test]
 	fn top_pair_ensure_valid_zero() {
 		let min_xy: (i32, i32) = (-1, 0);
 		let max_xy: (i32, i32) = (1, 1);
-		let q = get_quadrants(min_xy, max_xy);
+		let q = get_quadrants(min_xy, max_xy).unwrap();
 		assert_eq!(Quadrants::TopPair, q);
 	}
 	#[test]
 	fn top_pair() {
 		let min_xy: (i32, i32) = (-1, 1);
 		let max_xy: (i32, i32) = (1, 2);
-		let q = get_quadrants(min_xy, max_xy);
+		let q = get_quadrants(min_xy, max_xy).unwrap();
 		assert_eq!(Quadrants::TopPair, q);
 	}
 	#[test]
 	fn bottom_pair_ensure_valid_zero() {
 		let min_xy: (i32, i32) = (-1, -1);
 		let max_xy: (i32, i32) = (1, 0);
-		let q = get_quadrants(min_xy, max_xy);
+		let q = get_quadrants(min_xy, max_xy).unwrap();
 		assert_eq!(Quadrants::BottomPair, q);
 	}
 	#[test]
 	fn bottom_pair() {
 		let min_xy: (i32, i32) = (-1, -1);
 		let max_xy: (i32, i32) = (1, -2);
-		let q = get_quadrants(min_xy, max_xy);
+		let q = get_quadrants(min_xy, max_xy).unwrap();
 		assert_eq!(Quadrants::BottomPair, q);
 	}
 	#[test]
 	fn left_pair_ensure_valid_zero() {
 		let min_xy: (i32, i32) = (-1, -1);
 		let max_xy: (i32, i32) = (0, 1);
-		let q = get_quadrants(min_xy, max_xy);
+		let q = get_quadrants(min_xy, max_xy).unwrap();
 		assert_eq!(Quadrants::LeftPair, q);
 	}
 	#[test]
 	fn left_pair() {
 		let min_xy: (i32, i32) = (-2, -1);
 		let max_xy: (i32, i32) = (-1, 1);
-		let q = get_quadrants(min_xy, max_xy);
+		let q = get_quadrants(min_xy, max_xy).unwrap();
 		assert_eq!(Quadrants::LeftPair, q);
 	}
 	#[test]
 	fn right_pair_ensure_valid_zero() {
 		let min_xy: (i32, i32) = (0, -1);
 		let max_xy: (i32, i32) = (1, 1);
-		let q = get_quadrants(min_xy, max_xy);
+		let q = get_quadrants(min_xy, max_xy).unwrap();
 		assert_eq!(Quadrants::RightPair, q);
 	}
 	#[test]
 	fn right_pair() {
 		let min_xy: (i32, i32) = (1, -1);
 		let max_xy: (i32, i32) = (2, 1);
-		let q = get_quadrants(min_xy, max_xy);
+		let q = get_quadrants(min_xy, max_xy).unwrap();
 		assert_eq!(Quadrants::RightPair, q);
 	}
 	#[test]
 	fn top_right_ensure_zero() {
 		let min_xy: (i32, i32) = (0, 0);
 		let max_xy: (i32, i32) = (1, 1);
-		let q = get_quadrants(min_xy, max_xy);
+		let q = get_quadrants(min_xy, max_xy).unwrap();
 		assert_eq!(Quadrants::TopRight, q);
 	}
 	#[test]
 	fn top_right_ensure_zero2() {
 		let min_xy: (i32, i32) = (1, 0);
 		let max_xy: (i32, i32) = (2, 1);
-		let q = get_quadrants(min_xy, max_xy);
+		let q = get_quadrants(min_xy, max_xy).unwrap();
 		assert_eq!(Quadrants::TopRight, q);
 	}
 	#[test]
 	fn top_right_ensure_zero3() {
 		let min_xy: (i32, i32) = (0, 1);
 		let max_xy: (i32, i32) = (1, 2);
-		let q = get_quadrants(min_xy, max_xy);
+		let q = get_quadrants(min_xy, max_xy).unwrap();
 		assert_eq!(Quadrants::TopRight, q);
 	}
 	#[test]
 	fn top_right() {
 		let min_xy: (i32, i32) = (1, 1);
 		let max_xy: (i32, i32) = (2, 2);
-		let q = get_quadrants(min_xy, max_xy);
+		let q = get_quadrants(min_xy, max_xy).unwrap();
 		assert_eq!(Quadrants::TopRight, q);
 	}
 	#[test]
 	fn top_left_ensure_zero() {
 		let min_xy: (i32, i32) = (-1, 0);
 		let max_xy: (i32, i32) = (0, 1);
-		let q = get_quadrants(min_xy, max_xy);
+		let q = get_quadrants(min_xy, max_xy).unwrap();
 		assert_eq!(Quadrants::TopLeft, q);
 	}
 	#[test]
 	fn top_left_ensure_zero2() {
 		let min_xy: (i32, i32) = (-2, 0);
 		let max_xy: (i32, i32) = (-1, 1);
-		let q = get_quadrants(min_xy, max_xy);
+		let q = get_quadrants(min_xy, max_xy).unwrap();
 		assert_eq!(Quadrants::TopLeft, q);
 	}
 	#[test]
 	fn top_left_ensure_zero3() {
 		let min_xy: (i32, i32) = (-1, 1);
 		let max_xy: (i32, i32) = (0, 2);
-		let q = get_quadrants(min_xy, max_xy);
+		let q = get_quadrants(min_xy, max_xy).unwrap();
 		assert_eq!(Quadrants::TopLeft, q);
 	}
 	#[test]
 	fn top_left() {
 		let min_xy: (i32, i32) = (-2, 1);
 		let max_xy: (i32, i32) = (-1, 2);
-		let q = get_quadrants(min_xy, max_xy);
+		let q = get_quadrants(min_xy, max_xy).unwrap();
 		assert_eq!(Quadrants::TopLeft, q);
 	}
 	#[test]
 	fn bottom_left_ensure_zero() {
 		let min_xy: (i32, i32) = (-1, -1);
 		let max_xy: (i32, i32) = (0, 0);
-		let q = get_quadrants(min_xy, max_xy);
+		let q = get_quadrants(min_xy, max_xy).unwrap();
 		assert_eq!(Quadrants::BottomLeft, q);
 	}
 	#[test]
 	fn bottom_left_ensure_zero2() {
 		let min_xy: (i32, i32) = (-2, -1);
 		let max_xy: (i32, i32) = (-1, 0);
-		let q = get_quadrants(min_xy, max_xy);
+		let q = get_quadrants(min_xy, max_xy).unwrap();
 		assert_eq!(Quadrants::BottomLeft, q);
 	}
 	#[test]
 	fn bottom_left_ensure_zero3() {
 		let min_xy: (i32, i32) = (-1, -2);
 		let max_xy: (i32, i32) = (0, -1);
-		let q = get_quadrants(min_xy, max_xy);
+		let q = get_quadrants(min_xy, max_xy).unwrap();
 		assert_eq!(Quadrants::BottomLeft, q);
 	}
 	#[test]
 	fn bottom_left() {
 		let min_xy: (i32, i32) = (-2, -2);
 		let max_xy: (i32, i32) = (-1, -1);
-		let q = get_quadrants(min_xy, max_xy);
+		let q = get_quadrants(min_xy, max_xy).unwrap();
 		assert_eq!(Quadrants::BottomLeft, q);
 	}
 	#[test]
 	fn bottom_right_ensure_zero() {
 		let min_xy: (i32, i32) = (0, -1);
 		let max_xy: (i32, i32) = (1, 0);
-		let q = get_quadrants(min_xy, max_xy);
+		let q = get_quadrants(min_xy, max_xy).unwrap();
 		assert_eq!(Quadrants::BottomRight, q);
 	}
 	#[test]
 	fn bottom_right_ensure_zero2() {
 		let min_xy: (i32, i32) = (1, -1);
 		let max_xy: (i32, i32) = (2, 0);
-		let q = get_quadrants(min_xy, max_xy);
+		let q = get_quadrants(min_xy, max_xy).unwrap();
 		assert_eq!(Quadrants::BottomRight, q);
 	}
 	#[test]
 	fn bottom_right_ensure_zero3() {
 		let min_xy: (i32, i32) = (0, -2);
 		let max_xy: (i32, i32) = (1, -1);
-		let q = get_quadrants(min_xy, max_xy);
+		let q = get_quadrants(min_xy, max_xy).unwrap();
 		assert_eq!(Quadrants::BottomRight, q);
 	}
 	#[test]
 	fn bottom_right() {
 		let min_xy: (i32, i32) = (1, -2);
 		let max_xy: (i32, i32) = (2, -1);
-		let q = get_quadrants(min_xy, max_xy);
+		let q = get_quadrants(min_xy, max_xy).unwrap();
 		assert_eq!(Quadrants::BottomRight, q);
 	}
+	#[test]
+	fn zero_height_y_is_an_error() {
+		let min_xy: (i32, i32) = (0, 0);
+		let max_xy: (i32, i32) = (2, 0);
+		assert!(get_quadrants(min_xy, max_xy).is_err());
+	}
 }
@@ -1,7 +1,7 @@
 //! Draws a title onto a canvas
 
 use image::{ImageBuffer, Rgba};
-use rusttype::PositionedGlyph;
+use rusttype::{Font, PositionedGlyph};
 use tracing::debug;
 
 use crate::{
@@ -10,33 +10,33 @@ use crate::{
 		VHConsumedCanvasSpace, CANVAS_BORDER_PIXELS,
 	},
 	colours::*,
-	get_system_font,
+	error::PlotError,
 };
 
 use super::glyphs::{create_glyphs, draw_glyphs};
 
 /// Draws the title of the graph onto the canvas, returns the amount of vertical pixel space occupied
-/// from the top of the canvas with an additional buffer of `CANVAS_BORDER_PIXELS`
+/// from the top of the canvas with an additional buffer of `CANVAS_BORDER_PIXELS`. `scale` is the factor
+/// the canvas is supersampled by
 pub fn build_title(
 	canvas: &mut ImageBuffer<Rgba<u8>, Vec<u8>>,
 	title: &str,
+	font: &Font,
 	font_size: f32,
-) -> VHConsumedCanvasSpace {
-	let font = get_system_font();
-	let glyphs: Vec<PositionedGlyph> = create_glyphs(font_size, title, &font);
+	scale: u32,
+) -> Result<VHConsumedCanvasSpace, PlotError> {
+	let border = CANVAS_BORDER_PIXELS * scale;
+	let glyphs: Vec<PositionedGlyph> = create_glyphs(font_size, title, font);
 	let height = get_maximum_height_of_glyphs(&glyphs);
 	let width = get_width_of_glyphs(&glyphs);
 	// position the title in the middle
-	let position: (u32, u32) = (
-		(canvas.dimensions().0 / 2) - (width as u32 / 2),
-		CANVAS_BORDER_PIXELS,
-	);
+	let position: (u32, u32) = ((canvas.dimensions().0 / 2) - (width / 2), border);
 	debug!("Title position {:?}", position);
 	draw_glyphs(canvas, BLACK, glyphs, position);
-	VHConsumedCanvasSpace {
-		v_space_from_top: position.1 + height + CANVAS_BORDER_PIXELS,
+	Ok(VHConsumedCanvasSpace {
+		v_space_from_top: position.1 + height + border,
 		h_space_from_left: 0,
 		v_space_from_bottom: 0,
 		h_space_from_right: 0,
-	}
+	})
 }
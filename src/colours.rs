@@ -1,12 +1,21 @@
 //! Shorthand constants for common colours
 
-use serde::Deserialize;
+use serde::{
+	de::{self, EnumAccess, SeqAccess, VariantAccess, Visitor},
+	ser::SerializeTupleVariant,
+	Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::fmt;
 /// Rgba White
 pub const WHITE: [u8; 4] = [255, 255, 255, 255];
 /// Rgba Black
 pub const BLACK: [u8; 4] = [0, 0, 0, 255];
 /// Rgba Grey
 pub const GREY: [u8; 4] = [161, 161, 161, 255];
+/// Rgba Dark Grey
+pub const DARK_GREY: [u8; 4] = [96, 96, 96, 255];
+/// Rgba Light Grey
+pub const LIGHT_GREY: [u8; 4] = [221, 221, 221, 255];
 /// Rgba Orange
 pub const ORANGE: [u8; 4] = [255, 146, 0, 255];
 /// Rgba Red
@@ -19,7 +28,7 @@ pub const GREEN: [u8; 4] = [0, 255, 0, 255];
 pub const PINK: [u8; 4] = [255, 169, 208, 255];
 
 /// Colours that can be used to plot data points
-#[derive(Debug, Deserialize, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 #[allow(clippy::missing_docs_in_private_items)]
 pub enum Colour {
 	White,
@@ -30,8 +39,50 @@ pub enum Colour {
 	Blue,
 	Green,
 	Pink,
+	/// Any colour, written in a config as `Hex("#rrggbb")` or with an alpha channel as `Hex("#rrggbbaa")`
+	Hex([u8; 4]),
+	/// Any opaque colour given by its red, green and blue channels, e.g `Rgb(31, 119, 180)`
+	Rgb(u8, u8, u8),
+	/// Any colour given by its red, green, blue and alpha channels, e.g `Rgba(255, 0, 0, 128)`, an alpha
+	/// below 255 lets whatever is drawn beneath show through
+	Rgba(u8, u8, u8, u8),
+	/// One of the CSS colour names, e.g `DarkSlateBlue`, see [`Colour::from_name`]
+	Named(NamedColour),
 }
 
+/// A colour from [`CSS_COLOURS`], only created by looking up its name
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct NamedColour {
+	/// The name as written in [`CSS_COLOURS`]
+	name: &'static str,
+	/// The colour of the name
+	rgba: [u8; 4],
+}
+
+impl NamedColour {
+	/// The name of the colour as written in [`CSS_COLOURS`]
+	pub fn name(&self) -> &'static str {
+		self.name
+	}
+}
+
+/// Every variant of [`Colour`] in declaration order
+const COLOUR_VARIANTS: [&str; 11] = [
+	"White", "Black", "Grey", "Orange", "Red", "Blue", "Green", "Pink", "Hex", "Rgb", "Rgba",
+];
+
+/// The colours written by name alone, in declaration order
+const NAMED_COLOURS: [(&str, Colour); 8] = [
+	("White", Colour::White),
+	("Black", Colour::Black),
+	("Grey", Colour::Grey),
+	("Orange", Colour::Orange),
+	("Red", Colour::Red),
+	("Blue", Colour::Blue),
+	("Green", Colour::Green),
+	("Pink", Colour::Pink),
+];
+
 impl Colour {
 	/// Get the `u8` representation of a colour that should be used when drawing data points
 	pub fn get_pixel_colour(colour: Colour) -> [u8; 4] {
@@ -44,6 +95,689 @@ impl Colour {
 			Colour::Blue => BLUE,
 			Colour::Green => GREEN,
 			Colour::Pink => PINK,
+			Colour::Hex(rgba) => rgba,
+			Colour::Rgb(r, g, b) => [r, g, b, 255],
+			Colour::Rgba(r, g, b, a) => [r, g, b, a],
+			Colour::Named(named) => named.rgba,
+		}
+	}
+	/// Look up a colour by name ignoring case. The eight original colours keep their own shades while
+	/// every other name comes from [`CSS_COLOURS`], an unknown name is described along with the closest
+	/// known name when there is one that is similar
+	pub fn from_name(name: &str) -> Result<Colour, String> {
+		let lowercase = name.to_lowercase();
+		if let Some((_, colour)) = NAMED_COLOURS
+			.iter()
+			.find(|(known, _)| known.to_lowercase() == lowercase)
+		{
+			return Ok(*colour);
+		}
+		if let Some((known, rgba)) = CSS_COLOURS
+			.iter()
+			.find(|(known, _)| known.to_lowercase() == lowercase)
+		{
+			return Ok(Colour::Named(NamedColour {
+				name: known,
+				rgba: *rgba,
+			}));
+		}
+		let closest = NAMED_COLOURS
+			.iter()
+			.map(|(known, _)| *known)
+			.chain(CSS_COLOURS.iter().map(|(known, _)| *known))
+			.map(|known| (edit_distance(&lowercase, &known.to_lowercase()), known))
+			.min_by_key(|(distance, _)| *distance);
+		match closest {
+			Some((distance, known)) if distance <= (lowercase.chars().count() / 3).max(2) => Err(
+				format!("unknown colour \"{}\", did you mean \"{}\"?", name, known),
+			),
+			_ => Err(format!(
+				"unknown colour \"{}\", expected a colour name, Hex, Rgb or Rgba",
+				name
+			)),
+		}
+	}
+	/// The colour made more transparent by an `opacity` from 0 to 1 applied on top of its own alpha
+	pub fn with_opacity(self, opacity: f32) -> Colour {
+		if opacity >= 1.0 {
+			return self;
+		}
+		let [r, g, b, a] = Colour::get_pixel_colour(self);
+		Colour::Rgba(r, g, b, (a as f32 * opacity.max(0.0)).round() as u8)
+	}
+	/// Parse a hex colour of the form `#rrggbb`, or `#rrggbbaa` with an alpha channel, the `#` is optional
+	pub fn from_hex(text: &str) -> Result<[u8; 4], String> {
+		let digits = text.strip_prefix('#').unwrap_or(text);
+		let invalid = || {
+			format!(
+				"\"{}\" is not a hex colour, expected \"#rrggbb\" or \"#rrggbbaa\"",
+				text
+			)
+		};
+		if !(digits.len() == 6 || digits.len() == 8) || !digits.is_ascii() {
+			return Err(invalid());
+		}
+		let mut rgba = [255; 4];
+		for (channel, i) in rgba.iter_mut().zip((0..digits.len()).step_by(2)) {
+			*channel = u8::from_str_radix(&digits[i..i + 2], 16).map_err(|_| invalid())?;
+		}
+		Ok(rgba)
+	}
+	/// Write a colour as `#rrggbb`, adding the alpha channel only when it is not opaque
+	fn to_hex(rgba: [u8; 4]) -> String {
+		let hex = format!("#{:02x}{:02x}{:02x}", rgba[0], rgba[1], rgba[2]);
+		match rgba[3] {
+			255 => hex,
+			alpha => format!("{}{:02x}", hex, alpha),
+		}
+	}
+}
+
+impl Serialize for Colour {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let channels = match *self {
+			Colour::Hex(rgba) => {
+				return serializer.serialize_newtype_variant(
+					"Colour",
+					8,
+					"Hex",
+					&Colour::to_hex(rgba),
+				)
+			}
+			Colour::Rgb(r, g, b) => (9, vec![r, g, b]),
+			Colour::Rgba(r, g, b, a) => (10, vec![r, g, b, a]),
+			Colour::Named(named) => {
+				return serializer.serialize_unit_variant("Colour", 11, named.name)
+			}
+			named => {
+				let index = NAMED_COLOURS
+					.iter()
+					.position(|(_, colour)| *colour == named)
+					.unwrap_or_default();
+				return serializer.serialize_unit_variant(
+					"Colour",
+					index as u32,
+					COLOUR_VARIANTS[index],
+				);
+			}
+		};
+		let (index, values) = channels;
+		let mut variant = serializer.serialize_tuple_variant(
+			"Colour",
+			index,
+			COLOUR_VARIANTS[index as usize],
+			values.len(),
+		)?;
+		for value in values {
+			variant.serialize_field(&value)?;
+		}
+		variant.end()
+	}
+}
+
+impl<'de> Deserialize<'de> for Colour {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		deserializer.deserialize_enum("Colour", &COLOUR_VARIANTS, ColourVisitor)
+	}
+}
+
+/// The name of a variant, read as an identifier so that ron accepts it unquoted
+struct VariantName(String);
+
+impl<'de> Deserialize<'de> for VariantName {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		/// Reads the name of a variant
+		struct NameVisitor;
+		impl<'de> Visitor<'de> for NameVisitor {
+			type Value = VariantName;
+			fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+				formatter.write_str("the name of a colour")
+			}
+			fn visit_str<E: de::Error>(self, name: &str) -> Result<Self::Value, E> {
+				Ok(VariantName(name.to_owned()))
+			}
+		}
+		deserializer.deserialize_identifier(NameVisitor)
+	}
+}
+
+/// Reads the channels of an `Rgb` or `Rgba` colour, an omitted alpha is opaque
+struct ChannelsVisitor(usize);
+
+impl<'de> Visitor<'de> for ChannelsVisitor {
+	type Value = [u8; 4];
+	fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		write!(formatter, "{} channels from 0 to 255", self.0)
+	}
+	fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+		let mut rgba = [255; 4];
+		for (i, channel) in rgba.iter_mut().take(self.0).enumerate() {
+			*channel = seq
+				.next_element()?
+				.ok_or_else(|| de::Error::invalid_length(i, &self))?;
+		}
+		Ok(rgba)
+	}
+}
+
+/// Reads a named colour or one given by value, parsing a hex string as it is read
+struct ColourVisitor;
+
+impl<'de> Visitor<'de> for ColourVisitor {
+	type Value = Colour;
+	fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		formatter.write_str("a colour name, Hex(\"#rrggbb\"), Rgb(r, g, b) or Rgba(r, g, b, a)")
+	}
+	fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+		let (VariantName(name), variant) = data.variant()?;
+		match name.as_str() {
+			"Hex" => {
+				let text: String = variant.newtype_variant()?;
+				Colour::from_hex(&text)
+					.map(Colour::Hex)
+					.map_err(de::Error::custom)
+			}
+			"Rgb" => {
+				let [r, g, b, _] = variant.tuple_variant(3, ChannelsVisitor(3))?;
+				Ok(Colour::Rgb(r, g, b))
+			}
+			"Rgba" => {
+				let [r, g, b, a] = variant.tuple_variant(4, ChannelsVisitor(4))?;
+				Ok(Colour::Rgba(r, g, b, a))
+			}
+			_ => {
+				let named = Colour::from_name(&name).map_err(de::Error::custom)?;
+				variant.unit_variant()?;
+				Ok(named)
+			}
+		}
+	}
+}
+
+/// The number of single character insertions, deletions and substitutions needed to turn `from` into `to`
+fn edit_distance(from: &str, to: &str) -> usize {
+	let to: Vec<char> = to.chars().collect();
+	let mut previous: Vec<usize> = (0..=to.len()).collect();
+	for (i, a) in from.chars().enumerate() {
+		let mut current = vec![i + 1];
+		for (j, b) in to.iter().enumerate() {
+			let substitution = previous[j] + usize::from(a != *b);
+			current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
 		}
+		previous = current;
+	}
+	previous[to.len()]
+}
+
+/// The CSS colour names besides the eight original colours, which keep their own shades
+pub const CSS_COLOURS: [(&str, [u8; 4]); 140] = [
+	("AliceBlue", [240, 248, 255, 255]),
+	("AntiqueWhite", [250, 235, 215, 255]),
+	("Aqua", [0, 255, 255, 255]),
+	("Aquamarine", [127, 255, 212, 255]),
+	("Azure", [240, 255, 255, 255]),
+	("Beige", [245, 245, 220, 255]),
+	("Bisque", [255, 228, 196, 255]),
+	("BlanchedAlmond", [255, 235, 205, 255]),
+	("BlueViolet", [138, 43, 226, 255]),
+	("Brown", [165, 42, 42, 255]),
+	("BurlyWood", [222, 184, 135, 255]),
+	("CadetBlue", [95, 158, 160, 255]),
+	("Chartreuse", [127, 255, 0, 255]),
+	("Chocolate", [210, 105, 30, 255]),
+	("Coral", [255, 127, 80, 255]),
+	("CornflowerBlue", [100, 149, 237, 255]),
+	("Cornsilk", [255, 248, 220, 255]),
+	("Crimson", [220, 20, 60, 255]),
+	("Cyan", [0, 255, 255, 255]),
+	("DarkBlue", [0, 0, 139, 255]),
+	("DarkCyan", [0, 139, 139, 255]),
+	("DarkGoldenRod", [184, 134, 11, 255]),
+	("DarkGray", [169, 169, 169, 255]),
+	("DarkGrey", [169, 169, 169, 255]),
+	("DarkGreen", [0, 100, 0, 255]),
+	("DarkKhaki", [189, 183, 107, 255]),
+	("DarkMagenta", [139, 0, 139, 255]),
+	("DarkOliveGreen", [85, 107, 47, 255]),
+	("DarkOrange", [255, 140, 0, 255]),
+	("DarkOrchid", [153, 50, 204, 255]),
+	("DarkRed", [139, 0, 0, 255]),
+	("DarkSalmon", [233, 150, 122, 255]),
+	("DarkSeaGreen", [143, 188, 143, 255]),
+	("DarkSlateBlue", [72, 61, 139, 255]),
+	("DarkSlateGray", [47, 79, 79, 255]),
+	("DarkSlateGrey", [47, 79, 79, 255]),
+	("DarkTurquoise", [0, 206, 209, 255]),
+	("DarkViolet", [148, 0, 211, 255]),
+	("DeepPink", [255, 20, 147, 255]),
+	("DeepSkyBlue", [0, 191, 255, 255]),
+	("DimGray", [105, 105, 105, 255]),
+	("DimGrey", [105, 105, 105, 255]),
+	("DodgerBlue", [30, 144, 255, 255]),
+	("FireBrick", [178, 34, 34, 255]),
+	("FloralWhite", [255, 250, 240, 255]),
+	("ForestGreen", [34, 139, 34, 255]),
+	("Fuchsia", [255, 0, 255, 255]),
+	("Gainsboro", [220, 220, 220, 255]),
+	("GhostWhite", [248, 248, 255, 255]),
+	("Gold", [255, 215, 0, 255]),
+	("GoldenRod", [218, 165, 32, 255]),
+	("Gray", [128, 128, 128, 255]),
+	("GreenYellow", [173, 255, 47, 255]),
+	("HoneyDew", [240, 255, 240, 255]),
+	("HotPink", [255, 105, 180, 255]),
+	("IndianRed", [205, 92, 92, 255]),
+	("Indigo", [75, 0, 130, 255]),
+	("Ivory", [255, 255, 240, 255]),
+	("Khaki", [240, 230, 140, 255]),
+	("Lavender", [230, 230, 250, 255]),
+	("LavenderBlush", [255, 240, 245, 255]),
+	("LawnGreen", [124, 252, 0, 255]),
+	("LemonChiffon", [255, 250, 205, 255]),
+	("LightBlue", [173, 216, 230, 255]),
+	("LightCoral", [240, 128, 128, 255]),
+	("LightCyan", [224, 255, 255, 255]),
+	("LightGoldenRodYellow", [250, 250, 210, 255]),
+	("LightGray", [211, 211, 211, 255]),
+	("LightGrey", [211, 211, 211, 255]),
+	("LightGreen", [144, 238, 144, 255]),
+	("LightPink", [255, 182, 193, 255]),
+	("LightSalmon", [255, 160, 122, 255]),
+	("LightSeaGreen", [32, 178, 170, 255]),
+	("LightSkyBlue", [135, 206, 250, 255]),
+	("LightSlateGray", [119, 136, 153, 255]),
+	("LightSlateGrey", [119, 136, 153, 255]),
+	("LightSteelBlue", [176, 196, 222, 255]),
+	("LightYellow", [255, 255, 224, 255]),
+	("Lime", [0, 255, 0, 255]),
+	("LimeGreen", [50, 205, 50, 255]),
+	("Linen", [250, 240, 230, 255]),
+	("Magenta", [255, 0, 255, 255]),
+	("Maroon", [128, 0, 0, 255]),
+	("MediumAquaMarine", [102, 205, 170, 255]),
+	("MediumBlue", [0, 0, 205, 255]),
+	("MediumOrchid", [186, 85, 211, 255]),
+	("MediumPurple", [147, 112, 219, 255]),
+	("MediumSeaGreen", [60, 179, 113, 255]),
+	("MediumSlateBlue", [123, 104, 238, 255]),
+	("MediumSpringGreen", [0, 250, 154, 255]),
+	("MediumTurquoise", [72, 209, 204, 255]),
+	("MediumVioletRed", [199, 21, 133, 255]),
+	("MidnightBlue", [25, 25, 112, 255]),
+	("MintCream", [245, 255, 250, 255]),
+	("MistyRose", [255, 228, 225, 255]),
+	("Moccasin", [255, 228, 181, 255]),
+	("NavajoWhite", [255, 222, 173, 255]),
+	("Navy", [0, 0, 128, 255]),
+	("OldLace", [253, 245, 230, 255]),
+	("Olive", [128, 128, 0, 255]),
+	("OliveDrab", [107, 142, 35, 255]),
+	("OrangeRed", [255, 69, 0, 255]),
+	("Orchid", [218, 112, 214, 255]),
+	("PaleGoldenRod", [238, 232, 170, 255]),
+	("PaleGreen", [152, 251, 152, 255]),
+	("PaleTurquoise", [175, 238, 238, 255]),
+	("PaleVioletRed", [219, 112, 147, 255]),
+	("PapayaWhip", [255, 239, 213, 255]),
+	("PeachPuff", [255, 218, 185, 255]),
+	("Peru", [205, 133, 63, 255]),
+	("Plum", [221, 160, 221, 255]),
+	("PowderBlue", [176, 224, 230, 255]),
+	("Purple", [128, 0, 128, 255]),
+	("RebeccaPurple", [102, 51, 153, 255]),
+	("RosyBrown", [188, 143, 143, 255]),
+	("RoyalBlue", [65, 105, 225, 255]),
+	("SaddleBrown", [139, 69, 19, 255]),
+	("Salmon", [250, 128, 114, 255]),
+	("SandyBrown", [244, 164, 96, 255]),
+	("SeaGreen", [46, 139, 87, 255]),
+	("SeaShell", [255, 245, 238, 255]),
+	("Sienna", [160, 82, 45, 255]),
+	("Silver", [192, 192, 192, 255]),
+	("SkyBlue", [135, 206, 235, 255]),
+	("SlateBlue", [106, 90, 205, 255]),
+	("SlateGray", [112, 128, 144, 255]),
+	("SlateGrey", [112, 128, 144, 255]),
+	("Snow", [255, 250, 250, 255]),
+	("SpringGreen", [0, 255, 127, 255]),
+	("SteelBlue", [70, 130, 180, 255]),
+	("Tan", [210, 180, 140, 255]),
+	("Teal", [0, 128, 128, 255]),
+	("Thistle", [216, 191, 216, 255]),
+	("Tomato", [255, 99, 71, 255]),
+	("Turquoise", [64, 224, 208, 255]),
+	("Violet", [238, 130, 238, 255]),
+	("Wheat", [245, 222, 179, 255]),
+	("WhiteSmoke", [245, 245, 245, 255]),
+	("Yellow", [255, 255, 0, 255]),
+	("YellowGreen", [154, 205, 50, 255]),
+];
+
+/// A gradient of colours that values are mapped along
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq)]
+pub enum Colormap {
+	/// Dark purple through blue and green to yellow, even in lightness so that it reads in greyscale
+	Viridis,
+	/// Blue through light grey to red, for values either side of a midpoint
+	BlueRed,
+}
+
+impl Colormap {
+	/// The colours spaced evenly along the gradient, between which colours are interpolated
+	fn stops(self) -> &'static [[u8; 3]] {
+		match self {
+			Colormap::Viridis => &[
+				[68, 1, 84],
+				[59, 82, 139],
+				[33, 145, 140],
+				[94, 201, 98],
+				[253, 231, 37],
+			],
+			Colormap::BlueRed => &[[59, 76, 192], [221, 221, 221], [180, 4, 38]],
+		}
+	}
+	/// The colour a fraction `t` of the way along the gradient, clamped between its ends
+	pub fn sample(self, t: f32) -> Colour {
+		let stops = self.stops();
+		let position = t.clamp(0.0, 1.0) * (stops.len() - 1) as f32;
+		// the last stop is reached at t = 1 and has nothing after it to blend towards
+		let i = (position as usize).min(stops.len() - 2);
+		let fraction = position - i as f32;
+		let channel = |c: usize| {
+			let (from, to) = (stops[i][c] as f32, stops[i + 1][c] as f32);
+			(from + (to - from) * fraction).round() as u8
+		};
+		Colour::Rgb(channel(0), channel(1), channel(2))
+	}
+}
+
+/// Colour each point of a data set by a value in its csv row, mapped along a colormap
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq)]
+pub struct ColourBy {
+	/// The zero indexed column holding the value of each point
+	pub csv_column: usize,
+	/// The gradient the values are mapped along, from the smallest value to the largest
+	pub colormap: Colormap,
+}
+
+/// Colours handed out in turn to data sets which don't specify one
+pub const COLOUR_CYCLE: [Colour; 7] = [
+	Colour::Orange,
+	Colour::Blue,
+	Colour::Red,
+	Colour::Green,
+	Colour::Pink,
+	Colour::Grey,
+	Colour::Black,
+];
+
+/// The Okabe–Ito palette, distinguishable under the common forms of colour blindness
+pub const OKABE_ITO: [Colour; 8] = [
+	Colour::Rgb(230, 159, 0),
+	Colour::Rgb(86, 180, 233),
+	Colour::Rgb(0, 158, 115),
+	Colour::Rgb(240, 228, 66),
+	Colour::Rgb(0, 114, 178),
+	Colour::Rgb(213, 94, 0),
+	Colour::Rgb(204, 121, 167),
+	Colour::Rgb(0, 0, 0),
+];
+
+/// Greys stepped in luminance from black, stopping short of white so points stay visible on the background
+pub const GRAYSCALE: [Colour; 5] = [
+	Colour::Rgb(0, 0, 0),
+	Colour::Rgb(60, 60, 60),
+	Colour::Rgb(100, 100, 100),
+	Colour::Rgb(140, 140, 140),
+	Colour::Rgb(180, 180, 180),
+];
+
+/// The pool of colours handed out to data sets which don't specify one
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Default)]
+pub enum Palette {
+	/// The `COLOUR_CYCLE`
+	#[default]
+	Default,
+	/// The Okabe–Ito palette
+	ColourBlindSafe,
+	/// Steps of grey, the symbol of each data set is also varied so they can be told apart in print
+	Grayscale,
+}
+
+impl Palette {
+	/// The colours cycled through in order
+	pub fn colours(self) -> &'static [Colour] {
+		match self {
+			Palette::Default => &COLOUR_CYCLE,
+			Palette::ColourBlindSafe => &OKABE_ITO,
+			Palette::Grayscale => &GRAYSCALE,
+		}
+	}
+}
+
+/// How colours from the palette are assigned to data sets without an explicit colour
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Default)]
+pub enum ColourAssignment {
+	/// The nth data set takes the nth colour of the cycle, or the next one free when it is already used by
+	/// another set. Adding or removing a set shifts the colour of every set after it
+	#[default]
+	Positional,
+	/// The colour is picked from a hash of the data set name so a set keeps its colour however the
+	/// other sets change. If the colour is already taken the next free colour in the cycle is used
+	NameHash,
+}
+
+impl ColourAssignment {
+	/// Resolve the colour of each data set from its name and optional explicit colour, returned in the
+	/// same order as the sets
+	pub fn assign(self, sets: &[(&str, Option<Colour>)], palette: &[Colour]) -> Vec<Colour> {
+		// explicit colours are reserved first so assigned sets avoid them
+		let mut taken: Vec<Colour> = sets.iter().filter_map(|(_, colour)| *colour).collect();
+		let mut colours = Vec::with_capacity(sets.len());
+		for (i, (name, colour)) in sets.iter().enumerate() {
+			if let Some(c) = colour {
+				colours.push(*c);
+				continue;
+			}
+			let start = match self {
+				ColourAssignment::Positional => i % palette.len(),
+				ColourAssignment::NameHash => (fnv1a(name) % palette.len() as u64) as usize,
+			};
+			// fall back to the starting colour when every colour has been taken
+			let assigned = (0..palette.len())
+				.map(|offset| palette[(start + offset) % palette.len()])
+				.find(|c| !taken.contains(c))
+				.unwrap_or(palette[start]);
+			taken.push(assigned);
+			colours.push(assigned);
+		}
+		colours
+	}
+}
+
+/// FNV-1a hash, used over the standard library hasher as its output is stable across releases and platforms
+fn fnv1a(text: &str) -> u64 {
+	let mut hash: u64 = 0xcbf29ce484222325;
+	for byte in text.bytes() {
+		hash ^= byte as u64;
+		hash = hash.wrapping_mul(0x100000001b3);
+	}
+	hash
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::colours::{
+		Colormap, Colour, ColourAssignment, Palette, COLOUR_CYCLE, GRAYSCALE, OKABE_ITO, ORANGE,
+	};
+	#[test]
+	fn positional_follows_cycle() {
+		let colours = ColourAssignment::Positional.assign(
+			&[("a", None), ("b", Some(Colour::Pink)), ("c", None)],
+			&COLOUR_CYCLE,
+		);
+		assert_eq!(
+			vec![COLOUR_CYCLE[0], Colour::Pink, COLOUR_CYCLE[2]],
+			colours
+		);
+	}
+	#[test]
+	fn positional_skips_colours_used_by_other_sets() {
+		let colours = ColourAssignment::Positional.assign(
+			&[
+				("a", None),
+				("b", Some(COLOUR_CYCLE[0])),
+				("c", None),
+				("d", None),
+			],
+			&COLOUR_CYCLE,
+		);
+		assert_eq!(
+			vec![
+				COLOUR_CYCLE[1],
+				COLOUR_CYCLE[0],
+				COLOUR_CYCLE[2],
+				COLOUR_CYCLE[3]
+			],
+			colours
+		);
+	}
+	#[test]
+	fn name_hash_independent_of_position() {
+		let alone = ColourAssignment::NameHash.assign(&[("temperature", None)], &COLOUR_CYCLE);
+		let last = ColourAssignment::NameHash
+			.assign(&[("pressure", None), ("temperature", None)], &COLOUR_CYCLE);
+		assert_eq!(alone[0], last[1]);
+	}
+	#[test]
+	fn name_hash_independent_of_unrelated_sets() {
+		let before = ColourAssignment::NameHash
+			.assign(&[("temperature", None), ("humidity", None)], &COLOUR_CYCLE);
+		let after = ColourAssignment::NameHash.assign(
+			&[
+				("temperature", None),
+				("wind", Some(Colour::Black)),
+				("humidity", None),
+			],
+			&COLOUR_CYCLE,
+		);
+		assert_eq!(before[0], after[0]);
+		assert_eq!(before[1], after[2]);
+	}
+	#[test]
+	fn name_hash_collision_takes_next_free_colour() {
+		let colours =
+			ColourAssignment::NameHash.assign(&[("same", None), ("same", None)], &COLOUR_CYCLE);
+		assert_ne!(colours[0], colours[1]);
+	}
+	#[test]
+	fn name_hash_avoids_explicit_colours() {
+		let hashed = ColourAssignment::NameHash.assign(&[("temperature", None)], &COLOUR_CYCLE)[0];
+		let colours = ColourAssignment::NameHash.assign(
+			&[("temperature", None), ("other", Some(hashed))],
+			&COLOUR_CYCLE,
+		);
+		assert_ne!(hashed, colours[0]);
+		assert_eq!(hashed, colours[1]);
+	}
+	#[test]
+	fn opacity_scales_the_alpha_of_a_colour() {
+		assert_eq!(Colour::Blue, Colour::Blue.with_opacity(1.0));
+		assert_eq!(Colour::Rgba(0, 0, 255, 128), Colour::Blue.with_opacity(0.5));
+		assert_eq!(
+			Colour::Rgba(255, 0, 0, 64),
+			Colour::Rgba(255, 0, 0, 128).with_opacity(0.5)
+		);
+	}
+	#[test]
+	fn hex_colours_are_parsed() {
+		assert_eq!(Ok([31, 119, 180, 255]), Colour::from_hex("#1f77b4"));
+		assert_eq!(Ok([255, 0, 0, 128]), Colour::from_hex("FF000080"));
+		for invalid in ["#1f77b", "#1f77b4f", "#gg77b4", "#1f77b4ff00", "#1f77é"] {
+			assert!(Colour::from_hex(invalid).is_err(), "{}", invalid);
+		}
+	}
+	#[test]
+	fn colours_round_trip_through_ron() {
+		for (text, colour) in [
+			("Orange", Colour::Orange),
+			("Hex(\"#1f77b4\")", Colour::Hex([31, 119, 180, 255])),
+			("Hex(\"#ff000080\")", Colour::Hex([255, 0, 0, 128])),
+			("Rgb(31,119,180)", Colour::Rgb(31, 119, 180)),
+			("Rgba(255,0,0,128)", Colour::Rgba(255, 0, 0, 128)),
+		] {
+			assert_eq!(colour, ron::from_str::<Colour>(text).unwrap());
+			assert_eq!(text, ron::to_string(&colour).unwrap());
+		}
+		assert_eq!(ORANGE, Colour::get_pixel_colour(Colour::Orange));
+		let error = ron::from_str::<Colour>("Hex(\"#1f77\")").unwrap_err();
+		assert!(
+			error.to_string().contains("\"#1f77\" is not a hex colour"),
+			"{}",
+			error
+		);
+		assert!(ron::from_str::<Colour>("Vermillion").is_err());
+		assert!(ron::from_str::<Colour>("Rgb(31, 119)").is_err());
+		assert!(ron::from_str::<Colour>("Rgb(31, 119, 256)").is_err());
+		assert_eq!(
+			[255, 0, 0, 128],
+			Colour::get_pixel_colour(ron::from_str("Rgba(255, 0, 0, 128)").unwrap())
+		);
+	}
+	#[test]
+	fn css_colours_are_found_ignoring_case() {
+		for name in ["DarkSlateBlue", "darkslateblue", "DARKSLATEBLUE"] {
+			assert_eq!(
+				[72, 61, 139, 255],
+				Colour::get_pixel_colour(Colour::from_name(name).unwrap())
+			);
+		}
+		assert_eq!(Ok(Colour::Orange), Colour::from_name("orange"));
+		let colour: Colour = ron::from_str("rebeccapurple").unwrap();
+		assert_eq!("RebeccaPurple", ron::to_string(&colour).unwrap());
+		assert_eq!(colour, ron::from_str("RebeccaPurple").unwrap());
+	}
+	#[test]
+	fn unknown_colours_suggest_the_closest_name() {
+		let error = ron::from_str::<Colour>("DarkSlateBlu").unwrap_err();
+		assert!(
+			error
+				.to_string()
+				.contains("did you mean \"DarkSlateBlue\"?"),
+			"{}",
+			error
+		);
+		let error = Colour::from_name("Chartruese").unwrap_err();
+		assert!(error.contains("did you mean \"Chartreuse\"?"), "{}", error);
+		let error = Colour::from_name("Vermillionish").unwrap_err();
+		assert!(!error.contains("did you mean"), "{}", error);
+	}
+	#[test]
+	fn colormaps_interpolate_between_stops() {
+		assert_eq!(Colour::Rgb(68, 1, 84), Colormap::Viridis.sample(0.0));
+		assert_eq!(Colour::Rgb(253, 231, 37), Colormap::Viridis.sample(1.0));
+		assert_eq!(Colour::Rgb(221, 221, 221), Colormap::BlueRed.sample(0.5));
+		// halfway between the first two stops
+		assert_eq!(Colour::Rgb(140, 149, 207), Colormap::BlueRed.sample(0.25));
+		// values beyond the range take the colour of the nearest end
+		assert_eq!(Colormap::BlueRed.sample(1.0), Colormap::BlueRed.sample(3.0));
+		assert_eq!(
+			Colormap::BlueRed.sample(0.0),
+			Colormap::BlueRed.sample(-1.0)
+		);
+	}
+	#[test]
+	fn palettes_are_cycled_in_order() {
+		let sets = [("a", None), ("b", None), ("c", None)];
+		assert_eq!(
+			OKABE_ITO[..3].to_vec(),
+			ColourAssignment::Positional.assign(&sets, Palette::ColourBlindSafe.colours())
+		);
+		let sets: Vec<(&str, Option<Colour>)> = (0..6).map(|_| ("same", None)).collect();
+		let colours = ColourAssignment::Positional.assign(&sets, Palette::Grayscale.colours());
+		assert_eq!(GRAYSCALE.to_vec(), colours[..5].to_vec());
+		// with every grey taken the cycle wraps around
+		assert_eq!(GRAYSCALE[0], colours[5]);
+		assert_eq!(&COLOUR_CYCLE, Palette::default().colours());
 	}
 }
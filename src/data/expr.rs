@@ -0,0 +1,230 @@
+//! A small arithmetic expression language for deriving values from several csv columns, e.g
+//! `col(2) / col(3) * 100`
+//!
+//! Supports numbers, `col(n)` references to a zero-indexed csv column, `+`, `-`, `*`, `/`, unary minus and
+//! parentheses with the usual precedence
+
+use std::fmt;
+
+/// A parsed arithmetic expression which can be evaluated against each row of a csv
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expression {
+	/// A literal number
+	Number(f64),
+	/// The value of a zero-indexed csv column
+	Column(usize),
+	/// The negation of an expression
+	Negate(Box<Expression>),
+	/// Sum of two expressions
+	Add(Box<Expression>, Box<Expression>),
+	/// Difference of two expressions
+	Subtract(Box<Expression>, Box<Expression>),
+	/// Product of two expressions
+	Multiply(Box<Expression>, Box<Expression>),
+	/// Quotient of two expressions
+	Divide(Box<Expression>, Box<Expression>),
+}
+
+/// Describes why an expression could not be parsed
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpressionError {
+	/// Byte offset into the expression text where the problem was found
+	pub position: usize,
+	/// What was wrong
+	pub message: String,
+}
+
+impl fmt::Display for ExpressionError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} at position {}", self.message, self.position)
+	}
+}
+
+impl Expression {
+	/// Parse the text of an expression
+	pub fn parse(text: &str) -> Result<Expression, ExpressionError> {
+		let mut parser = Parser { text, position: 0 };
+		let expression = parser.parse_sum()?;
+		parser.skip_whitespace();
+		if parser.position < text.len() {
+			return Err(parser.error("Unexpected trailing characters"));
+		}
+		Ok(expression)
+	}
+	/// Evaluate the expression where `column` supplies the value of a csv column, in `f64` so that large
+	/// values such as timestamps keep their precision. Division by zero follows
+	/// IEEE rules so may produce an infinite or NaN result which callers should check for. The first error
+	/// returned by `column` is returned
+	pub fn evaluate<E, F: FnMut(usize) -> Result<f64, E>>(&self, column: &mut F) -> Result<f64, E> {
+		Ok(match self {
+			Expression::Number(n) => *n,
+			Expression::Column(c) => column(*c)?,
+			Expression::Negate(e) => -e.evaluate(column)?,
+			Expression::Add(a, b) => a.evaluate(column)? + b.evaluate(column)?,
+			Expression::Subtract(a, b) => a.evaluate(column)? - b.evaluate(column)?,
+			Expression::Multiply(a, b) => a.evaluate(column)? * b.evaluate(column)?,
+			Expression::Divide(a, b) => a.evaluate(column)? / b.evaluate(column)?,
+		})
+	}
+}
+
+/// Recursive descent parser over the text of an expression
+struct Parser<'a> {
+	/// The full expression
+	text: &'a str,
+	/// Byte offset of the next character to read
+	position: usize,
+}
+
+impl<'a> Parser<'a> {
+	/// `sum = product (('+' | '-') product)*`
+	fn parse_sum(&mut self) -> Result<Expression, ExpressionError> {
+		let mut expression = self.parse_product()?;
+		loop {
+			if self.consume('+') {
+				let rhs = self.parse_product()?;
+				expression = Expression::Add(Box::new(expression), Box::new(rhs));
+			} else if self.consume('-') {
+				let rhs = self.parse_product()?;
+				expression = Expression::Subtract(Box::new(expression), Box::new(rhs));
+			} else {
+				return Ok(expression);
+			}
+		}
+	}
+	/// `product = unary (('*' | '/') unary)*`
+	fn parse_product(&mut self) -> Result<Expression, ExpressionError> {
+		let mut expression = self.parse_unary()?;
+		loop {
+			if self.consume('*') {
+				let rhs = self.parse_unary()?;
+				expression = Expression::Multiply(Box::new(expression), Box::new(rhs));
+			} else if self.consume('/') {
+				let rhs = self.parse_unary()?;
+				expression = Expression::Divide(Box::new(expression), Box::new(rhs));
+			} else {
+				return Ok(expression);
+			}
+		}
+	}
+	/// `unary = '-' unary | atom`
+	fn parse_unary(&mut self) -> Result<Expression, ExpressionError> {
+		if self.consume('-') {
+			Ok(Expression::Negate(Box::new(self.parse_unary()?)))
+		} else {
+			self.parse_atom()
+		}
+	}
+	/// `atom = number | 'col(' integer ')' | '(' sum ')'`
+	fn parse_atom(&mut self) -> Result<Expression, ExpressionError> {
+		self.skip_whitespace();
+		let rest = &self.text[self.position..];
+		if self.consume('(') {
+			let expression = self.parse_sum()?;
+			if !self.consume(')') {
+				return Err(self.error("Expected `)`"));
+			}
+			Ok(expression)
+		} else if rest.starts_with("col") {
+			self.position += 3;
+			if !self.consume('(') {
+				return Err(self.error("Expected `(` after `col`"));
+			}
+			self.skip_whitespace();
+			let start = self.position;
+			let digits = self.take_while(|c| c.is_ascii_digit());
+			let column = digits.parse::<usize>().map_err(|_| ExpressionError {
+				position: start,
+				message: String::from("Expected a column number"),
+			})?;
+			if !self.consume(')') {
+				return Err(self.error("Expected `)`"));
+			}
+			Ok(Expression::Column(column))
+		} else if rest.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+			let start = self.position;
+			let number = self.take_while(|c| c.is_ascii_digit() || c == '.');
+			number
+				.parse::<f64>()
+				.map(Expression::Number)
+				.map_err(|_| ExpressionError {
+					position: start,
+					message: format!("Invalid number `{}`", number),
+				})
+		} else if rest.is_empty() {
+			Err(self.error("Unexpected end of expression"))
+		} else {
+			Err(self.error("Expected a number, `col(n)` or `(`"))
+		}
+	}
+	/// Skip whitespace then consume `c` if it is the next character
+	fn consume(&mut self, c: char) -> bool {
+		self.skip_whitespace();
+		if self.text[self.position..].starts_with(c) {
+			self.position += c.len_utf8();
+			true
+		} else {
+			false
+		}
+	}
+	/// Advance past any whitespace
+	fn skip_whitespace(&mut self) {
+		self.take_while(char::is_whitespace);
+	}
+	/// Advance past characters matching `predicate` and return them
+	fn take_while<P: Fn(char) -> bool>(&mut self, predicate: P) -> &'a str {
+		let rest = &self.text[self.position..];
+		let length = rest.find(|c| !predicate(c)).unwrap_or(rest.len());
+		self.position += length;
+		&rest[..length]
+	}
+	/// Create an error at the current position
+	fn error(&self, message: &str) -> ExpressionError {
+		ExpressionError {
+			position: self.position,
+			message: message.to_owned(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::data::expr::Expression;
+	fn evaluate(text: &str, row: &[f64]) -> f64 {
+		Expression::parse(text)
+			.unwrap()
+			.evaluate(&mut |column| Ok::<f64, ()>(row[column]))
+			.unwrap()
+	}
+	#[test]
+	fn column_ratio() {
+		assert_eq!(
+			50.0,
+			evaluate("col(2) / col(3) * 100", &[0.0, 0.0, 1.0, 2.0])
+		);
+	}
+	#[test]
+	fn precedence_and_parentheses() {
+		assert_eq!(7.0, evaluate("1 + 2 * 3", &[]));
+		assert_eq!(9.0, evaluate("(1 + 2) * 3", &[]));
+		assert_eq!(-1.0, evaluate("1 - 4 / 2", &[]));
+		assert_eq!(-4.5, evaluate("-col(0) * 1.5", &[3.0]));
+		assert_eq!(3.0, evaluate("--3", &[]));
+	}
+	#[test]
+	fn division_by_zero_is_not_finite() {
+		assert!(!evaluate("col(0) / col(1)", &[1.0, 0.0]).is_finite());
+		assert!(evaluate("col(0) / col(1)", &[0.0, 0.0]).is_nan());
+	}
+	#[test]
+	fn parse_errors_report_position() {
+		let error = Expression::parse("col(2) / ").unwrap_err();
+		assert_eq!(9, error.position);
+		let error = Expression::parse("col(x)").unwrap_err();
+		assert_eq!("Expected a column number at position 4", error.to_string());
+		assert!(Expression::parse("(1 + 2").is_err());
+		assert!(Expression::parse("1 2").is_err());
+		assert!(Expression::parse("1..2").is_err());
+		assert!(Expression::parse("").is_err());
+	}
+}
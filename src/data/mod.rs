@@ -0,0 +1,314 @@
+//! For reading a `.csv` file and parsing its numeric cells
+
+use csv::StringRecord;
+use flate2::read::MultiGzDecoder;
+use serde::{Deserialize, Serialize};
+use std::{
+	ffi::OsStr,
+	fs::File,
+	io::Read,
+	num::ParseFloatError,
+	path::{Path, PathBuf},
+};
+use tracing::trace;
+
+use crate::error::PlotError;
+
+pub mod expr;
+
+/// Where the values of an axis are read from, either the number of a zero-indexed csv column or an
+/// arithmetic expression of several columns such as `"col(2) / col(3) * 100"`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum CsvColumn {
+	/// A single csv column
+	Index(usize),
+	/// An expression evaluated per row, see [`expr`]
+	Expression(String),
+}
+
+/// Describes how numbers are written in csv cells so that exports from different locales can be parsed.
+///
+/// E.g US style `1,234.5` would be `NumberFormat(thousands_separator: Some(','), decimal_separator: '.')`
+/// while European style `1 234,5` would be `NumberFormat(thousands_separator: Some(' '), decimal_separator: ',')`
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq)]
+#[serde(default)]
+pub struct NumberFormat {
+	/// Optional character used to group digits which is stripped before parsing
+	pub thousands_separator: Option<char>,
+	/// Character separating the integer and fractional parts of a number
+	pub decimal_separator: char,
+}
+
+impl Default for NumberFormat {
+	fn default() -> Self {
+		NumberFormat {
+			thousands_separator: None,
+			decimal_separator: '.',
+		}
+	}
+}
+
+impl NumberFormat {
+	/// Parse a csv cell into an `f32`, the thousands separator is removed and the decimal separator
+	/// swapped for a `.` so that values like `1 234,5` or `1.2e-3` can be read
+	pub fn parse(&self, value: &str) -> Result<f32, ParseFloatError> {
+		self.clean(value).parse::<f32>()
+	}
+	/// As [`NumberFormat::parse`] with `f64` precision, for values such as epoch timestamps which an `f32`
+	/// cannot represent
+	pub fn parse_f64(&self, value: &str) -> Result<f64, ParseFloatError> {
+		self.clean(value).parse::<f64>()
+	}
+	/// Strip the thousands separator and replace the decimal separator with a `.`
+	fn clean(&self, value: &str) -> String {
+		let mut cleaned = String::with_capacity(value.len());
+		for c in value.trim().chars() {
+			if Some(c) == self.thousands_separator {
+				continue;
+			}
+			if c == self.decimal_separator {
+				cleaned.push('.');
+			} else {
+				cleaned.push(c);
+			}
+		}
+		cleaned
+	}
+}
+
+/// Unit of time used when converting timestamps into the time elapsed since a reference point
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq)]
+pub enum RelativeUnit {
+	/// Elapsed seconds
+	Seconds,
+	/// Elapsed minutes
+	Minutes,
+	/// Elapsed hours
+	Hours,
+}
+
+impl RelativeUnit {
+	/// Convert a number of seconds into this unit
+	pub fn from_seconds(&self, seconds: f64) -> f64 {
+		match self {
+			RelativeUnit::Seconds => seconds,
+			RelativeUnit::Minutes => seconds / 60.0,
+			RelativeUnit::Hours => seconds / 3600.0,
+		}
+	}
+	/// Name of the unit used to fill in a `{unit}` placeholder of an axis label
+	pub fn name(&self) -> &'static str {
+		match self {
+			RelativeUnit::Seconds => "seconds",
+			RelativeUnit::Minutes => "minutes",
+			RelativeUnit::Hours => "hours",
+		}
+	}
+}
+
+/// Which timestamp the elapsed time of data sets with a relative x-axis is measured from
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Default)]
+pub enum RelativeTimeOrigin {
+	/// Every set is measured from the earliest first timestamp across all sets
+	Earliest,
+	/// Each set is measured from its own first timestamp so that overlaid runs all start at zero
+	#[default]
+	PerDataset,
+}
+
+/// Expands a data path which may contain glob wildcards, e.g `logs/2024-*.csv`, into the files it matches
+/// sorted by file name. A path without wildcards is returned as is, a pattern matching nothing is an error
+pub fn expand_data_path(data_path: &str) -> Result<Vec<PathBuf>, PlotError> {
+	if !data_path.contains(['*', '?', '[']) {
+		return Ok(vec![PathBuf::from(data_path)]);
+	}
+	let paths = glob::glob(data_path).map_err(|e| {
+		PlotError::Config(format!(
+			"Invalid glob pattern in data path {}: {}",
+			data_path, e
+		))
+	})?;
+	let mut files: Vec<PathBuf> = Vec::new();
+	for path in paths {
+		match path {
+			Ok(p) => files.push(p),
+			Err(e) => {
+				return Err(PlotError::Io {
+					context: format!("Unable to read path matched by {}", data_path),
+					source: e.into(),
+				})
+			}
+		}
+	}
+	if files.is_empty() {
+		return Err(PlotError::Config(format!(
+			"Data path {} did not match any files",
+			data_path
+		)));
+	}
+	files.sort_by(|a, b| a.file_name().cmp(&b.file_name()));
+	trace!("Data path {} matched {:?}", data_path, files);
+	Ok(files)
+}
+
+/// Reads a csv of data points and returns a vector of rows. The path may contain glob wildcards in which
+/// case the records of each matched file are concatenated in file name order. For large files prefer
+/// [`for_each_record`] which avoids holding every row in memory
+pub fn load_data(
+	data_path: &str,
+	has_headers: bool,
+	csv_delimiter: &str,
+) -> Result<Vec<StringRecord>, PlotError> {
+	let mut string_records: Vec<StringRecord> = Vec::new();
+	for path in expand_data_path(data_path)? {
+		for_each_record(path, has_headers, csv_delimiter, |_, record| {
+			string_records.push(record.clone());
+			Ok(())
+		})?;
+	}
+	Ok(string_records)
+}
+
+/// Streams a csv of data points, calling `f` with the row number (starting from 1) and the record of each
+/// row in turn. A single record buffer is reused so only one row is held in memory at a time. Files ending
+/// in `.gz` are decompressed as they are read. Reading stops at the first error, including one returned by `f`
+pub fn for_each_record<P, F>(
+	data_path: P,
+	has_headers: bool,
+	csv_delimiter: &str,
+	mut f: F,
+) -> Result<(), PlotError>
+where
+	P: AsRef<Path>,
+	F: FnMut(usize, &StringRecord) -> Result<(), PlotError>,
+{
+	let file = File::open(data_path.as_ref()).map_err(|e| PlotError::Io {
+		context: format!("Unable to read csv data {:?}", data_path.as_ref()),
+		source: e,
+	})?;
+	let reader: Box<dyn Read> = if data_path.as_ref().extension() == Some(OsStr::new("gz")) {
+		trace!("Decompressing gzip csv data {:?}", data_path.as_ref());
+		Box::new(MultiGzDecoder::new(file))
+	} else {
+		Box::new(file)
+	};
+	let mut data = csv::ReaderBuilder::new()
+		.has_headers(has_headers)
+		.delimiter(csv_delimiter.as_bytes()[0])
+		.from_reader(reader);
+	let mut record = StringRecord::new();
+	let mut row = 0;
+	loop {
+		match data.read_record(&mut record) {
+			Ok(true) => {
+				row += 1;
+				trace!("Csv data: {:?}", record);
+				f(row, &record)?
+			}
+			Ok(false) => return Ok(()),
+			Err(e) => {
+				return Err(PlotError::Csv {
+					path: data_path.as_ref().to_owned(),
+					source: e,
+				})
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::data::{expand_data_path, load_data, NumberFormat, RelativeUnit};
+	use std::{fs, path::PathBuf};
+	#[test]
+	fn default_format_parses_plain_and_scientific() {
+		let format = NumberFormat::default();
+		assert_eq!(Ok(1234.5), format.parse("1234.5"));
+		assert_eq!(Ok(-0.5), format.parse("-0.5"));
+		assert_eq!(Ok(1.2e-3), format.parse("1.2e-3"));
+		assert_eq!(Ok(4.0), format.parse(" 4 "));
+	}
+	#[test]
+	fn timestamps_keep_precision_as_elapsed_time() {
+		let format = NumberFormat::default();
+		let first = format.parse_f64("1700000000").unwrap();
+		let later = format.parse_f64("1700000090.5").unwrap();
+		assert_eq!(90.5, RelativeUnit::Seconds.from_seconds(later - first));
+		assert_eq!(1.5, RelativeUnit::Minutes.from_seconds(90.0));
+		assert_eq!(0.5, RelativeUnit::Hours.from_seconds(1800.0));
+	}
+	#[test]
+	fn default_format_rejects_grouped_numbers() {
+		let format = NumberFormat::default();
+		assert!(format.parse("1,234.5").is_err());
+	}
+	#[test]
+	fn us_style() {
+		let format = NumberFormat {
+			thousands_separator: Some(','),
+			decimal_separator: '.',
+		};
+		assert_eq!(Ok(1234.5), format.parse("1,234.5"));
+		assert_eq!(Ok(-1234567.0), format.parse("-1,234,567"));
+		assert_eq!(Ok(1.5e3), format.parse("1.5e3"));
+	}
+	#[test]
+	fn eu_style_space_grouping() {
+		let format = NumberFormat {
+			thousands_separator: Some(' '),
+			decimal_separator: ',',
+		};
+		assert_eq!(Ok(1234.5), format.parse("1 234,5"));
+		assert_eq!(Ok(-0.25), format.parse("-0,25"));
+		assert_eq!(Ok(1.2e-3), format.parse("1,2e-3"));
+	}
+	#[test]
+	fn eu_style_dot_grouping() {
+		let format = NumberFormat {
+			thousands_separator: Some('.'),
+			decimal_separator: ',',
+		};
+		assert_eq!(Ok(1234567.5), format.parse("1.234.567,5"));
+		assert_eq!(Ok(12.0), format.parse("12"));
+	}
+	#[test]
+	fn path_without_wildcards_is_unchanged() {
+		assert_eq!(
+			vec![PathBuf::from("missing.csv")],
+			expand_data_path("missing.csv").unwrap()
+		);
+	}
+	#[test]
+	fn glob_concatenates_in_file_name_order() {
+		let dir = std::env::temp_dir().join(format!("plotrs_glob_{}", std::process::id()));
+		fs::create_dir_all(&dir).unwrap();
+		fs::write(dir.join("2024-02.csv"), "x,y\n3,4\n").unwrap();
+		fs::write(dir.join("2024-01.csv"), "x,y\n1,2\n").unwrap();
+		fs::write(dir.join("other.csv"), "x,y\n5,6\n").unwrap();
+		let pattern = dir.join("2024-*.csv");
+		let pattern = pattern.to_str().unwrap();
+		assert_eq!(
+			vec![dir.join("2024-01.csv"), dir.join("2024-02.csv")],
+			expand_data_path(pattern).unwrap()
+		);
+		let records = load_data(pattern, true, ",").unwrap();
+		fs::remove_dir_all(&dir).unwrap();
+		let x: Vec<&str> = records.iter().map(|r| r.get(0).unwrap()).collect();
+		assert_eq!(vec!["1", "3"], x);
+	}
+	#[test]
+	fn unmatched_glob_and_missing_file_are_errors() {
+		let pattern =
+			std::env::temp_dir().join(format!("plotrs_none_{}_*.csv", std::process::id()));
+		let e = expand_data_path(pattern.to_str().unwrap()).unwrap_err();
+		assert!(e.to_string().contains("did not match any files"), "{}", e);
+		let e = load_data("plotrs_missing.csv", true, ",").unwrap_err();
+		assert!(
+			e.to_string()
+				.starts_with("Unable to read csv data \"plotrs_missing.csv\""),
+			"{}",
+			e
+		);
+	}
+}
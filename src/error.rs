@@ -0,0 +1,117 @@
+//! Errors that stop a graph from being drawn. Library functions return them so that the caller decides
+//! how to report a failure, the `plotrs` binary logs the message and exits
+
+use std::{io, path::PathBuf};
+use thiserror::Error;
+
+use crate::canvas::ImageSaveError;
+
+/// Why a graph could not be drawn
+#[derive(Debug, Error)]
+pub enum PlotError {
+	/// The config, or a style it uses, is missing, malformed or contains an invalid value
+	#[error("{0}")]
+	Config(String),
+	/// A graph config could not be read or parsed
+	#[error(transparent)]
+	Deserialise(#[from] ConfigError),
+	/// A graph, whether deserialised or built with a `ScatterBuilder`, describes something which cannot be drawn
+	#[error(transparent)]
+	Invalid(#[from] ValidationError),
+	/// A file could not be read or written, `context` describes which and why it was needed
+	#[error("{context}, error: {source}")]
+	Io {
+		/// What was being attempted
+		context: String,
+		/// The underlying error
+		#[source]
+		source: io::Error,
+	},
+	/// A csv file could not be read or a cell of it could not be parsed to a number
+	#[error("Could not parse data in {path:?}, column {column}, row {row} to a number for {description}, error: {message}")]
+	CsvParse {
+		/// The csv file
+		path: PathBuf,
+		/// Zero indexed row of the record, excluding any headers
+		row: usize,
+		/// Zero indexed column of the cell
+		column: usize,
+		/// The value the cell was read for, e.g `x axis`
+		description: String,
+		/// Why the cell could not be parsed
+		message: String,
+	},
+	/// A record of a csv file could not be read, e.g a row has fewer fields than the others
+	#[error("Unable to read record in csv data {path:?}: {source}")]
+	Csv {
+		/// The csv file
+		path: PathBuf,
+		/// The underlying error, which includes the position of the record
+		#[source]
+		source: csv::Error,
+	},
+	/// The data or layout cannot be drawn, e.g every value is the same so the axes have no length
+	#[error("{0}")]
+	Render(String),
+	/// The finished image could not be saved
+	#[error(transparent)]
+	Save(#[from] ImageSaveError),
+	/// Neither a system nor a bundled font is available to draw text with
+	#[error("Could not construct/find a suitable font")]
+	Font,
+}
+
+/// A graph config which could not be read or parsed
+#[derive(Debug, Error)]
+pub enum ConfigError {
+	/// The config could not be read from its source
+	#[error("Failed to read config, error: {0}")]
+	Io(#[from] io::Error),
+	/// The config is not valid ron
+	#[error("Failed to load config at line {line}, column {column}, maybe you're missing a comma? Error: {message}")]
+	Syntax {
+		/// One indexed line of the config where parsing stopped
+		line: usize,
+		/// One indexed column of the config where parsing stopped
+		column: usize,
+		/// What was wrong, e.g `Expected comma`
+		message: String,
+	},
+	/// The config is valid ron but does not describe a graph, e.g a required field is missing. ron does not
+	/// record where these errors occur
+	#[error("Failed to load config, error: {0}")]
+	Content(String),
+}
+
+impl From<ron::Error> for ConfigError {
+	fn from(e: ron::Error) -> Self {
+		match e.code {
+			ron::error::ErrorCode::Io(message) => {
+				ConfigError::Io(io::Error::new(io::ErrorKind::Other, message))
+			}
+			code if e.position.line == 0 => ConfigError::Content(code.to_string()),
+			code => ConfigError::Syntax {
+				line: e.position.line,
+				column: e.position.col,
+				message: code.to_string(),
+			},
+		}
+	}
+}
+
+/// A graph which is well formed but cannot be drawn
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum ValidationError {
+	/// An axis resolution of zero, naming the field
+	#[error("Provided {0} must be greater than zero")]
+	ZeroResolution(&'static str),
+	/// An axis range which is not finite or whose minimum is not smaller than its maximum, naming the field
+	#[error("Provided {0} must have a finite minimum smaller than its maximum")]
+	InvalidAxisRange(&'static str),
+	/// A padding fraction which is not finite or is negative, naming the field
+	#[error("Provided {0} must be a finite fraction of at least zero")]
+	InvalidPadding(&'static str),
+	/// There is nothing to plot
+	#[error("A graph needs at least one data set")]
+	NoDataSets,
+}
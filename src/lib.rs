@@ -0,0 +1,129 @@
+//! Library for plotting csv data sets onto a graph. A graph definition is read from a `.ron` file, data is extracted from one or more csv files and the result is written out as a `.png` image.
+//!
+//! The `plotrs` binary is a thin CLI wrapper around this library and is built with the `cli` feature.
+//!
+//! ## Features
+//!
+//! * `cli` - the dependencies required by the `plotrs` binary (argument parsing and log output)
+//! * `system-fonts` - look up a sans-serif font installed on the host via `font-kit`
+//! * `bundled-font` - embed DejaVu Sans into the build, used when a system font cannot be found or when `system-fonts` is disabled
+//!
+//! All three are enabled by default. To embed the library without the CLI or `font-kit`:
+//!
+//! ```toml
+//! plotrs = {version = "0.1", default-features = false, features = ["bundled-font"]}
+//! ```
+//!
+//! A host with a font of its own can disable both font features and pass the bytes of its font in
+//! [`RenderOptions::font`](scatter::RenderOptions::font), without one rendering fails with
+//! [`PlotError::Font`](error::PlotError::Font)
+//!
+//! ## Reproducible Output
+//!
+//! The same config and data always produce a byte-identical image. To keep it that way maps in a config
+//! deserialise into a `BTreeMap` so they are iterated in the same order on every run, never a `HashMap`
+//! whose order is randomised per process, and anything derived from a hash uses a hasher whose output is
+//! stable across releases and platforms, such as the `fnv1a` behind `ColourAssignment::NameHash`
+//!
+//! ## Graph Configs
+//!
+//! Each graph type parses its config with `from_ron_str` and `from_reader` returning a
+//! [`ConfigError`](error::ConfigError) which points at the line ron stopped on, e.g
+//! [`Scatter::from_ron_str`](scatter::Scatter::from_ron_str). Reading from a path is a wrapper around them so
+//! configs held in memory are parsed identically
+
+#[cfg(feature = "system-fonts")]
+use font_kit::{
+	family_name::FamilyName, handle::Handle, properties::Properties, source::SystemSource,
+};
+use rusttype::Font;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "system-fonts")]
+use std::fs;
+#[cfg(any(feature = "system-fonts", feature = "bundled-font"))]
+use tracing::trace;
+
+use crate::error::PlotError;
+
+pub mod canvas;
+pub mod colours;
+pub mod data;
+pub mod error;
+pub mod scatter;
+pub mod style;
+
+/// DejaVu Sans, used whenever a system font is unavailable
+#[cfg(feature = "bundled-font")]
+const BUNDLED_FONT: &[u8] = include_bytes!("../assets/fonts/DejaVuSans.ttf");
+
+/// Loads a font from the bytes of a `.ttf` or `.otf` file, such as one supplied by an embedding host
+pub fn load_font(bytes: Vec<u8>) -> Result<Font<'static>, PlotError> {
+	Font::try_from_vec(bytes).ok_or(PlotError::Font)
+}
+
+/// Retrieves a system font, falling back to the bundled font if one cannot be found
+pub fn get_system_font() -> Result<Font<'static>, PlotError> {
+	#[cfg(feature = "system-fonts")]
+	if let Some(font) = find_system_font() {
+		return Ok(font);
+	}
+	get_fallback_font()
+}
+
+/// Searches the host for a sans-serif font
+#[cfg(feature = "system-fonts")]
+fn find_system_font() -> Option<Font<'static>> {
+	let font_path: Handle = SystemSource::new()
+		.select_best_match(&[FamilyName::SansSerif], &Properties::new())
+		.ok()?;
+	match font_path {
+		Handle::Path {
+			path,
+			font_index: _,
+		} => {
+			trace!("Font path: {:?}", path);
+			let bytes = fs::read(path.as_path()).ok()?;
+			Font::try_from_vec(bytes)
+		}
+		Handle::Memory {
+			bytes,
+			font_index: _,
+		} => Font::try_from_vec(bytes.to_vec()),
+	}
+}
+
+/// Loads the font embedded in the build
+#[cfg(feature = "bundled-font")]
+fn get_fallback_font() -> Result<Font<'static>, PlotError> {
+	trace!("Using bundled font");
+	Ok(Font::try_from_bytes(BUNDLED_FONT).expect("Bundled font is a valid ttf"))
+}
+
+/// Without a bundled font there is nothing to fall back to
+#[cfg(not(feature = "bundled-font"))]
+fn get_fallback_font() -> Result<Font<'static>, PlotError> {
+	Err(PlotError::Font)
+}
+
+/// Deserialise a field present in a config as `Some`, allowing optional fields to be written without
+/// wrapping them in `Some(..)`. Missing fields fall back to `None` via `#[serde(default)]`
+pub(crate) fn deserialize_some<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+	T: Deserialize<'de>,
+	D: Deserializer<'de>,
+{
+	T::deserialize(deserializer).map(Some)
+}
+
+/// The counterpart of [`deserialize_some`], writing the value of a `Some` without wrapping it so that a
+/// serialised config reads back. Fields using it skip serialising a `None`
+pub(crate) fn serialize_some<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+	T: Serialize,
+	S: Serializer,
+{
+	match value {
+		Some(v) => v.serialize(serializer),
+		None => serializer.serialize_none(),
+	}
+}
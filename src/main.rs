@@ -25,40 +25,181 @@
 //! ```bash
 //! plotrs -g scatter -c scatter_config.ron -o here/please
 //! ```
+//!
+//! An example config with every field present and commented can be written as a starting point with:
+//!
+//! ```bash
+//! plotrs scaffold scatter > scatter.ron
+//! ```
 
-use clap::Parser;
-use font_kit::{
-	family_name::FamilyName, handle::Handle, properties::Properties, source::SystemSource,
+use clap::{Parser, Subcommand};
+use plotrs::{
+	canvas::{
+		progress::{RenderProgress, RenderStage},
+		ExistingOutput, OutputFormat,
+	},
+	scatter,
 };
-use rusttype::Font;
-use std::fs;
-use tracing::error;
-use tracing::{self, trace};
-mod canvas;
-mod colours;
-mod data;
-mod scatter;
+use std::{
+	io::{self, IsTerminal, Write},
+	sync::{Arc, Mutex},
+};
+use tracing::{self, error};
 
 /// Programme arguments
 #[derive(Parser, Debug)]
-#[clap(author, version, about, long_about = None)]
+#[clap(author, version, about, long_about = None, subcommand_negates_reqs = true)]
 struct Args {
+	/// Optional subcommand run instead of generating a graph
+	#[clap(subcommand)]
+	command: Option<Command>,
 	/// Graph type to generate, accepted values: "scatter"
-	#[clap(short, long)]
-	graph: String,
+	#[clap(short, long, required = true)]
+	graph: Option<String>,
 	/// Relative path to a .ron config file containing graph metadata
-	#[clap(short, long)]
-	config: String,
-	/// Relative path to a directory where your png will be placed. Png names are based on our config graph title
+	#[clap(short, long, required = true)]
+	config: Option<String>,
+	/// Relative path to a directory where your png will be placed. Png names are based on our config graph title. Use "-" to write the image to stdout instead
 	#[clap(short, long, default_value_t = String::from("."))]
 	output: String,
 	/// Override the default csv delimiter "," with your own, e.g ";"
 	#[clap(long, default_value_t = String::from(","))]
 	csv_delimiter: String,
+	/// Optional path of a .ron file to write the pixel positions of the plot area to
+	#[clap(long)]
+	geometry_report: Option<String>,
+	/// Optional path of a .ron file to line up the x-axes of separately rendered graphs. The first graph records the pixel columns of its x-axis and later graphs reuse them
+	#[clap(long)]
+	align_file: Option<String>,
+	/// Print the config as ron with its style merged and automatically chosen values, such as axis resolutions, resolved instead of drawing the graph
+	#[clap(long)]
+	print_config: bool,
+	/// Print the number of points, symbol and grid pixels and text elements along with a rough prediction of how long drawing takes, instead of drawing the graph
+	#[clap(long)]
+	estimate: bool,
+	/// Optional directory to write a csv per data set containing the values used for plotting, useful for verifying what was drawn
+	#[clap(long)]
+	dump_processed_data: Option<String>,
+	/// Optional path to a .ron style file providing defaults, such as grid and legend, for any values the config does not specify
+	#[clap(long)]
+	style: Option<String>,
+	/// Optional comma separated names of the only data sets to plot, e.g "run A,run C"
+	#[clap(long, use_value_delimiter = true)]
+	only: Option<Vec<String>>,
+	/// Optional comma separated names of data sets to leave out of the graph
+	#[clap(long, use_value_delimiter = true)]
+	exclude: Option<Vec<String>>,
+	/// Optional file name, without an extension, for the saved graph. Defaults to the graph title in lowercase with whitespace and symbols replaced by "_"
+	#[clap(long)]
+	output_name: Option<String>,
+	/// Image format to save the graph as, accepted values: "png", "jpeg", "bmp", "webp", "tiff"
+	#[clap(long, default_value = "png")]
+	format: OutputFormat,
+	/// Embed the plotrs version, config path, a hash of the config and the data file paths into the png as text chunks
+	#[clap(long)]
+	embed_metadata: bool,
+	/// Quality of lossy formats such as jpeg, from 1 to 100
+	#[clap(long, default_value_t = 90)]
+	quality: u8,
+	/// Overwrite the output file if it already exists, by default an existing file is left alone and an error reported
+	#[clap(long, conflicts_with = "auto-suffix")]
+	force: bool,
+	/// Save to "<name>_1", "<name>_2" etc when the output file already exists rather than reporting an error
+	#[clap(long)]
+	auto_suffix: bool,
 	/// Set the verbosity level with a series of `v`'s, e.g `-v` or `-vv`
 	#[clap(flatten)]
 	verbose: clap_verbosity_flag::Verbosity<clap_verbosity_flag::InfoLevel>,
 }
+/// Subcommands which don't generate a graph
+#[derive(Subcommand, Debug)]
+enum Command {
+	/// Print an example config of a graph type with every field present and commented, e.g `plotrs scaffold scatter > scatter.ron`
+	Scaffold {
+		/// Graph type to scaffold, accepted values: "scatter"
+		graph: String,
+	},
+}
+/// A progress bar kept on the last line of a terminal's stderr while a graph is drawn
+#[derive(Default)]
+struct ProgressBar {
+	/// The bar as last drawn, empty before the first stage and once finished
+	line: Mutex<String>,
+}
+
+impl ProgressBar {
+	/// Number of characters between the brackets of the bar
+	const WIDTH: usize = 30;
+	/// Redraw the bar filled to `fraction` of its width followed by a description of the stage
+	fn draw(&self, fraction: f32, message: &str) {
+		let filled = ((fraction * Self::WIDTH as f32) as usize).min(Self::WIDTH);
+		let mut line = self.line.lock().unwrap();
+		*line = format!(
+			"[{}{}] {:>3}% {}",
+			"=".repeat(filled),
+			" ".repeat(Self::WIDTH - filled),
+			(fraction * 100.0) as u32,
+			message
+		);
+		let stderr = io::stderr();
+		let mut handle = stderr.lock();
+		let _ = write!(handle, "\r\x1b[2K{}", line);
+		let _ = handle.flush();
+	}
+	/// Remove the bar from the terminal
+	fn finish(&self) {
+		let mut line = self.line.lock().unwrap();
+		if !line.is_empty() {
+			line.clear();
+			let stderr = io::stderr();
+			let mut handle = stderr.lock();
+			let _ = write!(handle, "\r\x1b[2K");
+			let _ = handle.flush();
+		}
+	}
+}
+
+impl RenderProgress for ProgressBar {
+	fn stage(&self, stage: RenderStage) {
+		// plotting is given the largest share as it dominates the time spent on large data sets
+		match stage {
+			RenderStage::LoadingData => self.draw(0.0, "loading data"),
+			RenderStage::ComputingBounds => self.draw(0.3, "computing bounds"),
+			RenderStage::DrawingAxes => self.draw(0.4, "drawing axes"),
+			RenderStage::PlottingSet { index, count } => self.draw(
+				0.45 + 0.45 * index as f32 / count as f32,
+				&format!("plotting set {}/{}", index + 1, count),
+			),
+			RenderStage::Saving => self.draw(0.9, "saving"),
+		}
+	}
+}
+
+/// Writes log lines to stderr, clearing any progress bar first and redrawing it beneath them
+struct LogWriter(Option<Arc<ProgressBar>>);
+
+impl Write for LogWriter {
+	fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+		let stderr = io::stderr();
+		let bar = match &self.0 {
+			Some(bar) => bar,
+			None => return stderr.lock().write(buf),
+		};
+		let line = bar.line.lock().unwrap();
+		let mut handle = stderr.lock();
+		if line.is_empty() {
+			return handle.write(buf);
+		}
+		handle.write_all(b"\r\x1b[2K")?;
+		handle.write_all(buf)?;
+		handle.write_all(line.as_bytes())?;
+		Ok(buf.len())
+	}
+	fn flush(&mut self) -> io::Result<()> {
+		io::stderr().flush()
+	}
+}
+
 /// Process Cli arguments and call appropriate methods for graph creation
 fn main() {
 	let args = Args::parse();
@@ -73,55 +214,101 @@ fn main() {
 		},
 		None => tracing::Level::INFO,
 	};
-	tracing_subscriber::fmt().with_max_level(log_level).init();
+	// a bar is only drawn for a person watching a terminal who hasn't asked for quiet
+	let progress = match args.verbose.log_level() {
+		Some(level) if level >= log::Level::Info && io::stderr().is_terminal() => {
+			Some(Arc::new(ProgressBar::default()))
+		}
+		_ => None,
+	};
+	// logs are kept out of stdout so that an image written there with `-o -` can be piped
+	let log_progress = progress.clone();
+	tracing_subscriber::fmt()
+		.with_max_level(log_level)
+		.with_writer(move || LogWriter(log_progress.clone()))
+		.init();
 
+	if let Some(Command::Scaffold { graph }) = args.command {
+		match graph.to_lowercase().as_str() {
+			"scatter" => print!("{}", scatter::scatter_scaffold()),
+			_ => {
+				error!("Invalid graph type selected. Valid graphs are 'scatter'.");
+				std::process::exit(1);
+			}
+		}
+		return;
+	}
+	// without a subcommand clap requires both a graph type and config
+	let (graph, config) = match (args.graph, args.config) {
+		(Some(graph), Some(config)) => (graph, config),
+		_ => unreachable!("clap requires a graph type and config"),
+	};
+	if !(1..=100).contains(&args.quality) {
+		error!("Quality must be between 1 and 100");
+		std::process::exit(1);
+	}
 	if args.csv_delimiter.len() != 1 {
 		error!("Csv delimiter must be a single character");
 		std::process::exit(1);
 	}
 
-	match args.graph.to_lowercase().as_str() {
-		"scatter" => scatter::scatter_builder(
-			args.config.as_str(),
-			args.output.as_str(),
-			args.csv_delimiter.as_str(),
-		),
-		_ => {
-			error!("Invalid graph type selected. Valid graphs are 'scatter'.");
-			std::process::exit(1);
-		}
-	}
-}
-
-/// Retrieves a system font
-pub fn get_system_font() -> Font<'static> {
-	let font_path: Handle = SystemSource::new()
-		.select_best_match(&[FamilyName::SansSerif], &Properties::new())
-		.unwrap();
-	match font_path {
-		Handle::Path {
-			path,
-			font_index: _,
-		} => {
-			trace!("Font path: {:?}", path);
-			let bytes = fs::read(path.as_path()).unwrap();
-			match Font::try_from_vec(bytes) {
-				Some(x) => x,
-				None => {
-					error!("Could not construct/find a suitable font");
+	let options = scatter::BuildOptions {
+		output: args.output,
+		render: scatter::RenderOptions {
+			csv_delimiter: args.csv_delimiter,
+			geometry_report: args.geometry_report,
+			dump_processed_data: args.dump_processed_data,
+			align_file: args.align_file,
+			progress: progress
+				.clone()
+				.map(|bar| bar as Arc<dyn RenderProgress + Send + Sync>),
+			font: None,
+		},
+		embed_metadata: args.embed_metadata,
+		style: args.style,
+		only: args.only,
+		exclude: args.exclude,
+		output_name: args.output_name,
+		format: args.format,
+		quality: args.quality,
+		existing_output: if args.force {
+			ExistingOutput::Overwrite
+		} else if args.auto_suffix {
+			ExistingOutput::AutoSuffix
+		} else {
+			ExistingOutput::Refuse
+		},
+	};
+	match graph.to_lowercase().as_str() {
+		"scatter" if args.print_config => {
+			match scatter::scatter_resolved_config(config.as_str(), &options) {
+				Ok(ron) => println!("{}", ron),
+				Err(e) => {
+					error!("{}", e);
 					std::process::exit(1);
 				}
 			}
 		}
-		Handle::Memory {
-			bytes,
-			font_index: _,
-		} => match Font::try_from_vec(bytes.to_vec()) {
-			Some(x) => x,
-			None => {
-				error!("Could not construct/find a suitable font");
+		"scatter" if args.estimate => match scatter::scatter_estimate(config.as_str(), &options) {
+			Ok(estimate) => println!("{}", estimate),
+			Err(e) => {
+				error!("{}", e);
 				std::process::exit(1);
 			}
 		},
+		"scatter" => {
+			let result = scatter::scatter_builder(config.as_str(), &options);
+			if let Some(bar) = &progress {
+				bar.finish();
+			}
+			if let Err(e) = result {
+				error!("{}", e);
+				std::process::exit(1);
+			}
+		}
+		_ => {
+			error!("Invalid graph type selected. Valid graphs are 'scatter'.");
+			std::process::exit(1);
+		}
 	}
 }
@@ -0,0 +1,652 @@
+//! Create a [`Scatter`] in code rather than deserialising a `.ron` config, e.g
+//!
+//! ```no_run
+//! use plotrs::scatter::{DataSetBuilder, ScatterBuilder};
+//!
+//! let image = ScatterBuilder::new()
+//!     .title("Energy against Time")
+//!     .canvas_size(840, 600)
+//!     .axis_labels("Time (ms)", "Energy (kJ)")
+//!     .grid(true)
+//!     .add_data_set(DataSetBuilder::new("Run A", "run_a.csv", 0, 1))
+//!     .render()
+//!     .unwrap();
+//! ```
+//!
+//! Values which are not set take the same defaults as a config omitting them
+
+use image::{ImageBuffer, Rgba};
+use std::collections::BTreeMap;
+
+use crate::{
+	canvas::{
+		axes::{CustomTick, GridStyle, LabelOrientation, YAxis},
+		best_fit::{BestFit, Corner, LineStyle},
+		fill::FillGradient,
+		format::Notation,
+		legend::LegendPosition,
+		plot::DataSymbol,
+		Chrome,
+	},
+	colours::{Colormap, Colour, ColourAssignment, ColourBy, Palette},
+	data::{CsvColumn, NumberFormat, RelativeUnit},
+	error::PlotError,
+};
+
+use super::{scatter_render, DataSet, DataValues, RenderOptions, Scatter};
+
+/// Builds the specification of a scatter graph in code rather than deserialising a `.ron` config
+#[derive(Debug)]
+pub struct ScatterBuilder {
+	/// The graph being built
+	scatter: Scatter,
+}
+
+impl Default for ScatterBuilder {
+	fn default() -> Self {
+		ScatterBuilder::new()
+	}
+}
+
+impl ScatterBuilder {
+	/// Start an untitled `800` by `600` pixel graph without axis labels or data sets
+	pub fn new() -> ScatterBuilder {
+		ScatterBuilder {
+			scatter: Scatter {
+				title: String::new(),
+				canvas_pixel_size: (800, 600),
+				x_axis_label: String::new(),
+				x_axis_resolution: None,
+				y_axis_label: String::new(),
+				y_axis_label_orientation: None,
+				y_axis_resolution: None,
+				x_axis_range: None,
+				y_axis_range: None,
+				axis_padding_fraction: None,
+				x_axis_padding_fraction: None,
+				y_axis_padding_fraction: None,
+				y2_axis_label: None,
+				y2_axis_resolution: None,
+				x_tick_decimal_places: None,
+				y_tick_decimal_places: None,
+				tick_format: None,
+				x_tick_format: None,
+				y_tick_format: None,
+				x_tick_suffix: None,
+				y_tick_suffix: None,
+				x_ticks: None,
+				y_ticks: None,
+				x_tick_label_every: None,
+				y_tick_label_every: None,
+				x_tick_datetime_format: None,
+				x_minor_ticks: None,
+				y_minor_ticks: None,
+				minor_grid: None,
+				highlight_zero: None,
+				show_x_axis: None,
+				show_y_axis: None,
+				show_ticks: None,
+				show_tick_labels: None,
+				x_axis_reversed: None,
+				y_axis_reversed: None,
+				axis_arrows: None,
+				axis_thickness: None,
+				grid_thickness: None,
+				grid_style: None,
+				grid_dash_length: None,
+				axis_colour: None,
+				tick_label_colour: None,
+				grid_colour: None,
+				has_grid: None,
+				has_legend: None,
+				equation_corner: None,
+				legend_position: None,
+				legend_frame: None,
+				legend_background: None,
+				legend_overlay: None,
+				legend_title: None,
+				legend_font_size: None,
+				legend_row_spacing: None,
+				data_sets: Vec::new(),
+				number_format: None,
+				chrome: None,
+				colour_assignment: None,
+				palette: None,
+				relative_time_origin: None,
+				output_name: None,
+				locale: None,
+				supersample: None,
+				antialias: None,
+				style_path: None,
+			},
+		}
+	}
+	/// The title drawn above the graph
+	pub fn title(mut self, title: &str) -> Self {
+		self.scatter.title = title.to_owned();
+		self
+	}
+	/// Size of the image in pixels
+	pub fn canvas_size(mut self, width: u32, height: u32) -> Self {
+		self.scatter.canvas_pixel_size = (width, height);
+		self
+	}
+	/// Labels of the x and y axes
+	pub fn axis_labels(mut self, x_axis_label: &str, y_axis_label: &str) -> Self {
+		self.scatter.x_axis_label = x_axis_label.to_owned();
+		self.scatter.y_axis_label = y_axis_label.to_owned();
+		self
+	}
+	/// Whether the y-axis label is written above the axis or turned to read upwards alongside it
+	pub fn y_axis_label_orientation(mut self, orientation: LabelOrientation) -> Self {
+		self.scatter.y_axis_label_orientation = Some(orientation);
+		self
+	}
+	/// Number of times the x and y axes are divided, an axis given `None` is rounded outward to a nice step
+	pub fn resolution(
+		mut self,
+		x_axis_resolution: Option<u32>,
+		y_axis_resolution: Option<u32>,
+	) -> Self {
+		self.scatter.x_axis_resolution = x_axis_resolution;
+		self.scatter.y_axis_resolution = y_axis_resolution;
+		self
+	}
+	/// Fixed `(min, max)` ranges of the x and y axes, an axis given `None` is fitted to the data
+	pub fn axis_ranges(
+		mut self,
+		x_axis_range: Option<(f32, f32)>,
+		y_axis_range: Option<(f32, f32)>,
+	) -> Self {
+		self.scatter.x_axis_range = x_axis_range;
+		self.scatter.y_axis_range = y_axis_range;
+		self
+	}
+	/// Fraction of its value by which the smallest and largest data values of both axes are moved outward
+	/// before the axes are fitted to them, `0.0` fits the axes to the data bounds
+	pub fn axis_padding_fraction(mut self, axis_padding_fraction: f32) -> Self {
+		self.scatter.axis_padding_fraction = Some(axis_padding_fraction);
+		self
+	}
+	/// Padding fractions of the x and y axes, an axis given `None` uses the fraction given to
+	/// [`ScatterBuilder::axis_padding_fraction`]
+	pub fn axis_padding_fractions(
+		mut self,
+		x_axis_padding_fraction: Option<f32>,
+		y_axis_padding_fraction: Option<f32>,
+	) -> Self {
+		self.scatter.x_axis_padding_fraction = x_axis_padding_fraction;
+		self.scatter.y_axis_padding_fraction = y_axis_padding_fraction;
+		self
+	}
+	/// Label of the secondary y-axis and the number of times it is divided, `None` divides it as the y-axis
+	/// is. The axis is drawn when a data set is plotted against it, see [`DataSetBuilder::y_axis`]
+	pub fn y2_axis(mut self, y2_axis_label: &str, y2_axis_resolution: Option<u32>) -> Self {
+		self.scatter.y2_axis_label = Some(y2_axis_label.to_owned());
+		self.scatter.y2_axis_resolution = y2_axis_resolution;
+		self
+	}
+	/// Number of decimal places the x and y tick labels are written with, an axis given `None` uses the
+	/// fewest places which tell its labels apart
+	pub fn tick_decimal_places(
+		mut self,
+		x_tick_decimal_places: Option<u8>,
+		y_tick_decimal_places: Option<u8>,
+	) -> Self {
+		self.scatter.x_tick_decimal_places = x_tick_decimal_places;
+		self.scatter.y_tick_decimal_places = y_tick_decimal_places;
+		self
+	}
+	/// Whether tick labels are written plainly, in scientific notation or picked by the span of each axis
+	pub fn tick_format(mut self, tick_format: Notation) -> Self {
+		self.scatter.tick_format = Some(tick_format);
+		self
+	}
+	/// Notation of the x and y tick labels in place of the one given to [`ScatterBuilder::tick_format`], e.g
+	/// `Notation::Percent` for an axis of ratios
+	pub fn axis_tick_formats(
+		mut self,
+		x_tick_format: Option<Notation>,
+		y_tick_format: Option<Notation>,
+	) -> Self {
+		self.scatter.x_tick_format = x_tick_format;
+		self.scatter.y_tick_format = y_tick_format;
+		self
+	}
+	/// Text appended to every x and y tick label, e.g `Some(" ms")` for labels of `10 ms`, `20 ms`
+	pub fn tick_suffixes(
+		mut self,
+		x_tick_suffix: Option<&str>,
+		y_tick_suffix: Option<&str>,
+	) -> Self {
+		self.scatter.x_tick_suffix = x_tick_suffix.map(str::to_owned);
+		self.scatter.y_tick_suffix = y_tick_suffix.map(str::to_owned);
+		self
+	}
+	/// Values of the x and y axis scale markers in place of those spread by the resolution, an axis given
+	/// `None` keeps the markers of its resolution
+	pub fn custom_ticks(
+		mut self,
+		x_ticks: Option<Vec<CustomTick>>,
+		y_ticks: Option<Vec<CustomTick>>,
+	) -> Self {
+		self.scatter.x_ticks = x_ticks;
+		self.scatter.y_ticks = y_ticks;
+		self
+	}
+	/// Write a tick label at every this many scale markers of the x and y axes, an axis given `None` skips as
+	/// few labels as stop neighbouring labels overlapping
+	pub fn tick_label_every(mut self, x_every: Option<u32>, y_every: Option<u32>) -> Self {
+		self.scatter.x_tick_label_every = x_every;
+		self.scatter.y_tick_label_every = y_every;
+		self
+	}
+	/// Write the x-axis tick labels as the dates and times of epoch timestamps with a strftime style pattern,
+	/// e.g `"%H:%M"`, or `"auto"` to pick one from the span of the axis
+	pub fn x_tick_datetime_format(mut self, pattern: &str) -> Self {
+		self.scatter.x_tick_datetime_format = Some(pattern.to_owned());
+		self
+	}
+	/// Number of mini-markings between the scale markers of the x and y axes, `Some(0)` disables them and an
+	/// axis given `None` draws as many as evenly divide the pixels between its markers
+	pub fn minor_ticks(mut self, x_minor_ticks: Option<u32>, y_minor_ticks: Option<u32>) -> Self {
+		self.scatter.x_minor_ticks = x_minor_ticks;
+		self.scatter.y_minor_ticks = y_minor_ticks;
+		self
+	}
+	/// Draw faint gridlines from every mini-marking
+	pub fn minor_grid(mut self, minor_grid: bool) -> Self {
+		self.scatter.minor_grid = Some(minor_grid);
+		self
+	}
+	/// Whether the lines where x and y are zero are drawn darker and thicker than the grid
+	pub fn highlight_zero(mut self, highlight_zero: bool) -> Self {
+		self.scatter.highlight_zero = Some(highlight_zero);
+		self
+	}
+	/// Whether the x-axis and the y-axis are drawn, each with its scale markers and their labels. Points
+	/// are plotted in the same place either way
+	pub fn show_axes(mut self, show_x_axis: bool, show_y_axis: bool) -> Self {
+		self.scatter.show_x_axis = Some(show_x_axis);
+		self.scatter.show_y_axis = Some(show_y_axis);
+		self
+	}
+	/// Whether the scale markers of the axes are drawn
+	pub fn show_ticks(mut self, show_ticks: bool) -> Self {
+		self.scatter.show_ticks = Some(show_ticks);
+		self
+	}
+	/// Whether the values along the axes are written
+	pub fn show_tick_labels(mut self, show_tick_labels: bool) -> Self {
+		self.scatter.show_tick_labels = Some(show_tick_labels);
+		self
+	}
+	/// Whether the x-axis is drawn with its values decreasing to the right and the y-axis with its values
+	/// decreasing upward
+	pub fn reverse_axes(mut self, x_axis_reversed: bool, y_axis_reversed: bool) -> Self {
+		self.scatter.x_axis_reversed = Some(x_axis_reversed);
+		self.scatter.y_axis_reversed = Some(y_axis_reversed);
+		self
+	}
+	/// Whether arrowheads are drawn at the right end of the x-axis and the top end of the y-axis
+	pub fn axis_arrows(mut self, axis_arrows: bool) -> Self {
+		self.scatter.axis_arrows = Some(axis_arrows);
+		self
+	}
+	/// Pixel thickness of the axis lines and their scale markers
+	pub fn axis_thickness(mut self, thickness: u32) -> Self {
+		self.scatter.axis_thickness = Some(thickness);
+		self
+	}
+	/// Pixel thickness of the background gridlines
+	pub fn grid_thickness(mut self, thickness: u32) -> Self {
+		self.scatter.grid_thickness = Some(thickness);
+		self
+	}
+	/// The pattern the gridlines are drawn with, `dash_length` is the pixel length of the dashes of a
+	/// `GridStyle::Dashed` grid and `None` keeps the default of `4`
+	pub fn grid_style(mut self, grid_style: GridStyle, dash_length: Option<u32>) -> Self {
+		self.scatter.grid_style = Some(grid_style);
+		self.scatter.grid_dash_length = dash_length;
+		self
+	}
+	/// Colours of the axis lines and their scale markers, of the values written along the axes and of the
+	/// gridlines
+	pub fn axis_colours(
+		mut self,
+		axis_colour: Colour,
+		tick_label_colour: Colour,
+		grid_colour: Colour,
+	) -> Self {
+		self.scatter.axis_colour = Some(axis_colour);
+		self.scatter.tick_label_colour = Some(tick_label_colour);
+		self.scatter.grid_colour = Some(grid_colour);
+		self
+	}
+	/// Draw a light grey background grid
+	pub fn grid(mut self, has_grid: bool) -> Self {
+		self.scatter.has_grid = Some(has_grid);
+		self
+	}
+	/// Draw a legend naming each data set
+	pub fn legend(mut self, has_legend: bool) -> Self {
+		self.scatter.has_legend = Some(has_legend);
+		self
+	}
+	/// The corner of the plot equations of best fit curves are written in
+	pub fn equation_corner(mut self, corner: Corner) -> Self {
+		self.scatter.equation_corner = Some(corner);
+		self
+	}
+	/// Where the legend is drawn, inside a corner of the axes or outside of them
+	pub fn legend_position(mut self, position: LegendPosition) -> Self {
+		self.scatter.legend_position = Some(position);
+		self
+	}
+	/// Draw a border around the legend
+	pub fn legend_frame(mut self, frame: bool) -> Self {
+		self.scatter.legend_frame = Some(frame);
+		self
+	}
+	/// The colour filling the legend behind its entries
+	pub fn legend_background(mut self, colour: Colour) -> Self {
+		self.scatter.legend_background = Some(colour);
+		self
+	}
+	/// Draw the legend over a corner inside the axes without reserving canvas space
+	pub fn legend_overlay(mut self, overlay: bool) -> Self {
+		self.scatter.legend_overlay = Some(overlay);
+		self
+	}
+	/// A heading written above the entries of the legend
+	pub fn legend_title(mut self, title: &str) -> Self {
+		self.scatter.legend_title = Some(title.to_string());
+		self
+	}
+	/// The font size of the legend and the distance between its rows as a multiple of the tallest name,
+	/// `None` keeps the default
+	pub fn legend_sizes(mut self, font_size: Option<f32>, row_spacing: Option<f32>) -> Self {
+		self.scatter.legend_font_size = font_size;
+		self.scatter.legend_row_spacing = row_spacing;
+		self
+	}
+	/// How numbers are written in the csv files
+	pub fn number_format(mut self, number_format: NumberFormat) -> Self {
+		self.scatter.number_format = Some(number_format);
+		self
+	}
+	/// How much of the graph surrounding the plot area is drawn
+	pub fn chrome(mut self, chrome: Chrome) -> Self {
+		self.scatter.chrome = Some(chrome);
+		self
+	}
+	/// How colours are picked for data sets without one
+	pub fn colour_assignment(mut self, colour_assignment: ColourAssignment) -> Self {
+		self.scatter.colour_assignment = Some(colour_assignment);
+		self
+	}
+	/// The pool of colours given to data sets without one
+	pub fn palette(mut self, palette: Palette) -> Self {
+		self.scatter.palette = Some(palette);
+		self
+	}
+	/// Language whose separators are used to write tick labels, e.g `"de"`
+	pub fn locale(mut self, locale: &str) -> Self {
+		self.scatter.locale = Some(locale.to_owned());
+		self
+	}
+	/// Draw the graph `factor` times larger and shrink it back to smooth edges
+	pub fn supersample(mut self, factor: u32) -> Self {
+		self.scatter.supersample = Some(factor);
+		self
+	}
+	/// Smooth the edges of circles, triangles, stars and best fit curves
+	pub fn antialias(mut self, antialias: bool) -> Self {
+		self.scatter.antialias = Some(antialias);
+		self
+	}
+	/// Add a data set to plot, sets are drawn in the order they are added
+	pub fn add_data_set(mut self, data_set: DataSetBuilder) -> Self {
+		self.scatter.data_sets.push(data_set.data_set);
+		self
+	}
+	/// Validate the graph and resolve the values derived from its data sets, such as colours, exactly as
+	/// for a deserialised config
+	pub fn build(self) -> Result<Scatter, PlotError> {
+		self.scatter.prepare()
+	}
+	/// Build and draw the graph with the default [`RenderOptions`], returning the image without saving it
+	pub fn render(self) -> Result<ImageBuffer<Rgba<u8>, Vec<u8>>, PlotError> {
+		scatter_render(&self.build()?, &RenderOptions::default())
+	}
+}
+
+/// Builds a data set read from the columns of a csv file or held in memory. Settings which select csv
+/// columns are ignored by a set held in memory
+#[derive(Debug)]
+pub struct DataSetBuilder {
+	/// The data set being built
+	data_set: DataSet,
+}
+
+impl DataSetBuilder {
+	/// Plot the values of two zero indexed columns of a csv file with headers, drawn as black crosses
+	pub fn new(
+		name: &str,
+		data_path: &str,
+		x_axis_csv_column: usize,
+		y_axis_csv_column: usize,
+	) -> DataSetBuilder {
+		DataSetBuilder {
+			data_set: DataSet {
+				data_path: data_path.to_owned(),
+				one_set_per_file: None,
+				has_headers: true,
+				x_axis_csv_column: CsvColumn::Index(x_axis_csv_column),
+				x_axis_error_bar_csv_column: None,
+				y_axis_csv_column: CsvColumn::Index(y_axis_csv_column),
+				y_axis_error_bar_csv_column: None,
+				name: name.to_owned(),
+				colour: None,
+				colour_csv_column: None,
+				colour_by: None,
+				opacity: None,
+				symbol: DataSymbol::Cross,
+				symbol_radius: 3,
+				symbol_thickness: 0,
+				symbol_csv_column: None,
+				symbol_map: None,
+				best_fit: None,
+				sort_by_x: None,
+				size_by_residual: None,
+				max_symbol_radius: None,
+				show_fit_statistics: None,
+				best_fit_thickness: None,
+				best_fit_style: None,
+				best_fit_domain: None,
+				fit_label: None,
+				show_equation: None,
+				x_transform: None,
+				y_transform: None,
+				x_relative_to_first: None,
+				fill_gradient: None,
+				include_in_bounds: None,
+				show_in_legend: None,
+				legend_order: None,
+				y_axis: None,
+				points: None,
+			},
+		}
+	}
+	/// Plot `(x, y)` points held in memory rather than read from a csv, drawn as black crosses
+	pub fn from_points(name: &str, points: &[(f32, f32)]) -> DataSetBuilder {
+		DataSetBuilder::from_values(
+			name,
+			points.iter().map(|(x, y)| (*x, None, *y, None)).collect(),
+		)
+	}
+	/// Plot `(x, ux, y, uy)` values held in memory, where `ux` and `uy` are optional uncertainties drawn as
+	/// error bars, rather than read from a csv
+	pub fn from_values(name: &str, values: Vec<DataValues>) -> DataSetBuilder {
+		let mut builder = DataSetBuilder::new(name, "", 0, 1);
+		builder.data_set.points = Some(values);
+		builder
+	}
+	/// Whether the first row of the csv holds headers rather than data
+	pub fn has_headers(mut self, has_headers: bool) -> Self {
+		self.data_set.has_headers = has_headers;
+		self
+	}
+	/// Plot each file matched by a glob pattern in the data path as its own set named after the file
+	pub fn one_set_per_file(mut self, one_set_per_file: bool) -> Self {
+		self.data_set.one_set_per_file = Some(one_set_per_file);
+		self
+	}
+	/// Derive the x values from an expression of several columns, e.g `"col(2) / col(3)"`
+	pub fn x_axis_expression(mut self, expression: &str) -> Self {
+		self.data_set.x_axis_csv_column = CsvColumn::Expression(expression.to_owned());
+		self
+	}
+	/// Derive the y values from an expression of several columns, e.g `"col(2) / col(3) * 100"`
+	pub fn y_axis_expression(mut self, expression: &str) -> Self {
+		self.data_set.y_axis_csv_column = CsvColumn::Expression(expression.to_owned());
+		self
+	}
+	/// Columns holding the uncertainty of the x and y values
+	pub fn error_bar_columns(mut self, x_column: Option<usize>, y_column: Option<usize>) -> Self {
+		self.data_set.x_axis_error_bar_csv_column = x_column;
+		self.data_set.y_axis_error_bar_csv_column = y_column;
+		self
+	}
+	/// The colour points are drawn with, otherwise one is picked from the colour cycle
+	pub fn colour(mut self, colour: Colour) -> Self {
+		self.data_set.colour = Some(colour);
+		self
+	}
+	/// A column whose cells colour each point, either naming a colour or a category given one from the
+	/// colour cycle with its own row in the legend
+	pub fn colour_csv_column(mut self, column: usize) -> Self {
+		self.data_set.colour_csv_column = Some(column);
+		self
+	}
+	/// Colour each point by the value in a column, mapped along `colormap` from the smallest value of every
+	/// set coloured by value to the largest, with a colour bar drawn beside the axes
+	pub fn colour_by(mut self, csv_column: usize, colormap: Colormap) -> Self {
+		self.data_set.colour_by = Some(ColourBy {
+			csv_column,
+			colormap,
+		});
+		self
+	}
+	/// How opaque the points and their error bars are drawn from 0 to 1, so that overlapping points darken
+	/// progressively
+	pub fn opacity(mut self, opacity: f32) -> Self {
+		self.data_set.opacity = Some(opacity);
+		self
+	}
+	/// The shape of each point with its size and line thickness in `1 + n` pixels
+	pub fn symbol(mut self, symbol: DataSymbol, radius: u32, thickness: u32) -> Self {
+		self.data_set.symbol = symbol;
+		self.data_set.symbol_radius = radius;
+		self.data_set.symbol_thickness = thickness;
+		self
+	}
+	/// A column whose cells pick the symbol of each point, either naming a symbol or a key of `symbol_map`,
+	/// with a row in the legend for each
+	pub fn symbol_csv_column(
+		mut self,
+		column: usize,
+		symbol_map: Option<BTreeMap<String, DataSymbol>>,
+	) -> Self {
+		self.data_set.symbol_csv_column = Some(column);
+		self.data_set.symbol_map = symbol_map;
+		self
+	}
+	/// A curve to draw alongside the points
+	pub fn best_fit(mut self, best_fit: BestFit) -> Self {
+		self.data_set.best_fit = Some(best_fit);
+		self
+	}
+	/// Sort the values ascending by x before plotting
+	pub fn sort_by_x(mut self, sort_by_x: bool) -> Self {
+		self.data_set.sort_by_x = Some(sort_by_x);
+		self
+	}
+	/// Scale each symbol by its residual against the best fit up to `max_symbol_radius`, defaulting to
+	/// three times `symbol_radius + 1`
+	pub fn size_by_residual(mut self, max_symbol_radius: Option<u32>) -> Self {
+		self.data_set.size_by_residual = Some(true);
+		self.data_set.max_symbol_radius = max_symbol_radius;
+		self
+	}
+	/// Write the equation, R² and root mean square error of the best fit beside the end of the curve
+	pub fn show_fit_statistics(mut self, show_fit_statistics: bool) -> Self {
+		self.data_set.show_fit_statistics = Some(show_fit_statistics);
+		self
+	}
+	/// Pixel thickness of the best fit curve
+	pub fn best_fit_thickness(mut self, thickness: u32) -> Self {
+		self.data_set.best_fit_thickness = Some(thickness);
+		self
+	}
+	/// The pattern the best fit curve is drawn with
+	pub fn best_fit_style(mut self, style: LineStyle) -> Self {
+		self.data_set.best_fit_style = Some(style);
+		self
+	}
+	/// Restrict the best fit curve to x values from `min` to `max`
+	pub fn best_fit_domain(mut self, min: f32, max: f32) -> Self {
+		self.data_set.best_fit_domain = Some((min, max));
+		self
+	}
+	/// Name of the best fit curve in the legend
+	pub fn fit_label(mut self, label: &str) -> Self {
+		self.data_set.fit_label = Some(label.to_owned());
+		self
+	}
+	/// Write the equation of the best fit curve in a corner of the plot
+	pub fn show_equation(mut self, show_equation: bool) -> Self {
+		self.data_set.show_equation = Some(show_equation);
+		self
+	}
+	/// `(multiplier, offset)` applied to each x value as it is read
+	pub fn x_transform(mut self, multiplier: f32, offset: f32) -> Self {
+		self.data_set.x_transform = Some((multiplier, offset));
+		self
+	}
+	/// `(multiplier, offset)` applied to each y value as it is read
+	pub fn y_transform(mut self, multiplier: f32, offset: f32) -> Self {
+		self.data_set.y_transform = Some((multiplier, offset));
+		self
+	}
+	/// Treat the x values as epoch timestamps in seconds and plot the time elapsed since the first row
+	pub fn x_relative_to_first(mut self, unit: RelativeUnit) -> Self {
+		self.data_set.x_relative_to_first = Some(unit);
+		self
+	}
+	/// Fill the area between the series and the x-axis
+	pub fn fill_gradient(mut self, fill_gradient: FillGradient) -> Self {
+		self.data_set.fill_gradient = Some(fill_gradient);
+		self
+	}
+	/// Whether the values of the set are used to find the axis ranges
+	pub fn include_in_bounds(mut self, include_in_bounds: bool) -> Self {
+		self.data_set.include_in_bounds = Some(include_in_bounds);
+		self
+	}
+	/// Whether the set and its best fit curve are given rows in the legend
+	pub fn show_in_legend(mut self, show_in_legend: bool) -> Self {
+		self.data_set.show_in_legend = Some(show_in_legend);
+		self
+	}
+	/// The position of the set's rows in the legend, independent of the order sets are drawn in
+	pub fn legend_order(mut self, legend_order: u32) -> Self {
+		self.data_set.legend_order = Some(legend_order);
+		self
+	}
+	/// The y-axis the values of the set are plotted against
+	pub fn y_axis(mut self, y_axis: YAxis) -> Self {
+		self.data_set.y_axis = Some(y_axis);
+		self
+	}
+}
@@ -1,284 +1,1031 @@
 //! Reads data sets to identify data ranges, points for plotting and legend fields
 
+use csv::StringRecord;
 use image::{ImageBuffer, Rgba};
-use tracing::{debug, error};
+use std::{cmp::Ordering, path::Path};
+use tracing::{debug, info, warn};
 
 use crate::{
-	canvas::{legend::LegendField, plot::DataPoint},
-	data::load_data,
+	canvas::{
+		axes::{get_reversed_value, YAxis},
+		best_fit::{get_default_best_fit_thickness, BestFit},
+		format::{format_value, FormatHint},
+		legend::{LegendField, LegendSwatch},
+		plot::{scale_symbol_size, DataPoint, DataSymbol},
+		to_file_name,
+	},
+	colours::{Colormap, Colour, Palette},
+	data::{
+		expand_data_path, expr::Expression, for_each_record, CsvColumn, NumberFormat,
+		RelativeTimeOrigin,
+	},
+	error::PlotError,
 };
 
 use super::DataSet;
 
-/// Reads the supplied csv files and finds the minimum and maximum x and y values across all sets.
-/// This faciliates drawing values on axes and finding the ratio of pixels to a data point for plotting
-pub fn get_data_bounds(data_set: &[DataSet], csv_delimiter: &str) -> ((f32, f32), (f32, f32)) {
-	let mut min_x = f32::MAX;
-	let mut min_ux = Some(f32::MAX); // TODO: unused at present
-	let mut min_y = f32::MAX;
-	let mut min_uy = Some(f32::MAX); // TODO: unused at present
-	let mut max_x = f32::MIN;
-	let mut max_ux = Some(f32::MIN); // TODO: unused at present
-	let mut max_y = f32::MIN;
-	let mut max_uy = Some(f32::MIN); // TODO: unused at present
-								 // iterate over each set
+/// The values extracted from a single csv row of a data set in the form `(x, ux, y, uy)`
+pub type DataValues = (f32, Option<f32>, f32, Option<f32>);
+
+/// The colour of each value of a set whose points are coloured by a column of its csv
+#[derive(Debug, Clone, PartialEq)]
+pub enum PointColours {
+	/// Colours read from the `colour_csv_column` of the set
+	Cells {
+		/// The colour of each value, in the same order as the values
+		colours: Vec<Colour>,
+		/// The cells of the column which are not colours, in order of first appearance, with the colour
+		/// each was given from the colour cycle
+		categories: Vec<(String, Colour)>,
+	},
+	/// Values read from the `colour_by` column of the set, mapped along the colormap once the range of
+	/// every set coloured by value is known
+	Values {
+		/// The value of each point, in the same order as the values
+		values: Vec<f32>,
+		/// The gradient the values are mapped along
+		colormap: Colormap,
+	},
+}
+
+impl PointColours {
+	/// The colour of the `i`th value, a value is mapped along its colormap by its position in `colour_range`.
+	/// `None` when the value has no colour of its own, e.g it is not a finite number
+	pub fn get(&self, i: usize, colour_range: Option<(f32, f32)>) -> Option<Colour> {
+		match self {
+			PointColours::Cells { colours, .. } => colours.get(i).copied(),
+			PointColours::Values { values, colormap } => {
+				let value = values.get(i).filter(|value| value.is_finite())?;
+				let (min, max) = colour_range?;
+				// every value is the same so sits in the middle of the gradient
+				let t = if max > min {
+					(value - min) / (max - min)
+				} else {
+					0.5
+				};
+				Some(colormap.sample(t))
+			}
+		}
+	}
+	/// The colour of the set's row in the legend when it differs from the set's own, the middle of a
+	/// colormap
+	pub fn legend_colour(&self) -> Option<Colour> {
+		match self {
+			PointColours::Cells { .. } => None,
+			PointColours::Values { colormap, .. } => Some(colormap.sample(0.5)),
+		}
+	}
+	/// The categories given their own rows in the legend
+	pub fn categories(&self) -> &[(String, Colour)] {
+		match self {
+			PointColours::Cells { categories, .. } => categories,
+			PointColours::Values { .. } => &[],
+		}
+	}
+}
+
+/// The point colours of each set, `None` for sets without a colour column
+pub type SetPointColours = Vec<Option<PointColours>>;
+
+/// The symbol of each value of a set whose points take their symbol from a column of its csv
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointSymbols {
+	/// The symbol of each value, in the same order as the values, `None` for an empty cell which keeps the
+	/// symbol of the set
+	symbols: Vec<Option<DataSymbol>>,
+	/// The non-empty cells of the column, in order of first appearance, with the symbol each resolved to
+	categories: Vec<(String, DataSymbol)>,
+}
+
+impl PointSymbols {
+	/// The symbol of the `i`th value, `None` when it keeps the symbol of the set
+	pub fn get(&self, i: usize) -> Option<DataSymbol> {
+		self.symbols.get(i).copied().flatten()
+	}
+	/// The categories given their own rows in the legend
+	pub fn categories(&self) -> &[(String, DataSymbol)] {
+		&self.categories
+	}
+}
+
+/// The point symbols of each set, `None` for sets without a symbol column
+pub type SetPointSymbols = Vec<Option<PointSymbols>>;
+
+/// A cell of the column that colours the points of a set
+#[derive(Debug, Clone)]
+enum ColourCell {
+	/// The text of a `colour_csv_column` cell
+	Text(String),
+	/// The number in a `colour_by` cell
+	Value(f32),
+}
+
+/// The values read from the csv files of a set with their x values at full precision and the cells of the
+/// colour column, either of which are empty when unused, along with the symbols of a set with a symbol
+/// column
+type CsvValues = (
+	Vec<DataValues>,
+	Vec<f64>,
+	Vec<ColourCell>,
+	Option<PointSymbols>,
+);
+
+/// The values of each set with the colours and symbols of the points of sets that read them from a column
+type LoadedValues = (Vec<Vec<DataValues>>, SetPointColours, SetPointSymbols);
+
+/// The smallest and largest finite values of every set coloured by value, which span its colormap
+pub fn get_colour_range(point_colours: &[Option<PointColours>]) -> Option<(f32, f32)> {
+	point_colours
+		.iter()
+		.flatten()
+		.flat_map(|point_colours| match point_colours {
+			PointColours::Values { values, .. } => values.as_slice(),
+			PointColours::Cells { .. } => &[],
+		})
+		.filter(|value| value.is_finite())
+		.fold(None, |range, value| match range {
+			Some((min, max)) => Some((f32::min(min, *value), f32::max(max, *value))),
+			None => Some((*value, *value)),
+		})
+}
+
+/// Extracts the values of each set, either held in memory or read from its csv files in a single pass, then
+/// applies the processing configured for the set such as transforms and sorting. Values from either source
+/// are processed identically. The values of each set are returned in the same order as the sets, along with
+/// the colour of each value of the sets which have a `colour_csv_column` or `colour_by` and the symbol of
+/// each value of the sets which have a `symbol_csv_column`
+pub fn load_data_values(
+	data_set: &[DataSet],
+	csv_delimiter: &str,
+	number_format: &NumberFormat,
+	relative_time_origin: RelativeTimeOrigin,
+	palette: Palette,
+) -> Result<LoadedValues, PlotError> {
+	let mut values = Vec::with_capacity(data_set.len());
+	// x values of sets which are converted to elapsed time, kept at full precision until the origin is known
+	let mut timestamps: Vec<Vec<f64>> = Vec::with_capacity(data_set.len());
+	// the cells of the colour column of each set, one for each value
+	let mut colour_cells: Vec<Vec<ColourCell>> = Vec::with_capacity(data_set.len());
+	let mut point_symbols: SetPointSymbols = Vec::with_capacity(data_set.len());
 	for set in data_set.iter() {
-		// read the csv each set corresponds to
-		let data = load_data(set.data_path.as_str(), set.has_headers, csv_delimiter);
-		// used for error debuging
-		let mut row = 1;
-		for record in data.iter() {
-			// x
-			match record.get(set.x_axis_csv_column) {
-				Some(string_value) => match string_value.parse::<f32>() {
-					// determine the smallest and highest x value
-					Ok(value) => {
-						if value < min_x {
-							min_x = value;
-						}
-						if value > max_x {
-							max_x = value;
+		let (set_values, set_timestamps, set_colour_cells, set_point_symbols) = match &set.points {
+			Some(points) => {
+				debug!(
+					"Using {} values of {} held in memory",
+					points.len(),
+					set.name
+				);
+				let set_timestamps = match set.x_relative_to_first {
+					Some(_) => points.iter().map(|(x, _, _, _)| *x as f64).collect(),
+					None => Vec::new(),
+				};
+				(points.clone(), set_timestamps, Vec::new(), None)
+			}
+			None => read_csv_values(set, csv_delimiter, number_format)?,
+		};
+		values.push(set_values);
+		timestamps.push(set_timestamps);
+		colour_cells.push(set_colour_cells);
+		point_symbols.push(set_point_symbols);
+	}
+	// the earliest first timestamp of all relative sets
+	let earliest = timestamps
+		.iter()
+		.filter_map(|t| t.first())
+		.cloned()
+		.reduce(f64::min);
+	for ((((set, set_values), set_timestamps), set_colour_cells), set_point_symbols) in data_set
+		.iter()
+		.zip(values.iter_mut())
+		.zip(timestamps)
+		.zip(colour_cells.iter_mut())
+		.zip(point_symbols.iter_mut())
+	{
+		if let (Some(unit), Some(first)) = (set.x_relative_to_first, set_timestamps.first()) {
+			let origin = match relative_time_origin {
+				RelativeTimeOrigin::Earliest => earliest.unwrap_or(*first),
+				RelativeTimeOrigin::PerDataset => *first,
+			};
+			debug!("Measuring x of {} relative to {}", set.name, origin);
+			for (value, timestamp) in set_values.iter_mut().zip(set_timestamps.iter()) {
+				value.0 = unit.from_seconds(timestamp - origin) as f32;
+			}
+		}
+		for value in set_values.iter_mut() {
+			let (x, ux) = apply_transform(set.x_transform, value.0, value.1);
+			let (y, uy) = apply_transform(set.y_transform, value.2, value.3);
+			*value = (x, ux, y, uy);
+		}
+		if set.sort_by_x == Some(true) {
+			// stable so that equal x values keep their csv order, the colour and symbol of each value move
+			// with it
+			let mut order: Vec<usize> = (0..set_values.len()).collect();
+			order.sort_by(|a, b| {
+				set_values[*a]
+					.0
+					.partial_cmp(&set_values[*b].0)
+					.unwrap_or(Ordering::Equal)
+			});
+			*set_values = order.iter().map(|i| set_values[*i]).collect();
+			if !set_colour_cells.is_empty() {
+				*set_colour_cells = order.iter().map(|i| set_colour_cells[*i].clone()).collect();
+			}
+			if let Some(set_point_symbols) = set_point_symbols {
+				set_point_symbols.symbols = order
+					.iter()
+					.map(|i| set_point_symbols.symbols[*i])
+					.collect();
+			}
+		}
+	}
+	let point_colours = get_point_colours(data_set, &colour_cells, palette);
+	Ok((values, point_colours, point_symbols))
+}
+
+/// Resolve the colour of each value of the sets with a `colour_csv_column`. A cell naming a colour or
+/// holding a `#rrggbb` hex colour is drawn in it, an empty cell takes the colour of the set and any other
+/// cell is a category given the next colour of the palette. A category has the same colour in every set. The
+/// values of sets with a `colour_by` are kept to be mapped along their colormap
+fn get_point_colours(
+	data_set: &[DataSet],
+	colour_cells: &[Vec<ColourCell>],
+	palette: Palette,
+) -> SetPointColours {
+	let mut assigned: Vec<(String, Colour)> = Vec::new();
+	let mut point_colours = Vec::with_capacity(data_set.len());
+	for (set, cells) in data_set.iter().zip(colour_cells.iter()) {
+		if let Some(colour_by) = set.colour_by {
+			let values = cells
+				.iter()
+				.map(|cell| match cell {
+					ColourCell::Value(value) => *value,
+					ColourCell::Text(_) => f32::NAN,
+				})
+				.collect();
+			point_colours.push(Some(PointColours::Values {
+				values,
+				colormap: colour_by.colormap,
+			}));
+			continue;
+		}
+		if set.colour_csv_column.is_none() {
+			point_colours.push(None);
+			continue;
+		}
+		let mut colours = Vec::with_capacity(cells.len());
+		let mut categories: Vec<(String, Colour)> = Vec::new();
+		for cell in cells.iter().map(|cell| match cell {
+			ColourCell::Text(text) => text.trim(),
+			ColourCell::Value(_) => "",
+		}) {
+			let named = match cell.strip_prefix('#') {
+				Some(_) => Colour::from_hex(cell).ok().map(Colour::Hex),
+				None => Colour::from_name(cell).ok(),
+			};
+			let colour = match named {
+				Some(colour) => colour,
+				None if cell.is_empty() => set.colour(),
+				None => {
+					let colour = match assigned.iter().find(|(category, _)| category == cell) {
+						Some((_, colour)) => *colour,
+						None => {
+							let colours = palette.colours();
+							let colour = colours[assigned.len() % colours.len()];
+							debug!("Category {} has colour {:?}", cell, colour);
+							assigned.push((cell.to_owned(), colour));
+							colour
 						}
+					};
+					if !categories.iter().any(|(category, _)| category == cell) {
+						categories.push((cell.to_owned(), colour));
 					}
-					Err(e) => {
-						error!(
-							"Could not parse data in column {}, row {} for x axis, error: {}",
-							set.x_axis_csv_column, row, e
-						);
-						std::process::exit(1);
-					}
-				},
-				None => {
-					error!(
-						"Could not extract record in column {} for x axis, row {}",
-						set.x_axis_csv_column, row
-					);
-					std::process::exit(1);
+					colour
 				}
 			};
-			// ux
-			match set.x_axis_error_bar_csv_column {
-				Some(column) => match record.get(column) {
-					Some(string_value) => match string_value.parse::<f32>() {
-						// determine the smallest uncertainty value in x
-						Ok(value) => {
-							if value < min_ux.unwrap() {
-								min_ux = Some(value)
-							}
-							if value > max_ux.unwrap() {
-								max_ux = Some(value)
-							}
-						}
-						Err(e) => {
-							error!(
-										"Could not parse data in column {}, row{}, to f32 for error bar x, error: {}",
-										set.x_axis_csv_column, row, e
-									);
-							std::process::exit(1);
-						}
-					},
+			colours.push(colour);
+		}
+		point_colours.push(Some(PointColours::Cells {
+			colours,
+			categories,
+		}));
+	}
+	point_colours
+}
+
+/// Stream the csv files a set corresponds to, a glob pattern concatenates each match, parsing the columns
+/// selected by the set. When the x values are measured relative to the first they are also returned at
+/// full precision, as are the cells of the colour column of a set that has one. The cells of a symbol column
+/// are resolved as they are read, a cell that is neither a key of the `symbol_map` nor the name of a symbol
+/// is an error
+fn read_csv_values(
+	set: &DataSet,
+	csv_delimiter: &str,
+	number_format: &NumberFormat,
+) -> Result<CsvValues, PlotError> {
+	let x_expression = get_column_expression(set, &set.x_axis_csv_column, "x axis")?;
+	let y_expression = get_column_expression(set, &set.y_axis_csv_column, "y axis")?;
+	let mut set_values: Vec<DataValues> = Vec::new();
+	let mut set_timestamps: Vec<f64> = Vec::new();
+	let mut set_colour_cells: Vec<ColourCell> = Vec::new();
+	let mut set_point_symbols = set.symbol_csv_column.map(|_| PointSymbols {
+		symbols: Vec::new(),
+		categories: Vec::new(),
+	});
+	let mut skipped_rows = 0;
+	for path in expand_data_path(&set.data_path)? {
+		for_each_record(&path, set.has_headers, csv_delimiter, |row, record| {
+			let cell = |column: usize, description: &str| {
+				parse_cell(record, column, &path, row, description, number_format)
+			};
+			let x = x_expression.evaluate(&mut |column| cell(column, "x axis"))?;
+			let ux = match set.x_axis_error_bar_csv_column {
+				Some(column) => Some(cell(column, "error bar x")? as f32),
+				None => None,
+			};
+			let y = y_expression.evaluate(&mut |column| cell(column, "y axis"))?;
+			let uy = match set.y_axis_error_bar_csv_column {
+				Some(column) => Some(cell(column, "error bar y")? as f32),
+				None => None,
+			};
+			let colour_cell = match (set.colour_by, set.colour_csv_column) {
+				(Some(colour_by), _) => Some(ColourCell::Value(cell(
+					colour_by.csv_column,
+					"colour by",
+				)? as f32)),
+				(None, Some(column)) => match record.get(column) {
+					Some(text) => Some(ColourCell::Text(text.to_owned())),
 					None => {
-						error!(
-							"Could not extract record in column {} for error bar x, row {}",
-							set.x_axis_csv_column, row
-						);
-						std::process::exit(1);
+						return Err(PlotError::CsvParse {
+							path: path.clone(),
+							row,
+							column,
+							description: String::from("colour"),
+							message: format!("the row has only {} columns", record.len()),
+						})
 					}
 				},
-				None => min_ux = None,
+				(None, None) => None,
 			};
-			//y
-			match record.get(set.y_axis_csv_column) {
-				Some(string_value) => match string_value.parse::<f32>() {
-					// determine the smallest y value
-					Ok(value) => {
-						if value < min_y {
-							min_y = value
-						}
-						if value > max_y {
-							max_y = value
-						}
+			let symbol_cell =
+				match set.symbol_csv_column {
+					Some(column) => {
+						let text = record.get(column).map(str::trim).ok_or_else(|| {
+							PlotError::CsvParse {
+								path: path.clone(),
+								row,
+								column,
+								description: String::from("symbol"),
+								message: format!("the row has only {} columns", record.len()),
+							}
+						})?;
+						let symbol = match text.is_empty() {
+							true => None,
+							false => Some(
+								set.symbol_map
+									.as_ref()
+									.and_then(|symbol_map| symbol_map.get(text).copied())
+									.or_else(|| DataSymbol::from_name(text))
+									.ok_or_else(|| PlotError::CsvParse {
+										path: path.clone(),
+										row,
+										column,
+										description: String::from("symbol"),
+										message: format!(
+										"\"{}\" is neither a key of the symbol_map nor the name of a symbol",
+										text
+									),
+									})?,
+							),
+						};
+						Some((text, symbol))
 					}
-					Err(e) => {
-						error!(
-									"Could not parse data in column {}, row {} to f32 for y axis, error: {}",
-									set.x_axis_csv_column, row, e
-								);
-						std::process::exit(1);
+					None => None,
+				};
+			// an expression may divide by zero, such rows have no value to plot
+			if !x.is_finite() || !y.is_finite() {
+				debug!(
+					"Skipping row {} of {:?} in {}, evaluated to ({}, {})",
+					row, path, set.name, x, y
+				);
+				skipped_rows += 1;
+				return Ok(());
+			}
+			if set.x_relative_to_first.is_some() {
+				set_timestamps.push(x);
+			}
+			set_values.push((x as f32, ux, y as f32, uy));
+			set_colour_cells.extend(colour_cell);
+			if let (Some(point_symbols), Some((text, symbol))) =
+				(set_point_symbols.as_mut(), symbol_cell)
+			{
+				if let Some(symbol) = symbol {
+					if !point_symbols
+						.categories
+						.iter()
+						.any(|(category, _)| category == text)
+					{
+						point_symbols.categories.push((text.to_owned(), symbol));
 					}
-				},
-				None => {
-					error!(
-						"Could not extract record in column {} for y axis, row {}",
-						set.x_axis_csv_column, row
-					);
-					std::process::exit(1);
 				}
-			};
-			// uy
-			match set.y_axis_error_bar_csv_column {
-				Some(column) => match record.get(column) {
-					// determine the smallest uncertainty value in y
-					Some(string_value) => match string_value.parse::<f32>() {
-						Ok(value) => {
-							if value < min_uy.unwrap() {
-								min_uy = Some(value)
-							}
-							if value > max_uy.unwrap() {
-								max_uy = Some(value)
-							}
-						}
-						Err(e) => {
-							error!(
-										"Could not parse data in column {}, row {} to f32 for y error bar, error: {}",
-										set.x_axis_csv_column, row, e
-									);
-							std::process::exit(1);
-						}
-					},
-					None => {
-						error!(
-							"Could not extract record in column {}, row {} for y error bar",
-							set.x_axis_csv_column, row
-						);
-						std::process::exit(1);
-					}
-				},
-				None => min_uy = None,
-			};
-			row += 1;
-		}
+				point_symbols.symbols.push(symbol);
+			}
+			Ok(())
+		})?;
+	}
+	if skipped_rows > 0 {
+		warn!(
+			"Skipped {} rows of {} where a column expression was not a finite number",
+			skipped_rows, set.name
+		);
+	}
+	Ok((
+		set_values,
+		set_timestamps,
+		set_colour_cells,
+		set_point_symbols,
+	))
+}
+
+/// Parse the column of an axis into an expression, the error names the set and expression if it is invalid
+fn get_column_expression(
+	set: &DataSet,
+	column: &CsvColumn,
+	description: &str,
+) -> Result<Expression, PlotError> {
+	match column {
+		CsvColumn::Index(index) => Ok(Expression::Column(*index)),
+		CsvColumn::Expression(text) => Expression::parse(text).map_err(|e| {
+			PlotError::Config(format!(
+				"Invalid {} column expression \"{}\" in data set {}: {}",
+				description, text, set.name, e
+			))
+		}),
+	}
+}
+
+/// Apply an optional `(multiplier, offset)` transform to a value, its uncertainty is a magnitude so is
+/// only scaled by the multiplier
+fn apply_transform(
+	transform: Option<(f32, f32)>,
+	value: f32,
+	uncertainty: Option<f32>,
+) -> (f32, Option<f32>) {
+	match transform {
+		Some((multiplier, offset)) => (
+			value * multiplier + offset,
+			uncertainty.map(|u| u * multiplier.abs()),
+		),
+		None => (value, uncertainty),
+	}
+}
+
+/// Extract a value from a column of a csv record, `description` is used to identify the value in error messages
+fn parse_cell(
+	record: &StringRecord,
+	column: usize,
+	path: &Path,
+	row: usize,
+	description: &str,
+	number_format: &NumberFormat,
+) -> Result<f64, PlotError> {
+	let parse_error = |message: String| PlotError::CsvParse {
+		path: path.to_owned(),
+		row,
+		column,
+		description: description.to_owned(),
+		message,
+	};
+	match record.get(column) {
+		Some(string_value) => number_format
+			.parse_f64(string_value)
+			.map_err(|e| parse_error(e.to_string())),
+		None => Err(parse_error(format!(
+			"the row has only {} columns",
+			record.len()
+		))),
+	}
+}
+
+/// The values of the sets included in the bounds, or of every set if they are all excluded. Given a
+/// `y_axis` only the sets plotted against it are considered
+pub fn get_values_in_bounds<'a>(
+	data_set: &'a [DataSet],
+	data_values: &'a [Vec<DataValues>],
+	y_axis: Option<YAxis>,
+) -> impl Iterator<Item = &'a DataValues> {
+	let is_on_axis = move |set: &DataSet| y_axis.map_or(true, |axis| set.y_axis() == axis);
+	let is_any_included = data_set
+		.iter()
+		.any(|set| is_on_axis(set) && set.is_in_bounds());
+	if !is_any_included && y_axis.is_none() {
+		warn!(
+			"Every data set has include_in_bounds disabled, the axes are sized to fit all of them"
+		);
+	}
+	data_set
+		.iter()
+		.zip(data_values.iter())
+		.filter(move |(set, _)| is_on_axis(set) && (set.is_in_bounds() || !is_any_included))
+		.flat_map(|(_, values)| values.iter())
+}
+
+/// Finds the minimum and maximum x and y of some values, whether they were read from csv or held in memory.
+/// This faciliates drawing values on axes and finding the ratio of pixels to a data point for plotting
+pub fn get_data_bounds<'a, I>(values: I) -> ((f32, f32), (f32, f32))
+where
+	I: IntoIterator<Item = &'a DataValues>,
+{
+	let mut min_x = f32::MAX;
+	let mut min_y = f32::MAX;
+	let mut max_x = f32::MIN;
+	let mut max_y = f32::MIN;
+	for (x, _, y, _) in values {
+		min_x = min_x.min(*x);
+		max_x = max_x.max(*x);
+		min_y = min_y.min(*y);
+		max_y = max_y.max(*y);
 	}
 	((min_x, min_y), (max_x, max_y))
 }
 
-/// Iterate through the data sets extracting the values from the csv and plot them
-pub fn build_data_points(
+/// Find the curve drawn through each set, in the same order as the sets, fitting any regression to the
+/// values of its set
+pub fn fit_best_fits(
 	data_set: &[DataSet],
-	csv_delimiter: &str,
+	values: &[Vec<DataValues>],
+) -> Result<Vec<Option<BestFit>>, PlotError> {
+	data_set
+		.iter()
+		.zip(values.iter())
+		.map(|(set, values)| {
+			set.best_fit
+				.as_ref()
+				.map(|curve| {
+					let points: Vec<(f32, f32)> =
+						values.iter().map(|(x, _, y, _)| (*x, *y)).collect();
+					curve.fit(&points)
+				})
+				.transpose()
+		})
+		.collect()
+}
+/// Iterate through each data set paired with its fitted best fit curve, values and the colour and symbol of
+/// each value when it has a colour or symbol column and plot them, mirroring them about the middle of the x-axis and the y-axis
+/// when `is_reversed`. Values coloured by a colormap are mapped along it by their position in `colour_range`
+#[allow(clippy::too_many_arguments)]
+pub fn build_data_points<'a, I>(
+	sets_values: I,
 	canvas: &mut ImageBuffer<Rgba<u8>, Vec<u8>>,
 	x_scale_factor: f32,
 	y_scale_factor: f32,
 	origin_offset: (u32, u32),
-) {
+	origin_values: (f32, f32),
+	x_data_min_max_limits: (i32, i32),
+	y_data_min_max_limits: (i32, i32),
+	is_reversed: (bool, bool),
+	is_range_fixed: bool,
+	colour_range: Option<(f32, f32)>,
+	antialias: bool,
+	scale: u32,
+) where
+	I: IntoIterator<
+		Item = (
+			&'a DataSet,
+			Option<&'a BestFit>,
+			&'a [DataValues],
+			Option<&'a PointColours>,
+			Option<&'a PointSymbols>,
+		),
+	>,
+{
 	debug!("Building data points...");
 	// iterate over each set
-	for set in data_set.iter() {
-		// read the csv each set corresponds to
-		let data = load_data(set.data_path.as_str(), set.has_headers, csv_delimiter);
-		// used for error debuging
-		let mut row = 1;
-		for record in data.iter() {
-			// extract the x-y values from each record
-			let x = match record.get(set.x_axis_csv_column) {
-				Some(string_value) => match string_value.parse::<f32>() {
-					Ok(value) => value,
-					Err(e) => {
-						error!(
-							"Could not parse data in column {}, row {} for x axis, error: {}",
-							set.x_axis_csv_column, row, e
-						);
-						std::process::exit(1);
-					}
-				},
-				None => {
-					error!(
-						"Could not extract record in column {}, row {} for x axis",
-						set.x_axis_csv_column, row
-					);
-					std::process::exit(1);
-				}
-			};
-			let ux = match set.x_axis_error_bar_csv_column {
-				Some(column) => match record.get(column) {
-					Some(string_value) => match string_value.parse::<f32>() {
-						Ok(value) => Some(value),
-						Err(e) => {
-							error!(
-										"Could not parse data in column {}, row{}, to f32 for error bar x, error: {}",
-										set.x_axis_csv_column, row, e
-									);
-							std::process::exit(1);
-						}
-					},
-					None => {
-						error!(
-							"Could not extract record in column {}, row {} for error bar x",
-							set.x_axis_csv_column, row
-						);
-						std::process::exit(1);
-					}
-				},
-				None => None,
-			};
-			let y = match record.get(set.y_axis_csv_column) {
-				Some(string_value) => match string_value.parse::<f32>() {
-					Ok(value) => value,
-					Err(e) => {
-						error!(
-									"Could not parse data in column {}, row {} to f32 for y axis, error: {}",
-									set.x_axis_csv_column, row, e
-								);
-						std::process::exit(1);
-					}
-				},
-				None => {
-					error!(
-						"Could not extract record in column {}, row {} for y axis",
-						set.x_axis_csv_column, row
-					);
-					std::process::exit(1);
-				}
-			};
-			let uy = match set.y_axis_error_bar_csv_column {
-				Some(column) => match record.get(column) {
-					Some(string_value) => match string_value.parse::<f32>() {
-						Ok(value) => Some(value),
-						Err(e) => {
-							error!(
-										"Could not parse data in column {}, row {} to f32 for y error bar, error: {}",
-										set.x_axis_csv_column, row, e
-									);
-							std::process::exit(1);
-						}
-					},
-					None => {
-						error!(
-							"Could not extract record in column {}, row {} for y error bar",
-							set.x_axis_csv_column, row
-						);
-						std::process::exit(1);
-					}
-				},
-				None => None,
-			};
-			// plot the value
+	for (set, curve, values, point_colours, point_symbols) in sets_values {
+		let residual_radii = get_residual_radii(set, curve, values);
+		let mut clipped = 0;
+		for (i, (x, ux, y, uy)) in values.iter().enumerate() {
+			// sets excluded from the bounds, or any set when an axis range is fixed, may have values
+			// beyond the axes
+			if (is_range_fixed || !set.is_in_bounds())
+				&& is_outside_limits(*x, *y, x_data_min_max_limits, y_data_min_max_limits)
+			{
+				clipped += 1;
+				continue;
+			}
+			// plot the value relative to the origin of the axes
 			let point: DataPoint = DataPoint {
-				x,
-				ux,
-				y,
-				uy,
-				colour: set.colour,
-				symbol: set.symbol,
-				symbol_radius: set.symbol_radius,
-				symbol_thickness: set.symbol_thickness,
+				x: get_reversed_value(*x as f64, x_data_min_max_limits, is_reversed.0) as f32
+					- origin_values.0,
+				ux: *ux,
+				y: get_reversed_value(*y as f64, y_data_min_max_limits, is_reversed.1) as f32
+					- origin_values.1,
+				uy: *uy,
+				colour: point_colours
+					.and_then(|point_colours| point_colours.get(i, colour_range))
+					.unwrap_or_else(|| set.colour())
+					.with_opacity(set.opacity.unwrap_or(1.0)),
+				symbol: point_symbols
+					.and_then(|point_symbols| point_symbols.get(i))
+					.unwrap_or(set.symbol),
+				symbol_radius: scale_symbol_size(
+					residual_radii
+						.as_ref()
+						.map_or(set.symbol_radius, |radii| radii[i]),
+					scale,
+				),
+				symbol_thickness: scale_symbol_size(set.symbol_thickness, scale),
+				line_thickness: scale,
+				antialias,
 			};
 			point.draw_point(canvas, x_scale_factor, y_scale_factor, origin_offset);
-			row += 1;
 		}
+		if clipped > 0 {
+			warn!(
+				"{} of {} points of {} lie outside of the axes and were clipped",
+				clipped,
+				values.len(),
+				set.name
+			);
+		}
+	}
+}
+/// Whether a value lies beyond the data limits of the axes
+fn is_outside_limits(
+	x: f32,
+	y: f32,
+	x_data_min_max_limits: (i32, i32),
+	y_data_min_max_limits: (i32, i32),
+) -> bool {
+	x < x_data_min_max_limits.0 as f32
+		|| x > x_data_min_max_limits.1 as f32
+		|| y < y_data_min_max_limits.0 as f32
+		|| y > y_data_min_max_limits.1 as f32
+}
+/// When a set has `size_by_residual` enabled find the symbol radius of each value by scaling the magnitude
+/// of its residual against the set's fitted best fit `curve` between `symbol_radius` and `max_symbol_radius`.
+/// The largest residual takes the maximum radius while a zero residual keeps the minimum
+fn get_residual_radii(
+	set: &DataSet,
+	curve: Option<&BestFit>,
+	values: &[DataValues],
+) -> Option<Vec<u32>> {
+	if set.size_by_residual != Some(true) {
+		return None;
+	}
+	// validated when deserialising the config
+	let curve = curve?;
+	let residuals: Vec<f32> = values
+		.iter()
+		.map(|(x, _, y, _)| (y - curve.evaluate(*x)).abs())
+		.collect();
+	let max_residual = residuals.iter().cloned().fold(0.0, f32::max);
+	let min_radius = set.symbol_radius;
+	let max_radius = set.max_symbol_radius();
+	debug!(
+		"Sizing {} by residual, largest residual {} maps to radius {}",
+		set.name, max_residual, max_radius
+	);
+	Some(
+		residuals
+			.iter()
+			.map(|residual| {
+				if max_residual.is_normal() {
+					min_radius
+						+ ((residual / max_residual) * (max_radius - min_radius) as f32).round()
+							as u32
+				} else {
+					min_radius
+				}
+			})
+			.collect(),
+	)
+}
+/// Writes the values of each set to `<dir>/<set name>.csv` exactly as they will be used for plotting so
+/// that any processing can be verified. Rows outside of the axes data limits are flagged in a `clipped`
+/// column rather than being omitted, sets plotted against the secondary y-axis are checked against its
+/// `y2_data_min_max_limits`
+pub fn dump_data_values(
+	data_set: &[DataSet],
+	data_values: &[Vec<DataValues>],
+	dir: &str,
+	x_data_min_max_limits: (i32, i32),
+	y_data_min_max_limits: (i32, i32),
+	y2_data_min_max_limits: Option<(i32, i32)>,
+) -> Result<(), PlotError> {
+	let mut used_names: Vec<String> = Vec::new();
+	for (set, values) in data_set.iter().zip(data_values.iter()) {
+		// Avoid sets with the same name overwriting one another
+		let mut file_name = to_file_name(&set.name);
+		if used_names.contains(&file_name) {
+			file_name = format!("{}_{}", file_name, used_names.len());
+		}
+		used_names.push(file_name.clone());
+		let path = Path::new(dir).join(file_name + ".csv");
+		info!("Writing processed data of {} to {:?}", set.name, path);
+		let write_error = |e: csv::Error| PlotError::Io {
+			context: format!("Unable to write processed data to {:?}", path),
+			source: e.into(),
+		};
+		let mut writer = csv::Writer::from_path(&path).map_err(|e| PlotError::Io {
+			context: format!("Unable to create processed data file {:?}", path),
+			source: e.into(),
+		})?;
+		writer
+			.write_record(["x", "y", "ux", "uy", "clipped"])
+			.map_err(write_error)?;
+		let y_limits = match (set.y_axis(), y2_data_min_max_limits) {
+			(YAxis::Secondary, Some(limits)) => limits,
+			_ => y_data_min_max_limits,
+		};
+		for (x, ux, y, uy) in values.iter() {
+			let is_clipped = is_outside_limits(*x, *y, x_data_min_max_limits, y_limits);
+			let row = [
+				format_value(*x as f64, FormatHint::default()),
+				format_value(*y as f64, FormatHint::default()),
+				ux.map(|u| format_value(u as f64, FormatHint::default()))
+					.unwrap_or_default(),
+				uy.map(|u| format_value(u as f64, FormatHint::default()))
+					.unwrap_or_default(),
+				is_clipped.to_string(),
+			];
+			writer.write_record(&row).map_err(write_error)?;
+		}
+		writer.flush().map_err(|e| PlotError::Io {
+			context: format!("Unable to write processed data to {:?}", path),
+			source: e,
+		})?;
 	}
+	Ok(())
 }
-/// Extracts the colour, symbol and data set names for use in building a legend
-pub fn get_legend_fields(data_set: &[DataSet]) -> Vec<LegendField> {
+/// Extracts the colour, symbol and data set names for use in building a legend, symbols are sized for a
+/// canvas drawn `scale` times larger. A set whose points are coloured by category or take their symbol from
+/// a column has an entry for each category in place of its own, colour categories first. A set with a fitted best fit is followed by an entry for its curve, drawn
+/// as a line as thick as the curve on a canvas `canvas_width` pixels wide
+pub fn get_legend_fields(
+	data_set: &[DataSet],
+	best_fits: &[Option<BestFit>],
+	point_colours: &[Option<PointColours>],
+	point_symbols: &[Option<PointSymbols>],
+	scale: u32,
+	canvas_width: u32,
+) -> Vec<LegendField> {
 	let mut legend_fields: Vec<LegendField> = Vec::new();
-	for set in data_set.iter() {
-		legend_fields.push(LegendField {
-			symbol: set.symbol,
-			symbol_radius: set.symbol_radius,
-			symbol_thickness: set.symbol_thickness,
-			colour: set.colour,
-			name: set.name.to_owned(),
-		});
+	// the sort is stable so sets sharing an order, or without one, keep their config order
+	let mut sets: Vec<_> = data_set
+		.iter()
+		.zip(best_fits.iter())
+		.zip(point_colours.iter())
+		.zip(point_symbols.iter())
+		.map(|(((set, curve), point_colours), point_symbols)| {
+			(set, curve, point_colours, point_symbols)
+		})
+		.collect();
+	sets.sort_by_key(|(set, _, _, _)| (set.legend_order.is_none(), set.legend_order));
+	for (set, curve, point_colours, point_symbols) in sets {
+		if !set.is_in_legend() {
+			continue;
+		}
+		let mut name = set.name.to_owned();
+		if set.size_by_residual == Some(true) {
+			name.push_str(" (size: |residual|)");
+		}
+		// sets plotted against the secondary y-axis are told apart from the rest
+		if set.y_axis() == YAxis::Secondary {
+			name.push_str(" (right axis)");
+		}
+		let colour = point_colours
+			.as_ref()
+			.and_then(PointColours::legend_colour)
+			.unwrap_or_else(|| set.colour());
+		let colour_categories = point_colours
+			.as_ref()
+			.map_or(&[][..], |point_colours| point_colours.categories());
+		let symbol_categories = point_symbols
+			.as_ref()
+			.map_or(&[][..], |point_symbols| point_symbols.categories());
+		// a category drawn in its own colour keeps the symbol of the set while a category given its own
+		// symbol keeps the colour of the set
+		let mut entries: Vec<(String, Colour, DataSymbol)> = colour_categories
+			.iter()
+			.map(|(category, colour)| (category.clone(), *colour, set.symbol))
+			.chain(
+				symbol_categories
+					.iter()
+					.map(|(category, symbol)| (category.clone(), colour, *symbol)),
+			)
+			.collect();
+		if entries.is_empty() {
+			entries.push((name, colour, set.symbol));
+		}
+		for (name, colour, symbol) in entries {
+			legend_fields.push(LegendField {
+				swatch: LegendSwatch::Symbol(symbol),
+				symbol_radius: scale_symbol_size(set.symbol_radius, scale),
+				symbol_thickness: scale_symbol_size(set.symbol_thickness, scale),
+				colour,
+				name,
+			});
+		}
+		if let Some(curve) = curve {
+			let thickness = set
+				.best_fit_thickness
+				.unwrap_or_else(|| get_default_best_fit_thickness(canvas_width));
+			legend_fields.push(LegendField {
+				swatch: LegendSwatch::Line {
+					thickness: thickness * scale,
+					style: set.best_fit_style.unwrap_or_default(),
+				},
+				symbol_radius: 0,
+				symbol_thickness: 0,
+				colour: curve.colour(),
+				name: set
+					.fit_label
+					.clone()
+					.unwrap_or_else(|| match curve.summary() {
+						Some(summary) => format!("{} (fit, {})", set.name, summary),
+						None => format!("{} (fit)", set.name),
+					}),
+			});
+		}
 	}
 	legend_fields
 }
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		canvas::{
+			best_fit::{BestFit, LineStyle},
+			legend::LegendSwatch,
+			plot::DataSymbol,
+		},
+		colours::{Colour, Palette},
+		data::{NumberFormat, RelativeTimeOrigin},
+		scatter::{
+			data::{
+				apply_transform, get_legend_fields, get_residual_radii, load_data_values,
+				DataValues,
+			},
+			DataSet, DataSetBuilder, ScatterBuilder,
+		},
+	};
+	use std::fs;
+
+	/// The data sets of a graph of the single `set`
+	fn data_sets(set: DataSetBuilder) -> Vec<DataSet> {
+		ScatterBuilder::new()
+			.add_data_set(set)
+			.build()
+			.unwrap()
+			.data_sets
+	}
+	#[test]
+	fn sorting_by_x_is_stable_and_keeps_each_row_together() {
+		let dir = std::env::temp_dir().join(format!("plotrs_sort_by_x_{}", std::process::id()));
+		fs::create_dir_all(&dir).unwrap();
+		let path = dir.join("data.csv");
+		fs::write(
+			&path,
+			"x,y,ux,uy,colour,symbol\n\
+			3,30,0.3,3,red,Circle\n\
+			1,10,0.1,1,blue,Cross\n\
+			2,21,0.21,2.1,green,Square\n\
+			2,20,0.2,2,black,Star\n\
+			1.5,15,0.15,1.5,teal,X\n",
+		)
+		.unwrap();
+		let data_sets = data_sets(
+			DataSetBuilder::new("Sorted", path.to_str().unwrap(), 0, 1)
+				.error_bar_columns(Some(2), Some(3))
+				.colour_csv_column(4)
+				.symbol_csv_column(5, None)
+				.sort_by_x(true),
+		);
+		let (values, point_colours, point_symbols) = load_data_values(
+			&data_sets,
+			",",
+			&NumberFormat::default(),
+			RelativeTimeOrigin::default(),
+			Palette::default(),
+		)
+		.unwrap();
+		fs::remove_dir_all(&dir).unwrap();
+		// the rows with equal x keep their csv order
+		let expected: Vec<DataValues> = vec![
+			(1.0, Some(0.1), 10.0, Some(1.0)),
+			(1.5, Some(0.15), 15.0, Some(1.5)),
+			(2.0, Some(0.21), 21.0, Some(2.1)),
+			(2.0, Some(0.2), 20.0, Some(2.0)),
+			(3.0, Some(0.3), 30.0, Some(3.0)),
+		];
+		assert_eq!(vec![expected], values);
+		let point_colours = point_colours[0].as_ref().unwrap();
+		let point_symbols = point_symbols[0].as_ref().unwrap();
+		let rows = [
+			("blue", DataSymbol::Cross),
+			("teal", DataSymbol::X),
+			("green", DataSymbol::Square),
+			("black", DataSymbol::Star),
+			("red", DataSymbol::Circle),
+		];
+		for (i, (colour, symbol)) in rows.iter().enumerate() {
+			assert_eq!(
+				Some(Colour::from_name(colour).unwrap()),
+				point_colours.get(i, None)
+			);
+			assert_eq!(Some(*symbol), point_symbols.get(i));
+		}
+	}
+	#[test]
+	fn transform_scales_and_offsets_values() {
+		assert_eq!((7.0, None), apply_transform(Some((2.0, 1.0)), 3.0, None));
+		assert_eq!((0.5, Some(0.25)), apply_transform(None, 0.5, Some(0.25)));
+	}
+	#[test]
+	fn uncertainty_is_only_scaled_by_the_multiplier() {
+		assert_eq!(
+			(2010.0, Some(6.0)),
+			apply_transform(Some((1000.0, 10.0)), 2.0, Some(0.006))
+		);
+	}
+	#[test]
+	fn negative_multiplier_keeps_uncertainty_positive() {
+		let (value, uncertainty) = apply_transform(Some((-2.0, 5.0)), 3.0, Some(0.5));
+		assert_eq!(-1.0, value);
+		assert_eq!(Some(1.0), uncertainty);
+	}
+	#[test]
+	fn residuals_map_between_the_symbol_radius_and_the_max() {
+		let curve = BestFit::Linear {
+			gradient: 1.0,
+			y_intercept: 0.0,
+			colour: Colour::Black,
+		};
+		let points = [(0.0, 0.0), (1.0, 2.0), (2.0, 4.0), (3.0, 2.5)];
+		let set = DataSetBuilder::from_points("Residuals", &points)
+			.symbol(DataSymbol::Circle, 2, 0)
+			.best_fit(curve.clone())
+			.size_by_residual(Some(10));
+		let set = &data_sets(set)[0];
+		let values: Vec<DataValues> = points.iter().map(|(x, y)| (*x, None, *y, None)).collect();
+		// a point on the curve keeps the symbol radius while the largest residual, 2, takes the max
+		assert_eq!(
+			Some(vec![2, 6, 10, 4]),
+			get_residual_radii(set, Some(&curve), &values)
+		);
+		// every point on the curve has no largest residual to scale by so all keep the symbol radius
+		let on_curve: Vec<DataValues> = (0..3).map(|x| (x as f32, None, x as f32, None)).collect();
+		assert_eq!(
+			Some(vec![2, 2, 2]),
+			get_residual_radii(set, Some(&curve), &on_curve)
+		);
+	}
+	#[test]
+	fn residual_radii_are_only_found_when_enabled() {
+		let curve = BestFit::Linear {
+			gradient: 1.0,
+			y_intercept: 0.0,
+			colour: Colour::Black,
+		};
+		let set = DataSetBuilder::from_points("Plain", &[(0.0, 1.0)]).best_fit(curve.clone());
+		let set = &data_sets(set)[0];
+		assert_eq!(
+			None,
+			get_residual_radii(set, Some(&curve), &[(0.0, None, 1.0, None)])
+		);
+	}
+	#[test]
+	fn fitted_curve_is_named_by_its_label_or_after_its_set() {
+		let curve = BestFit::Linear {
+			gradient: 1.0,
+			y_intercept: 0.0,
+			colour: Colour::Red,
+		};
+		let names = |set: DataSetBuilder| {
+			let sets = data_sets(set.best_fit(curve.clone()));
+			get_legend_fields(&sets, &[Some(curve.clone())], &[None], &[None], 1, 1000)
+				.into_iter()
+				.map(|field| (field.name, field.swatch))
+				.collect::<Vec<_>>()
+		};
+		let points = [(0.0, 0.0), (1.0, 1.0)];
+		let default = names(DataSetBuilder::from_points("Runs", &points));
+		assert_eq!(2, default.len());
+		assert_eq!("Runs", default[0].0);
+		assert_eq!("Runs (fit)", default[1].0);
+		let labelled = names(
+			DataSetBuilder::from_points("Runs", &points)
+				.fit_label("Trend")
+				.best_fit_style(LineStyle::Dashed),
+		);
+		assert_eq!("Trend", labelled[1].0);
+		// the line takes the style of the curve
+		assert_eq!(
+			LegendSwatch::Line {
+				thickness: 1,
+				style: LineStyle::Dashed
+			},
+			labelled[1].1
+		);
+	}
+}
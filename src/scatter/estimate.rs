@@ -0,0 +1,153 @@
+//! Estimates the work involved in drawing a graph so that the cost of a huge plot can be judged before
+//! committing to rendering it
+
+use std::{
+	fmt,
+	time::{Duration, Instant},
+};
+use tracing::debug;
+
+use crate::{
+	canvas::{draw_base_canvas, plot::DataPoint, plot::DataSymbol, quadrants::Quadrants, Chrome},
+	colours::Colour,
+};
+
+use super::{data::DataValues, Scatter};
+
+/// Number of points drawn by the micro-benchmark used to calibrate the time per pixel
+const BENCHMARK_POINTS: u32 = 10_000;
+
+/// The amount of drawing a graph requires
+#[derive(Debug, Default, PartialEq)]
+pub struct Estimate {
+	/// Number of data points across all sets
+	pub points: u64,
+	/// Distinct pixels covered by the symbols of every data point
+	pub symbol_pixels: u64,
+	/// Pixel coordinates generated to draw the symbols, overlapping coordinates are drawn repeatedly
+	pub symbol_coordinates: u64,
+	/// Pixels of the background grid
+	pub grid_pixels: u64,
+	/// Pieces of text such as the title, labels, tick markings and legend entries
+	pub text_elements: u64,
+	/// Nanoseconds this machine took to draw a single pixel coordinate when calibrated
+	pub nanos_per_pixel: f64,
+}
+
+impl Estimate {
+	/// Tally the work of drawing the loaded data sets with the resolved axis resolutions
+	pub fn new(
+		scatter: &Scatter,
+		data_values: &[Vec<DataValues>],
+		quadrants: &Quadrants,
+		x_axis_resolution: u32,
+		y_axis_resolution: u32,
+	) -> Estimate {
+		let mut estimate = Estimate::default();
+		for (set, values) in scatter.data_sets.iter().zip(data_values.iter()) {
+			// sizing by residual varies the radius per point, assume the largest as an upper bound
+			let radius = if set.size_by_residual == Some(true) {
+				set.max_symbol_radius()
+			} else {
+				set.symbol_radius
+			};
+			let points = values.len() as u64;
+			estimate.points += points;
+			estimate.symbol_pixels += points * set.symbol.pixel_count(radius, set.symbol_thickness);
+			estimate.symbol_coordinates +=
+				points * coordinate_count(set.symbol, radius, set.symbol_thickness);
+		}
+		// paired axes draw their resolution either side of the origin
+		let x_divisions = if quadrants.is_x_paired() {
+			2 * x_axis_resolution
+		} else {
+			x_axis_resolution
+		} as u64;
+		let y_divisions = if quadrants.is_y_paired() {
+			2 * y_axis_resolution
+		} else {
+			y_axis_resolution
+		} as u64;
+		if scatter.has_grid == Some(true) {
+			// the plot area is not known until the chrome is drawn so bound it by the canvas
+			let (width, height) = scatter.canvas_pixel_size;
+			let thickness = scatter.grid_thickness.unwrap_or(1) as u64;
+			estimate.grid_pixels =
+				(x_divisions * height as u64 + y_divisions * width as u64) * thickness;
+		}
+		// a marking for every division plus the origin of each axis
+		estimate.text_elements = x_divisions + y_divisions + 2;
+		if scatter.chrome.unwrap_or_default() == Chrome::Full {
+			// title and both axis labels
+			estimate.text_elements += 3;
+			if scatter.has_legend == Some(true) {
+				estimate.text_elements += scatter.data_sets.len() as u64;
+			}
+		}
+		estimate
+	}
+	/// Predict how long drawing the symbols and grid takes at the calibrated time per pixel coordinate
+	pub fn predict(&self) -> Duration {
+		let pixels = (self.symbol_coordinates + self.grid_pixels) as f64;
+		Duration::from_secs_f64(pixels * self.nanos_per_pixel / 1e9)
+	}
+}
+
+/// The number of pixel coordinates `find_pixels` generates for a symbol, including any it repeats
+fn coordinate_count(symbol: DataSymbol, radius: u32, thickness: u32) -> u64 {
+	// far enough from the canvas corner that no coordinate underflows
+	let offset = 4 * (radius + thickness) + 8;
+	symbol
+		.find_pixels((offset, offset), thickness, radius)
+		.len() as u64
+}
+
+/// Time drawing a small plot of crosses to find roughly how many nanoseconds this machine takes per
+/// pixel coordinate
+pub fn calibrate() -> f64 {
+	let mut canvas = draw_base_canvas((256, 256));
+	let symbol = DataSymbol::Cross;
+	let coordinates = coordinate_count(symbol, 3, 0);
+	let start = Instant::now();
+	for i in 0..BENCHMARK_POINTS {
+		let point = DataPoint {
+			x: (16 + i % 224) as f32,
+			y: (16 + (i / 224) % 224) as f32,
+			ux: None,
+			uy: None,
+			colour: Colour::Black,
+			symbol,
+			symbol_radius: 3,
+			symbol_thickness: 0,
+			line_thickness: 1,
+			antialias: false,
+		};
+		point.draw_point(&mut canvas, 1.0, 1.0, (0, 255));
+	}
+	let elapsed = start.elapsed();
+	debug!(
+		"Drew {} benchmark points in {:?}",
+		BENCHMARK_POINTS, elapsed
+	);
+	elapsed.as_nanos() as f64 / (BENCHMARK_POINTS as u64 * coordinates) as f64
+}
+
+/// The estimated work and a rough prediction of the time it takes to draw, one figure per line
+impl fmt::Display for Estimate {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		writeln!(f, "Points: {}", self.points)?;
+		writeln!(
+			f,
+			"Symbol pixels: {} ({} coordinates drawn)",
+			self.symbol_pixels, self.symbol_coordinates
+		)?;
+		writeln!(f, "Grid pixels: {}", self.grid_pixels)?;
+		writeln!(f, "Text elements: {}", self.text_elements)?;
+		write!(
+			f,
+			"Predicted drawing time: {:.2?} (calibrated at {:.2}ns per pixel)",
+			self.predict(),
+			self.nanos_per_pixel
+		)
+	}
+}
@@ -5,22 +5,21 @@
 //!    title: "Engery against Time for Fuzzing About Things",
 //!    canvas_pixel_size: (840, 600),
 //!    x_axis_label: "Time (ms)",
-//!    x_axis_resolution: 11, // Number of times the x-axis will be divided to show your data scale
+//!    x_axis_resolution: 11, // Number of times the x-axis will be divided to show your data scale, omit to choose one automatically
 //!    y_axis_label: "Energy (kJ)",
-//!    y_axis_resolution: 11, // Number of times the y-axis will be divided to show your data scale
 //!    has_grid: false, // Should the graph have a light grey background grid
 //!    has_legend: false, // should a legend be generated? Only really useful with multiple data sets
 //!    // data sets can be sourced from the same csv or from different ones and each can be configured with different colours/symbols
 //!    data_sets: [
 //!        DataSet(
-//!            data_path: "scatter.csv",
+//!            data_path: "scatter.csv", // may contain glob wildcards such as "logs/2024-*.csv" to concatenate files
 //!            has_headers: true, // if your data has headers set to `true` so they can be ignored
 //!            x_axis_csv_column: 0, // which column contains the x values
 //!            x_axis_error_bar_csv_column: None, // which column contains x uncertainty Some(usize) or None
 //!            y_axis_csv_column: 1, // which column contains the y values
 //!            y_axis_error_bar_csv_column: None, // which column contains y uncertainty Some(usize) or None
 //!            name: "Very interesting", // legend will indicate which colour and symbol correspond to which data set
-//!            colour: Orange, // the colour to render a data point
+//!            colour: Orange, // the colour to render a data point, when omitted one is picked from a cycle of colours
 //!            symbol: Cross, // the shape a plotted data point should take
 //!            symbol_radius: 5, // The size of a drawn symbol in (1+ symbol_radius) pixels
 //!            symbol_thickness: 0, // The thinkness of a drawn symbol in (1 + symbol_thickness) pixels
@@ -30,181 +29,1068 @@
 //! )
 //! ```
 
-use ron::de::from_reader;
-use serde::Deserialize;
-use std::fs::File;
-use tracing::{debug, error, info};
+use image::{ImageBuffer, Rgba};
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+use std::{
+	collections::BTreeMap,
+	fs::File,
+	io::Read,
+	path::{Path, PathBuf},
+	sync::Arc,
+};
+use tracing::{debug, info, warn};
 
+mod builder;
 mod data;
+mod estimate;
+mod scaffold;
+
+pub use builder::{DataSetBuilder, ScatterBuilder};
+pub use data::DataValues;
+pub use estimate::Estimate;
+pub use scaffold::scatter_scaffold;
 
 use crate::{
 	canvas::{
 		axes::axis_x::build_x_axis_label,
 		axes::axis_y::build_y_axis_label,
 		axes::{
-			axis_x::get_x_axis_pixel_length, axis_y::get_y_axis_pixel_length, draw_xy_axes,
-			get_xy_axis_pixel_min_max, get_xy_axis_pixel_origin,
+			axis_x::get_x_axis_pixel_length,
+			axis_y::get_y_axis_pixel_length,
+			axis_y2::{build_y2_axis_label, draw_y2_axis, get_y2_axis_space},
+			draw_xy_axes, get_aligned_axis_limits, get_auto_axis_resolution, get_custom_ticks,
+			get_nice_axis_limits, get_reversed_value, get_scale_marking_space,
+			get_xy_axis_pixel_min_max, get_xy_axis_pixel_origin, is_axis_fitted, AxisStyle,
+			CustomTick, GridStyle, LabelOrientation, MinorTicks, YAxis,
 		},
-		best_fit::BestFit,
-		draw_base_canvas,
+		best_fit::{
+			draw_curve, draw_equations, draw_fit_statistics, get_default_best_fit_thickness,
+			get_fit_statistics, BestFit, Corner, LineStyle,
+		},
+		colour_bar::{build_colour_bar, get_colour_bar_space},
+		datetime::{calendar_limits, DateTimeFormat},
+		downsample, draw_base_canvas,
+		fill::{fill_gradient_under, FillGradient},
+		format::{Locale, Notation, TickFormat},
+		geometry::{PlotAlignment, PlotGeometry},
+		get_output_path,
 		glyphs::FontSizes,
-		legend::build_legend,
-		plot::DataSymbol,
-		quadrants::get_quadrants,
-		save_image,
+		legend::{
+			build_legend, get_legend_bounds, get_legend_origin, get_legend_space,
+			get_outside_legend_origin, get_quietest_corner, Legend, LegendPosition, LegendStyle,
+		},
+		metadata::{provenance_chunks, TextChunk},
+		plot::{get_x_pixel, get_y_pixel, DataPoint, DataSymbol, SYMBOL_CYCLE},
+		progress::{RenderProgress, RenderStage},
+		quadrants::{get_quadrants, Quadrants},
 		title::build_title,
-		VHConsumedCanvasSpace,
+		to_file_name, write_image, Chrome, ExistingOutput, OutputFormat, VHConsumedCanvasSpace,
+		CANVAS_BORDER_PIXELS,
 	},
 	colours::*,
-	scatter::data::{build_data_points, get_data_bounds, get_legend_fields},
+	data::{expand_data_path, CsvColumn, NumberFormat, RelativeTimeOrigin, RelativeUnit},
+	deserialize_some,
+	error::{ConfigError, PlotError, ValidationError},
+	get_system_font, load_font,
+	scatter::data::{
+		build_data_points, dump_data_values, fit_best_fits, get_colour_range, get_data_bounds,
+		get_legend_fields, get_values_in_bounds, load_data_values, PointColours,
+	},
+	scatter::estimate::calibrate,
+	serialize_some,
+	style::Style,
 };
-/// Specification of a scatter graph
-#[derive(Debug, Deserialize)]
-struct Scatter {
+/// Specification of a scatter graph, deserialised from a `.ron` config or created with a [`ScatterBuilder`]
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct Scatter {
 	/// The title of the graph
 	title: String,
 	/// Image size in pixels
 	canvas_pixel_size: (u32, u32),
 	/// X-axis label
 	x_axis_label: String,
-	/// Number of times the x-axis will be divided to show your data scale. Advised to make it a ratio of your largest x value
-	x_axis_resolution: u32,
+	/// Optional, number of times the x-axis will be divided to show your data scale. Advised to make it a ratio of your largest x value,
+	/// when omitted a nice step of `1`, `2` or `5` times a power of ten is chosen and the axis rounded outward to it
+	#[serde(
+		default,
+		deserialize_with = "deserialize_some",
+		serialize_with = "serialize_some",
+		skip_serializing_if = "Option::is_none"
+	)]
+	x_axis_resolution: Option<u32>,
 	/// Y-axis label
 	y_axis_label: String,
-	/// Number of times the y-axis will be divided to show your data scale. Advised to make it a ratio of your largest y value
-	y_axis_resolution: u32,
-	/// Should the graph has a light grey background grid
-	has_grid: bool,
-	/// Should a legend be generated
-	has_legend: bool,
+	/// Optional, `Horizontal` writes the y-axis label above the axis and `Vertical` turns it to read upwards
+	/// alongside the axis, taking less width from the plot. Defaults to `Horizontal`
+	y_axis_label_orientation: Option<LabelOrientation>,
+	/// Optional, number of times the y-axis will be divided to show your data scale. Advised to make it a ratio of your largest y value,
+	/// when omitted a nice step of `1`, `2` or `5` times a power of ten is chosen and the axis rounded outward to it
+	#[serde(
+		default,
+		deserialize_with = "deserialize_some",
+		serialize_with = "serialize_some",
+		skip_serializing_if = "Option::is_none"
+	)]
+	y_axis_resolution: Option<u32>,
+	/// Optional, fixed `(min, max)` of the x-axis used instead of the data bounds plus a buffer, e.g
+	/// `Some((0.0, 100.0))` so graphs of separate runs can be compared. Values are rounded outward to whole
+	/// numbers and points outside of the range are clipped
+	x_axis_range: Option<(f32, f32)>,
+	/// Optional, fixed `(min, max)` of the y-axis used instead of the data bounds plus a buffer. Values are
+	/// rounded outward to whole numbers and points outside of the range are clipped
+	y_axis_range: Option<(f32, f32)>,
+	/// Optional, fraction of its value by which the smallest and largest data values are moved outward before
+	/// the axes are fitted to them, so points are not drawn on an axis. Defaults to `0.1`, `Some(0.0)` fits
+	/// the axes to the data bounds
+	axis_padding_fraction: Option<f32>,
+	/// Optional, padding fraction of the x-axis in place of `axis_padding_fraction`
+	x_axis_padding_fraction: Option<f32>,
+	/// Optional, padding fraction of the y-axis and any secondary y-axis in place of `axis_padding_fraction`
+	y_axis_padding_fraction: Option<f32>,
+	/// Optional, label of the secondary y-axis drawn along the right edge of the plot when any data set is
+	/// plotted against it
+	y2_axis_label: Option<String>,
+	/// Optional, number of times the secondary y-axis is divided. Defaults to the divisions of the y-axis so
+	/// that the scale markers of both axes line up, either way its limits are rounded outward to a nice step
+	y2_axis_resolution: Option<u32>,
+	/// Optional, number of decimal places every x-axis tick label is written with, e.g `Some(2)` for `0.50`.
+	/// Defaults to the fewest places which tell the labels apart, without trailing zeros
+	x_tick_decimal_places: Option<u8>,
+	/// Optional, number of decimal places every y-axis tick label is written with, defaults to the fewest
+	/// places which tell the labels apart, without trailing zeros
+	y_tick_decimal_places: Option<u8>,
+	/// Optional, `Plain`, `Scientific`, `Percent` or `Auto` notation of the tick labels, e.g `Scientific`
+	/// writes `1.5e9` and `Percent` writes `0.25` as `25%`. Defaults to `Auto` which uses scientific notation
+	/// on an axis spanning more than `10^4` or less than `10^-4`
+	tick_format: Option<Notation>,
+	/// Optional, notation of the x-axis tick labels in place of `tick_format`, e.g `Some(Percent)` for ratio
+	/// data. Defaults to `tick_format`
+	x_tick_format: Option<Notation>,
+	/// Optional, notation of the y-axis tick labels in place of `tick_format`, defaults to `tick_format`
+	y_tick_format: Option<Notation>,
+	/// Optional, text appended to every x-axis tick label, e.g `Some(" ms")` writes `10 ms`, `20 ms`
+	x_tick_suffix: Option<String>,
+	/// Optional, text appended to every y-axis tick label. The secondary y-axis is written without it
+	y_tick_suffix: Option<String>,
+	/// Optional, values of the x-axis scale markers in place of those spread by `x_axis_resolution`, e.g
+	/// `Some([0.0, 3.14159, 6.28318])`. Each may be given its own label as `(3.14159, "π")`, values outside of
+	/// the axis are skipped. Defaults to the markers of the resolution
+	x_ticks: Option<Vec<CustomTick>>,
+	/// Optional, values of the y-axis scale markers in place of those spread by `y_axis_resolution`, each
+	/// may be given its own label. Defaults to the markers of the resolution
+	y_ticks: Option<Vec<CustomTick>>,
+	/// Optional, write an x-axis tick label at every this many scale markers counted from the origin, e.g
+	/// `Some(2)` labels every other marker. Defaults to skipping as few labels as stop neighbours overlapping
+	x_tick_label_every: Option<u32>,
+	/// Optional, write a y-axis tick label at every this many scale markers counted from the origin. Defaults
+	/// to skipping as few labels as stop neighbours overlapping
+	y_tick_label_every: Option<u32>,
+	/// Optional, treat the x values as epoch timestamps in seconds and write the x-axis tick labels with a
+	/// strftime style pattern, e.g `Some("%H:%M")`, or `Some("auto")` to pick `%H:%M:%S`, `%H:%M`, `%b %-d` or
+	/// `%Y-%m` from the span of the axis. Without an `x_axis_resolution` or `x_axis_range` the labels fall on
+	/// whole minutes, hours or days. Timestamps are read with `f32` precision, about a minute for current
+	/// dates, and are written in UTC. Defaults to plain numbers
+	x_tick_datetime_format: Option<String>,
+	/// Optional, number of mini-markings drawn between each pair of x-axis scale markers, `Some(0)` disables
+	/// them. Defaults to as many as evenly divide the pixels between the markers
+	x_minor_ticks: Option<u32>,
+	/// Optional, number of mini-markings drawn between each pair of y-axis scale markers, `Some(0)` disables
+	/// them. Defaults to as many as evenly divide the pixels between the markers
+	y_minor_ticks: Option<u32>,
+	/// Optional, extend faint gridlines across the plot from every mini-marking, defaults to `false`
+	minor_grid: Option<bool>,
+	/// Optional, draw the lines where x and y are zero darker and twice as thick as the grid when they fall
+	/// within the axes, defaults to `false`
+	highlight_zero: Option<bool>,
+	/// Optional, draw the x-axis line together with its scale markers and their labels, defaults to `true`.
+	/// The points are plotted in the same place either way
+	show_x_axis: Option<bool>,
+	/// Optional, draw the y-axis line together with its scale markers and their labels, defaults to `true`
+	show_y_axis: Option<bool>,
+	/// Optional, draw the scale markers of the axes, defaults to `true`
+	show_ticks: Option<bool>,
+	/// Optional, write the values along the axes, defaults to `true`. Without them, or the scale markers,
+	/// a `PlotOnly` or `BareAxes` graph gives their space to the plot
+	show_tick_labels: Option<bool>,
+	/// Optional, draw the x-axis with its values decreasing to the right, defaults to `false`
+	x_axis_reversed: Option<bool>,
+	/// Optional, draw the y-axis with its values decreasing upward, e.g for depth profiles or rankings,
+	/// defaults to `false`. A secondary y-axis is reversed with it
+	y_axis_reversed: Option<bool>,
+	/// Optional, draw arrowheads at the right end of the x-axis and the top end of the y-axis, defaults to
+	/// `false`. The axes are shortened to leave the arrowheads room
+	axis_arrows: Option<bool>,
+	/// Optional, pixel thickness of the axis lines and their scale markers, e.g `Some(3)` so that the axes
+	/// survive the image being shrunk. Defaults to `1`
+	axis_thickness: Option<u32>,
+	/// Optional, pixel thickness of the background gridlines, defaults to `1`
+	grid_thickness: Option<u32>,
+	/// Optional, `Solid`, `Dashed` or `Dotted` pattern of the background gridlines so that a grid behind
+	/// dense data is less prominent. Defaults to `Solid`
+	grid_style: Option<GridStyle>,
+	/// Optional, pixel length of the dashes of `Dashed` gridlines and of the gaps between them, defaults to `4`
+	grid_dash_length: Option<u32>,
+	/// Optional, colour of the axis lines and their scale markers, defaults to `Black`
+	axis_colour: Option<Colour>,
+	/// Optional, colour of the values written along the axes, defaults to `Black`
+	tick_label_colour: Option<Colour>,
+	/// Optional, colour of the background gridlines and of the minor gridlines, defaults to `Grey` with
+	/// lighter minor gridlines
+	grid_colour: Option<Colour>,
+	/// Should the graph has a light grey background grid, defaults to `false` when neither the config
+	/// nor its style specify it
+	#[serde(
+		default,
+		deserialize_with = "deserialize_some",
+		serialize_with = "serialize_some",
+		skip_serializing_if = "Option::is_none"
+	)]
+	has_grid: Option<bool>,
+	/// Should a legend be generated, defaults to `false` when neither the config nor its style specify it
+	#[serde(
+		default,
+		deserialize_with = "deserialize_some",
+		serialize_with = "serialize_some",
+		skip_serializing_if = "Option::is_none"
+	)]
+	has_legend: Option<bool>,
+	/// Optional, `TopLeft`, `TopRight`, `BottomLeft` or `BottomRight`, the corner of the plot the equations of
+	/// data sets with `show_equation` are written in, defaults to `TopLeft`
+	equation_corner: Option<Corner>,
+	/// Optional, `TopLeft`, `TopRight`, `BottomLeft` or `BottomRight` to draw the legend in that corner
+	/// inside the axes, or `Outside` to draw it in space reserved to their right, defaults to `Outside`
+	legend_position: Option<LegendPosition>,
+	/// Optional, should a border be drawn around the legend, defaults to `false`
+	legend_frame: Option<bool>,
+	/// Optional, the colour filling the legend behind its entries, defaults to none outside the axes and
+	/// `White` inside them
+	legend_background: Option<Colour>,
+	/// Optional, draw the legend last over a corner inside the axes without reserving canvas space, in the
+	/// corner of `legend_position` or else the corner covering the least of the data, defaults to `false`
+	legend_overlay: Option<bool>,
+	/// Optional, a heading written above the entries of the legend a little larger than their names,
+	/// defaults to none
+	legend_title: Option<String>,
+	/// Optional, the font size of the legend, defaults to a size derived from the canvas width
+	legend_font_size: Option<f32>,
+	/// Optional, the distance between the tops of consecutive legend rows as a multiple of the height of
+	/// the tallest name, defaults to `2.0`
+	legend_row_spacing: Option<f32>,
 	/// Defines where the data is and which parts to use
 	data_sets: Vec<DataSet>,
+	/// How numbers are written in the csv files, defaults to no thousands separator and a `.` decimal separator
+	#[serde(
+		default,
+		deserialize_with = "deserialize_some",
+		serialize_with = "serialize_some",
+		skip_serializing_if = "Option::is_none"
+	)]
+	number_format: Option<NumberFormat>,
+	/// Optional, how much of the graph surrounding the plot area is drawn, defaults to `Full`
+	chrome: Option<Chrome>,
+	/// Optional, how colours are picked for data sets without one, defaults to `Positional`
+	colour_assignment: Option<ColourAssignment>,
+	/// Optional, the pool of colours given to data sets without one, defaults to `Default`
+	palette: Option<Palette>,
+	/// Optional, whether data sets with `x_relative_to_first` measure time from their own first timestamp
+	/// or the earliest across all sets, defaults to `PerDataset`
+	relative_time_origin: Option<RelativeTimeOrigin>,
+	/// Optional, file name without an extension to save the graph as, defaults to the title in lowercase with
+	/// whitespace and symbols replaced by `_`
+	output_name: Option<String>,
+	/// Optional, language whose separators are used to write tick labels, e.g `Some("de")` for `1.234,5`.
+	/// One of `en`, `de` or `fr`, defaults to plain numbers such as `1234.5`
+	locale: Option<String>,
+	/// Optional, draw the graph this many times larger and shrink it back to `canvas_pixel_size` before
+	/// saving to smooth the edges of symbols, curves and text, e.g `Some(4)`. Defaults to `1`
+	supersample: Option<u32>,
+	/// Optional, smooth the edges of circles, triangles, stars and best fit curves by partly covering the
+	/// pixels along them rather than drawing hard edged pixels, defaults to `false`
+	antialias: Option<bool>,
+	/// Optional, path to a `.ron` [`Style`] whose values are used for any of the fields above which
+	/// the config does not specify
+	style_path: Option<String>,
 }
 /// The source of each data set and how it should be represented
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct DataSet {
-	/// Path to csv data
+	/// Path to csv data, may contain glob wildcards to concatenate several files in file name order. Files
+	/// ending in `.gz` are decompressed as they are read
 	data_path: String,
+	/// Optional, when `data_path` is a glob pattern plot each matched file as its own set named after the
+	/// file, defaults to `false`
+	one_set_per_file: Option<bool>,
 	/// Does the csv contain headers
 	has_headers: bool,
-	/// Which column in the csv contains the x-axis data
-	x_axis_csv_column: usize,
+	/// Which column in the csv contains the x-axis data, or an expression of several columns
+	x_axis_csv_column: CsvColumn,
 	/// Optional, the column which contains an uncertainty measure
 	x_axis_error_bar_csv_column: Option<usize>,
-	/// Which column in the csv contains the y-axis data
-	y_axis_csv_column: usize,
+	/// Which column in the csv contains the y-axis data, or an expression of several columns
+	y_axis_csv_column: CsvColumn,
 	/// Optional, the csv column which contains an uncertainty measure
 	y_axis_error_bar_csv_column: Option<usize>,
 	/// Name of the data set, useful when generating a legend to distinguish sets
 	name: String,
-	/// The colour a data point should be plotted as
-	colour: Colour,
+	/// Optional, the colour a data point should be plotted as. When omitted a colour is assigned from
+	/// the colour cycle based on the graph's `colour_assignment`
+	#[serde(
+		default,
+		deserialize_with = "deserialize_some",
+		serialize_with = "serialize_some",
+		skip_serializing_if = "Option::is_none"
+	)]
+	colour: Option<Colour>,
+	/// Optional, a column whose cells colour each point. A cell naming a colour, e.g `teal` or `#1f77b4`, is
+	/// drawn in it while any other text is a category given a colour from the cycle and its own row in the
+	/// legend in place of the set's. Empty cells keep the colour of the set
+	colour_csv_column: Option<usize>,
+	/// Optional, e.g `Some(ColourBy(csv_column: 3, colormap: Viridis))` to colour each point by the value in a
+	/// column, mapped along a `Viridis` or `BlueRed` colormap from the smallest value of every set coloured
+	/// by value to the largest. A colour bar showing the mapping is drawn to the right of the axes
+	colour_by: Option<ColourBy>,
+	/// Optional, from 0 to 1, how opaque the points and their error bars are drawn so that overlapping points
+	/// darken progressively rather than merging into a solid blob, defaults to `1.0`. The legend is opaque
+	opacity: Option<f32>,
 	/// The shape used to represent the data point
 	symbol: DataSymbol,
 	/// The size of a drawn symbol in (1+ symbol_radius) pixels
 	symbol_radius: u32,
 	/// The thinkness of a drawn symbol in (1 + symbol_thickness) pixels
 	symbol_thickness: u32,
+	/// Optional, a column whose cells pick the symbol of each point. A cell is looked up in `symbol_map` or
+	/// else read as the name of a symbol, e.g `Circle`, and is given its own row in the legend in place of
+	/// the set's. Empty cells keep the symbol of the set
+	symbol_csv_column: Option<usize>,
+	/// Optional, e.g `Some({"pass": Circle, "fail": Cross})`, the symbol drawn for each value of the
+	/// `symbol_csv_column`, requires a `symbol_csv_column`
+	symbol_map: Option<BTreeMap<String, DataSymbol>>,
 	/// Optional, a type of best fit line to draw
 	best_fit: Option<BestFit>,
+	/// Optional, sort the values ascending by x before plotting, values with equal x keep their csv order,
+	/// defaults to `false`
+	sort_by_x: Option<bool>,
+	/// Optional, scale the symbol radius of each point by the magnitude of its residual against `best_fit`
+	/// so that badly fit points stand out, requires a `best_fit`
+	size_by_residual: Option<bool>,
+	/// Optional, the radius given to the point with the largest residual when `size_by_residual` is
+	/// enabled, defaults to three times `symbol_radius + 1`
+	max_symbol_radius: Option<u32>,
+	/// Optional, write the equation of `best_fit` with its R² and root mean square error against the points
+	/// of the set beside the end of the curve, requires a `best_fit`. Defaults to `false`
+	show_fit_statistics: Option<bool>,
+	/// Optional, pixel thickness of the `best_fit` curve, defaults to one pixel for every 1000 pixels of
+	/// canvas width
+	best_fit_thickness: Option<u32>,
+	/// Optional, `Solid`, `Dashed` or `Dotted`, the pattern the `best_fit` curve is drawn with, defaults to
+	/// `Solid`
+	best_fit_style: Option<LineStyle>,
+	/// Optional, `(min, max)` x values the `best_fit` curve is restricted to, e.g where a model is valid.
+	/// The curve is drawn where the domain overlaps the x-axis, defaults to the whole axis
+	best_fit_domain: Option<(f32, f32)>,
+	/// Optional, name of the `best_fit` curve in the legend, defaults to the name of the set followed by
+	/// ` (fit)`
+	fit_label: Option<String>,
+	/// Optional, write the equation of `best_fit` in the `equation_corner` of the plot in the colour of the
+	/// curve, requires a `best_fit`. Defaults to `false`
+	show_equation: Option<bool>,
+	/// Optional, `(multiplier, offset)` applied to each x value as it is read, e.g `Some((0.001, 0.0))`
+	/// to plot milliseconds as seconds. Error bars are scaled by the multiplier only
+	x_transform: Option<(f32, f32)>,
+	/// Optional, `(multiplier, offset)` applied to each y value as it is read, e.g `Some((0.000001, 0.0))`
+	/// to plot bytes as megabytes. Error bars are scaled by the multiplier only
+	y_transform: Option<(f32, f32)>,
+	/// Optional, treat the x values as epoch timestamps in seconds and plot the time elapsed since the
+	/// first row in the given unit instead
+	x_relative_to_first: Option<RelativeUnit>,
+	/// Optional, fill the area between the series, joined in order of x, and the x-axis with the set's
+	/// colour fading from `from_opacity` at the series to `to_opacity` at the axis
+	fill_gradient: Option<FillGradient>,
+	/// Optional, whether the values of the set are used to find the axis ranges, defaults to `true`. An
+	/// excluded set is drawn against the axes of the other sets with points outside of them clipped
+	include_in_bounds: Option<bool>,
+	/// Optional, whether the set and its `best_fit` curve are given rows in the legend, defaults to `true`.
+	/// Useful for sets that only support the others visually such as a baseline
+	show_in_legend: Option<bool>,
+	/// Optional, the position of the set's rows in the legend, sets are listed in ascending order followed
+	/// by those without one in config order. The order the sets are drawn in is unchanged
+	legend_order: Option<u32>,
+	/// Optional, `Primary` or `Secondary`, the y-axis the values are plotted against, defaults to `Primary`.
+	/// Sets plotted against the `Secondary` axis share the x-axis but have a y-axis with its own scale
+	/// drawn along the right edge of the plot
+	y_axis: Option<YAxis>,
+	/// Values supplied in memory by a [`DataSetBuilder`], plotted instead of reading `data_path`
+	#[serde(skip)]
+	points: Option<Vec<DataValues>>,
+}
+
+/// Options which control how a graph is drawn, none of which write a file unless given a path
+#[derive(Debug, Clone)]
+pub struct RenderOptions {
+	/// Delimiter used within the csv data files
+	pub csv_delimiter: String,
+	/// Optional path to write the pixel positions of the plot area to
+	pub geometry_report: Option<String>,
+	/// Optional directory to write the values of each data set to as they will be plotted
+	pub dump_processed_data: Option<String>,
+	/// Optional path of a `.ron` file recording where the x-axis is placed. The first graph writes it and
+	/// later graphs place their x-axis at the same pixel columns
+	pub align_file: Option<String>,
+	/// Optional hook told as drawing reaches each stage, nothing is reported without one
+	pub progress: Option<Arc<dyn RenderProgress + Send + Sync>>,
+	/// Optional bytes of a `.ttf` or `.otf` font to write text with instead of looking up a system or
+	/// bundled font
+	pub font: Option<Vec<u8>>,
+}
+
+impl RenderOptions {
+	/// Tell the progress hook, if any, that drawing has reached `stage`
+	fn report(&self, stage: RenderStage) {
+		if let Some(progress) = &self.progress {
+			progress.stage(stage);
+		}
+	}
+}
+
+impl Default for RenderOptions {
+	/// Comma delimited csv without writing any reports
+	fn default() -> Self {
+		RenderOptions {
+			csv_delimiter: String::from(","),
+			geometry_report: None,
+			dump_processed_data: None,
+			align_file: None,
+			progress: None,
+			font: None,
+		}
+	}
+}
+
+/// Options supplied alongside a config which control how a graph is built and written out
+#[derive(Debug, Clone)]
+pub struct BuildOptions {
+	/// Directory the png will be written to
+	pub output: String,
+	/// How the graph is drawn
+	pub render: RenderOptions,
+	/// Optional path to a `.ron` style applied underneath the config, takes precedence over the
+	/// config's own `style_path`
+	pub style: Option<String>,
+	/// Optional names of the only data sets to plot
+	pub only: Option<Vec<String>>,
+	/// Optional names of data sets to leave out of the graph
+	pub exclude: Option<Vec<String>>,
+	/// Optional file name, without an extension, to save the graph as instead of one derived from the title
+	pub output_name: Option<String>,
+	/// Image format the graph is saved as
+	pub format: OutputFormat,
+	/// Quality of lossy formats from 1 to 100
+	pub quality: u8,
+	/// What to do when the image file already exists
+	pub existing_output: ExistingOutput,
+	/// Embed the plotrs version, config path and hash and the data file paths into a png
+	pub embed_metadata: bool,
+}
+
+impl Default for BuildOptions {
+	/// The defaults of the `plotrs` binary, a png written to the working directory from comma delimited csv
+	fn default() -> Self {
+		BuildOptions {
+			output: String::from("."),
+			render: RenderOptions::default(),
+			style: None,
+			only: None,
+			exclude: None,
+			output_name: None,
+			format: OutputFormat::default(),
+			quality: 90,
+			existing_output: ExistingOutput::default(),
+			embed_metadata: false,
+		}
+	}
 }
 
-/// Creates a canvas and draws the scatter graph over it
-pub fn scatter_builder(path: &str, output: &str, csv_delimiter: &str) {
+/// Reads a config, draws the scatter graph it describes and saves it, returning the path the image was
+/// written to
+pub fn scatter_builder(path: &str, options: &BuildOptions) -> Result<PathBuf, PlotError> {
 	info!("Building scatter chart...");
-	let scatter: Scatter = Scatter::deserialise(path);
+	let scatter: Scatter = Scatter::deserialise(path, options.style.as_deref())?
+		.filter_data_sets(options.only.as_deref(), options.exclude.as_deref())?;
+	// a name given on the command line takes precedence over the config, then the title
+	let file_name = match options
+		.output_name
+		.as_ref()
+		.or(scatter.output_name.as_ref())
+	{
+		Some(name) => name.to_owned(),
+		None => to_file_name(&scatter.title),
+	};
+	// an output that cannot be written is reported before spending time drawing the graph
+	let output = get_output_path(
+		&options.output,
+		&file_name,
+		options.format,
+		options.existing_output,
+	)?;
+	let canvas = scatter_render(&scatter, &options.render)?;
+	let metadata = if options.embed_metadata {
+		get_provenance(path, &scatter.data_sets)?
+	} else {
+		Vec::new()
+	};
+	// save the resulting image
+	options.render.report(RenderStage::Saving);
+	write_image(canvas, output, options.format, options.quality, &metadata).map_err(PlotError::from)
+}
+
+/// Reads a config as [`scatter_builder`] would and serialises it back to `.ron` with its style merged in and
+/// the values chosen automatically, such as colours, axis resolutions and ranges, written out. Drawing the
+/// returned config produces the same graph
+pub fn scatter_resolved_config(path: &str, options: &BuildOptions) -> Result<String, PlotError> {
+	let mut scatter: Scatter = Scatter::deserialise(path, options.style.as_deref())?
+		.filter_data_sets(options.only.as_deref(), options.exclude.as_deref())?;
+	let (data_values, point_colours, _) = load_data_values(
+		&scatter.data_sets,
+		options.render.csv_delimiter.as_str(),
+		&scatter.number_format.unwrap_or_default(),
+		scatter.relative_time_origin.unwrap_or_default(),
+		scatter.palette.unwrap_or_default(),
+	)?;
+	let limits = AxisLimits::new(&scatter, &data_values, &point_colours)?;
+	// limits rounded to a nice step are pinned so the config draws the same axes
+	if scatter.x_axis_range.is_none() {
+		scatter.x_axis_range = Some((limits.min_xy_scaled.0 as f32, limits.max_xy_scaled.0 as f32));
+	}
+	if scatter.y_axis_range.is_none() {
+		scatter.y_axis_range = Some((limits.min_xy_scaled.1 as f32, limits.max_xy_scaled.1 as f32));
+	}
+	scatter.x_axis_resolution = Some(limits.x_axis_resolution);
+	scatter.y_axis_resolution = Some(limits.y_axis_resolution);
+	if let Some((_, y2_axis_resolution)) = limits.y2_axis {
+		scatter.y2_axis_resolution = Some(y2_axis_resolution);
+	}
+	Ok(scatter.to_ron_string()?)
+}
+
+/// Reads a config as [`scatter_builder`] would and estimates the work involved in drawing the graph it
+/// describes without drawing it
+pub fn scatter_estimate(path: &str, options: &BuildOptions) -> Result<Estimate, PlotError> {
+	let scatter: Scatter = Scatter::deserialise(path, options.style.as_deref())?
+		.filter_data_sets(options.only.as_deref(), options.exclude.as_deref())?;
+	let (data_values, point_colours, _) = load_data_values(
+		&scatter.data_sets,
+		options.render.csv_delimiter.as_str(),
+		&scatter.number_format.unwrap_or_default(),
+		scatter.relative_time_origin.unwrap_or_default(),
+		scatter.palette.unwrap_or_default(),
+	)?;
+	let limits = AxisLimits::new(&scatter, &data_values, &point_colours)?;
+	info!("Calibrating drawing speed...");
+	let nanos_per_pixel = calibrate();
+	let estimate = Estimate::new(
+		&scatter,
+		&data_values,
+		&limits.quadrants,
+		limits.x_axis_resolution,
+		limits.y_axis_resolution,
+	);
+	Ok(Estimate {
+		nanos_per_pixel,
+		..estimate
+	})
+}
+
+/// The extent of the data once buffer space has been added around it along with the quadrants and
+/// resolutions of the axes which span it
+struct AxisLimits {
+	/// Smallest x and y spanned by the axes
+	min_xy_scaled: (i32, i32),
+	/// Largest x and y spanned by the axes
+	max_xy_scaled: (i32, i32),
+	/// Which quadrants of the graph are drawn
+	quadrants: Quadrants,
+	/// Number of times the x-axis is divided, resolved from the config or chosen automatically
+	x_axis_resolution: u32,
+	/// Number of times the y-axis is divided, resolved from the config or chosen automatically
+	y_axis_resolution: u32,
+	/// The smallest and largest values spanned by the secondary y-axis along with the number of times it
+	/// is divided, when any data set is plotted against it
+	y2_axis: Option<((i32, i32), u32)>,
+	/// The smallest and largest values of the sets coloured by value, spanning their colormaps
+	colour_range: Option<(f32, f32)>,
+}
+
+impl AxisLimits {
+	/// Find the limits of the axes needed to plot the values of the data sets, along with the range of the
+	/// values their points are coloured by
+	fn new(
+		scatter: &Scatter,
+		data_values: &[Vec<DataValues>],
+		point_colours: &[Option<PointColours>],
+	) -> Result<AxisLimits, PlotError> {
+		// Find the size of the data - this tells us whether any axis requires a negative range.
+		// Of the form `(min_x, min_y), (max_x, max_y)`
+		info!("Finding min and max range of data...");
+		// every set shares the x-axis while those plotted against the secondary y-axis are left out of the
+		// bounds of the y-axis
+		let x_bounds = get_data_bounds(get_values_in_bounds(&scatter.data_sets, data_values, None));
+		let y_bounds = get_data_bounds(get_values_in_bounds(
+			&scatter.data_sets,
+			data_values,
+			Some(YAxis::Primary),
+		));
+		let (min_xy, max_xy): ((f32, f32), (f32, f32)) = (
+			((x_bounds.0).0, (y_bounds.0).1),
+			((x_bounds.1).0, (y_bounds.1).1),
+		);
+		// We want to create buffer space around our bounds so data points are not plotted directly on an axis, if
+		// large symbols are used for plotting they may obscure data labels on an axis. We scale the bounds by the
+		// padding fraction, 10% by default, taking care to shrink and expand bounds based on their sign
+		let (x_padding, y_padding) = scatter.padding_fractions();
+		let min_x_scaled = buffer_min(min_xy.0, x_padding);
+		let min_y_scaled = buffer_min(min_xy.1, y_padding);
+		let max_x_scaled = buffer_max(max_xy.0, x_padding);
+		let max_y_scaled = buffer_max(max_xy.1, y_padding);
+		// data without extent, e.g every y value is zero, is given an axis of whole units either side of it
+		let x_scaled = widen_degenerate_limits((min_x_scaled as i32, max_x_scaled as i32));
+		let y_scaled = widen_degenerate_limits((min_y_scaled as i32, max_y_scaled as i32));
+		// without a fixed range or resolution the buffered bounds are rounded outward to a nice step, or to
+		// whole calendar steps for timestamps
+		let x_auto = if scatter.x_axis_range.is_none() && scatter.x_axis_resolution.is_none() {
+			scatter
+				.x_tick_datetime_format
+				.as_ref()
+				.map(|_| calendar_limits(min_xy.0 as f64, max_xy.0 as f64, 10))
+				.filter(|((min, _), _)| *min >= 0)
+				.or_else(|| Some(get_nice_axis_limits(x_scaled)))
+		} else {
+			None
+		};
+		let y_auto = if scatter.y_axis_range.is_none() && scatter.y_axis_resolution.is_none() {
+			Some(get_nice_axis_limits(y_scaled))
+		} else {
+			None
+		};
+		// a fixed range takes the place of the buffered bounds of its axis
+		let (min_x, max_x) = match x_auto {
+			Some((limits, _)) => limits,
+			None => scatter.x_axis_range.map_or(x_scaled, range_limits),
+		};
+		let (min_y, max_y) = match y_auto {
+			Some((limits, _)) => limits,
+			None => scatter.y_axis_range.map_or(y_scaled, range_limits),
+		};
+		let min_xy_scaled = (min_x, min_y);
+		debug!("Minimum x-y with buffer space {:?}", min_xy_scaled);
+		let max_xy_scaled = (max_x, max_y);
+		debug!("Maximum x-y with buffer space {:?}", max_xy_scaled);
+		// With the scaled values we find which quadrants to draw are valid
+		let quadrants = get_quadrants(min_xy_scaled, max_xy_scaled)?;
+		info!("Quadrants to draw based on data set {:?}", quadrants);
+		// Axes spanning negative and positive values draw their resolution in both directions
+		let x_axis_resolution = resolve_axis_resolution(
+			"x_axis_resolution",
+			scatter.x_axis_resolution,
+			x_auto.map(|(_, resolution)| resolution),
+			(min_xy_scaled.0, max_xy_scaled.0),
+			quadrants.is_x_paired(),
+		);
+		let y_axis_resolution = resolve_axis_resolution(
+			"y_axis_resolution",
+			scatter.y_axis_resolution,
+			y_auto.map(|(_, resolution)| resolution),
+			(min_xy_scaled.1, max_xy_scaled.1),
+			quadrants.is_y_paired(),
+		);
+		let y2_axis = if scatter
+			.data_sets
+			.iter()
+			.any(|set| set.y_axis() == YAxis::Secondary)
+		{
+			if matches!(
+				quadrants,
+				Quadrants::TopLeft | Quadrants::BottomLeft | Quadrants::LeftPair
+			) {
+				return Err(PlotError::Render("Unable to draw a secondary y-axis as every x value is negative, which places the y-axis along the right edge of the plot".to_owned()));
+			}
+			let (min_y2, max_y2) = get_data_bounds(get_values_in_bounds(
+				&scatter.data_sets,
+				data_values,
+				Some(YAxis::Secondary),
+			));
+			// without a resolution the secondary axis is divided as the primary is so their markers line up
+			let y2_axis_resolution =
+				scatter
+					.y2_axis_resolution
+					.unwrap_or(if quadrants.is_y_paired() {
+						2 * y_axis_resolution
+					} else {
+						y_axis_resolution
+					});
+			let limits = get_aligned_axis_limits(
+				(
+					buffer_min(min_y2.1, y_padding),
+					buffer_max(max_y2.1, y_padding),
+				),
+				y2_axis_resolution,
+			);
+			debug!(
+				"Secondary y-axis spans {:?} divided {} times",
+				limits, y2_axis_resolution
+			);
+			Some((limits, y2_axis_resolution))
+		} else {
+			None
+		};
+		Ok(AxisLimits {
+			min_xy_scaled,
+			max_xy_scaled,
+			quadrants,
+			x_axis_resolution,
+			y_axis_resolution,
+			y2_axis,
+			colour_range: get_colour_range(point_colours),
+		})
+	}
+}
+
+/// Move the smallest value of some data further from the largest by a `fraction` of it, taking care to shrink
+/// or expand it based on its sign, so that points are not plotted directly on an axis. A bound of zero stays
+/// at zero
+fn buffer_min(min: f32, fraction: f32) -> f32 {
+	if min.is_sign_positive() {
+		min / (1.0 + fraction)
+	} else {
+		min * (1.0 + fraction)
+	}
+}
+
+/// Move the largest value of some data further from the smallest by a `fraction` of it, see [`buffer_min`]
+fn buffer_max(max: f32, fraction: f32) -> f32 {
+	if max.is_sign_positive() {
+		max * (1.0 + fraction)
+	} else {
+		max / (1.0 + fraction)
+	}
+}
+
+/// Widen whole number limits which are equal by one unit either side so that the axis has length, e.g
+/// `(0, 0)` becomes `(-1, 1)`
+fn widen_degenerate_limits(limits: (i32, i32)) -> (i32, i32) {
+	if limits.0 == limits.1 {
+		(limits.0 - 1, limits.1 + 1)
+	} else {
+		limits
+	}
+}
+
+/// The whole number limits of an axis spanning a fixed range, rounded outward so the range is covered
+fn range_limits(range: (f32, f32)) -> (i32, i32) {
+	(range.0.floor() as i32, range.1.ceil() as i32)
+}
+
+/// Draw a graph onto a new canvas and return it without saving it. The csv files of the data sets are
+/// read but nothing is written unless `options` name a report, e.g to serve a graph as a png:
+///
+/// ```
+/// use image::{codecs::png::PngEncoder, ColorType, ImageEncoder};
+/// use plotrs::scatter::{scatter_render, DataSetBuilder, RenderOptions, ScatterBuilder};
+///
+/// # let data_path = std::env::temp_dir().join("plotrs_render_doc.csv");
+/// # std::fs::write(&data_path, "x,y\n1,2\n2,4\n3,5\n").unwrap();
+/// # let data_path = data_path.to_str().unwrap();
+/// let scatter = ScatterBuilder::new()
+///     .title("Served")
+///     .canvas_size(400, 300)
+///     .axis_labels("x", "y")
+///     .add_data_set(DataSetBuilder::new("data", data_path, 0, 1))
+///     .build()?;
+/// let image = scatter_render(&scatter, &RenderOptions::default())?;
+/// let mut png: Vec<u8> = Vec::new();
+/// PngEncoder::new(&mut png).write_image(
+///     image.as_raw(),
+///     image.width(),
+///     image.height(),
+///     ColorType::Rgba8,
+/// )?;
+/// assert!(png.starts_with(b"\x89PNG"));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn scatter_render(
+	scatter: &Scatter,
+	options: &RenderOptions,
+) -> Result<ImageBuffer<Rgba<u8>, Vec<u8>>, PlotError> {
+	let (x_tick_format, y_tick_format) = scatter.tick_formats()?;
+	// the secondary y-axis is in a unit of its own
+	let y2_tick_format = TickFormat {
+		suffix: None,
+		..y_tick_format.clone()
+	};
+	let scale = scatter.supersample()?;
+	let axis_style = scatter.axis_style()?;
 	info!("Drawing canvas...");
-	let mut canvas = draw_base_canvas(scatter.canvas_pixel_size);
+	// when supersampling everything is drawn `scale` times larger and downsampled before saving
+	let canvas_size = (
+		scatter.canvas_pixel_size.0 * scale,
+		scatter.canvas_pixel_size.1 * scale,
+	);
+	let mut canvas = draw_base_canvas(canvas_size);
 	// Calcualte font sizes
 	info!("Calculating font sizes...");
-	let font_sizes = FontSizes::new(&scatter.canvas_pixel_size);
+	let mut font_sizes = FontSizes::new(&scatter.canvas_pixel_size);
+	if let Some(legend_font_size) = scatter.legend_font_size {
+		font_sizes.legend_font_size = legend_font_size;
+	}
+	let font_sizes = font_sizes.scale(scale);
+	// the font is loaded once and shared by everything written onto the canvas, one supplied by the caller
+	// takes the place of looking one up
+	let font = match &options.font {
+		Some(bytes) => load_font(bytes.clone())?,
+		None => get_system_font()?,
+	};
 	// To fit the various labels, axes, legend and title all onto the canvas snugly we need some values
 	// telling us how much space has already been occupied by previous elements.
 	// We use these 4 values of the struct to indicate the amount of pixel space consumed from each border of the canvas
 	// in vertical and horizontal directions. The general convention is the are arranged clockwise from the top
-	let mut canvas_edges_used = VHConsumedCanvasSpace::new();
+	let mut canvas_edges_used = VHConsumedCanvasSpace::with_border(CANVAS_BORDER_PIXELS * scale);
+	let chrome = scatter.chrome.unwrap_or_default();
 	// Place the title at the top of the canvas and tell us how many v-pixels have been used
-	info!("Building title...");
-	// Building the title tells us how many pixel have been consumed from the top of the canvas
-	canvas_edges_used.add(build_title(
-		&mut canvas,
-		&scatter.title,
-		font_sizes.title_font_size,
-	));
-	// optionally build the legend
-	if scatter.has_legend {
-		let legend_fields = get_legend_fields(&scatter.data_sets);
-		let legend_origin_x = canvas.dimensions().0
-			- canvas_edges_used.h_space_from_right
-			- (canvas.dimensions().0 / 10);
-		let legend_origin_y = canvas_edges_used.v_space_from_top + canvas.dimensions().1 / 4;
-		// let legend_origin: (u32, u32) = (axis_max.0, axis_max.1 * 2);
-		canvas_edges_used.add(build_legend(
+	if chrome == Chrome::Full {
+		info!("Building title...");
+		// Building the title tells us how many pixel have been consumed from the top of the canvas
+		canvas_edges_used.add(build_title(
 			&mut canvas,
-			(legend_origin_x, legend_origin_y),
-			legend_fields,
-			font_sizes.legend_font_size,
-		));
+			&scatter.title,
+			&font,
+			font_sizes.title_font_size,
+			scale,
+		)?);
 	}
-	// Find the size of the data - this tells us whether any axis requires a negative range.
-	// Of the form `(min_x, min_y), (max_x, max_y)`
-	info!("Finding min and max range of data...");
-	let (min_xy, max_xy): ((f32, f32), (f32, f32)) =
-		get_data_bounds(&scatter.data_sets, csv_delimiter);
-	// We want to create buffer space around our bounds so data points are not plotted directly on an axis, if
-	// large symbols are used for plotting they may obscure data labels on an axis. We scale the bounds by 10%
-	// taking care to shrink and expand bounds based on their sign
-	let min_x_scaled = if min_xy.0.is_sign_positive() {
-		min_xy.0 / 1.1
-	} else {
-		min_xy.0 * 1.1
+	options.report(RenderStage::LoadingData);
+	let (data_values, point_colours, point_symbols) = load_data_values(
+		&scatter.data_sets,
+		options.csv_delimiter.as_str(),
+		&scatter.number_format.unwrap_or_default(),
+		scatter.relative_time_origin.unwrap_or_default(),
+		scatter.palette.unwrap_or_default(),
+	)?;
+	// regressions are fitted to the values of their set before anything is drawn against them
+	let best_fits = fit_best_fits(&scatter.data_sets, &data_values)?;
+	// optionally build the legend once the curves it names are fitted, its extent is kept so that
+	// equations can be written clear of it. A legend inside the axes is placed once they are known
+	let legend_overlay = scatter.legend_overlay == Some(true);
+	let legend_style = LegendStyle {
+		position: scatter.legend_position.unwrap_or_default(),
+		// an overlaid legend is framed by default to set it apart from the data beneath
+		frame: scatter.legend_frame.unwrap_or(legend_overlay),
+		background: scatter.legend_background,
 	};
-	let min_y_scaled = if min_xy.1.is_sign_positive() {
-		min_xy.1 / 1.1
-	} else {
-		min_xy.1 * 1.1
+	let mut legend_bounds = None;
+	let mut inside_legend = None;
+	let mut outside_legend = None;
+	if scatter.has_legend == Some(true) && chrome == Chrome::Full {
+		let legend = Legend {
+			title: scatter.legend_title.clone(),
+			row_spacing: scatter.legend_row_spacing.unwrap_or(2.0),
+			antialias: scatter.antialias == Some(true),
+			fields: get_legend_fields(
+				&scatter.data_sets,
+				&best_fits,
+				&point_colours,
+				&point_symbols,
+				scale,
+				scatter.canvas_pixel_size.0,
+			),
+		};
+		match (legend_style.position.corner(), legend_overlay) {
+			_ if legend.fields.is_empty() => {
+				warn!("Every data set is hidden from the legend, no legend is drawn")
+			}
+			// without a corner an overlaid legend takes whichever is quietest once the data is drawn
+			(corner, true) => inside_legend = Some((corner, legend)),
+			(Some(corner), false) => inside_legend = Some((Some(corner), legend)),
+			(None, false) => {
+				// the legend is as wide as its longest name and sits against the right border
+				let (legend_origin_x, legend_origin_y) = get_outside_legend_origin(
+					canvas.dimensions().0 - canvas_edges_used.h_space_from_right - 1,
+					canvas_edges_used.v_space_from_top + canvas.dimensions().1 / 4,
+					&legend,
+					&font,
+					font_sizes.legend_font_size,
+					scale,
+				)?;
+				legend_bounds = Some(get_legend_bounds(
+					(legend_origin_x, legend_origin_y),
+					&legend,
+					&font,
+					font_sizes.legend_font_size,
+					scale,
+				)?);
+				// the space is reserved now so that the axes are placed clear of the legend, which
+				// is drawn once they are
+				canvas_edges_used.add(get_legend_space(
+					&legend,
+					&font,
+					font_sizes.legend_font_size,
+					scale,
+					legend_style.position,
+				)?);
+				outside_legend = Some(((legend_origin_x, legend_origin_y), legend));
+			}
+		}
+	}
+	options.report(RenderStage::ComputingBounds);
+	let AxisLimits {
+		min_xy_scaled,
+		max_xy_scaled,
+		quadrants,
+		x_axis_resolution,
+		y_axis_resolution,
+		y2_axis,
+		colour_range,
+	} = AxisLimits::new(scatter, &data_values, &point_colours)?;
+	// a colour bar for points coloured by value is given the space to the right of the axes, between them
+	// and any legend outside of them, its column is kept to draw it once the height of the axes is known
+	let colour_bar = match (
+		scatter.data_sets.iter().find_map(|set| set.colour_by),
+		colour_range,
+	) {
+		(Some(colour_by), Some(colour_range)) if chrome == Chrome::Full => {
+			let space =
+				get_colour_bar_space(colour_range, &font, font_sizes.axis_unit_font_size, scale)?;
+			let left = canvas.dimensions().0
+				- canvas_edges_used.h_space_from_right
+				- space.h_space_from_right;
+			canvas_edges_used.add(space);
+			Some((left, colour_by.colormap, colour_range))
+		}
+		_ => None,
 	};
-	let max_x_scaled = if max_xy.0.is_sign_positive() {
-		max_xy.0 * 1.1
+	let has_tick_labels = chrome != Chrome::BareAxes && scatter.show_tick_labels != Some(false);
+	if chrome == Chrome::Full {
+		// the secondary y-axis label shares the row of the y-axis label
+		let label_row = canvas_edges_used.v_space_from_top;
+		info!("Building y-axis label...");
+		// Draws the y-axis label and returns the amount of pixel space used up by the glyphs
+		let y_label_space = build_y_axis_label(
+			&mut canvas,
+			scatter.y_axis_label.clone(),
+			&font,
+			font_sizes.axis_font_size,
+			&quadrants,
+			scatter.y_axis_label_orientation.unwrap_or_default(),
+			canvas_edges_used.v_space_from_top,
+			canvas_edges_used.h_space_from_right,
+			canvas_edges_used.v_space_from_bottom,
+			canvas_edges_used.h_space_from_left,
+			scale,
+		)?;
+		// the values along the y-axis are written into the width taken by a horizontal label, values wider
+		// than the label, e.g with a tick suffix, or those beside a turned label need space of their own
+		let (left_of_axis, right_of_axis) =
+			if scatter.y_axis_label_orientation == Some(LabelOrientation::Vertical) {
+				(0, 0)
+			} else {
+				(
+					y_label_space.h_space_from_left,
+					y_label_space.h_space_from_right,
+				)
+			};
+		let markings = get_scale_marking_space(
+			&quadrants,
+			&font,
+			font_sizes.axis_unit_font_size,
+			(min_xy_scaled.1, max_xy_scaled.1),
+			has_tick_labels,
+			&y_tick_format,
+			&axis_style,
+			scale,
+		)?;
+		canvas_edges_used.add(y_label_space);
+		canvas_edges_used.add(VHConsumedCanvasSpace {
+			v_space_from_top: 0,
+			h_space_from_right: markings.h_space_from_right.saturating_sub(right_of_axis),
+			v_space_from_bottom: 0,
+			h_space_from_left: markings.h_space_from_left.saturating_sub(left_of_axis),
+		});
+		info!("Building x-axis label...");
+		// Draws the x-axis label and returns the amount of pixel ocupied from the bottom
+		canvas_edges_used.add(build_x_axis_label(
+			&mut canvas,
+			scatter.x_axis_label.clone(),
+			&font,
+			font_sizes.axis_font_size,
+			&quadrants,
+			canvas_edges_used.v_space_from_top,
+			canvas_edges_used.h_space_from_right,
+			canvas_edges_used.v_space_from_bottom,
+			canvas_edges_used.h_space_from_left,
+			scale,
+		)?);
+		if let (Some(_), Some(label)) = (y2_axis, &scatter.y2_axis_label) {
+			info!("Building secondary y-axis label...");
+			canvas_edges_used.add(build_y2_axis_label(
+				&mut canvas,
+				label,
+				&font,
+				font_sizes.axis_font_size,
+				&quadrants,
+				label_row,
+				canvas_edges_used.h_space_from_right,
+				scale,
+			)?);
+		}
 	} else {
-		max_xy.0 / 1.1
+		// Without axis labels only the scale markings need to fit around the axes
+		canvas_edges_used.add(get_scale_marking_space(
+			&quadrants,
+			&font,
+			font_sizes.axis_unit_font_size,
+			(min_xy_scaled.1, max_xy_scaled.1),
+			chrome == Chrome::PlotOnly && scatter.show_tick_labels != Some(false),
+			&y_tick_format,
+			&axis_style,
+			scale,
+		)?);
+	}
+	if let Some((y2_data_min_max_limits, y2_axis_resolution)) = y2_axis {
+		canvas_edges_used.add(get_y2_axis_space(
+			&font,
+			font_sizes.axis_unit_font_size,
+			y2_data_min_max_limits,
+			y2_axis_resolution,
+			has_tick_labels,
+			&y2_tick_format,
+			&axis_style,
+			scale,
+		)?);
+	}
+	// Arrowheads point beyond the top and right ends of the axes so need room before the canvas border
+	let arrow_space = axis_style.get_arrow_space(scale);
+	canvas_edges_used.add(VHConsumedCanvasSpace {
+		v_space_from_top: arrow_space,
+		h_space_from_right: arrow_space,
+		v_space_from_bottom: 0,
+		h_space_from_left: 0,
+	});
+	// A previous graph may have recorded where its x-axis was placed for this one to line up with
+	let alignment = match options.align_file.as_deref() {
+		Some(align_path) => PlotAlignment::read(align_path)?,
+		None => None,
 	};
-	let max_y_scaled = if max_xy.1.is_sign_positive() {
-		max_xy.1 * 1.1
-	} else {
-		max_xy.1 / 1.1
+	let x_axis_pixel_bounds = match &alignment {
+		Some(alignment) => Some(get_aligned_x_axis_bounds(
+			alignment,
+			options.align_file.as_deref().unwrap_or_default(),
+			&canvas_edges_used,
+			scatter.canvas_pixel_size.0,
+			x_axis_resolution,
+			quadrants.is_x_paired(),
+			scale,
+		)?),
+		None => None,
 	};
-	let min_xy_scaled = (min_x_scaled as i32, min_y_scaled as i32);
-	debug!("Minimum x-y with buffer space {:?}", min_xy_scaled);
-	let max_xy_scaled = (max_x_scaled as i32, max_y_scaled as i32);
-	debug!("Maximum x-y with buffer space {:?}", max_xy_scaled);
-	// With the scaled values we find which quadrants to draw are valid
-	let quadrants = get_quadrants(min_xy_scaled, max_xy_scaled);
-	info!("Quadrants to draw based on data set {:?}", quadrants);
-	info!("Building y-axis label...");
-	// Draws the y-axis label and returns the amount of pixel space used up by the glyphs
-	canvas_edges_used.add(build_y_axis_label(
-		&mut canvas,
-		scatter.y_axis_label,
-		font_sizes.axis_font_size,
-		&quadrants,
-		canvas_edges_used.v_space_from_top,
-		canvas_edges_used.h_space_from_right,
-		canvas_edges_used.v_space_from_bottom,
-		canvas_edges_used.h_space_from_left,
-	));
-	info!("Building x-axis label...");
-	// Draws the x-axis label and returns the amount of pixel ocupied from the bottom
-	canvas_edges_used.add(build_x_axis_label(
-		&mut canvas,
-		scatter.x_axis_label,
-		font_sizes.axis_font_size,
-		&quadrants,
-		canvas_edges_used.v_space_from_top,
-		canvas_edges_used.h_space_from_right,
-		canvas_edges_used.v_space_from_bottom,
-		canvas_edges_used.h_space_from_left,
-	));
 	// With the text drawn we can calculate the rectangular space for the axes, represrnted as two tuples
 	// pinpointing the bottom left origin of the graph and the top right corner.
 	// Pixel position showing the maximum extents of the axes
@@ -215,11 +1101,21 @@ pub fn scatter_builder(path: &str, output: &str, csv_delimiter: &str) {
 		canvas_edges_used.v_space_from_bottom,
 		canvas_edges_used.h_space_from_left,
 		canvas.dimensions(),
-		scatter.x_axis_resolution,
-		scatter.y_axis_resolution,
+		x_axis_resolution,
+		y_axis_resolution,
+		x_axis_pixel_bounds,
 	);
 	debug!("Minimum axis placement {:?}", axis_min);
 	debug!("Maximun axis placement {:?}", axis_max);
+	if let (Some(align_path), None) = (&options.align_file, &alignment) {
+		// the first graph records its placement for the others
+		PlotAlignment {
+			canvas_width: scatter.canvas_pixel_size.0,
+			axis_min_pixel_column: axis_min.0 / scale,
+			axis_max_pixel_column: axis_max.0 / scale,
+		}
+		.write(align_path)?;
+	}
 	// Pixel position of axes origin can be determined from the min-max intersection
 	let axis_origin: (u32, u32) = get_xy_axis_pixel_origin(&quadrants, axis_min, axis_max);
 	debug!("Origin axis placement {:?}", axis_origin);
@@ -235,21 +1131,104 @@ pub fn scatter_builder(path: &str, output: &str, csv_delimiter: &str) {
 	let y_data_min_max_limits: (i32, i32) = (min_xy_scaled.1, max_xy_scaled.1);
 	// Now we can find the number of axis units per x and y
 	// Ensure we don't divide by zero!
-	if !(max_xy_scaled.0 as f32 - min_xy_scaled.0 as f32).is_normal() {
-		error!("Difference between the smallest and largest x values have produced Zero, Infinite, NaN or a Subnormal value. Likely if your data set only contains a single row. Ensure you have multiple rows and that your largest x value minus your smallest x doesn't produce zero");
-		std::process::exit(1)
+	if !((max_xy_scaled.0 as f64 - min_xy_scaled.0 as f64) as f32).is_normal() {
+		return Err(PlotError::Render("Difference between the smallest and largest x values have produced Zero, Infinite, NaN or a Subnormal value. Likely if your data set only contains a single row. Ensure you have multiple rows and that your largest x value minus your smallest x doesn't produce zero".to_owned()));
 	}
 	let x_axis_data_scale_factor: f32 =
-		x_axis_length as f32 / (max_xy_scaled.0 as f32 - min_xy_scaled.0 as f32).abs();
-	if !(max_xy_scaled.1 as f32 - min_xy_scaled.1 as f32).is_normal() {
-		error!("Difference between the smallest and largest y values have produced Zero, Infinite, NaN or a Subnormal value. Likely if your data set only contains a single row. Ensure you have multiple rows and that your largest y value minus your smallest y doesn't produce zero");
-		std::process::exit(1)
+		x_axis_length as f32 / ((max_xy_scaled.0 as f64 - min_xy_scaled.0 as f64) as f32).abs();
+	if !((max_xy_scaled.1 as f64 - min_xy_scaled.1 as f64) as f32).is_normal() {
+		return Err(PlotError::Render("Difference between the smallest and largest y values have produced Zero, Infinite, NaN or a Subnormal value. Likely if your data set only contains a single row. Ensure you have multiple rows and that your largest y value minus your smallest y doesn't produce zero".to_owned()));
 	}
 	let y_axis_data_scale_factor: f32 =
-		y_axis_length as f32 / (max_xy_scaled.1 as f32 - min_xy_scaled.1 as f32).abs();
+		y_axis_length as f32 / ((max_xy_scaled.1 as f64 - min_xy_scaled.1 as f64) as f32).abs();
 	debug!("X-axis scale factor {}", x_axis_data_scale_factor);
 	debug!("Y-axis scale factor {}", y_axis_data_scale_factor);
-
+	if let Some(report_path) = &options.geometry_report {
+		// report positions on the saved image rather than the supersampled canvas
+		PlotGeometry {
+			canvas_pixel_size: scatter.canvas_pixel_size,
+			axis_min_pixel: (axis_min.0 / scale, axis_min.1 / scale),
+			axis_max_pixel: (axis_max.0 / scale, axis_max.1 / scale),
+			axis_origin_pixel: (axis_origin.0 / scale, axis_origin.1 / scale),
+			x_data_min_max_limits,
+			y_data_min_max_limits,
+			y2_data_min_max_limits: y2_axis.map(|(limits, _)| limits),
+		}
+		.write(report_path)?;
+	}
+	if let Some(dir) = &options.dump_processed_data {
+		dump_data_values(
+			&scatter.data_sets,
+			&data_values,
+			dir,
+			x_data_min_max_limits,
+			y_data_min_max_limits,
+			y2_axis.map(|(limits, _)| limits),
+		)?;
+	}
+	let origin_values = quadrants.origin_values(x_data_min_max_limits, y_data_min_max_limits);
+	// the zero lines are projected in the same way as the points so that points of zero land on them
+	let zero_lines = if scatter.highlight_zero == Some(true) {
+		let has_zero = |limits: (i32, i32)| limits.0 <= 0 && 0 <= limits.1;
+		(
+			has_zero(x_data_min_max_limits).then(|| {
+				let x = get_reversed_value(0.0, x_data_min_max_limits, axis_style.is_x_reversed);
+				get_x_pixel(
+					x as f32 - origin_values.0,
+					x_axis_data_scale_factor,
+					axis_origin.0,
+				)
+			}),
+			has_zero(y_data_min_max_limits).then(|| {
+				let y = get_reversed_value(0.0, y_data_min_max_limits, axis_style.is_y_reversed);
+				get_y_pixel(
+					y as f32 - origin_values.1,
+					y_axis_data_scale_factor,
+					axis_origin.1,
+				)
+			}),
+		)
+	} else {
+		(None, None)
+	};
+	// custom scale markers are projected in the same way as the points
+	let x_ticks = scatter.x_ticks.as_ref().map(|ticks| {
+		get_custom_ticks("x", ticks, x_data_min_max_limits, &x_tick_format)
+			.into_iter()
+			.map(|(value, text)| {
+				let x = get_reversed_value(
+					value as f64,
+					x_data_min_max_limits,
+					axis_style.is_x_reversed,
+				);
+				let px = get_x_pixel(
+					x as f32 - origin_values.0,
+					x_axis_data_scale_factor,
+					axis_origin.0,
+				);
+				(px, text)
+			})
+			.collect::<Vec<_>>()
+	});
+	let y_ticks = scatter.y_ticks.as_ref().map(|ticks| {
+		get_custom_ticks("y", ticks, y_data_min_max_limits, &y_tick_format)
+			.into_iter()
+			.map(|(value, text)| {
+				let y = get_reversed_value(
+					value as f64,
+					y_data_min_max_limits,
+					axis_style.is_y_reversed,
+				);
+				let py = get_y_pixel(
+					y as f32 - origin_values.1,
+					y_axis_data_scale_factor,
+					axis_origin.1,
+				);
+				(py, text)
+			})
+			.collect::<Vec<_>>()
+	});
+	options.report(RenderStage::DrawingAxes);
 	draw_xy_axes(
 		&quadrants,
 		&mut canvas,
@@ -260,73 +1239,989 @@ pub fn scatter_builder(path: &str, output: &str, csv_delimiter: &str) {
 		y_axis_length,
 		x_data_min_max_limits,
 		y_data_min_max_limits,
+		&font,
 		font_sizes.axis_unit_font_size,
-		scatter.has_grid,
-		scatter.x_axis_resolution,
-		scatter.y_axis_resolution,
+		scatter.has_grid == Some(true),
+		zero_lines,
+		x_ticks.as_deref(),
+		y_ticks.as_deref(),
+		MinorTicks {
+			count: scatter.x_minor_ticks,
+			has_grid: scatter.minor_grid == Some(true),
+		},
+		MinorTicks {
+			count: scatter.y_minor_ticks,
+			has_grid: scatter.minor_grid == Some(true),
+		},
+		has_tick_labels,
+		x_axis_resolution,
+		y_axis_resolution,
+		&x_tick_format,
+		&y_tick_format,
+		axis_style,
+		scale,
+	)?;
+	if let Some((origin, legend)) = outside_legend {
+		build_legend(
+			&mut canvas,
+			origin,
+			legend,
+			&font,
+			font_sizes.legend_font_size,
+			scale,
+			legend_style,
+		)?;
+	}
+	if let Some((left, colormap, colour_range)) = colour_bar {
+		build_colour_bar(
+			&mut canvas,
+			left,
+			axis_max.1,
+			axis_min.1,
+			colormap,
+			colour_range,
+			&font,
+			font_sizes.axis_unit_font_size,
+			scale,
+		)?;
+	}
+	// The scale factor, the value at the origin and the limits of the y-axis each set is plotted against
+	let primary_projection = (
+		y_axis_data_scale_factor,
+		origin_values.1,
+		y_data_min_max_limits,
 	);
+	let mut secondary_projection = primary_projection;
+	if let Some((y2_data_min_max_limits, y2_axis_resolution)) = y2_axis {
+		draw_y2_axis(
+			&mut canvas,
+			axis_min,
+			axis_max,
+			y2_data_min_max_limits,
+			y2_axis_resolution,
+			&font,
+			font_sizes.axis_unit_font_size,
+			has_tick_labels,
+			&y2_tick_format,
+			axis_style,
+			scale,
+		)?;
+		// the secondary axis spans the same pixels as the y-axis so the origin row marks its own value
+		let y2_axis_data_scale_factor = y_axis_length as f32
+			/ (y2_data_min_max_limits.1 as f64 - y2_data_min_max_limits.0 as f64) as f32;
+		let y2_origin_value = y2_data_min_max_limits.0 as f32
+			+ (axis_min.1 - axis_origin.1) as f32 / y2_axis_data_scale_factor;
+		secondary_projection = (
+			y2_axis_data_scale_factor,
+			y2_origin_value,
+			y2_data_min_max_limits,
+		);
+	}
+	let y_projection = |set: &DataSet| match set.y_axis() {
+		YAxis::Primary => primary_projection,
+		YAxis::Secondary => secondary_projection,
+	};
+	// a reversed axis mirrors each value about its middle before it is projected onto the canvas
+	let reverse_x = |x: f32| {
+		get_reversed_value(x as f64, x_data_min_max_limits, axis_style.is_x_reversed) as f32
+	};
+	let reverse_y = |set: &DataSet, y: f32| {
+		get_reversed_value(y as f64, y_projection(set).2, axis_style.is_y_reversed) as f32
+	};
+	// fill beneath any series requesting it so that curves and points are drawn over the fill
+	for (set, values) in scatter.data_sets.iter().zip(data_values.iter()) {
+		if let Some(gradient) = set.fill_gradient {
+			let (y_axis_data_scale_factor, origin_value_y, _) = y_projection(set);
+			let values: Vec<(f32, f32)> = values
+				.iter()
+				.map(|(x, _, y, _)| {
+					(
+						reverse_x(*x) - origin_values.0,
+						reverse_y(set, *y) - origin_value_y,
+					)
+				})
+				.collect();
+			fill_gradient_under(
+				&mut canvas,
+				&values,
+				set.colour(),
+				gradient,
+				x_axis_data_scale_factor,
+				y_axis_data_scale_factor,
+				axis_origin,
+				axis_min,
+				axis_max,
+			);
+		}
+	}
 	// if a line of best fit has been specified then draw it
-	for set in &scatter.data_sets {
-		match &set.best_fit {
-			Some(curve) => {
-				info!("Plotting best fit...");
-				let points = curve.find_coordinates(
-					x_data_min_max_limits.0,
-					x_data_min_max_limits.1,
-					y_data_min_max_limits.0,
-					y_data_min_max_limits.1,
-					scatter.canvas_pixel_size.0 as i32 * 2,
+	for ((set, curve), values) in scatter
+		.data_sets
+		.iter()
+		.zip(best_fits.iter())
+		.zip(data_values.iter())
+	{
+		if let Some(curve) = curve {
+			info!("Plotting best fit...");
+			let (y_axis_data_scale_factor, origin_value_y, y_data_min_max_limits) =
+				y_projection(set);
+			let runs = curve.find_coordinates(
+				x_data_min_max_limits.0,
+				x_data_min_max_limits.1,
+				y_data_min_max_limits.0,
+				y_data_min_max_limits.1,
+				axis_max.0 - axis_min.0,
+				set.best_fit_domain,
+			)?;
+			let style = set.best_fit_style.unwrap_or_default();
+			let thickness = set
+				.best_fit_thickness
+				.unwrap_or_else(|| get_default_best_fit_thickness(scatter.canvas_pixel_size.0));
+			// the gaps of a dashed or dotted curve split each run further, measured up the y-axis for a
+			// vertical line
+			let is_drawn = |p: &DataPoint| match curve {
+				BestFit::VerticalLine { .. } => style.is_drawn(p.y, y_data_min_max_limits),
+				_ => style.is_drawn(p.x, x_data_min_max_limits),
+			};
+			for dash in runs
+				.iter()
+				.flat_map(|run| run.split(|p| !is_drawn(p)).filter(|dash| !dash.is_empty()))
+			{
+				let pixels: Vec<(u32, u32)> = dash
+					.iter()
+					.map(|p| {
+						(
+							get_x_pixel(
+								reverse_x(p.x) - origin_values.0,
+								x_axis_data_scale_factor,
+								axis_origin.0,
+							),
+							get_y_pixel(
+								reverse_y(set, p.y) - origin_value_y,
+								y_axis_data_scale_factor,
+								axis_origin.1,
+							),
+						)
+					})
+					.collect();
+				draw_curve(
+					&mut canvas,
+					&pixels,
+					thickness,
+					scale,
+					Colour::get_pixel_colour(curve.colour()),
+					scatter.antialias == Some(true),
 				);
-				let origin_offset = (axis_origin.0, axis_origin.1);
-				for p in points.iter() {
-					p.draw_point(
+			}
+			if set.show_fit_statistics == Some(true) {
+				let values: Vec<(f32, f32)> = values.iter().map(|(x, _, y, _)| (*x, *y)).collect();
+				// written beside the last point of the curve drawn within the axes
+				match (
+					get_fit_statistics(curve, &values),
+					runs.last().and_then(|run| run.last()),
+				) {
+					(Some(statistics), Some(end)) => draw_fit_statistics(
 						&mut canvas,
-						x_axis_data_scale_factor,
-						y_axis_data_scale_factor,
-						origin_offset,
-					);
+						&curve
+							.equation()
+							.into_iter()
+							.chain(std::iter::once(statistics.label()))
+							.collect::<Vec<_>>(),
+						Colour::get_pixel_colour(curve.colour()),
+						(
+							get_x_pixel(
+								reverse_x(end.x) - origin_values.0,
+								x_axis_data_scale_factor,
+								axis_origin.0,
+							),
+							get_y_pixel(
+								reverse_y(set, end.y) - origin_value_y,
+								y_axis_data_scale_factor,
+								axis_origin.1,
+							),
+						),
+						axis_min,
+						axis_max,
+						&font,
+						font_sizes.axis_unit_font_size,
+					)?,
+					_ => warn!(
+						"The fit statistics of {} are undefined or its curve lies outside of the axes",
+						set.name
+					),
 				}
 			}
-			None => {}
 		}
 	}
-	// get the csv data content and plot it
-	build_data_points(
-		&scatter.data_sets,
-		csv_delimiter,
-		&mut canvas,
-		x_axis_data_scale_factor,
-		y_axis_data_scale_factor,
-		(axis_origin.0, axis_origin.1),
+	// equations are written together so that they stack in their corner
+	let equations: Vec<(String, [u8; 4])> = scatter
+		.data_sets
+		.iter()
+		.zip(best_fits.iter())
+		.filter(|(set, _)| set.show_equation == Some(true))
+		.filter_map(|(set, curve)| {
+			let curve = curve.as_ref()?;
+			match curve.equation() {
+				Some(equation) => Some((equation, Colour::get_pixel_colour(curve.colour()))),
+				None => {
+					warn!("The best fit of {} has no equation to write", set.name);
+					None
+				}
+			}
+		})
+		.collect();
+	if let Some((Some(corner), legend)) = &inside_legend {
+		let origin = get_legend_origin(
+			*corner,
+			axis_min,
+			axis_max,
+			legend,
+			&font,
+			font_sizes.legend_font_size,
+			scale,
+		)?;
+		legend_bounds = Some(get_legend_bounds(
+			origin,
+			legend,
+			&font,
+			font_sizes.legend_font_size,
+			scale,
+		)?);
+	}
+	if !equations.is_empty() {
+		draw_equations(
+			&mut canvas,
+			&equations,
+			scatter.equation_corner.unwrap_or_default(),
+			axis_min,
+			axis_max,
+			legend_bounds,
+			&font,
+			font_sizes.legend_font_size,
+		)?;
+	}
+	// plot the csv data content, each set is reported as it is reached
+	let count = scatter.data_sets.len();
+	for (index, (set, values)) in scatter
+		.data_sets
+		.iter()
+		.zip(data_values.iter().map(Vec::as_slice))
+		.enumerate()
+	{
+		options.report(RenderStage::PlottingSet { index, count });
+		let (y_axis_data_scale_factor, origin_value_y, y_data_min_max_limits) = y_projection(set);
+		build_data_points(
+			std::iter::once((
+				set,
+				best_fits[index].as_ref(),
+				values,
+				point_colours[index].as_ref(),
+				point_symbols[index].as_ref(),
+			)),
+			&mut canvas,
+			x_axis_data_scale_factor,
+			y_axis_data_scale_factor,
+			(axis_origin.0, axis_origin.1),
+			(origin_values.0, origin_value_y),
+			x_data_min_max_limits,
+			y_data_min_max_limits,
+			(axis_style.is_x_reversed, axis_style.is_y_reversed),
+			scatter.x_axis_range.is_some() || scatter.y_axis_range.is_some(),
+			colour_range,
+			scatter.antialias == Some(true),
+			scale,
+		);
+	}
+	// a legend inside the axes is drawn last so that it sits above the points
+	if let Some((corner, legend)) = inside_legend {
+		let corner = match corner {
+			Some(corner) => corner,
+			None => get_quietest_corner(
+				&canvas,
+				axis_min,
+				axis_max,
+				&legend,
+				&font,
+				font_sizes.legend_font_size,
+				scale,
+			)?,
+		};
+		let origin = get_legend_origin(
+			corner,
+			axis_min,
+			axis_max,
+			&legend,
+			&font,
+			font_sizes.legend_font_size,
+			scale,
+		)?;
+		build_legend(
+			&mut canvas,
+			origin,
+			legend,
+			&font,
+			font_sizes.legend_font_size,
+			scale,
+			LegendStyle {
+				position: LegendPosition::from(corner),
+				..legend_style
+			},
+		)?;
+	}
+	Ok(downsample(canvas, scale))
+}
+
+/// Identify the config and data files a graph is drawn from so that they can be embedded into the image
+fn get_provenance(path: &str, data_sets: &[DataSet]) -> Result<Vec<TextChunk>, PlotError> {
+	let config_contents = std::fs::read(path).map_err(|e| PlotError::Io {
+		context: format!("Failed to read .ron file at {} to hash it", path),
+		source: e,
+	})?;
+	let mut data_paths: Vec<PathBuf> = Vec::new();
+	for set in data_sets.iter().filter(|set| set.points.is_none()) {
+		for data_path in expand_data_path(&set.data_path)? {
+			// sets commonly share a csv
+			if !data_paths.contains(&data_path) {
+				data_paths.push(data_path);
+			}
+		}
+	}
+	Ok(provenance_chunks(
+		Path::new(path),
+		&config_contents,
+		&data_paths,
+	))
+}
+
+/// Find the x-axis pixel columns recorded in an alignment file on a canvas supersampled by `scale`, the
+/// error describes the problem if the graph's labels don't fit or its resolution can't divide the recorded axis
+#[allow(clippy::too_many_arguments)]
+fn get_aligned_x_axis_bounds(
+	alignment: &PlotAlignment,
+	align_path: &str,
+	canvas_edges_used: &VHConsumedCanvasSpace,
+	canvas_width: u32,
+	x_axis_resolution: u32,
+	is_x_paired: bool,
+	scale: u32,
+) -> Result<(u32, u32), PlotError> {
+	// the alignment is recorded in pixels of the saved image
+	let unscaled = |pixels: u32| (pixels + scale - 1) / scale;
+	alignment
+		.check_fits(
+			canvas_width,
+			unscaled(canvas_edges_used.h_space_from_left),
+			unscaled(canvas_edges_used.h_space_from_right),
+		)
+		.map_err(|e| PlotError::Render(format!("Unable to align with {}, {}", align_path, e)))?;
+	let bounds = (
+		alignment.axis_min_pixel_column * scale,
+		alignment.axis_max_pixel_column * scale,
 	);
+	if !is_axis_fitted(bounds.1 - bounds.0, x_axis_resolution, is_x_paired) {
+		return Err(PlotError::Render(format!(
+			"Unable to align with {}, the recorded x-axis of {} pixels cannot be divided by the x_axis_resolution of {}. Use the same x-axis resolution as the first graph",
+			align_path,
+			alignment.axis_max_pixel_column - alignment.axis_min_pixel_column,
+			x_axis_resolution
+		)));
+	}
+	debug!("Aligning x-axis to pixel columns {:?}", bounds);
+	Ok(bounds)
+}
 
-	// save the resulting image
-	save_image(canvas, output, scatter.title);
+/// Use the resolution of an axis if one has been provided, otherwise the resolution found alongside limits
+/// rounded to a nice step, or for a fixed range automatically choose one based on the data limits spanned by
+/// the axis. A provided resolution of zero has already been refused by `Scatter::validate`
+fn resolve_axis_resolution(
+	name: &str,
+	provided: Option<u32>,
+	auto: Option<u32>,
+	data_min_max_limits: (i32, i32),
+	is_paired: bool,
+) -> u32 {
+	match provided {
+		Some(resolution) => {
+			debug!("Using provided {} of {}", name, resolution);
+			resolution
+		}
+		None => {
+			let resolution =
+				auto.unwrap_or_else(|| get_auto_axis_resolution(data_min_max_limits, is_paired));
+			if is_paired {
+				info!(
+					"{} not provided, automatically chose {} for each of the positive and negative halves of the axis spanning {:?}",
+					name, resolution, data_min_max_limits
+				);
+			} else {
+				info!(
+					"{} not provided, automatically chose {} for the axis spanning {:?}",
+					name, resolution, data_min_max_limits
+				);
+			}
+			resolution
+		}
+	}
+}
+
+impl DataSet {
+	/// The colour of the set, data sets are always assigned a colour after deserialising
+	fn colour(&self) -> Colour {
+		self.colour.unwrap_or(Colour::Black)
+	}
+	/// Whether the values of the set are used to find the axis ranges
+	fn is_in_bounds(&self) -> bool {
+		self.include_in_bounds != Some(false)
+	}
+	/// Whether the set is given a row in the legend
+	fn is_in_legend(&self) -> bool {
+		self.show_in_legend != Some(false)
+	}
+	/// The y-axis the values of the set are plotted against
+	fn y_axis(&self) -> YAxis {
+		self.y_axis.unwrap_or_default()
+	}
+	/// The largest symbol radius used when sizing points by residual
+	fn max_symbol_radius(&self) -> u32 {
+		self.max_symbol_radius
+			.unwrap_or((self.symbol_radius + 1) * 3)
+	}
 }
 
 impl Scatter {
+	/// The locale used for tick labels, the error lists the supported locales if it is unknown
+	fn locale(&self) -> Result<Option<Locale>, PlotError> {
+		match &self.locale {
+			Some(locale) => locale.parse().map(Some).map_err(PlotError::Config),
+			None => Ok(None),
+		}
+	}
+	/// How the tick labels of the x and y axes are written
+	fn tick_formats(&self) -> Result<(TickFormat, TickFormat), PlotError> {
+		let locale = self.locale()?;
+		let notation = self.tick_format.unwrap_or_default();
+		let label_every = |name: &str, every: Option<u32>| match every {
+			Some(0) => Err(PlotError::Config(format!("{} must be at least 1", name))),
+			every => Ok(every),
+		};
+		Ok((
+			TickFormat {
+				decimal_places: self.x_tick_decimal_places,
+				notation: self.x_tick_format.unwrap_or(notation),
+				locale,
+				datetime: self
+					.x_tick_datetime_format
+					.as_deref()
+					.map(DateTimeFormat::parse),
+				suffix: self.x_tick_suffix.clone(),
+				label_every: label_every("x_tick_label_every", self.x_tick_label_every)?,
+			},
+			TickFormat {
+				decimal_places: self.y_tick_decimal_places,
+				notation: self.y_tick_format.unwrap_or(notation),
+				locale,
+				datetime: None,
+				suffix: self.y_tick_suffix.clone(),
+				label_every: label_every("y_tick_label_every", self.y_tick_label_every)?,
+			},
+		))
+	}
+	/// The padding fractions of the x and y axes, already validated as finite and at least zero
+	fn padding_fractions(&self) -> (f32, f32) {
+		let padding = self.axis_padding_fraction.unwrap_or(0.1);
+		(
+			self.x_axis_padding_fraction.unwrap_or(padding),
+			self.y_axis_padding_fraction.unwrap_or(padding),
+		)
+	}
+	/// The factor the canvas is supersampled by, which must not be zero
+	fn supersample(&self) -> Result<u32, PlotError> {
+		match self.supersample {
+			Some(0) => Err(PlotError::Config(
+				"supersample must be at least 1".to_owned(),
+			)),
+			Some(factor) => Ok(factor),
+			None => Ok(1),
+		}
+	}
+	/// Which parts of the axes are drawn and the thickness, colours and pattern of the axes and gridlines,
+	/// neither thickness nor the dash length may be zero
+	fn axis_style(&self) -> Result<AxisStyle, PlotError> {
+		let default = AxisStyle::default();
+		let at_least_one = |name: &str, pixels: Option<u32>, default: u32| match pixels {
+			Some(0) => Err(PlotError::Config(format!("{} must be at least 1", name))),
+			Some(pixels) => Ok(pixels),
+			None => Ok(default),
+		};
+		let pixel_colour = |colour: Option<Colour>, default: [u8; 4]| {
+			colour.map_or(default, Colour::get_pixel_colour)
+		};
+		Ok(AxisStyle {
+			has_x_axis: self.show_x_axis != Some(false),
+			has_y_axis: self.show_y_axis != Some(false),
+			has_ticks: self.show_ticks != Some(false),
+			has_arrows: self.axis_arrows == Some(true),
+			is_x_reversed: self.x_axis_reversed == Some(true),
+			is_y_reversed: self.y_axis_reversed == Some(true),
+			axis_thickness: at_least_one(
+				"axis_thickness",
+				self.axis_thickness,
+				default.axis_thickness,
+			)?,
+			grid_thickness: at_least_one(
+				"grid_thickness",
+				self.grid_thickness,
+				default.grid_thickness,
+			)?,
+			grid_style: self.grid_style.unwrap_or_default(),
+			grid_dash_length: at_least_one(
+				"grid_dash_length",
+				self.grid_dash_length,
+				default.grid_dash_length,
+			)?,
+			axis_colour: pixel_colour(self.axis_colour, default.axis_colour),
+			tick_label_colour: pixel_colour(self.tick_label_colour, default.tick_label_colour),
+			grid_colour: pixel_colour(self.grid_colour, default.grid_colour),
+			minor_grid_colour: pixel_colour(self.grid_colour, default.minor_grid_colour),
+		})
+	}
 	/// Based on a path deserialise a `.ron` file into a graph data structure
-	fn deserialise(path: &str) -> Scatter {
+	fn deserialise(path: &str, style_path: Option<&str>) -> Result<Scatter, PlotError> {
 		// attempt to open the .ron file
-		let f = match File::open(path) {
-			Ok(file) => file,
-			Err(e) => {
-				error!("Failed to open .ron file at {}, error: {:?}", path, e);
-				std::process::exit(1)
-			}
-		};
-		// attempt to deserialise the config data
-		let scatter: Scatter = match from_reader(f) {
-			Ok(x) => x,
-			Err(e) => {
-				error!(
-					"Failed to load config, maybe you're missing a comma? Error: {}",
-					e
-				);
-				std::process::exit(1);
-			}
+		let f = File::open(path).map_err(|e| PlotError::Io {
+			context: format!("Failed to open .ron file at {}", path),
+			source: e,
+		})?;
+		let scatter = Scatter::from_reader(f)?;
+		// a style given on the command line takes precedence over one named by the config
+		let style_path = style_path
+			.map(str::to_owned)
+			.or_else(|| scatter.style_path.clone());
+		let scatter = match style_path {
+			Some(style_path) => scatter.apply_style(Style::load(&style_path)?),
+			None => scatter,
 		};
+		scatter.prepare()
+	}
+	/// Parse a graph from the contents of a `.ron` config, e.g one received over HTTP or generated from a
+	/// template. The graph must be [prepared](Scatter::prepare) before it is drawn
+	pub fn from_ron_str(config: &str) -> Result<Scatter, ConfigError> {
+		let scatter: Scatter = ron::from_str(config)?;
 		debug!("Ron config {:?}", &scatter);
+		Ok(scatter)
+	}
+	/// Parse a graph from a reader of a `.ron` config. The graph must be [prepared](Scatter::prepare) before
+	/// it is drawn
+	pub fn from_reader<R: Read>(mut reader: R) -> Result<Scatter, ConfigError> {
+		let mut config = String::new();
+		reader.read_to_string(&mut config)?;
+		Scatter::from_ron_str(&config)
+	}
+	/// Serialise a graph into a `.ron` config which [`Scatter::from_ron_str`] reads back identically.
+	/// Values held in memory by a [`DataSetBuilder`] are not written
+	pub fn to_ron_string(&self) -> Result<String, ConfigError> {
+		let pretty = PrettyConfig::new()
+			.struct_names(true)
+			.decimal_floats(true)
+			.indentor(String::from("\t"));
+		Ok(ron::ser::to_string_pretty(self, pretty)?)
+	}
+	/// Validate a graph and fill in the values derived from its data sets, such as colours and the sets
+	/// split per file. Graphs read from a `.ron` file or built with a [`ScatterBuilder`] are prepared already
+	pub fn prepare(self) -> Result<Scatter, PlotError> {
+		self.validate()?;
+		Ok(self
+			.split_sets_per_file()?
+			.assign_colours()
+			.fill_relative_unit_placeholder())
+	}
+	/// Ensure the graph can be drawn
+	fn validate(&self) -> Result<(), PlotError> {
+		if self.x_axis_resolution == Some(0) {
+			return Err(ValidationError::ZeroResolution("x_axis_resolution").into());
+		}
+		if self.y_axis_resolution == Some(0) {
+			return Err(ValidationError::ZeroResolution("y_axis_resolution").into());
+		}
+		if self.y2_axis_resolution == Some(0) {
+			return Err(ValidationError::ZeroResolution("y2_axis_resolution").into());
+		}
+		if self.data_sets.is_empty() {
+			return Err(ValidationError::NoDataSets.into());
+		}
+		// the secondary y-axis takes its place from the plot of the primary
+		if self
+			.data_sets
+			.iter()
+			.all(|set| set.y_axis() == YAxis::Secondary)
+		{
+			return Err(PlotError::Config(
+				"Every data set is plotted against the secondary y-axis, at least one must use the primary y-axis".to_owned(),
+			));
+		}
+		for (field, range) in [
+			("x_axis_range", self.x_axis_range),
+			("y_axis_range", self.y_axis_range),
+		] {
+			if let Some((min, max)) = range {
+				if !min.is_finite() || !max.is_finite() || min >= max {
+					return Err(ValidationError::InvalidAxisRange(field).into());
+				}
+			}
+		}
+		for (field, padding) in [
+			("axis_padding_fraction", self.axis_padding_fraction),
+			("x_axis_padding_fraction", self.x_axis_padding_fraction),
+			("y_axis_padding_fraction", self.y_axis_padding_fraction),
+		] {
+			if let Some(padding) = padding {
+				if !padding.is_finite() || padding < 0.0 {
+					return Err(ValidationError::InvalidPadding(field).into());
+				}
+			}
+		}
+		for (field, size) in [
+			("legend_font_size", self.legend_font_size),
+			("legend_row_spacing", self.legend_row_spacing),
+		] {
+			if let Some(size) = size {
+				if !size.is_finite() || size <= 0.0 {
+					return Err(PlotError::Config(format!(
+						"Provided {} of {} must be a finite number greater than zero",
+						field, size
+					)));
+				}
+			}
+		}
+		self.validate_residual_sizing()?;
+		for set in self.data_sets.iter() {
+			if set.best_fit_thickness == Some(0) {
+				return Err(PlotError::Config(format!(
+					"Data set {} has a best_fit_thickness of 0, it must be at least 1",
+					set.name
+				)));
+			}
+			if let Some((min, max)) = set.best_fit_domain {
+				if !min.is_finite() || !max.is_finite() || min >= max {
+					return Err(PlotError::Config(format!(
+						"Data set {} has a best_fit_domain of ({}, {}), it must be finite with the minimum below the maximum",
+						set.name, min, max
+					)));
+				}
+			}
+			if let Some(curve) = &set.best_fit {
+				curve.validate()?;
+			} else if set.show_fit_statistics == Some(true) {
+				return Err(PlotError::Config(format!(
+					"Data set {} has show_fit_statistics enabled but no best_fit to measure",
+					set.name
+				)));
+			} else if set.show_equation == Some(true) {
+				return Err(PlotError::Config(format!(
+					"Data set {} has show_equation enabled but no best_fit to write the equation of",
+					set.name
+				)));
+			}
+			if let Some(gradient) = &set.fill_gradient {
+				gradient.validate(&set.name)?;
+			}
+			if let Some(opacity) = set.opacity {
+				if !(0.0..=1.0).contains(&opacity) {
+					return Err(PlotError::Config(format!(
+						"Data set {} has an opacity of {}, it must be between 0 and 1",
+						set.name, opacity
+					)));
+				}
+			}
+			if set.colour_csv_column.is_some() && set.colour_by.is_some() {
+				return Err(PlotError::Config(format!(
+					"Data set {} has both a colour_csv_column and colour_by, its points can only be coloured by one",
+					set.name
+				)));
+			}
+			if (set.colour_csv_column.is_some() || set.colour_by.is_some()) && set.points.is_some()
+			{
+				return Err(PlotError::Config(format!(
+					"Data set {} colours its points by a csv column but its values are held in memory rather than read from a csv",
+					set.name
+				)));
+			}
+			if set.symbol_map.is_some() && set.symbol_csv_column.is_none() {
+				return Err(PlotError::Config(format!(
+					"Data set {} has a symbol_map but no symbol_csv_column to map",
+					set.name
+				)));
+			}
+			if set.symbol_csv_column.is_some() && set.points.is_some() {
+				return Err(PlotError::Config(format!(
+					"Data set {} picks the symbol of its points from a csv column but its values are held in memory rather than read from a csv",
+					set.name
+				)));
+			}
+		}
+		Ok(())
+	}
+	/// Replace a `{unit}` placeholder in the x-axis label with the unit of the first data set plotting
+	/// elapsed time
+	fn fill_relative_unit_placeholder(mut self) -> Scatter {
+		if let Some(unit) = self
+			.data_sets
+			.iter()
+			.find_map(|set| set.x_relative_to_first)
+		{
+			self.x_axis_label = self.x_axis_label.replace("{unit}", unit.name());
+		}
+		self
+	}
+	/// Fill in any fields the config does not specify from a style
+	fn apply_style(mut self, style: Style) -> Scatter {
+		self.has_grid = self.has_grid.or(style.has_grid);
+		self.has_legend = self.has_legend.or(style.has_legend);
+		self.number_format = self.number_format.or(style.number_format);
+		self.chrome = self.chrome.or(style.chrome);
+		self.colour_assignment = self.colour_assignment.or(style.colour_assignment);
+		self.palette = self.palette.or(style.palette);
+		self.axis_padding_fraction = self.axis_padding_fraction.or(style.axis_padding_fraction);
+		self.x_axis_padding_fraction = self
+			.x_axis_padding_fraction
+			.or(style.x_axis_padding_fraction);
+		self.y_axis_padding_fraction = self
+			.y_axis_padding_fraction
+			.or(style.y_axis_padding_fraction);
+		self.axis_thickness = self.axis_thickness.or(style.axis_thickness);
+		self.grid_thickness = self.grid_thickness.or(style.grid_thickness);
+		self.grid_style = self.grid_style.or(style.grid_style);
+		self.grid_dash_length = self.grid_dash_length.or(style.grid_dash_length);
+		self.axis_colour = self.axis_colour.or(style.axis_colour);
+		self.tick_label_colour = self.tick_label_colour.or(style.tick_label_colour);
+		self.grid_colour = self.grid_colour.or(style.grid_colour);
+		self.legend_background = self.legend_background.or(style.legend_background);
+		self.legend_font_size = self.legend_font_size.or(style.legend_font_size);
+		self
+	}
+	/// Sizing points by residual requires a curve to find the residual against
+	fn validate_residual_sizing(&self) -> Result<(), PlotError> {
+		for set in self.data_sets.iter() {
+			if set.size_by_residual != Some(true) {
+				continue;
+			}
+			match &set.best_fit {
+				Some(curve) => curve.validate()?,
+				None => {
+					return Err(PlotError::Config(format!(
+						"Data set {} has size_by_residual enabled but no best_fit to find residuals against",
+						set.name
+					)));
+				}
+			}
+			if set.max_symbol_radius() < set.symbol_radius {
+				return Err(PlotError::Config(format!(
+					"Data set {} has a max_symbol_radius of {} which is smaller than its symbol_radius of {}",
+					set.name,
+					set.max_symbol_radius(),
+					set.symbol_radius
+				)));
+			}
+		}
+		Ok(())
+	}
+	/// Give every data set without an explicit colour one from the palette. With a `Grayscale` palette a set
+	/// given a colour whose symbol is already used by an earlier set is also given the next unused symbol
+	fn assign_colours(mut self) -> Scatter {
+		let palette = self.palette.unwrap_or_default();
+		let names_colours: Vec<(&str, Option<Colour>)> = self
+			.data_sets
+			.iter()
+			.map(|set| (set.name.as_str(), set.colour))
+			.collect();
+		let colours = self
+			.colour_assignment
+			.unwrap_or_default()
+			.assign(&names_colours, palette.colours());
+		let mut used_symbols: Vec<DataSymbol> = Vec::with_capacity(self.data_sets.len());
+		for (set, colour) in self.data_sets.iter_mut().zip(colours) {
+			if palette == Palette::Grayscale
+				&& set.colour.is_none()
+				&& used_symbols.contains(&set.symbol)
+			{
+				if let Some(symbol) = SYMBOL_CYCLE.iter().find(|s| !used_symbols.contains(s)) {
+					debug!("Data set {} has symbol {:?}", set.name, symbol);
+					set.symbol = *symbol;
+				}
+			}
+			used_symbols.push(set.symbol);
+			debug!("Data set {} has colour {:?}", set.name, colour);
+			set.colour = Some(colour);
+		}
+		self
+	}
+	/// Keep only the data sets named in `only` and drop those named in `exclude`. Colours have already been
+	/// assigned so sets keep the colour they would have with every set plotted. It is an error for no sets
+	/// to remain
+	fn filter_data_sets(
+		mut self,
+		only: Option<&[String]>,
+		exclude: Option<&[String]>,
+	) -> Result<Scatter, PlotError> {
+		if only.is_none() && exclude.is_none() {
+			return Ok(self);
+		}
+		for name in self.unmatched_set_names(only, exclude) {
+			warn!("No data set is named {}", name);
+		}
+		self.data_sets.retain(|set| {
+			let is_included = only.map_or(true, |names| names.contains(&set.name));
+			let is_excluded = exclude.is_some_and(|names| names.contains(&set.name));
+			if !is_included || is_excluded {
+				debug!("Leaving out data set {}", set.name);
+			}
+			is_included && !is_excluded
+		});
+		if self.data_sets.is_empty() {
+			return Err(PlotError::Config(
+				"No data sets are left to plot after applying --only/--exclude".to_owned(),
+			));
+		}
+		Ok(self)
+	}
+	/// The names given to `only` or `exclude` which match no data set
+	fn unmatched_set_names<'a>(
+		&self,
+		only: Option<&'a [String]>,
+		exclude: Option<&'a [String]>,
+	) -> Vec<&'a String> {
+		only.unwrap_or_default()
+			.iter()
+			.chain(exclude.unwrap_or_default())
+			.filter(|name| !self.data_sets.iter().any(|set| &set.name == *name))
+			.collect()
+	}
+	/// Replace any data set with `one_set_per_file` enabled by a set for each file its data path matches
+	fn split_sets_per_file(mut self) -> Result<Scatter, PlotError> {
+		let mut data_sets = Vec::with_capacity(self.data_sets.len());
+		for set in self.data_sets.into_iter() {
+			// values held in memory have no files to split by
+			if set.one_set_per_file != Some(true) || set.points.is_some() {
+				data_sets.push(set);
+				continue;
+			}
+			for path in expand_data_path(&set.data_path)? {
+				let name = match path.file_stem() {
+					Some(stem) => stem.to_string_lossy().into_owned(),
+					None => path.to_string_lossy().into_owned(),
+				};
+				debug!("Splitting data set {} into {}", set.name, name);
+				data_sets.push(DataSet {
+					data_path: path.to_string_lossy().into_owned(),
+					one_set_per_file: None,
+					name,
+					..set.clone()
+				});
+			}
+		}
+		self.data_sets = data_sets;
+		Ok(self)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		canvas::axes::GridStyle,
+		colours::Colour,
+		error::PlotError,
+		scatter::{DataSetBuilder, Scatter, ScatterBuilder},
+		style::Style,
+	};
+	/// A graph of three sets named `a`, `b` and `c`
+	fn abc() -> Scatter {
+		["a", "b", "c"]
+			.iter()
+			.fold(ScatterBuilder::new(), |builder, name| {
+				builder.add_data_set(DataSetBuilder::from_points(name, &[(1.0, 1.0), (2.0, 2.0)]))
+			})
+			.build()
+			.unwrap()
+	}
+	/// The names of the sets of a graph in order
+	fn names(scatter: &Scatter) -> Vec<&str> {
 		scatter
+			.data_sets
+			.iter()
+			.map(|set| set.name.as_str())
+			.collect()
+	}
+	/// Names as given on the command line
+	fn strings(names: &[&str]) -> Vec<String> {
+		names.iter().map(|name| name.to_string()).collect()
+	}
+	#[test]
+	fn only_keeps_the_named_sets_in_config_order() {
+		let only = strings(&["c", "a"]);
+		let scatter = abc().filter_data_sets(Some(&only), None).unwrap();
+		assert_eq!(vec!["a", "c"], names(&scatter));
+	}
+	#[test]
+	fn exclude_drops_the_named_sets() {
+		let exclude = strings(&["b"]);
+		let scatter = abc().filter_data_sets(None, Some(&exclude)).unwrap();
+		assert_eq!(vec!["a", "c"], names(&scatter));
+		// exclude takes precedence over only
+		let only = strings(&["a", "b"]);
+		let scatter = abc().filter_data_sets(Some(&only), Some(&exclude)).unwrap();
+		assert_eq!(vec!["a"], names(&scatter));
+	}
+	#[test]
+	fn unknown_names_are_warned_about_and_ignored() {
+		let (only, exclude) = (strings(&["a", "z"]), strings(&["y", "c"]));
+		let scatter = abc();
+		assert_eq!(
+			vec!["z", "y"],
+			scatter.unmatched_set_names(Some(&only), Some(&exclude))
+		);
+		let scatter = scatter
+			.filter_data_sets(Some(&only), Some(&exclude))
+			.unwrap();
+		assert_eq!(vec!["a"], names(&scatter));
+	}
+	#[test]
+	fn filtering_out_every_set_is_an_error() {
+		let only = strings(&["z"]);
+		assert!(matches!(
+			abc().filter_data_sets(Some(&only), None),
+			Err(PlotError::Config(_))
+		));
+		let exclude = strings(&["a", "b", "c"]);
+		assert!(matches!(
+			abc().filter_data_sets(None, Some(&exclude)),
+			Err(PlotError::Config(_))
+		));
+	}
+	#[test]
+	fn config_values_take_precedence_over_the_style() {
+		let scatter = Scatter::from_ron_str(
+			r#"Scatter(
+				title: "Styled",
+				canvas_pixel_size: (400, 300),
+				x_axis_label: "x",
+				y_axis_label: "y",
+				axis_colour: Some(Red),
+				legend_font_size: Some(20.0),
+				data_sets: [],
+			)"#,
+		)
+		.unwrap();
+		let style: Style = ron::from_str(
+			"Style(axis_colour: Blue, grid_colour: Green, legend_font_size: 12.0, grid_style: Dotted, \
+			axis_thickness: 3)",
+		)
+		.unwrap();
+		let scatter = scatter.apply_style(style);
+		assert_eq!(Some(Colour::Red), scatter.axis_colour);
+		assert_eq!(Some(20.0), scatter.legend_font_size);
+		// values the config leaves out come from the style
+		assert_eq!(Some(Colour::Green), scatter.grid_colour);
+		assert_eq!(Some(GridStyle::Dotted), scatter.grid_style);
+		assert_eq!(Some(3), scatter.axis_thickness);
 	}
 }
@@ -0,0 +1,304 @@
+//! An example scatter config with every field present, printed by `plotrs scaffold scatter` as a starting
+//! point for writing a new graph
+
+use crate::{
+	canvas::{
+		axes::{GridStyle, LabelOrientation, YAxis},
+		best_fit::{Corner, LineStyle},
+		format::Notation,
+		legend::LegendPosition,
+		plot::DataSymbol,
+		Chrome,
+	},
+	colours::{Colour, ColourAssignment, Palette},
+	data::{CsvColumn, NumberFormat, RelativeTimeOrigin},
+};
+
+use super::{DataSet, Scatter};
+
+/// Comments appended to the lines of the scaffold, keyed by the indentation depth and name of a field so
+/// that the fields of a data set are not confused with those of a best fit curve
+const FIELD_COMMENTS: [(usize, &str, &str); 98] = [
+	(1, "title", "drawn above the graph and used to name the png"),
+	(1, "canvas_pixel_size", "width and height of the image in pixels"),
+	(1, "x_axis_label", "may contain a `{unit}` placeholder filled by `x_relative_to_first`"),
+	(1, "y_axis_label", "label of the y-axis"),
+	(1, "y_axis_label_orientation", "optional, Horizontal above the y-axis or Vertical alongside it, defaults to Horizontal"),
+	(1, "x_axis_range", "optional, fixed (min, max) of the x-axis, e.g Some((0.0, 100.0)), defaults to the data bounds"),
+	(1, "y_axis_range", "optional, fixed (min, max) of the y-axis, defaults to the data bounds"),
+	(1, "axis_padding_fraction", "optional, fraction the data bounds are padded by, Some(0.0) fits the data exactly, defaults to 0.1"),
+	(1, "x_axis_padding_fraction", "optional, padding fraction of the x-axis, defaults to axis_padding_fraction"),
+	(1, "y_axis_padding_fraction", "optional, padding fraction of the y-axis, defaults to axis_padding_fraction"),
+	(1, "y2_axis_label", "optional, label of the secondary y-axis drawn for sets with `y_axis: Some(Secondary)`"),
+	(1, "y2_axis_resolution", "optional, number of times the secondary y-axis is divided, defaults to match the y-axis"),
+	(1, "x_tick_decimal_places", "optional, decimal places of the x-axis labels, e.g Some(2), defaults to the fewest needed"),
+	(1, "y_tick_decimal_places", "optional, decimal places of the y-axis labels, defaults to the fewest needed"),
+	(1, "tick_format", "optional, Plain, Scientific, Percent or Auto, defaults to Auto which picks scientific for very large or small axes"),
+	(1, "x_tick_format", "optional, notation of the x-axis labels, e.g Some(Percent), defaults to tick_format"),
+	(1, "y_tick_format", "optional, notation of the y-axis labels, defaults to tick_format"),
+	(1, "x_tick_suffix", "optional, text appended to every x-axis label, e.g Some(\" ms\"), defaults to none"),
+	(1, "y_tick_suffix", "optional, text appended to every y-axis label, defaults to none"),
+	(1, "x_ticks", "optional, values of the x-axis markers, e.g Some([0.0, (3.14159, \"π\")]), defaults to those of x_axis_resolution"),
+	(1, "y_ticks", "optional, values of the y-axis markers, defaults to those of y_axis_resolution"),
+	(1, "x_tick_label_every", "optional, label every Nth x-axis scale marker, defaults to skipping labels which would overlap"),
+	(1, "y_tick_label_every", "optional, label every Nth y-axis scale marker, defaults to skipping labels which would overlap"),
+	(1, "x_tick_datetime_format", "optional, label epoch timestamps with a strftime pattern, e.g Some(\"%H:%M\") or Some(\"auto\"), defaults to numbers"),
+	(1, "x_minor_ticks", "optional, mini-markings between x-axis scale markers, Some(0) disables them, defaults to as many as fit evenly"),
+	(1, "y_minor_ticks", "optional, mini-markings between y-axis scale markers, defaults to as many as fit evenly"),
+	(1, "minor_grid", "optional, faint gridlines at the mini-markings, defaults to false"),
+	(1, "highlight_zero", "optional, darker, thicker lines where x and y are zero, defaults to false"),
+	(1, "show_x_axis", "optional, draw the x-axis with its markers and labels, defaults to true"),
+	(1, "show_y_axis", "optional, draw the y-axis with its markers and labels, defaults to true"),
+	(1, "show_ticks", "optional, draw the scale markers, defaults to true"),
+	(1, "show_tick_labels", "optional, write the values along the axes, defaults to true"),
+	(1, "x_axis_reversed", "optional, values decrease to the right, defaults to false"),
+	(1, "y_axis_reversed", "optional, values decrease upward, e.g for depth profiles, defaults to false"),
+	(1, "axis_arrows", "optional, draw arrowheads at the ends of the axes, defaults to false"),
+	(1, "axis_thickness", "optional, pixel thickness of the axes and their markers, defaults to 1"),
+	(1, "grid_thickness", "optional, pixel thickness of the gridlines, defaults to 1"),
+	(1, "grid_style", "optional, Solid, Dashed or Dotted gridlines, defaults to Solid"),
+	(1, "grid_dash_length", "optional, pixel length of the dashes of Dashed gridlines, defaults to 4"),
+	(1, "axis_colour", "optional, colour of the axes and their markers, defaults to Black"),
+	(1, "tick_label_colour", "optional, colour of the values along the axes, defaults to Black"),
+	(1, "grid_colour", "optional, colour of the gridlines, defaults to Grey"),
+	(1, "has_grid", "optional, light grey background grid, defaults to false"),
+	(1, "has_legend", "optional, legend naming each data set, defaults to false"),
+	(1, "equation_corner", "optional, TopLeft, TopRight, BottomLeft or BottomRight corner for show_equation, defaults to TopLeft"),
+	(1, "legend_position", "optional, TopLeft, TopRight, BottomLeft or BottomRight inside the axes, or Outside them, defaults to Outside"),
+	(1, "legend_frame", "optional, draw a border around the legend, defaults to false"),
+	(1, "legend_background", "optional, colour filling the legend behind its entries, defaults to none outside the axes and White inside them"),
+	(1, "legend_overlay", "optional, draw a framed legend over the quietest corner inside the axes, or that of legend_position, without reserving space, defaults to false"),
+	(1, "legend_title", "optional, heading written above the legend entries, defaults to none"),
+	(1, "legend_font_size", "optional, font size of the legend, defaults to a size derived from the canvas width"),
+	(1, "legend_row_spacing", "optional, distance between legend rows as a multiple of the tallest name, defaults to 2.0"),
+	(1, "data_sets", "each set may read from its own csv or share one"),
+	(1, "number_format", "optional, how numbers are written in the csv files, e.g `thousands_separator: Some(' '), decimal_separator: ','`"),
+	(1, "chrome", "optional, Full, PlotOnly or BareAxes, defaults to Full"),
+	(1, "colour_assignment", "optional, Positional or NameHash, defaults to Positional"),
+	(1, "palette", "optional, Default, ColourBlindSafe or Grayscale, defaults to Default"),
+	(1, "relative_time_origin", "optional, PerDataset or Earliest, defaults to PerDataset"),
+	(1, "output_name", "optional, file name without an extension, defaults to the title"),
+	(1, "locale", "optional, en, de or fr separators for tick labels, defaults to plain numbers"),
+	(1, "supersample", "optional, draw larger and shrink to smooth edges, defaults to 1"),
+	(1, "antialias", "optional, smooth the edges of curved and sloping symbols and best fit curves, defaults to false"),
+	(1, "style_path", "optional, a `.ron` style supplying values the config omits"),
+	(3, "data_path", "path to the csv, may contain glob wildcards and end in `.gz`"),
+	(3, "one_set_per_file", "optional, plot each file matched by a glob as its own set, defaults to false"),
+	(3, "has_headers", "set to `true` when the first row holds headers"),
+	(3, "x_axis_csv_column", "zero indexed column of the x values or an expression, e.g \"col(2) / col(3)\""),
+	(3, "x_axis_error_bar_csv_column", "column of the x uncertainty, Some(usize) or None"),
+	(3, "y_axis_csv_column", "zero indexed column of the y values or an expression"),
+	(3, "y_axis_error_bar_csv_column", "column of the y uncertainty, Some(usize) or None"),
+	(3, "name", "identifies the set in the legend"),
+	(3, "colour", "optional, White, Black, Grey, Orange, Red, Blue, Green, Pink, a CSS colour name, Hex(\"#rrggbb\"), Rgb(r, g, b) or Rgba(r, g, b, a), defaults to the colour cycle"),
+	(3, "colour_csv_column", "optional, column of colour names or categories colouring each point, defaults to None"),
+	(3, "colour_by", "optional, e.g Some(ColourBy(csv_column: 2, colormap: Viridis)) to colour points along a Viridis or BlueRed colormap"),
+	(3, "opacity", "optional, from 0 to 1, how opaque points and error bars are drawn, defaults to 1.0"),
+	(3, "symbol", "Cross, Circle, Triangle, TriangleDown, Square, Point, Diamond, Plus, Star or X"),
+	(3, "symbol_radius", "size of a symbol in (1 + symbol_radius) pixels"),
+	(3, "symbol_thickness", "thickness of a symbol in (1 + symbol_thickness) pixels"),
+	(3, "symbol_csv_column", "optional, column of symbol names or symbol_map keys picking the symbol of each point, defaults to None"),
+	(3, "symbol_map", "optional, e.g Some({\"pass\": Circle, \"fail\": Cross}), the symbol of each value of symbol_csv_column"),
+	(3, "best_fit", "a curve to draw, e.g Some(Linear(gradient: 1.0, y_intercept: 0.0, colour: Black))"),
+	(3, "sort_by_x", "optional, sort the values ascending by x, defaults to false"),
+	(3, "size_by_residual", "optional, scale symbols by their residual against best_fit, defaults to false"),
+	(3, "max_symbol_radius", "optional, radius of the largest residual, defaults to three times symbol_radius + 1"),
+	(3, "show_fit_statistics", "optional, write the equation, R² and RMSE of best_fit beside the curve, defaults to false"),
+	(3, "best_fit_thickness", "optional, pixel thickness of the best_fit curve, defaults to 1 per 1000 pixels of canvas width"),
+	(3, "best_fit_style", "optional, Solid, Dashed or Dotted pattern of the best_fit curve, defaults to Solid"),
+	(3, "best_fit_domain", "optional, (min, max) x values the best_fit curve is restricted to, defaults to the whole axis"),
+	(3, "fit_label", "optional, name of the best_fit curve in the legend, defaults to the set name followed by (fit)"),
+	(3, "show_equation", "optional, write the equation of best_fit in the equation_corner of the plot, defaults to false"),
+	(3, "x_transform", "optional, (multiplier, offset) applied to each x value, defaults to none"),
+	(3, "y_transform", "optional, (multiplier, offset) applied to each y value, defaults to none"),
+	(3, "x_relative_to_first", "optional, Some(Seconds), Some(Minutes) or Some(Hours) to plot epoch timestamps as elapsed time"),
+	(3, "fill_gradient", "optional, e.g Some(FillGradient(from_opacity: 0.6, to_opacity: 0.0)) to shade below the series"),
+	(3, "include_in_bounds", "optional, whether the set sizes the axes, defaults to true"),
+	(3, "show_in_legend", "optional, whether the set and its best_fit are given legend rows, defaults to true"),
+	(3, "legend_order", "optional, position of the set in the legend, defaults to config order after ordered sets"),
+	(3, "y_axis", "optional, Primary or Secondary, the y-axis the set is plotted against, defaults to Primary"),
+];
+
+/// A `.ron` config plotting the first two columns of `data.csv` with every field present and commented.
+/// Optional fields hold their default so the scaffold draws the same graph once they are removed
+pub fn scatter_scaffold() -> String {
+	let scatter = Scatter {
+		title: String::from("Energy against Time"),
+		canvas_pixel_size: (840, 600),
+		x_axis_label: String::from("Time (ms)"),
+		x_axis_resolution: None,
+		y_axis_label: String::from("Energy (kJ)"),
+		y_axis_label_orientation: Some(LabelOrientation::default()),
+		y_axis_resolution: None,
+		x_axis_range: None,
+		y_axis_range: None,
+		axis_padding_fraction: Some(0.1),
+		x_axis_padding_fraction: None,
+		y_axis_padding_fraction: None,
+		y2_axis_label: None,
+		y2_axis_resolution: None,
+		x_tick_decimal_places: None,
+		y_tick_decimal_places: None,
+		tick_format: Some(Notation::default()),
+		x_tick_format: None,
+		y_tick_format: None,
+		x_tick_suffix: None,
+		y_tick_suffix: None,
+		x_ticks: None,
+		y_ticks: None,
+		x_tick_label_every: None,
+		y_tick_label_every: None,
+		x_tick_datetime_format: None,
+		x_minor_ticks: None,
+		y_minor_ticks: None,
+		minor_grid: Some(false),
+		highlight_zero: Some(false),
+		show_x_axis: Some(true),
+		show_y_axis: Some(true),
+		show_ticks: Some(true),
+		show_tick_labels: Some(true),
+		x_axis_reversed: Some(false),
+		y_axis_reversed: Some(false),
+		axis_arrows: Some(false),
+		axis_thickness: Some(1),
+		grid_thickness: Some(1),
+		grid_style: Some(GridStyle::default()),
+		grid_dash_length: None,
+		axis_colour: None,
+		tick_label_colour: None,
+		grid_colour: None,
+		has_grid: Some(false),
+		has_legend: Some(false),
+		equation_corner: Some(Corner::TopLeft),
+		legend_position: Some(LegendPosition::Outside),
+		legend_frame: Some(false),
+		legend_background: None,
+		legend_overlay: Some(false),
+		legend_title: None,
+		legend_font_size: None,
+		legend_row_spacing: Some(2.0),
+		data_sets: vec![DataSet {
+			data_path: String::from("data.csv"),
+			one_set_per_file: Some(false),
+			has_headers: true,
+			x_axis_csv_column: CsvColumn::Index(0),
+			x_axis_error_bar_csv_column: None,
+			y_axis_csv_column: CsvColumn::Index(1),
+			y_axis_error_bar_csv_column: None,
+			name: String::from("Energy"),
+			colour: Some(Colour::Orange),
+			colour_csv_column: None,
+			colour_by: None,
+			opacity: Some(1.0),
+			symbol: DataSymbol::Cross,
+			symbol_radius: 3,
+			symbol_thickness: 0,
+			symbol_csv_column: None,
+			symbol_map: None,
+			best_fit: None,
+			sort_by_x: Some(false),
+			size_by_residual: Some(false),
+			max_symbol_radius: None,
+			show_fit_statistics: Some(false),
+			best_fit_thickness: None,
+			best_fit_style: Some(LineStyle::Solid),
+			best_fit_domain: None,
+			fit_label: None,
+			show_equation: Some(false),
+			x_transform: Some((1.0, 0.0)),
+			y_transform: Some((1.0, 0.0)),
+			x_relative_to_first: None,
+			fill_gradient: None,
+			include_in_bounds: Some(true),
+			show_in_legend: Some(true),
+			legend_order: None,
+			y_axis: Some(YAxis::Primary),
+			points: None,
+		}],
+		number_format: Some(NumberFormat::default()),
+		chrome: Some(Chrome::default()),
+		colour_assignment: Some(ColourAssignment::default()),
+		palette: Some(Palette::default()),
+		relative_time_origin: Some(RelativeTimeOrigin::default()),
+		output_name: None,
+		locale: None,
+		supersample: Some(1),
+		antialias: Some(false),
+		style_path: None,
+	};
+	let ron = scatter
+		.to_ron_string()
+		.expect("The scaffold holds no values which ron cannot represent");
+	let mut scaffold = String::from(
+		"// Generated by `plotrs scaffold scatter`, fields marked optional may be removed to use their default\n\
+		 // x_axis_resolution and y_axis_resolution may be added, e.g `x_axis_resolution: 10`, to fix the number of\n\
+		 // times an axis is divided, without them a nice step is picked automatically\n",
+	);
+	for line in ron.lines() {
+		scaffold.push_str(line);
+		if let Some(comment) = field_comment(line) {
+			scaffold.push_str(" // ");
+			scaffold.push_str(comment);
+		}
+		scaffold.push('\n');
+	}
+	scaffold
+}
+
+/// The comment of the field a line of serialised ron starts, if any
+fn field_comment(line: &str) -> Option<&'static str> {
+	let depth = line.chars().take_while(|c| *c == '\t').count();
+	let field = line[depth..].split(':').next()?;
+	FIELD_COMMENTS
+		.iter()
+		.find(|(d, name, _)| *d == depth && *name == field)
+		.map(|(_, _, comment)| *comment)
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::scatter::{
+		scaffold::{field_comment, FIELD_COMMENTS},
+		scatter_scaffold, Scatter,
+	};
+	use std::fs;
+	#[test]
+	fn scaffold_deserialises_and_validates() {
+		let scaffold = scatter_scaffold();
+		let scatter = Scatter::from_ron_str(&scaffold).unwrap();
+		assert_eq!(
+			scatter,
+			Scatter::from_ron_str(&scatter.to_ron_string().unwrap()).unwrap()
+		);
+		// every field of the graph and its data set is commented
+		let commented = scaffold
+			.lines()
+			.filter(|line| field_comment(line).is_some())
+			.count();
+		assert_eq!(FIELD_COMMENTS.len(), commented);
+		scatter.prepare().unwrap();
+	}
+	#[test]
+	fn example_configs_round_trip() {
+		let mut count = 0;
+		for dir in fs::read_dir("examples").unwrap() {
+			for file in fs::read_dir(dir.unwrap().path()).unwrap() {
+				let path = file.unwrap().path();
+				if path.extension().and_then(|e| e.to_str()) != Some("ron") {
+					continue;
+				}
+				let config = fs::read_to_string(&path).unwrap();
+				let scatter = Scatter::from_ron_str(&config).unwrap();
+				let serialised = scatter.to_ron_string().unwrap();
+				assert_eq!(
+					scatter,
+					Scatter::from_ron_str(&serialised).unwrap(),
+					"{:?}",
+					path
+				);
+				count += 1;
+			}
+		}
+		assert!(count > 0);
+	}
+}
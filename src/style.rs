@@ -0,0 +1,127 @@
+//! Shared styling which can be maintained in its own `.ron` file and applied to many graph configs, e.g
+//!
+//! ```txt
+//! Style(
+//!    has_grid: true,
+//!    has_legend: true,
+//!    number_format: NumberFormat(thousands_separator: Some(','), decimal_separator: '.'),
+//!    chrome: Full,
+//!    colour_assignment: NameHash,
+//!    palette: ColourBlindSafe,
+//!    axis_colour: DarkGrey,
+//!    grid_style: Dashed,
+//!    legend_font_size: 14.0,
+//! )
+//! ```
+//!
+//! Every field is optional, values specified by a graph config take precedence over the style
+
+use ron::de::from_reader;
+use serde::Deserialize;
+use std::fs::File;
+use tracing::debug;
+
+use crate::{
+	canvas::{axes::GridStyle, Chrome},
+	colours::{Colour, ColourAssignment, Palette},
+	data::NumberFormat,
+	deserialize_some,
+	error::PlotError,
+};
+
+/// Styling applied underneath the values of a graph config
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct Style {
+	/// Optional, should graphs have a light grey background grid
+	#[serde(default, deserialize_with = "deserialize_some")]
+	pub has_grid: Option<bool>,
+	/// Optional, should a legend be generated
+	#[serde(default, deserialize_with = "deserialize_some")]
+	pub has_legend: Option<bool>,
+	/// Optional, how numbers are written in csv files
+	#[serde(default, deserialize_with = "deserialize_some")]
+	pub number_format: Option<NumberFormat>,
+	/// Optional, how much of the graph surrounding the plot area is drawn
+	#[serde(default, deserialize_with = "deserialize_some")]
+	pub chrome: Option<Chrome>,
+	/// Optional, how colours are picked for data sets without one
+	#[serde(default, deserialize_with = "deserialize_some")]
+	pub colour_assignment: Option<ColourAssignment>,
+	/// Optional, the pool of colours given to data sets without one
+	#[serde(default, deserialize_with = "deserialize_some")]
+	pub palette: Option<Palette>,
+	/// Optional, fraction of its value by which the smallest and largest data values are moved outward
+	#[serde(default, deserialize_with = "deserialize_some")]
+	pub axis_padding_fraction: Option<f32>,
+	/// Optional, padding fraction of the x-axis in place of `axis_padding_fraction`
+	#[serde(default, deserialize_with = "deserialize_some")]
+	pub x_axis_padding_fraction: Option<f32>,
+	/// Optional, padding fraction of the y-axes in place of `axis_padding_fraction`
+	#[serde(default, deserialize_with = "deserialize_some")]
+	pub y_axis_padding_fraction: Option<f32>,
+	/// Optional, pixel thickness of the axis lines and their scale markers
+	#[serde(default, deserialize_with = "deserialize_some")]
+	pub axis_thickness: Option<u32>,
+	/// Optional, pixel thickness of the background gridlines
+	#[serde(default, deserialize_with = "deserialize_some")]
+	pub grid_thickness: Option<u32>,
+	/// Optional, `Solid`, `Dashed` or `Dotted` pattern of the background gridlines
+	#[serde(default, deserialize_with = "deserialize_some")]
+	pub grid_style: Option<GridStyle>,
+	/// Optional, pixel length of the dashes of `Dashed` gridlines and of the gaps between them
+	#[serde(default, deserialize_with = "deserialize_some")]
+	pub grid_dash_length: Option<u32>,
+	/// Optional, colour of the axis lines and their scale markers
+	#[serde(default, deserialize_with = "deserialize_some")]
+	pub axis_colour: Option<Colour>,
+	/// Optional, colour of the values written along the axes
+	#[serde(default, deserialize_with = "deserialize_some")]
+	pub tick_label_colour: Option<Colour>,
+	/// Optional, colour of the background gridlines and of the minor gridlines
+	#[serde(default, deserialize_with = "deserialize_some")]
+	pub grid_colour: Option<Colour>,
+	/// Optional, the colour filling the legend behind its entries
+	#[serde(default, deserialize_with = "deserialize_some")]
+	pub legend_background: Option<Colour>,
+	/// Optional, the font size of the legend
+	#[serde(default, deserialize_with = "deserialize_some")]
+	pub legend_font_size: Option<f32>,
+}
+
+impl Style {
+	/// Read a style from a `.ron` file, the error names the path if it cannot be opened or parsed
+	pub fn load(path: &str) -> Result<Style, PlotError> {
+		let f = File::open(path).map_err(|e| PlotError::Io {
+			context: format!("Failed to open style file at {}", path),
+			source: e,
+		})?;
+		let style: Style = from_reader(f).map_err(|e| {
+			PlotError::Config(format!(
+				"Failed to load style file at {}, error: {}",
+				path, e
+			))
+		})?;
+		debug!("Ron style {:?}", &style);
+		Ok(style)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{canvas::Chrome, colours::ColourAssignment, style::Style};
+	#[test]
+	fn fields_are_optional_and_written_without_some() {
+		let style: Style = ron::from_str("Style(has_grid: true, chrome: PlotOnly)").unwrap();
+		assert_eq!(
+			Style {
+				has_grid: Some(true),
+				chrome: Some(Chrome::PlotOnly),
+				..Style::default()
+			},
+			style
+		);
+		let style: Style = ron::from_str("Style(colour_assignment: NameHash)").unwrap();
+		assert_eq!(Some(ColourAssignment::NameHash), style.colour_assignment);
+		assert_eq!(None, style.has_legend);
+	}
+}
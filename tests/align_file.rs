@@ -0,0 +1,93 @@
+//! Graphs rendered with the same `--align-file` place their x-axes at the same pixel columns
+
+#![cfg(feature = "cli")]
+
+use std::{
+	fs,
+	path::Path,
+	process::{Command, ExitStatus},
+};
+
+/// Write a config plotting `y_axis_label` against x of 0 to 10 with y values scaled by `y_multiplier`
+/// so that graphs differ in the width of their y-axis labels
+fn write_config(dir: &Path, name: &str, y_axis_label: &str, y_multiplier: u32) {
+	let csv: String = (0..=10)
+		.map(|i| format!("{},{}\n", i, i * y_multiplier))
+		.collect();
+	fs::write(dir.join(format!("{}.csv", name)), format!("x,y\n{}", csv)).unwrap();
+	fs::write(
+		dir.join(format!("{}.ron", name)),
+		format!(
+			r#"Scatter(
+				title: {:?},
+				canvas_pixel_size: (600, 300),
+				x_axis_label: "x",
+				x_axis_resolution: 10,
+				y_axis_label: {:?},
+				data_sets: [
+					DataSet(
+						data_path: "{}.csv",
+						has_headers: true,
+						x_axis_csv_column: 0,
+						x_axis_error_bar_csv_column: None,
+						y_axis_csv_column: 1,
+						y_axis_error_bar_csv_column: None,
+						name: "y",
+						symbol: Cross,
+						symbol_radius: 3,
+						symbol_thickness: 0,
+						best_fit: None,
+					),
+				],
+			)"#,
+			name, y_axis_label, name
+		),
+	)
+	.unwrap();
+}
+
+/// Render a config with the shared alignment file, returning the exit status and the geometry report
+fn render(dir: &Path, name: &str) -> (ExitStatus, String) {
+	let report = format!("{}_geometry.ron", name);
+	let status = Command::new(env!("CARGO_BIN_EXE_plotrs"))
+		.current_dir(dir)
+		.args(["-g", "scatter", "-c", &format!("{}.ron", name), "-o", "."])
+		.args(["--align-file", "align.ron", "--geometry-report", &report])
+		.arg("-q")
+		.status()
+		.unwrap();
+	let geometry = fs::read_to_string(dir.join(report)).unwrap_or_default();
+	(status, geometry)
+}
+
+/// The line of a geometry report holding a field
+fn field<'a>(geometry: &'a str, name: &str) -> &'a str {
+	geometry.lines().find(|line| line.contains(name)).unwrap()
+}
+
+#[test]
+fn later_graphs_reuse_the_recorded_x_axis() {
+	let dir = std::env::temp_dir().join(format!("plotrs_align_{}", std::process::id()));
+	fs::create_dir_all(&dir).unwrap();
+	write_config(&dir, "wide", "A long y-axis label", 100000);
+	write_config(&dir, "narrow", "y", 1);
+	write_config(&dir, "wider", "An even longer y-axis label", 100000);
+	let (status, wide) = render(&dir, "wide");
+	assert!(status.success());
+	assert!(dir.join("align.ron").exists());
+	let (status, narrow) = render(&dir, "narrow");
+	assert!(status.success());
+	// narrower labels alone would let the plot start further left
+	assert_eq!(
+		field(&wide, "axis_min_pixel"),
+		field(&narrow, "axis_min_pixel")
+	);
+	assert_eq!(
+		field(&wide, "axis_max_pixel"),
+		field(&narrow, "axis_max_pixel")
+	);
+	// a graph needing more room than was recorded is refused
+	let (status, _) = render(&dir, "wider");
+	fs::remove_dir_all(&dir).unwrap();
+	assert!(!status.success());
+}
@@ -0,0 +1,17 @@
+//! Golden image of axes and gridlines drawn without any colours configured, so that adding colour
+//! options leaves the default output untouched
+
+#![cfg(feature = "cli")]
+
+mod common;
+
+/// Renders `tests/fixtures/axis_colours/grid.ron`. No text is drawn so the image does not depend upon
+/// the fonts installed. `expected.png` was rendered by the binary built from the commit before the
+/// colour options existed (with the resolutions written as `Some(..)` as that config required), so it
+/// pins the output the defaults must reproduce. Points are squares because circles have since been
+/// rasterised differently. Only regenerate it, with `plotrs -g scatter -c grid.ron -o .` from within
+/// the directory, after an intended change
+#[test]
+fn default_axis_colours_match_golden_image() {
+	common::render_and_compare("axis_colours", "grid.ron", "default_axes.png");
+}
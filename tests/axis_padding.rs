@@ -0,0 +1,120 @@
+//! The fraction the data bounds are padded by before the axes are fitted to them
+
+use plotrs::scatter::{scatter_render, DataSetBuilder, RenderOptions, ScatterBuilder};
+use std::fs;
+
+/// Render `scatter` and read back the x and y limits from its geometry report
+fn limits(scatter: ScatterBuilder, name: &str) -> (String, String) {
+	let dir = std::env::temp_dir().join(format!("plotrs_padding_{}_{}", name, std::process::id()));
+	fs::create_dir_all(&dir).unwrap();
+	let report = dir.join("geometry.ron");
+	let options = RenderOptions {
+		geometry_report: Some(report.display().to_string()),
+		..RenderOptions::default()
+	};
+	scatter_render(&scatter.build().unwrap(), &options).unwrap();
+	let geometry = fs::read_to_string(&report).unwrap();
+	fs::remove_dir_all(&dir).unwrap();
+	let field = |name: &str| {
+		geometry
+			.lines()
+			.find(|line| line.contains(name))
+			.unwrap()
+			.trim()
+			.to_owned()
+	};
+	(
+		field("x_data_min_max_limits"),
+		field("y_data_min_max_limits"),
+	)
+}
+
+#[test]
+fn padding_moves_the_bounds_outward() {
+	let scatter = |padding: Option<f32>| {
+		let builder = ScatterBuilder::new()
+			.canvas_size(400, 300)
+			.axis_labels("x", "y")
+			.resolution(Some(4), Some(4))
+			.add_data_set(DataSetBuilder::from_points(
+				"data",
+				&[(10.0, 20.0), (50.0, 80.0)],
+			));
+		match padding {
+			Some(padding) => builder.axis_padding_fraction(padding),
+			None => builder,
+		}
+	};
+	// 10% by default
+	assert_eq!(
+		(
+			"x_data_min_max_limits: (9, 55),".to_owned(),
+			"y_data_min_max_limits: (18, 88),".to_owned()
+		),
+		limits(scatter(None), "default")
+	);
+	assert_eq!(
+		(
+			"x_data_min_max_limits: (10, 50),".to_owned(),
+			"y_data_min_max_limits: (20, 80),".to_owned()
+		),
+		limits(scatter(Some(0.0)), "zero")
+	);
+	assert_eq!(
+		(
+			"x_data_min_max_limits: (5, 100),".to_owned(),
+			"y_data_min_max_limits: (20, 80),".to_owned()
+		),
+		limits(
+			scatter(Some(0.0)).axis_padding_fractions(Some(1.0), None),
+			"x_only"
+		)
+	);
+}
+
+#[test]
+fn zero_padding_keeps_bounds_of_zero() {
+	// the largest x and smallest y are exactly zero
+	let scatter = ScatterBuilder::new()
+		.canvas_size(400, 300)
+		.axis_labels("x", "y")
+		.resolution(Some(4), Some(4))
+		.axis_padding_fraction(0.0)
+		.add_data_set(DataSetBuilder::from_points(
+			"data",
+			&[(-40.0, 0.0), (-20.0, 10.0), (0.0, 20.0)],
+		));
+	assert_eq!(
+		(
+			"x_data_min_max_limits: (-40, 0),".to_owned(),
+			"y_data_min_max_limits: (0, 20),".to_owned()
+		),
+		limits(scatter, "zero_bounds")
+	);
+}
+
+#[test]
+fn bounds_without_extent_are_widened() {
+	// every y is zero, which has no extent to pad, with and without a resolution
+	let scatter = |resolution: Option<u32>| {
+		ScatterBuilder::new()
+			.canvas_size(400, 300)
+			.axis_labels("x", "y")
+			.resolution(Some(2), resolution)
+			.add_data_set(DataSetBuilder::from_points(
+				"data",
+				&[(0.0, 0.0), (1.0, 0.0), (2.0, 0.0)],
+			))
+	};
+	assert_eq!(
+		(
+			"x_data_min_max_limits: (0, 2),".to_owned(),
+			"y_data_min_max_limits: (-1, 1),".to_owned()
+		),
+		limits(scatter(Some(4)), "zero_y_resolution")
+	);
+	assert_eq!(
+		"y_data_min_max_limits: (-1, 1),".to_owned(),
+		limits(scatter(None), "zero_y_auto").1
+	);
+}
@@ -0,0 +1,46 @@
+//! Fixed axis ranges replace the data bounds so graphs of separate runs share their axes
+
+use plotrs::scatter::{scatter_render, DataSetBuilder, RenderOptions, ScatterBuilder};
+use std::fs;
+
+#[test]
+fn fixed_ranges_set_the_axis_limits() {
+	let dir = std::env::temp_dir().join(format!("plotrs_range_{}", std::process::id()));
+	fs::create_dir_all(&dir).unwrap();
+	let report = dir.join("geometry.ron");
+	let scatter = ScatterBuilder::new()
+		.canvas_size(400, 300)
+		.axis_labels("Time (ms)", "Energy (kJ)")
+		.axis_ranges(Some((0.0, 9.5)), Some((-20.0, 20.0)))
+		// the last point lies beyond the fixed x range and is clipped
+		.add_data_set(DataSetBuilder::from_points(
+			"data",
+			&[(1.0, 1.0), (2.0, 3.0), (4.0, -2.0), (50.0, 4.0)],
+		))
+		.build()
+		.unwrap();
+	let options = RenderOptions {
+		geometry_report: Some(report.display().to_string()),
+		..RenderOptions::default()
+	};
+	scatter_render(&scatter, &options).unwrap();
+	let geometry = fs::read_to_string(&report).unwrap();
+	fs::remove_dir_all(&dir).unwrap();
+	let field = |name: &str| {
+		geometry
+			.lines()
+			.find(|line| line.contains(name))
+			.unwrap()
+			.trim()
+			.to_owned()
+	};
+	// rounded outward to whole numbers
+	assert_eq!(
+		"x_data_min_max_limits: (0, 10),",
+		field("x_data_min_max_limits")
+	);
+	assert_eq!(
+		"y_data_min_max_limits: (-20, 20),",
+		field("y_data_min_max_limits")
+	);
+}
@@ -0,0 +1,647 @@
+//! Graphs can be built and rendered in code without a `.ron` config
+
+use plotrs::{
+	canvas::plot::DataSymbol,
+	canvas::{
+		axes::{CustomTick, GridStyle, LabelOrientation},
+		best_fit::BestFit,
+		format::Notation,
+	},
+	colours::{Colour, Palette, COLOUR_CYCLE, GRAYSCALE},
+	error::{PlotError, ValidationError},
+	scatter::{DataSetBuilder, ScatterBuilder},
+};
+use std::path::PathBuf;
+
+/// Path of a csv with headers holding x and y columns
+fn scatter_csv() -> String {
+	PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+		.join("tests/fixtures/gzip/scatter.csv")
+		.display()
+		.to_string()
+}
+
+#[test]
+fn builder_renders_without_saving() {
+	let image = ScatterBuilder::new()
+		.title("Built")
+		.canvas_size(400, 300)
+		.axis_labels("x", "y")
+		.resolution(Some(5), None)
+		.grid(true)
+		.legend(true)
+		.add_data_set(DataSetBuilder::new("data", &scatter_csv(), 0, 1))
+		.render()
+		.unwrap();
+	assert_eq!((400, 300), image.dimensions());
+	// something other than the white background has been drawn
+	assert!(image.pixels().any(|pixel| pixel.0 != [255, 255, 255, 255]));
+}
+
+#[test]
+fn sets_hidden_from_the_legend_get_no_rows() {
+	let graph = |legend: bool, show_in_legend: bool| {
+		ScatterBuilder::new()
+			.canvas_size(400, 300)
+			.axis_labels("x", "y")
+			.legend(legend)
+			.add_data_set(
+				DataSetBuilder::new("data", &scatter_csv(), 0, 1).show_in_legend(show_in_legend),
+			)
+			.render()
+			.unwrap()
+	};
+	// with every set hidden the legend is skipped rather than drawn empty
+	assert!(graph(false, true) == graph(true, false));
+	assert!(graph(false, true) != graph(true, true));
+}
+
+#[test]
+fn legend_order_is_independent_of_drawing_order() {
+	let control =
+		|| DataSetBuilder::from_points("control", &[(0.0, 0.0), (1.0, 1.0)]).colour(Colour::Grey);
+	let trial =
+		|| DataSetBuilder::from_points("trial", &[(4.0, 5.0), (5.0, 4.0)]).colour(Colour::Red);
+	let graph = |first: DataSetBuilder, second: DataSetBuilder| {
+		ScatterBuilder::new()
+			.canvas_size(400, 300)
+			.axis_labels("x", "y")
+			.legend(true)
+			.add_data_set(first)
+			.add_data_set(second)
+			.render()
+			.unwrap()
+	};
+	// the points do not overlap so only the legend tells the orders apart
+	let reordered = graph(control().legend_order(2), trial().legend_order(1));
+	assert!(reordered == graph(trial(), control()));
+	assert!(reordered != graph(control(), trial()));
+	// sets without an order follow those with one
+	assert!(reordered == graph(control(), trial().legend_order(1)));
+}
+
+#[test]
+fn build_refuses_graphs_which_cannot_be_drawn() {
+	let invalid = |builder: ScatterBuilder| match builder.build() {
+		Err(PlotError::Invalid(e)) => e,
+		other => panic!("expected a validation error, got {:?}", other),
+	};
+	assert_eq!(ValidationError::NoDataSets, invalid(ScatterBuilder::new()));
+	assert_eq!(
+		ValidationError::ZeroResolution("y_axis_resolution"),
+		invalid(
+			ScatterBuilder::new()
+				.resolution(None, Some(0))
+				.add_data_set(DataSetBuilder::new("data", &scatter_csv(), 0, 1))
+		)
+	);
+	assert_eq!(
+		ValidationError::InvalidAxisRange("x_axis_range"),
+		invalid(
+			ScatterBuilder::new()
+				.axis_ranges(Some((5.0, 5.0)), None)
+				.add_data_set(DataSetBuilder::new("data", &scatter_csv(), 0, 1))
+		)
+	);
+	assert_eq!(
+		ValidationError::InvalidPadding("y_axis_padding_fraction"),
+		invalid(
+			ScatterBuilder::new()
+				.axis_padding_fractions(None, Some(-0.5))
+				.add_data_set(DataSetBuilder::new("data", &scatter_csv(), 0, 1))
+		)
+	);
+}
+
+#[test]
+fn points_in_memory_match_the_same_points_read_from_csv() {
+	let csv = std::fs::read_to_string(scatter_csv()).unwrap();
+	let points: Vec<(f32, f32)> = csv
+		.lines()
+		.skip(1)
+		.map(|row| {
+			let (x, y) = row.split_once(',').unwrap();
+			(x.parse().unwrap(), y.parse().unwrap())
+		})
+		.collect();
+	let render = |data_set: DataSetBuilder| {
+		ScatterBuilder::new()
+			.title("Memory")
+			.canvas_size(400, 300)
+			.axis_labels("x", "y")
+			.add_data_set(data_set)
+			.render()
+			.unwrap()
+	};
+	let from_csv = render(DataSetBuilder::new("data", &scatter_csv(), 0, 1));
+	let from_memory = render(DataSetBuilder::from_points("data", &points));
+	assert!(from_csv == from_memory);
+}
+
+#[test]
+fn untitled_graph_renders() {
+	let image = ScatterBuilder::new()
+		.canvas_size(400, 300)
+		.axis_labels("Time (ms)", "Energy (kJ)")
+		.add_data_set(DataSetBuilder::from_points(
+			"data",
+			&[(0.0, 1.0), (1.0, 2.0)],
+		))
+		.render()
+		.unwrap();
+	assert_eq!((400, 300), image.dimensions());
+}
+
+#[test]
+fn fixed_tick_decimal_places_change_the_labels() {
+	let render = |places: Option<u8>| {
+		ScatterBuilder::new()
+			.title("Places")
+			.canvas_size(400, 300)
+			.axis_labels("Time (ms)", "Energy (kJ)")
+			.tick_decimal_places(places, places)
+			.add_data_set(DataSetBuilder::from_points(
+				"data",
+				&[(0.0, 1.0), (10.0, 20.0)],
+			))
+			.render()
+			.unwrap()
+	};
+	assert!(render(None) == render(None));
+	assert!(render(None) != render(Some(2)));
+}
+
+#[test]
+fn large_values_render_in_every_tick_notation() {
+	let render = |notation: Notation| {
+		ScatterBuilder::new()
+			.title("Large")
+			.canvas_size(400, 300)
+			.axis_labels("Time (ms)", "Energy (kJ)")
+			.tick_format(notation)
+			.add_data_set(DataSetBuilder::from_points(
+				"data",
+				&[(0.0, 0.0), (1.0e6, 2.0e6), (1.5e6, 3.0e6)],
+			))
+			.render()
+			.unwrap()
+	};
+	let plain = render(Notation::Plain);
+	let scientific = render(Notation::Scientific);
+	assert!(plain != scientific);
+	assert!(render(Notation::Auto) == scientific);
+}
+
+#[test]
+fn percent_labels_apply_to_each_axis_independently() {
+	let render = |builder: ScatterBuilder| {
+		builder
+			.title("Ratios")
+			.canvas_size(400, 300)
+			.axis_labels("Recall", "Precision")
+			.add_data_set(DataSetBuilder::from_points(
+				"data",
+				&[(0.0, 1.0), (0.5, 0.8), (1.0, 0.35)],
+			))
+			.render()
+			.unwrap()
+	};
+	let plain = render(ScatterBuilder::new());
+	let y_percent = render(ScatterBuilder::new().axis_tick_formats(None, Some(Notation::Percent)));
+	let x_percent = render(ScatterBuilder::new().axis_tick_formats(Some(Notation::Percent), None));
+	assert!(plain != y_percent);
+	assert!(x_percent != y_percent);
+	assert!(
+		render(ScatterBuilder::new().tick_format(Notation::Percent))
+			== render(
+				ScatterBuilder::new()
+					.axis_tick_formats(Some(Notation::Percent), Some(Notation::Percent))
+			)
+	);
+}
+
+#[test]
+fn minor_ticks_and_their_gridlines_can_be_configured() {
+	let render = |minor_ticks: Option<u32>, minor_grid: bool| {
+		ScatterBuilder::new()
+			.title("Minor")
+			.canvas_size(400, 300)
+			.axis_labels("Time (ms)", "Energy (kJ)")
+			.minor_ticks(minor_ticks, minor_ticks)
+			.minor_grid(minor_grid)
+			.add_data_set(DataSetBuilder::from_points(
+				"data",
+				&[(0.0, 1.0), (10.0, 20.0)],
+			))
+			.render()
+			.unwrap()
+	};
+	let without = render(Some(0), false);
+	let with = render(Some(4), false);
+	assert!(without != with);
+	assert!(without == render(Some(0), true));
+	assert!(with != render(Some(4), true));
+}
+
+#[test]
+fn axes_and_gridlines_can_be_thickened() {
+	let render = |axis_thickness: u32, grid_thickness: u32| {
+		ScatterBuilder::new()
+			.title("Thickness")
+			.canvas_size(400, 300)
+			.axis_labels("Time (ms)", "Energy (kJ)")
+			.grid(true)
+			.axis_thickness(axis_thickness)
+			.grid_thickness(grid_thickness)
+			.add_data_set(DataSetBuilder::from_points(
+				"data",
+				&[(0.0, 1.0), (10.0, 20.0)],
+			))
+			.render()
+	};
+	let thin = render(1, 1).unwrap();
+	assert!(thin != render(3, 1).unwrap());
+	assert!(thin != render(1, 3).unwrap());
+	// the thicker axis covers more of the canvas in black
+	let black =
+		|canvas: &image::RgbaImage| canvas.pixels().filter(|p| p.0 == [0, 0, 0, 255]).count();
+	assert!(black(&render(3, 1).unwrap()) > black(&thin));
+	assert!(render(0, 1).is_err());
+	assert!(render(1, 0).is_err());
+}
+
+#[test]
+fn gridlines_can_be_dashed_or_dotted() {
+	let render = |grid_style: GridStyle, dash_length: Option<u32>| {
+		ScatterBuilder::new()
+			.title("Grid")
+			.canvas_size(400, 300)
+			.axis_labels("x", "y")
+			.grid(true)
+			.grid_style(grid_style, dash_length)
+			.add_data_set(DataSetBuilder::new("data", &scatter_csv(), 0, 1))
+			.render()
+			.unwrap()
+	};
+	let solid = render(GridStyle::Solid, None);
+	let dashed = render(GridStyle::Dashed, None);
+	assert!(solid != dashed);
+	assert!(dashed != render(GridStyle::Dashed, Some(8)));
+	assert!(dashed != render(GridStyle::Dotted, None));
+	// the dash length only applies to dashes
+	assert!(solid == render(GridStyle::Solid, Some(8)));
+	assert!(matches!(
+		ScatterBuilder::new()
+			.grid_style(GridStyle::Dashed, Some(0))
+			.add_data_set(DataSetBuilder::new("data", &scatter_csv(), 0, 1))
+			.render(),
+		Err(PlotError::Config(_))
+	));
+}
+
+#[test]
+fn y_axis_label_can_be_vertical_in_every_quadrant() {
+	let render = |points: &[(f32, f32)], orientation: LabelOrientation| {
+		ScatterBuilder::new()
+			.title("Label")
+			.canvas_size(400, 300)
+			.axis_labels("x", "Energy (kJ)")
+			.y_axis_label_orientation(orientation)
+			.add_data_set(DataSetBuilder::from_points("data", points))
+			.render()
+			.unwrap()
+	};
+	for x in [(1.0, 10.0), (-10.0, -1.0), (-10.0, 10.0)] {
+		for y in [(1.0, 10.0), (-10.0, -1.0), (-10.0, 10.0)] {
+			let points = [(x.0, y.0), (x.1, y.1)];
+			assert!(
+				render(&points, LabelOrientation::Horizontal)
+					!= render(&points, LabelOrientation::Vertical),
+				"x {:?} y {:?}",
+				x,
+				y
+			);
+		}
+	}
+}
+
+#[test]
+fn tick_suffixes_fit_beside_the_axis() {
+	let render = |suffixes: (Option<&str>, Option<&str>)| {
+		ScatterBuilder::new()
+			.title("Suffix")
+			.canvas_size(400, 300)
+			.axis_labels("t", "E")
+			.tick_suffixes(suffixes.0, suffixes.1)
+			.add_data_set(DataSetBuilder::from_points(
+				"data",
+				&[(10.0, 1000.0), (50.0, 5000.0)],
+			))
+			.render()
+			.unwrap()
+	};
+	let plain = render((None, None));
+	let suffixed = render((Some(" ms"), Some(" kJ/mol")));
+	assert!(plain != suffixed);
+	// nothing is drawn within the border of the canvas even though the labels are far wider than `E`
+	let leftmost = |image: &image::RgbaImage| {
+		image
+			.enumerate_pixels()
+			.filter(|(_, _, pixel)| pixel.0 != [255, 255, 255, 255])
+			.map(|(x, _, _)| x)
+			.min()
+			.unwrap()
+	};
+	assert!(
+		leftmost(&suffixed) >= 10,
+		"drawn from column {}",
+		leftmost(&suffixed)
+	);
+}
+
+#[test]
+fn crowded_tick_labels_are_thinned() {
+	let render = |every: Option<u32>| {
+		ScatterBuilder::new()
+			.title("Crowded")
+			.canvas_size(400, 300)
+			.axis_labels("x", "y")
+			.resolution(Some(40), Some(30))
+			.tick_label_every(every, None)
+			.add_data_set(DataSetBuilder::from_points(
+				"data",
+				&[(10.0, 1000.0), (50.0, 5000.0)],
+			))
+			.render()
+	};
+	let auto = render(None).unwrap();
+	// forty labels cannot fit along 400 pixels, so labelling every marker differs from the default
+	assert!(auto != render(Some(1)).unwrap());
+	assert!(matches!(render(Some(0)), Err(PlotError::Config(_))));
+}
+
+#[test]
+fn custom_ticks_replace_the_resolution() {
+	let render = |x_ticks: Option<Vec<CustomTick>>| {
+		ScatterBuilder::new()
+			.title("Ticks")
+			.canvas_size(400, 300)
+			.axis_labels("x", "y")
+			.grid(true)
+			.custom_ticks(x_ticks, None)
+			.add_data_set(DataSetBuilder::new("data", &scatter_csv(), 0, 1))
+			.render()
+			.unwrap()
+	};
+	let resolution = render(None);
+	let custom = render(Some(vec![
+		CustomTick::Value(0.5),
+		CustomTick::Labelled(1.0, String::from("one")),
+	]));
+	assert!(resolution != custom);
+	// markers beyond the axis are skipped rather than drawn off the plot
+	assert!(render(Some(vec![CustomTick::Value(1000.0)])) != custom);
+}
+
+#[test]
+fn axis_colours_default_to_black_and_grey() {
+	let render = |colours: Option<(Colour, Colour, Colour)>| {
+		let builder = ScatterBuilder::new()
+			.title("Colours")
+			.canvas_size(400, 300)
+			.axis_labels("Time (ms)", "Energy (kJ)")
+			.grid(true)
+			.add_data_set(DataSetBuilder::from_points(
+				"data",
+				&[(0.0, 1.0), (10.0, 20.0)],
+			));
+		match colours {
+			Some((axis, tick_label, grid)) => builder.axis_colours(axis, tick_label, grid),
+			None => builder,
+		}
+		.render()
+		.unwrap()
+	};
+	let default = render(None);
+	assert!(default == render(Some((Colour::Black, Colour::Black, Colour::Grey))));
+	assert!(default != render(Some((Colour::Blue, Colour::Black, Colour::Grey))));
+	assert!(default != render(Some((Colour::Black, Colour::Red, Colour::Grey))));
+	assert!(default != render(Some((Colour::Black, Colour::Black, Colour::Pink))));
+}
+
+#[test]
+fn axis_arrows_stay_clear_of_the_canvas_border() {
+	let render = |axis_arrows: bool| {
+		ScatterBuilder::new()
+			.title("Arrows")
+			.canvas_size(400, 300)
+			.axis_labels("x", "y")
+			.axis_arrows(axis_arrows)
+			.add_data_set(DataSetBuilder::from_points(
+				"data",
+				&[(1.0, 1.0), (5.0, 5.0)],
+			))
+			.render()
+			.unwrap()
+	};
+	let plain = render(false);
+	let arrows = render(true);
+	assert!(plain != arrows);
+	// the arrowhead of the x-axis is kept within the border of the canvas
+	let rightmost = |image: &image::RgbaImage| {
+		image
+			.enumerate_pixels()
+			.filter(|(_, _, pixel)| pixel.0 != [255, 255, 255, 255])
+			.map(|(x, _, _)| x)
+			.max()
+			.unwrap()
+	};
+	assert!(
+		rightmost(&arrows) < 390,
+		"drawn to column {}",
+		rightmost(&arrows)
+	);
+}
+
+#[test]
+fn fit_statistics_are_written_beside_a_regression() {
+	let points: Vec<(f32, f32)> = (0..20)
+		.map(|i| {
+			(
+				i as f32 * 0.5,
+				(i as f32 * 0.5).powi(2) * 0.1 + (i % 3) as f32 * 0.2,
+			)
+		})
+		.collect();
+	let render = |show_fit_statistics: bool| {
+		ScatterBuilder::new()
+			.title("Regression")
+			.canvas_size(400, 300)
+			.axis_labels("x", "y")
+			.add_data_set(
+				DataSetBuilder::from_points("data", &points)
+					.best_fit(BestFit::PolynomialRegression {
+						degree: 2,
+						colour: Colour::Blue,
+					})
+					.show_fit_statistics(show_fit_statistics),
+			)
+			.render()
+			.unwrap()
+	};
+	let plain = render(false);
+	let annotated = render(true);
+	assert!(plain != annotated);
+	// statistics need a curve to measure
+	assert!(ScatterBuilder::new()
+		.add_data_set(DataSetBuilder::from_points("data", &points).show_fit_statistics(true))
+		.build()
+		.is_err());
+}
+
+#[test]
+fn best_fit_curves_can_be_thickened() {
+	let render = |thickness: Option<u32>| {
+		let mut data_set = DataSetBuilder::from_points("data", &[(1.0, 1.0), (9.0, 9.0)])
+			.colour(Colour::White)
+			.best_fit(BestFit::Linear {
+				gradient: 1.0,
+				y_intercept: 0.0,
+				colour: Colour::Blue,
+			});
+		if let Some(thickness) = thickness {
+			data_set = data_set.best_fit_thickness(thickness);
+		}
+		ScatterBuilder::new()
+			.title("Thick")
+			.canvas_size(400, 300)
+			.axis_labels("x", "y")
+			.add_data_set(data_set)
+			.render()
+			.map(|image| {
+				// pixels tinted blue by the curve
+				image
+					.pixels()
+					.filter(|pixel| pixel.0[2] > pixel.0[0].saturating_add(40))
+					.count()
+			})
+	};
+	let default = render(None).unwrap();
+	assert_eq!(default, render(Some(1)).unwrap());
+	assert!(render(Some(4)).unwrap() > default * 3);
+	assert!(render(Some(0)).is_err());
+}
+
+#[test]
+fn overlapping_translucent_points_darken_progressively() {
+	let image = ScatterBuilder::new()
+		.canvas_size(400, 300)
+		.axis_labels("x", "y")
+		.add_data_set(
+			DataSetBuilder::from_points("dense", &[(2.0, 2.0), (2.0, 2.0), (8.0, 8.0)])
+				.colour(Colour::Blue)
+				.opacity(0.5),
+		)
+		.render()
+		.unwrap();
+	let contains = |colour: [u8; 4]| image.pixels().any(|pixel| pixel.0 == colour);
+	// half of the white beneath shows through a single point and a quarter through two
+	assert!(contains([127, 127, 255, 255]));
+	assert!(contains([63, 63, 255, 255]));
+	assert!(!contains([0, 0, 255, 255]));
+}
+
+#[test]
+fn sets_without_a_colour_are_given_different_ones() {
+	let image = ScatterBuilder::new()
+		.canvas_size(400, 300)
+		.axis_labels("x", "y")
+		.legend(true)
+		.add_data_set(DataSetBuilder::from_points(
+			"first",
+			&[(1.0, 1.0), (2.0, 3.0)],
+		))
+		.add_data_set(DataSetBuilder::from_points(
+			"second",
+			&[(3.0, 2.0), (4.0, 4.0)],
+		))
+		.render()
+		.unwrap();
+	let contains = |colour: Colour| {
+		let rgba = Colour::get_pixel_colour(colour);
+		image.pixels().any(|pixel| pixel.0 == rgba)
+	};
+	assert!(contains(COLOUR_CYCLE[0]));
+	assert!(contains(COLOUR_CYCLE[1]));
+}
+#[test]
+fn grayscale_palette_varies_the_symbol_of_sets_that_share_one() {
+	let render = |second: DataSetBuilder| {
+		ScatterBuilder::new()
+			.canvas_size(400, 300)
+			.axis_labels("x", "y")
+			.palette(Palette::Grayscale)
+			.add_data_set(DataSetBuilder::from_points(
+				"first",
+				&[(1.0, 1.0), (2.0, 3.0)],
+			))
+			.add_data_set(second)
+			.render()
+			.unwrap()
+	};
+	let second = || DataSetBuilder::from_points("second", &[(3.0, 2.0), (4.0, 4.0)]);
+	let automatic = render(second());
+	assert_eq!(
+		render(second().symbol(DataSymbol::Circle, 3, 0)).into_raw(),
+		automatic.clone().into_raw()
+	);
+	for colour in &GRAYSCALE[..2] {
+		let rgba = Colour::get_pixel_colour(*colour);
+		assert!(automatic.pixels().any(|pixel| pixel.0 == rgba));
+	}
+	// an explicit colour keeps the symbol it was given
+	let explicit = render(second().colour(Colour::Red));
+	assert_ne!(
+		render(
+			second()
+				.colour(Colour::Red)
+				.symbol(DataSymbol::Circle, 3, 0)
+		)
+		.into_raw(),
+		explicit.into_raw()
+	);
+}
+
+#[test]
+fn antialiasing_blends_the_edges_of_symbols_and_curves() {
+	let render = |antialias: bool| {
+		ScatterBuilder::new()
+			.canvas_size(400, 300)
+			.axis_labels("x", "y")
+			.antialias(antialias)
+			.add_data_set(
+				DataSetBuilder::from_points("data", &[(1.0, 2.0), (5.0, 4.0), (9.0, 7.0)])
+					.colour(Colour::Red)
+					.symbol(DataSymbol::Circle, 6, 0)
+					.best_fit(BestFit::Linear {
+						gradient: 0.6,
+						y_intercept: 1.0,
+						colour: Colour::Blue,
+					}),
+			)
+			.render()
+			.unwrap()
+	};
+	// pixels partly covered by the points or the curve take a tint of their colour
+	let tints = |image: &image::ImageBuffer<image::Rgba<u8>, Vec<u8>>| {
+		image
+			.pixels()
+			.filter(|pixel| {
+				let [r, g, b, _] = pixel.0;
+				(r == 255 && g == b && g > 0 && g < 255) || (b == 255 && r == g && r > 0 && r < 255)
+			})
+			.count()
+	};
+	assert_eq!(0, tints(&render(false)));
+	assert!(tints(&render(true)) > 50);
+}
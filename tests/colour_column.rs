@@ -0,0 +1,87 @@
+//! Points coloured by a column of their csv rows
+
+use plotrs::{
+	canvas::plot::DataSymbol,
+	colours::{Colormap, Colour, COLOUR_CYCLE},
+	scatter::{DataSetBuilder, ScatterBuilder},
+};
+use std::fs;
+
+#[test]
+fn categories_replace_the_set_in_the_legend() {
+	let dir = std::env::temp_dir().join(format!("plotrs_colour_column_{}", std::process::id()));
+	fs::create_dir_all(&dir).unwrap();
+	let path = dir.join("data.csv");
+	fs::write(
+		&path,
+		"x,y,kind\n1,1,alpha\n2,4,beta\n3,2,alpha\n4,3,teal\n5,5,#ff00ff\n",
+	)
+	.unwrap();
+	let image = ScatterBuilder::new()
+		.canvas_size(400, 300)
+		.axis_labels("x", "y")
+		.legend(true)
+		.add_data_set(
+			DataSetBuilder::new("Kinds", path.to_str().unwrap(), 0, 1)
+				.colour(Colour::Pink)
+				.colour_csv_column(2)
+				.symbol(DataSymbol::Circle, 4, 1),
+		)
+		.render()
+		.unwrap();
+	fs::remove_dir_all(&dir).unwrap();
+	let contains = |colour: [u8; 4]| image.pixels().any(|pixel| pixel.0 == colour);
+	// categories take the colours of the cycle in order of first appearance
+	assert!(contains(Colour::get_pixel_colour(COLOUR_CYCLE[0])));
+	assert!(contains(Colour::get_pixel_colour(COLOUR_CYCLE[1])));
+	// cells naming a colour are drawn in it
+	assert!(contains([0, 128, 128, 255]));
+	assert!(contains([255, 0, 255, 255]));
+	// every point has a colour from its row and the set has no legend row of its own
+	assert!(!contains(Colour::get_pixel_colour(Colour::Pink)));
+}
+
+#[test]
+fn values_are_mapped_along_the_colormap_with_a_colour_bar() {
+	let dir = std::env::temp_dir().join(format!("plotrs_colour_by_{}", std::process::id()));
+	fs::create_dir_all(&dir).unwrap();
+	let path = dir.join("data.csv");
+	let csv: String = (0..10)
+		.map(|i| format!("{},{},{}\n", i, i * i, i * 10))
+		.collect();
+	fs::write(&path, format!("x,y,value\n{}", csv)).unwrap();
+	let render = |colour_bar: bool| {
+		let set = DataSetBuilder::new("Values", path.to_str().unwrap(), 0, 1).symbol(
+			DataSymbol::Circle,
+			4,
+			1,
+		);
+		let set = match colour_bar {
+			true => set.colour_by(2, Colormap::BlueRed),
+			false => set,
+		};
+		ScatterBuilder::new()
+			.canvas_size(400, 300)
+			.axis_labels("x", "y")
+			.add_data_set(set)
+			.render()
+			.unwrap()
+	};
+	let (plain, coloured) = (render(false), render(true));
+	fs::remove_dir_all(&dir).unwrap();
+	// the smallest and largest values take the ends of the colormap
+	for end in [0.0, 1.0] {
+		let colour = Colour::get_pixel_colour(Colormap::BlueRed.sample(end));
+		assert!(coloured.pixels().any(|pixel| pixel.0 == colour));
+	}
+	// the colour bar is given space to the right of the axes, which are shortened to make room for it
+	let rightmost_black = |image: &image::ImageBuffer<image::Rgba<u8>, Vec<u8>>| {
+		image
+			.enumerate_pixels()
+			.filter(|(_, y, pixel)| *y == 150 && pixel.0 == [0, 0, 0, 255])
+			.map(|(x, _, _)| x)
+			.max()
+			.unwrap()
+	};
+	assert!(rightmost_black(&plain) < rightmost_black(&coloured));
+}
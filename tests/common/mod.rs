@@ -0,0 +1,48 @@
+//! Helpers shared by the golden image tests
+
+use std::{fs, path::PathBuf, process::Command};
+
+/// Renders `config` from within `tests/fixtures/<fixture_dir>` with the CLI and compares the decoded
+/// pixels of the resulting `output_name` against `expected.png` in the same directory, so that encoder
+/// changes do not fail a test. Panics at the first pixel which differs
+pub fn render_and_compare(fixture_dir: &str, config: &str, output_name: &str) {
+	let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+		.join("tests/fixtures")
+		.join(fixture_dir);
+	let dir = std::env::temp_dir().join(format!("plotrs_{}_{}", fixture_dir, std::process::id()));
+	fs::create_dir_all(&dir).unwrap();
+	let status = Command::new(env!("CARGO_BIN_EXE_plotrs"))
+		.current_dir(&fixtures)
+		.args(["-g", "scatter", "-c", config, "-o"])
+		.arg(&dir)
+		.arg("-q")
+		.status()
+		.unwrap();
+	assert!(status.success());
+	let image = image::open(dir.join(output_name)).unwrap().to_rgba8();
+	fs::remove_dir_all(&dir).unwrap();
+	let expected = image::open(fixtures.join("expected.png"))
+		.unwrap()
+		.to_rgba8();
+	assert_eq!(
+		image.dimensions(),
+		expected.dimensions(),
+		"{} is not the size of tests/fixtures/{}/expected.png",
+		output_name,
+		fixture_dir
+	);
+	if let Some((x, y, pixel)) = image
+		.enumerate_pixels()
+		.find(|(x, y, pixel)| *pixel != expected.get_pixel(*x, *y))
+	{
+		panic!(
+			"{} differs from tests/fixtures/{}/expected.png at ({}, {}): {:?} != {:?}",
+			output_name,
+			fixture_dir,
+			x,
+			y,
+			pixel,
+			expected.get_pixel(x, y)
+		);
+	}
+}
@@ -0,0 +1,40 @@
+//! A host embedding the library can write text with a font of its own rather than one that is looked up
+
+use plotrs::{
+	error::PlotError,
+	scatter::{scatter_render, DataSetBuilder, RenderOptions, ScatterBuilder},
+};
+use std::fs;
+
+#[test]
+fn text_is_written_with_the_supplied_font() {
+	let scatter = ScatterBuilder::new()
+		.canvas_size(400, 300)
+		.title("Supplied font")
+		.axis_labels("x", "y")
+		.add_data_set(DataSetBuilder::from_points(
+			"data",
+			&[(1.0, 2.0), (3.0, 5.0)],
+		))
+		.build()
+		.unwrap();
+	let render = |font: Vec<u8>| {
+		let options = RenderOptions {
+			font: Some(font),
+			..RenderOptions::default()
+		};
+		scatter_render(&scatter, &options)
+	};
+	let font = fs::read(concat!(
+		env!("CARGO_MANIFEST_DIR"),
+		"/assets/fonts/DejaVuSans.ttf"
+	))
+	.unwrap();
+	let image = render(font).unwrap();
+	assert_eq!((400, 300), image.dimensions());
+	// the supplied bytes are used rather than falling back to a system or bundled font
+	assert!(matches!(
+		render(b"not a font".to_vec()),
+		Err(PlotError::Font)
+	));
+}
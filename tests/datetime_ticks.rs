@@ -0,0 +1,40 @@
+//! An x-axis of epoch timestamps is labelled with dates and times falling on whole calendar steps
+
+use plotrs::scatter::{scatter_render, DataSetBuilder, RenderOptions, ScatterBuilder};
+use std::fs;
+
+#[test]
+fn timestamps_snap_to_whole_hours() {
+	let dir = std::env::temp_dir().join(format!("plotrs_datetime_{}", std::process::id()));
+	fs::create_dir_all(&dir).unwrap();
+	let report = dir.join("geometry.ron");
+	// 2024-03-04 from 09:12:30 to 17:40:00 UTC
+	let scatter = ScatterBuilder::new()
+		.canvas_size(600, 400)
+		.axis_labels("Time (ms)", "Energy (kJ)")
+		.x_tick_datetime_format("auto")
+		.add_data_set(DataSetBuilder::from_points(
+			"data",
+			&[
+				(1709543550.0, 2.0),
+				(1709559000.0, 5.0),
+				(1709574000.0, 3.0),
+			],
+		))
+		.build()
+		.unwrap();
+	let options = RenderOptions {
+		geometry_report: Some(report.display().to_string()),
+		..RenderOptions::default()
+	};
+	scatter_render(&scatter, &options).unwrap();
+	let geometry = fs::read_to_string(&report).unwrap();
+	fs::remove_dir_all(&dir).unwrap();
+	let limits = geometry
+		.lines()
+		.find(|line| line.contains("x_data_min_max_limits"))
+		.unwrap()
+		.trim();
+	// from 08:00 to 19:00
+	assert_eq!("x_data_min_max_limits: (1709539200, 1709578800),", limits);
+}
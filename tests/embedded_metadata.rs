@@ -0,0 +1,93 @@
+//! `--embed-metadata` records the config and data a png was drawn from in its text chunks
+
+#![cfg(feature = "cli")]
+
+use std::{fs, path::PathBuf, process::Command};
+
+/// Directory holding `scatter.csv`
+fn fixtures() -> PathBuf {
+	PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/gzip")
+}
+
+/// Read the keyword and text of every text chunk in a png
+fn text_chunks(png_bytes: &[u8]) -> Vec<(String, String)> {
+	let reader = png::Decoder::new(png_bytes).read_info().unwrap();
+	let info = reader.info();
+	let mut chunks: Vec<(String, String)> = info
+		.uncompressed_latin1_text
+		.iter()
+		.map(|chunk| (chunk.keyword.clone(), chunk.text.clone()))
+		.collect();
+	for chunk in info.utf8_text.iter() {
+		chunks.push((chunk.keyword.clone(), chunk.get_text().unwrap()));
+	}
+	chunks
+}
+
+#[test]
+fn provenance_is_embedded_on_request() {
+	let dir = std::env::temp_dir().join(format!("plotrs_metadata_{}", std::process::id()));
+	fs::create_dir_all(&dir).unwrap();
+	let config = dir.join("scatter.ron");
+	let data_path = fixtures().join("scatter.csv");
+	fs::write(
+		&config,
+		format!(
+			r#"Scatter(
+				title: "Provenance",
+				canvas_pixel_size: (400, 300),
+				x_axis_label: "x",
+				y_axis_label: "y",
+				data_sets: [
+					DataSet(
+						data_path: {:?},
+						has_headers: true,
+						x_axis_csv_column: 0,
+						x_axis_error_bar_csv_column: None,
+						y_axis_csv_column: 1,
+						y_axis_error_bar_csv_column: None,
+						name: "data",
+						symbol: Cross,
+						symbol_radius: 3,
+						symbol_thickness: 0,
+						best_fit: None,
+					),
+				],
+			)"#,
+			data_path
+		),
+	)
+	.unwrap();
+	let render = |flags: &[&str]| {
+		let status = Command::new(env!("CARGO_BIN_EXE_plotrs"))
+			.args(["-g", "scatter", "-c"])
+			.arg(&config)
+			.arg("-o")
+			.arg(&dir)
+			.args(["-q", "--force"])
+			.args(flags)
+			.status()
+			.unwrap();
+		assert!(status.success());
+		fs::read(dir.join("provenance.png")).unwrap()
+	};
+	let plain = render(&[]);
+	let embedded = render(&["--embed-metadata"]);
+	fs::remove_dir_all(&dir).unwrap();
+	assert!(text_chunks(&plain).is_empty());
+	let chunks = text_chunks(&embedded);
+	let text = |keyword: &str| {
+		chunks
+			.iter()
+			.find(|(k, _)| k == keyword)
+			.map(|(_, text)| text.clone())
+			.unwrap_or_else(|| panic!("no {} chunk in {:?}", keyword, chunks))
+	};
+	assert_eq!(
+		format!("plotrs {}", env!("CARGO_PKG_VERSION")),
+		text("Software")
+	);
+	assert_eq!(config.display().to_string(), text("plotrs:config"));
+	assert!(text("plotrs:config-hash").starts_with("fnv1a-64:"));
+	assert_eq!(data_path.display().to_string(), text("plotrs:data"));
+}
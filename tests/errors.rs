@@ -0,0 +1,66 @@
+//! Failures are reported once by the binary with the detail of the `PlotError` behind them
+
+#![cfg(feature = "cli")]
+
+use std::{fs, path::Path, process::Command};
+
+/// Render a config, returning whether it succeeded along with everything logged
+fn render(dir: &Path, config: &str) -> (bool, String) {
+	let output = Command::new(env!("CARGO_BIN_EXE_plotrs"))
+		.current_dir(dir)
+		.args(["-g", "scatter", "-c", config, "-o", ".", "-q"])
+		.output()
+		.unwrap();
+	let mut log = String::from_utf8_lossy(&output.stdout).into_owned();
+	log.push_str(&String::from_utf8_lossy(&output.stderr));
+	(output.status.success(), log)
+}
+
+#[test]
+fn errors_name_the_file_row_and_column() {
+	let dir = std::env::temp_dir().join(format!("plotrs_errors_{}", std::process::id()));
+	fs::create_dir_all(&dir).unwrap();
+	fs::write(dir.join("data.csv"), "x,y\n1,2\n2,abc\n").unwrap();
+	fs::write(
+		dir.join("bad_cell.ron"),
+		r#"Scatter(
+			title: "Bad cell",
+			canvas_pixel_size: (400, 300),
+			x_axis_label: "x",
+			y_axis_label: "y",
+			data_sets: [
+				DataSet(
+					data_path: "data.csv",
+					has_headers: true,
+					x_axis_csv_column: 0,
+					x_axis_error_bar_csv_column: None,
+					y_axis_csv_column: 1,
+					y_axis_error_bar_csv_column: None,
+					name: "data",
+					symbol: Cross,
+					symbol_radius: 3,
+					symbol_thickness: 0,
+					best_fit: None,
+				),
+			],
+		)"#,
+	)
+	.unwrap();
+	let (is_success, bad_cell) = render(&dir, "bad_cell.ron");
+	assert!(!is_success);
+	let (is_missing_success, missing) = render(&dir, "missing.ron");
+	fs::remove_dir_all(&dir).unwrap();
+	assert!(
+		bad_cell.contains(
+			"Could not parse data in \"data.csv\", column 1, row 2 to a number for y axis"
+		),
+		"{}",
+		bad_cell
+	);
+	assert!(!is_missing_success);
+	assert!(
+		missing.contains("Failed to open .ron file at missing.ron, error: "),
+		"{}",
+		missing
+	);
+}
@@ -0,0 +1,74 @@
+//! An output that cannot be written is reported before the graph is drawn
+
+use plotrs::scatter::{scatter_builder, BuildOptions};
+use std::{fs, path::Path};
+
+/// Write a config titled `title` whose csv cannot be parsed, so drawing it would fail
+fn write_config(dir: &Path, title: &str) -> String {
+	fs::write(dir.join("data.csv"), "x,y\n1,one\n2,two\n").unwrap();
+	let config = dir.join("graph.ron");
+	fs::write(
+		&config,
+		format!(
+			r#"Scatter(
+				title: {:?},
+				canvas_pixel_size: (400, 300),
+				x_axis_label: "x",
+				y_axis_label: "y",
+				data_sets: [
+					DataSet(
+						data_path: {:?},
+						has_headers: true,
+						x_axis_csv_column: 0,
+						x_axis_error_bar_csv_column: None,
+						y_axis_csv_column: 1,
+						y_axis_error_bar_csv_column: None,
+						name: "y",
+						symbol: Cross,
+						symbol_radius: 3,
+						symbol_thickness: 0,
+						best_fit: None,
+					),
+				],
+			)"#,
+			title,
+			dir.join("data.csv").to_str().unwrap()
+		),
+	)
+	.unwrap();
+	config.to_str().unwrap().to_owned()
+}
+
+#[test]
+fn existing_output_is_refused_before_drawing() {
+	let dir = std::env::temp_dir().join(format!("plotrs_existing_{}", std::process::id()));
+	fs::create_dir_all(&dir).unwrap();
+	let config = write_config(&dir, "Graph");
+	let options = BuildOptions {
+		output: dir.to_str().unwrap().to_owned(),
+		..BuildOptions::default()
+	};
+	// without an existing image the unparsable csv is found while drawing
+	let drawing = scatter_builder(&config, &options).unwrap_err().to_string();
+	assert!(!drawing.contains("already exists"), "{}", drawing);
+	fs::write(dir.join("graph.png"), "").unwrap();
+	let refused = scatter_builder(&config, &options).unwrap_err().to_string();
+	fs::remove_dir_all(&dir).unwrap();
+	assert!(refused.contains("already exists"), "{}", refused);
+}
+
+#[test]
+fn empty_file_name_is_refused() {
+	let dir = std::env::temp_dir().join(format!("plotrs_unnamed_{}", std::process::id()));
+	fs::create_dir_all(&dir).unwrap();
+	let config = write_config(&dir, "");
+	let options = BuildOptions {
+		output: dir.to_str().unwrap().to_owned(),
+		..BuildOptions::default()
+	};
+	let error = scatter_builder(&config, &options).unwrap_err().to_string();
+	let hidden = dir.join(".png").exists();
+	fs::remove_dir_all(&dir).unwrap();
+	assert!(error.contains("file name is empty"), "{}", error);
+	assert!(!hidden);
+}
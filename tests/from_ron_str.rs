@@ -0,0 +1,86 @@
+//! Configs held in memory can be parsed without a file, with errors pointing at the offending line
+
+use plotrs::{
+	error::ConfigError,
+	scatter::{scatter_render, RenderOptions, Scatter},
+};
+use std::path::PathBuf;
+
+/// A config plotting the fixture csv, `separator` follows the title
+fn config(separator: &str) -> String {
+	let data_path =
+		PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/gzip/scatter.csv");
+	format!(
+		r#"Scatter(
+	title: "In memory"{}
+	canvas_pixel_size: (400, 300),
+	x_axis_label: "x",
+	y_axis_label: "y",
+	data_sets: [
+		DataSet(
+			data_path: {:?},
+			has_headers: true,
+			x_axis_csv_column: 0,
+			x_axis_error_bar_csv_column: None,
+			y_axis_csv_column: 1,
+			y_axis_error_bar_csv_column: None,
+			name: "data",
+			symbol: Cross,
+			symbol_radius: 3,
+			symbol_thickness: 0,
+			best_fit: None,
+		),
+	],
+)"#,
+		separator, data_path
+	)
+}
+
+#[test]
+fn config_string_and_reader_render() {
+	let scatter = Scatter::from_ron_str(&config(","))
+		.unwrap()
+		.prepare()
+		.unwrap();
+	let image = scatter_render(&scatter, &RenderOptions::default()).unwrap();
+	assert_eq!((400, 300), image.dimensions());
+	let from_reader = Scatter::from_reader(config(",").as_bytes())
+		.unwrap()
+		.prepare()
+		.unwrap();
+	assert!(image == scatter_render(&from_reader, &RenderOptions::default()).unwrap());
+}
+
+#[test]
+fn errors_name_the_line_where_parsing_stopped() {
+	match Scatter::from_ron_str(&config("")) {
+		Err(ConfigError::Syntax { line, column, .. }) => assert_eq!((3, 2), (line, column)),
+		other => panic!("expected a syntax error, got {:?}", other),
+	}
+	match Scatter::from_ron_str(r#"Scatter(title: "Untitled")"#) {
+		Err(ConfigError::Content(message)) => {
+			assert!(message.contains("canvas_pixel_size"), "{}", message)
+		}
+		other => panic!("expected a missing field, got {:?}", other),
+	}
+}
+
+#[test]
+fn resolutions_are_written_without_some() {
+	let scatter = Scatter::from_ron_str(&config(
+		",\n\tx_axis_resolution: 11,\n\ty_axis_resolution: 5,",
+	))
+	.unwrap();
+	let serialised = scatter.to_ron_string().unwrap();
+	assert!(
+		serialised.contains("x_axis_resolution: 11,"),
+		"{}",
+		serialised
+	);
+	assert!(
+		serialised.contains("y_axis_resolution: 5,"),
+		"{}",
+		serialised
+	);
+	assert_eq!(scatter, Scatter::from_ron_str(&serialised).unwrap());
+}
@@ -0,0 +1,13 @@
+//! Golden image of a sine-like series whose area is filled with a gradient towards the x-axis
+
+#![cfg(feature = "cli")]
+
+mod common;
+
+/// Renders `tests/fixtures/gradient/sine.ron`. No text is drawn so the image does not depend upon the
+/// fonts installed. After an intended change regenerate `expected.png` with
+/// `plotrs -g scatter -c sine.ron -o .` from within the directory
+#[test]
+fn sine_gradient_matches_golden_image() {
+	common::render_and_compare("gradient", "sine.ron", "gradient_sine.png");
+}
@@ -0,0 +1,86 @@
+//! Gzip compressed csv files are read as though they were plain
+
+use plotrs::data::load_data;
+#[cfg(feature = "cli")]
+use std::fs;
+use std::path::PathBuf;
+
+/// Directory holding `scatter.csv` alongside an identical `scatter.csv.gz`
+fn fixtures() -> PathBuf {
+	PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/gzip")
+}
+
+#[test]
+fn gzip_records_match_plain() {
+	let plain = load_data(fixtures().join("scatter.csv").to_str().unwrap(), true, ",").unwrap();
+	let gzip = load_data(
+		fixtures().join("scatter.csv.gz").to_str().unwrap(),
+		true,
+		",",
+	)
+	.unwrap();
+	assert_eq!(20, plain.len());
+	assert_eq!(plain, gzip);
+}
+
+/// Renders the same graph from the plain and compressed csv, both the processed values and the image
+/// should be identical
+#[cfg(feature = "cli")]
+#[test]
+fn gzip_graph_matches_plain() {
+	let dir = std::env::temp_dir().join(format!("plotrs_gzip_{}", std::process::id()));
+	let render = |data_file: &str| -> (Vec<u8>, Vec<u8>) {
+		let out = dir.join(data_file);
+		fs::create_dir_all(&out).unwrap();
+		let config = out.join("scatter.ron");
+		fs::write(
+			&config,
+			format!(
+				r#"Scatter(
+					title: "Gzip",
+					canvas_pixel_size: (400, 300),
+					x_axis_label: "x",
+					y_axis_label: "y",
+					data_sets: [
+						DataSet(
+							data_path: {:?},
+							has_headers: true,
+							x_axis_csv_column: 0,
+							x_axis_error_bar_csv_column: None,
+							y_axis_csv_column: 1,
+							y_axis_error_bar_csv_column: None,
+							name: "data",
+							symbol: Cross,
+							symbol_radius: 3,
+							symbol_thickness: 0,
+							best_fit: None,
+						),
+					],
+				)"#,
+				fixtures().join(data_file)
+			),
+		)
+		.unwrap();
+		let status = std::process::Command::new(env!("CARGO_BIN_EXE_plotrs"))
+			.args(["-g", "scatter", "-c"])
+			.arg(&config)
+			.arg("-o")
+			.arg(&out)
+			.arg("--dump-processed-data")
+			.arg(&out)
+			.arg("-q")
+			.status()
+			.unwrap();
+		assert!(status.success(), "rendering {} failed", data_file);
+		(
+			fs::read(out.join("data.csv")).unwrap(),
+			fs::read(out.join("gzip.png")).unwrap(),
+		)
+	};
+	let (plain_values, plain_image) = render("scatter.csv");
+	let (gzip_values, gzip_image) = render("scatter.csv.gz");
+	fs::remove_dir_all(&dir).unwrap();
+	assert!(!plain_values.is_empty());
+	assert_eq!(plain_values, gzip_values);
+	assert_eq!(plain_image, gzip_image);
+}
@@ -0,0 +1,60 @@
+//! Axes, scale markers and their values can be hidden, giving their space to the plot
+
+use plotrs::{
+	canvas::Chrome,
+	colours::Colour,
+	scatter::{scatter_render, DataSetBuilder, RenderOptions, ScatterBuilder},
+};
+use std::fs;
+
+/// Render a `PlotOnly` graph and read the bottom left corner of its plot area from the geometry report,
+/// counting the black pixels drawn
+fn render(name: &str, builder: ScatterBuilder) -> (String, usize) {
+	let dir = std::env::temp_dir().join(format!("plotrs_hidden_{}_{}", name, std::process::id()));
+	fs::create_dir_all(&dir).unwrap();
+	let report = dir.join("geometry.ron");
+	let scatter = builder
+		.canvas_size(400, 300)
+		.axis_labels("Time (ms)", "Energy (kJ)")
+		.chrome(Chrome::PlotOnly)
+		.add_data_set(
+			DataSetBuilder::from_points("data", &[(1.0, 1.0), (2.0, 3.0), (4.0, 2.0)])
+				.colour(Colour::Blue),
+		)
+		.build()
+		.unwrap();
+	let options = RenderOptions {
+		geometry_report: Some(report.display().to_string()),
+		..RenderOptions::default()
+	};
+	let canvas = scatter_render(&scatter, &options).unwrap();
+	let geometry = fs::read_to_string(&report).unwrap();
+	fs::remove_dir_all(&dir).unwrap();
+	let axis_min_pixel = geometry
+		.lines()
+		.find(|line| line.contains("axis_min_pixel"))
+		.unwrap()
+		.trim()
+		.to_owned();
+	let black = canvas
+		.pixels()
+		.filter(|pixel| pixel.0 == [0, 0, 0, 255])
+		.count();
+	(axis_min_pixel, black)
+}
+
+#[test]
+fn hidden_tick_labels_give_their_space_to_the_plot() {
+	let (shown, _) = render("shown", ScatterBuilder::new());
+	let (hidden, _) = render("labels", ScatterBuilder::new().show_tick_labels(false));
+	assert!(shown != hidden);
+	let (bare, black) = render(
+		"bare",
+		ScatterBuilder::new()
+			.show_axes(false, false)
+			.show_ticks(false),
+	);
+	// with nothing around the plot it reaches the border of the canvas
+	assert!(hidden != bare);
+	assert_eq!(0, black);
+}
@@ -0,0 +1,73 @@
+//! A data set excluded from the bounds must not stretch the axes and has its outlying points clipped
+
+#![cfg(feature = "cli")]
+
+use std::{fs, process::Command};
+
+#[test]
+fn excluded_set_does_not_stretch_the_axes() {
+	let dir = std::env::temp_dir().join(format!("plotrs_bounds_{}", std::process::id()));
+	fs::create_dir_all(&dir).unwrap();
+	// a primary series between 1 and 10 alongside a noisy one reaching 1000
+	let csv: String = (1..=10)
+		.map(|i| format!("{},{},{}\n", i, i, if i == 5 { 1000 } else { i + 1 }))
+		.collect();
+	fs::write(dir.join("data.csv"), format!("x,primary,noisy\n{}", csv)).unwrap();
+	let data_set = |name: &str, column: usize, include_in_bounds: &str| {
+		format!(
+			r#"DataSet(
+				data_path: "data.csv",
+				has_headers: true,
+				x_axis_csv_column: 0,
+				x_axis_error_bar_csv_column: None,
+				y_axis_csv_column: {},
+				y_axis_error_bar_csv_column: None,
+				name: {:?},
+				symbol: Cross,
+				symbol_radius: 3,
+				symbol_thickness: 0,
+				best_fit: None,
+				include_in_bounds: {},
+			)"#,
+			column, name, include_in_bounds
+		)
+	};
+	fs::write(
+		dir.join("bounds.ron"),
+		format!(
+			r#"Scatter(
+				title: "Bounds",
+				canvas_pixel_size: (400, 300),
+				x_axis_label: "x",
+				y_axis_label: "y",
+				data_sets: [{}, {}],
+			)"#,
+			data_set("primary", 1, "None"),
+			data_set("noisy", 2, "Some(false)")
+		),
+	)
+	.unwrap();
+	let status = Command::new(env!("CARGO_BIN_EXE_plotrs"))
+		.current_dir(&dir)
+		.args(["-g", "scatter", "-c", "bounds.ron", "-o", "."])
+		.args(["--geometry-report", "geometry.ron"])
+		.args(["--dump-processed-data", "."])
+		.arg("-q")
+		.status()
+		.unwrap();
+	assert!(status.success());
+	let geometry = fs::read_to_string(dir.join("geometry.ron")).unwrap();
+	let noisy = fs::read_to_string(dir.join("noisy.csv")).unwrap();
+	fs::remove_dir_all(&dir).unwrap();
+	// the y-axis is sized by the primary series alone, the largest value of 10 with a 10% buffer rounded
+	// outward to a nice step of 2
+	let y_limits = geometry
+		.lines()
+		.find(|line| line.contains("y_data_min_max_limits"))
+		.unwrap()
+		.to_owned();
+	assert!(y_limits.contains("(0, 12)"), "{}", y_limits);
+	// only the outlier of the noisy series is outside of the axes
+	let clipped: Vec<&str> = noisy.lines().filter(|row| row.ends_with("true")).collect();
+	assert_eq!(vec!["5,1000,,,true"], clipped);
+}
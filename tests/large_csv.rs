@@ -0,0 +1,86 @@
+//! Smoke test streaming a large csv, measuring peak heap usage against loading every record
+
+use plotrs::data::{for_each_record, load_data};
+use std::{
+	alloc::{GlobalAlloc, Layout, System},
+	fs::{self, File},
+	io::{BufWriter, Write},
+	sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Wraps the system allocator to track the current and peak number of bytes allocated
+struct PeakAllocator;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for PeakAllocator {
+	unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+		let ptr = System.alloc(layout);
+		if !ptr.is_null() {
+			let current = CURRENT.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+			PEAK.fetch_max(current, Ordering::SeqCst);
+		}
+		ptr
+	}
+	unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+		System.dealloc(ptr, layout);
+		CURRENT.fetch_sub(layout.size(), Ordering::SeqCst);
+	}
+}
+
+#[global_allocator]
+static ALLOCATOR: PeakAllocator = PeakAllocator;
+
+/// Run `f` returning how many bytes above the starting point were allocated at its peak
+fn measure_peak<F: FnOnce()>(f: F) -> usize {
+	let start = CURRENT.load(Ordering::SeqCst);
+	PEAK.store(start, Ordering::SeqCst);
+	f();
+	PEAK.load(Ordering::SeqCst) - start
+}
+
+#[test]
+fn streaming_large_csv_has_bounded_peak_memory() {
+	let rows = 200_000;
+	let path = std::env::temp_dir().join(format!("plotrs_large_{}.csv", std::process::id()));
+	{
+		let mut writer = BufWriter::new(File::create(&path).unwrap());
+		writeln!(writer, "x,y,uy").unwrap();
+		for i in 0..rows {
+			writeln!(writer, "{},{},{}", i, i as f32 * 0.5, 0.1).unwrap();
+		}
+	}
+	let file_size = fs::metadata(&path).unwrap().len() as usize;
+	let path_str = path.to_str().unwrap();
+
+	let mut streamed_rows = 0;
+	let mut sum = 0.0;
+	let streamed_peak = measure_peak(|| {
+		for_each_record(path_str, true, ",", |_, record| {
+			streamed_rows += 1;
+			sum += record.get(1).unwrap().parse::<f32>().unwrap();
+			Ok(())
+		})
+		.unwrap()
+	});
+	let mut loaded_rows = 0;
+	let loaded_peak = measure_peak(|| loaded_rows = load_data(path_str, true, ",").unwrap().len());
+	fs::remove_file(&path).unwrap();
+
+	assert_eq!(rows, streamed_rows);
+	assert_eq!(rows, loaded_rows);
+	assert!(sum > 0.0);
+	// Streaming only holds the reader buffer and a single record
+	assert!(
+		streamed_peak < 64 * 1024,
+		"streamed peak {} bytes",
+		streamed_peak
+	);
+	assert!(
+		loaded_peak > file_size,
+		"loaded peak {} bytes for a {} byte file",
+		loaded_peak,
+		file_size
+	);
+}
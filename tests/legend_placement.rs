@@ -0,0 +1,52 @@
+//! The legend is kept clear of the plotted data
+
+use plotrs::{
+	colours::Colour,
+	scatter::{DataSetBuilder, ScatterBuilder},
+};
+
+#[test]
+fn data_is_never_drawn_through_an_outside_legend() {
+	let red = Colour::get_pixel_colour(Colour::Red);
+	let blue = Colour::get_pixel_colour(Colour::Blue);
+	for (width, height) in [(400, 300), (640, 480), (1000, 400)] {
+		// a column of points down the right edge of the plot, beside the legend
+		let edge: Vec<(f32, f32)> = (0..=10).map(|y| (10.0, y as f32)).collect();
+		let image = ScatterBuilder::new()
+			.canvas_size(width, height)
+			.axis_labels("x", "y")
+			.legend(true)
+			.add_data_set(
+				DataSetBuilder::from_points("edge", &edge)
+					.colour(Colour::Red)
+					.show_in_legend(false),
+			)
+			.add_data_set(
+				DataSetBuilder::from_points("A rather long name for a single point", &[(0.0, 5.0)])
+					.colour(Colour::Blue),
+			)
+			.render()
+			.unwrap();
+		// the blue point is at the far left of the plot so blue in the right half is the legend symbol
+		let legend_left = image
+			.enumerate_pixels()
+			.filter(|(x, _, pixel)| *x > width / 2 && pixel.0 == blue)
+			.map(|(x, _, _)| x)
+			.min()
+			.expect("the legend is drawn");
+		let data_right = image
+			.enumerate_pixels()
+			.filter(|(_, _, pixel)| pixel.0 == red)
+			.map(|(x, _, _)| x)
+			.max()
+			.unwrap();
+		assert!(
+			data_right < legend_left,
+			"{}x{}: data reaches column {} but the legend starts at {}",
+			width,
+			height,
+			data_right,
+			legend_left
+		);
+	}
+}
@@ -0,0 +1,49 @@
+//! Axes without a resolution are rounded outward to a nice step so their labels come out round
+
+use plotrs::scatter::{scatter_render, DataSetBuilder, RenderOptions, ScatterBuilder};
+use std::fs;
+
+#[test]
+fn omitted_resolutions_round_the_limits_to_a_nice_step() {
+	let dir = std::env::temp_dir().join(format!("plotrs_nice_{}", std::process::id()));
+	fs::create_dir_all(&dir).unwrap();
+	let report = dir.join("geometry.ron");
+	let render = |x_axis_resolution: Option<u32>| {
+		let scatter = ScatterBuilder::new()
+			.canvas_size(400, 300)
+			.axis_labels("Time (ms)", "Energy (kJ)")
+			.resolution(x_axis_resolution, None)
+			.add_data_set(DataSetBuilder::from_points(
+				"data",
+				&[(0.0, 3.0), (40.0, 7.0), (87.0, 23.0)],
+			))
+			.build()
+			.unwrap();
+		let options = RenderOptions {
+			geometry_report: Some(report.display().to_string()),
+			..RenderOptions::default()
+		};
+		scatter_render(&scatter, &options).unwrap();
+		let geometry = fs::read_to_string(&report).unwrap();
+		let field = |name: &str| {
+			geometry
+				.lines()
+				.find(|line| line.contains(name))
+				.unwrap()
+				.trim()
+				.to_owned()
+		};
+		(
+			field("x_data_min_max_limits"),
+			field("y_data_min_max_limits"),
+		)
+	};
+	// 0 to 87 with a 10% buffer rounded outward to a step of 10, and 3 to 23 to a step of 5
+	let (x_limits, y_limits) = render(None);
+	assert_eq!("x_data_min_max_limits: (0, 100),", x_limits);
+	assert_eq!("y_data_min_max_limits: (0, 25),", y_limits);
+	// an explicit resolution keeps the buffered bounds
+	let (x_limits, _) = render(Some(5));
+	assert_eq!("x_data_min_max_limits: (0, 95),", x_limits);
+	fs::remove_dir_all(&dir).unwrap();
+}
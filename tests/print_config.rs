@@ -0,0 +1,52 @@
+//! The resolved config printed by `--print-config` is ron which draws the same graph
+
+use plotrs::scatter::{
+	scatter_render, scatter_resolved_config, BuildOptions, RenderOptions, Scatter,
+};
+use std::{fs, path::PathBuf};
+
+#[test]
+fn resolved_config_reads_back_and_draws_the_same_graph() {
+	let dir = std::env::temp_dir().join(format!("plotrs_print_config_{}", std::process::id()));
+	fs::create_dir_all(&dir).unwrap();
+	let data_path =
+		PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/gzip/scatter.csv");
+	let path = dir.join("config.ron");
+	let config = format!(
+		r#"Scatter(
+	title: "Resolved",
+	canvas_pixel_size: (400, 300),
+	x_axis_label: "x",
+	y_axis_label: "y",
+	data_sets: [
+		DataSet(
+			data_path: {:?},
+			has_headers: true,
+			x_axis_csv_column: 0,
+			x_axis_error_bar_csv_column: None,
+			y_axis_csv_column: 1,
+			y_axis_error_bar_csv_column: None,
+			name: "data",
+			symbol: Cross,
+			symbol_radius: 3,
+			symbol_thickness: 0,
+			best_fit: None,
+		),
+	],
+)"#,
+		data_path
+	);
+	fs::write(&path, &config).unwrap();
+	let resolved =
+		scatter_resolved_config(path.to_str().unwrap(), &BuildOptions::default()).unwrap();
+	fs::remove_dir_all(&dir).unwrap();
+	// the chosen resolutions and colours are written out as a config would give them
+	assert!(resolved.starts_with("Scatter("), "{}", resolved);
+	assert!(resolved.contains("x_axis_resolution: "), "{}", resolved);
+	assert!(resolved.contains("colour: "), "{}", resolved);
+	let render = |config: &str| {
+		let scatter = Scatter::from_ron_str(config).unwrap().prepare().unwrap();
+		scatter_render(&scatter, &RenderOptions::default()).unwrap()
+	};
+	assert!(render(&config) == render(&resolved));
+}
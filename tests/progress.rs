@@ -0,0 +1,37 @@
+//! A progress hook is told of each stage of drawing in order
+
+use plotrs::{
+	canvas::progress::RenderStage,
+	scatter::{scatter_render, DataSetBuilder, RenderOptions, ScatterBuilder},
+};
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn stages_are_reported_in_order() {
+	let stages = Arc::new(Mutex::new(Vec::new()));
+	let recorded = stages.clone();
+	let options = RenderOptions {
+		progress: Some(Arc::new(move |stage: RenderStage| {
+			recorded.lock().unwrap().push(stage)
+		})),
+		..RenderOptions::default()
+	};
+	let scatter = ScatterBuilder::new()
+		.canvas_size(400, 300)
+		.axis_labels("Time (ms)", "Energy (kJ)")
+		.add_data_set(DataSetBuilder::from_points("a", &[(0.0, 1.0), (1.0, 2.0)]))
+		.add_data_set(DataSetBuilder::from_points("b", &[(0.0, 2.0), (1.0, 3.0)]))
+		.build()
+		.unwrap();
+	scatter_render(&scatter, &options).unwrap();
+	assert_eq!(
+		vec![
+			RenderStage::LoadingData,
+			RenderStage::ComputingBounds,
+			RenderStage::DrawingAxes,
+			RenderStage::PlottingSet { index: 0, count: 2 },
+			RenderStage::PlottingSet { index: 1, count: 2 },
+		],
+		*stages.lock().unwrap()
+	);
+}
@@ -0,0 +1,82 @@
+//! The same config must always produce a byte-identical image, in particular features backed by maps or
+//! hashes must not depend on the randomised iteration order of a `HashMap`
+
+#![cfg(feature = "cli")]
+
+use std::{fs, path::PathBuf, process::Command};
+
+/// Directory holding `scatter.csv`
+fn fixtures() -> PathBuf {
+	PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/gzip")
+}
+
+/// Renders a graph using every map or hash based feature in separate processes, which each seed their
+/// `HashMap`s differently, and compares the images
+#[test]
+fn map_based_features_render_identically() {
+	let dir = std::env::temp_dir().join(format!("plotrs_reproducible_{}", std::process::id()));
+	fs::create_dir_all(&dir).unwrap();
+	let config = dir.join("scatter.ron");
+	let data_set = |name: &str, best_fit: &str| {
+		format!(
+			r#"DataSet(
+				data_path: {:?},
+				has_headers: true,
+				x_axis_csv_column: 0,
+				x_axis_error_bar_csv_column: None,
+				y_axis_csv_column: 1,
+				y_axis_error_bar_csv_column: None,
+				name: {:?},
+				symbol: Cross,
+				symbol_radius: 3,
+				symbol_thickness: 0,
+				best_fit: {},
+			)"#,
+			fixtures().join("scatter.csv"),
+			name,
+			best_fit
+		)
+	};
+	fs::write(
+		&config,
+		format!(
+			r#"Scatter(
+				title: "Reproducible",
+				canvas_pixel_size: (600, 400),
+				x_axis_label: "x",
+				y_axis_label: "y",
+				has_legend: true,
+				colour_assignment: Some(NameHash),
+				data_sets: [{}, {}, {}],
+			)"#,
+			// (x - 5)^7 / 10000 + 5 expanded, its large terms cancel one another so the rounding of the
+			// sum, and so the pixels drawn, changes with the order the terms are added in
+			data_set(
+				"polynomial",
+				"Some(GenericPolynomial(coefficients: {0: -2.8125, 5: 0.0525, 2: -6.5625, 7: 0.0001, 4: -0.4375, 1: 10.9375, 6: -0.0035, 3: 2.1875}, colour: Red))"
+			),
+			data_set("temperature", "None"),
+			data_set("pressure", "None"),
+		),
+	)
+	.unwrap();
+	let render = |name: &str| -> Vec<u8> {
+		let out = dir.join(name);
+		fs::create_dir_all(&out).unwrap();
+		let status = Command::new(env!("CARGO_BIN_EXE_plotrs"))
+			.args(["-g", "scatter", "-c"])
+			.arg(&config)
+			.arg("-o")
+			.arg(&out)
+			.arg("-q")
+			.status()
+			.unwrap();
+		assert!(status.success(), "rendering {} failed", name);
+		fs::read(out.join("reproducible.png")).unwrap()
+	};
+	let first = render("first");
+	let second = render("second");
+	fs::remove_dir_all(&dir).unwrap();
+	assert!(!first.is_empty());
+	assert!(first == second, "rendering the same config twice differed");
+}
@@ -0,0 +1,14 @@
+//! Golden image of a depth profile drawn against a reversed y-axis, its error bars, best fit and grid
+//! following the flip
+
+#![cfg(feature = "cli")]
+
+mod common;
+
+/// Renders `tests/fixtures/reversed_axes/depth.ron`. No text is drawn so the image does not depend upon
+/// the fonts installed. After an intended change regenerate `expected.png` with
+/// `plotrs -g scatter -c depth.ron -o .` from within the directory and rename `reversed_depth.png`
+#[test]
+fn reversed_depth_matches_golden_image() {
+	common::render_and_compare("reversed_axes", "depth.ron", "reversed_depth.png");
+}
@@ -0,0 +1,27 @@
+//! `plotrs scaffold scatter` prints a config which renders as written
+
+#![cfg(feature = "cli")]
+
+use std::{fs, process::Command};
+
+#[test]
+fn scaffold_renders_once_data_exists() {
+	let dir = std::env::temp_dir().join(format!("plotrs_scaffold_{}", std::process::id()));
+	fs::create_dir_all(&dir).unwrap();
+	let output = Command::new(env!("CARGO_BIN_EXE_plotrs"))
+		.args(["scaffold", "scatter"])
+		.output()
+		.unwrap();
+	assert!(output.status.success());
+	fs::write(dir.join("scatter.ron"), output.stdout).unwrap();
+	fs::write(dir.join("data.csv"), "x,y\n1,2\n2,4\n3,5\n4,9\n").unwrap();
+	let status = Command::new(env!("CARGO_BIN_EXE_plotrs"))
+		.current_dir(&dir)
+		.args(["-g", "scatter", "-c", "scatter.ron", "-o", ".", "-q"])
+		.status()
+		.unwrap();
+	let rendered = dir.join("energy_against_time.png").exists();
+	fs::remove_dir_all(&dir).unwrap();
+	assert!(status.success());
+	assert!(rendered);
+}
@@ -0,0 +1,118 @@
+//! Data sets in a second unit are drawn against a y-axis along the right edge of the plot
+
+use plotrs::{
+	canvas::axes::YAxis,
+	error::PlotError,
+	scatter::{scatter_render, DataSetBuilder, RenderOptions, ScatterBuilder},
+};
+use std::fs;
+
+#[test]
+fn secondary_axis_is_scaled_to_its_own_sets() {
+	let dir = std::env::temp_dir().join(format!("plotrs_y2_{}", std::process::id()));
+	fs::create_dir_all(&dir).unwrap();
+	let report = dir.join("geometry.ron");
+	let scatter = ScatterBuilder::new()
+		.canvas_size(400, 300)
+		.axis_labels("Hour", "Temperature (C)")
+		.resolution(Some(4), Some(5))
+		.y2_axis("Humidity (%)", None)
+		.add_data_set(DataSetBuilder::from_points(
+			"temperature",
+			&[(0.0, 12.0), (6.0, 21.0), (12.0, 25.0), (18.0, 15.0)],
+		))
+		.add_data_set(
+			DataSetBuilder::from_points(
+				"humidity",
+				&[(0.0, 85.0), (6.0, 60.0), (12.0, 35.0), (18.0, 70.0)],
+			)
+			.y_axis(YAxis::Secondary),
+		)
+		.build()
+		.unwrap();
+	let options = RenderOptions {
+		geometry_report: Some(report.display().to_string()),
+		..RenderOptions::default()
+	};
+	scatter_render(&scatter, &options).unwrap();
+	let geometry = fs::read_to_string(&report).unwrap();
+	fs::remove_dir_all(&dir).unwrap();
+	let field = |name: &str| {
+		geometry
+			.lines()
+			.find(|line| line.contains(name))
+			.unwrap()
+			.trim()
+			.to_owned()
+	};
+	// humidity does not stretch the primary axis
+	assert_eq!(
+		"y_data_min_max_limits: (10, 27),",
+		field("y_data_min_max_limits")
+	);
+	// humidity from 35 to 85 is buffered and divided as the primary axis, in steps of 20
+	assert_eq!(
+		"y2_data_min_max_limits: Some((20, 120)),",
+		field("y2_data_min_max_limits")
+	);
+}
+
+#[test]
+fn secondary_sets_are_dumped_against_the_secondary_axis() {
+	let dir = std::env::temp_dir().join(format!("plotrs_y2_dump_{}", std::process::id()));
+	fs::create_dir_all(&dir).unwrap();
+	let scatter = ScatterBuilder::new()
+		.canvas_size(400, 300)
+		.axis_labels("Hour", "Temperature (C)")
+		.resolution(Some(4), Some(5))
+		.y2_axis("Humidity (%)", None)
+		.add_data_set(DataSetBuilder::from_points(
+			"temperature",
+			&[(0.0, 12.0), (6.0, 21.0)],
+		))
+		.add_data_set(
+			DataSetBuilder::from_points("humidity", &[(0.0, 85.0), (6.0, 60.0)])
+				.y_axis(YAxis::Secondary),
+		)
+		.build()
+		.unwrap();
+	let options = RenderOptions {
+		dump_processed_data: Some(dir.display().to_string()),
+		..RenderOptions::default()
+	};
+	scatter_render(&scatter, &options).unwrap();
+	let humidity = fs::read_to_string(dir.join("humidity.csv")).unwrap();
+	fs::remove_dir_all(&dir).unwrap();
+	// humidity lies beyond the primary axis but within the secondary one
+	assert_eq!("x,y,ux,uy,clipped\n0,85,,,false\n6,60,,,false\n", humidity);
+}
+
+#[test]
+fn a_set_must_use_the_primary_axis() {
+	let result = ScatterBuilder::new()
+		.y2_axis("Humidity (%)", None)
+		.add_data_set(
+			DataSetBuilder::from_points("humidity", &[(0.0, 85.0), (6.0, 60.0)])
+				.y_axis(YAxis::Secondary),
+		)
+		.build();
+	assert!(matches!(result, Err(PlotError::Config(_))));
+}
+
+#[test]
+fn secondary_axis_needs_positive_x_values() {
+	let scatter = ScatterBuilder::new()
+		.y2_axis("Humidity (%)", None)
+		.add_data_set(DataSetBuilder::from_points(
+			"temperature",
+			&[(-6.0, 12.0), (-1.0, 21.0)],
+		))
+		.add_data_set(
+			DataSetBuilder::from_points("humidity", &[(-6.0, 85.0), (-1.0, 60.0)])
+				.y_axis(YAxis::Secondary),
+		)
+		.build()
+		.unwrap();
+	let result = scatter_render(&scatter, &RenderOptions::default());
+	assert!(matches!(result, Err(PlotError::Render(_))));
+}
@@ -0,0 +1,64 @@
+//! An output path of `-` writes the image to stdout rather than a file
+
+#![cfg(feature = "cli")]
+
+use std::{fs, path::PathBuf, process::Command};
+
+/// Directory holding `scatter.csv`
+fn fixtures() -> PathBuf {
+	PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/gzip")
+}
+
+/// The image written to stdout is identical to the one saved to disk and no logging is mixed into it
+#[test]
+fn stdout_image_matches_file() {
+	let dir = std::env::temp_dir().join(format!("plotrs_stdout_{}", std::process::id()));
+	fs::create_dir_all(&dir).unwrap();
+	let config = dir.join("scatter.ron");
+	fs::write(
+		&config,
+		format!(
+			r#"Scatter(
+				title: "Piped",
+				canvas_pixel_size: (400, 300),
+				x_axis_label: "x",
+				y_axis_label: "y",
+				data_sets: [
+					DataSet(
+						data_path: {:?},
+						has_headers: true,
+						x_axis_csv_column: 0,
+						x_axis_error_bar_csv_column: None,
+						y_axis_csv_column: 1,
+						y_axis_error_bar_csv_column: None,
+						name: "data",
+						symbol: Cross,
+						symbol_radius: 3,
+						symbol_thickness: 0,
+						best_fit: None,
+					),
+				],
+			)"#,
+			fixtures().join("scatter.csv")
+		),
+	)
+	.unwrap();
+	let render = |output: &str| {
+		Command::new(env!("CARGO_BIN_EXE_plotrs"))
+			.args(["-g", "scatter", "-c"])
+			.arg(&config)
+			.args(["-o", output])
+			.output()
+			.unwrap()
+	};
+	let piped = render("-");
+	let saved = render(dir.to_str().unwrap());
+	let image = fs::read(dir.join("piped.png")).unwrap();
+	fs::remove_dir_all(&dir).unwrap();
+	assert!(piped.status.success());
+	assert!(saved.status.success());
+	// the default verbosity logs progress, which must not end up in the image
+	assert!(!piped.stderr.is_empty());
+	assert!(saved.stdout.is_empty());
+	assert_eq!(image, piped.stdout);
+}
@@ -0,0 +1,82 @@
+//! Points given a symbol by a column of their csv rows
+
+use plotrs::{
+	canvas::plot::DataSymbol,
+	colours::Colour,
+	scatter::{DataSetBuilder, ScatterBuilder},
+};
+use std::{collections::BTreeMap, fs};
+
+#[test]
+fn categories_match_a_set_for_each_symbol() {
+	let dir = std::env::temp_dir().join(format!("plotrs_symbol_column_{}", std::process::id()));
+	fs::create_dir_all(&dir).unwrap();
+	let path = dir.join("data.csv");
+	fs::write(
+		&path,
+		"x,y,status\n1,1,pass\n2,6,fail\n3,2,pass\n5,4,Square\n6,7,fail\n",
+	)
+	.unwrap();
+	let path = path.to_str().unwrap();
+	let symbol_map = BTreeMap::from([
+		(String::from("pass"), DataSymbol::Circle),
+		(String::from("fail"), DataSymbol::Cross),
+	]);
+	let graph = || {
+		ScatterBuilder::new()
+			.canvas_size(400, 300)
+			.axis_labels("x", "y")
+			.legend(true)
+	};
+	let column = graph()
+		.add_data_set(
+			DataSetBuilder::new("Runs", path, 0, 1)
+				.colour(Colour::Blue)
+				.symbol(DataSymbol::Point, 4, 1)
+				.symbol_csv_column(2, Some(symbol_map)),
+		)
+		.render()
+		.unwrap();
+	// the same points split into a set per symbol, named after their cells
+	let split = [
+		("pass", DataSymbol::Circle),
+		("fail", DataSymbol::Cross),
+		("Square", DataSymbol::Square),
+	]
+	.iter()
+	.fold(graph(), |graph, (name, symbol)| {
+		let rows = match *name {
+			"pass" => "x,y\n1,1\n3,2\n",
+			"fail" => "x,y\n2,6\n6,7\n",
+			_ => "x,y\n5,4\n",
+		};
+		let set_path = dir.join(format!("{}.csv", name));
+		fs::write(&set_path, rows).unwrap();
+		graph.add_data_set(
+			DataSetBuilder::new(name, set_path.to_str().unwrap(), 0, 1)
+				.colour(Colour::Blue)
+				.symbol(*symbol, 4, 1),
+		)
+	})
+	.render()
+	.unwrap();
+	fs::remove_dir_all(&dir).unwrap();
+	assert!(column == split);
+}
+
+#[test]
+fn unknown_cells_are_an_error() {
+	let dir = std::env::temp_dir().join(format!("plotrs_symbol_unknown_{}", std::process::id()));
+	fs::create_dir_all(&dir).unwrap();
+	let path = dir.join("data.csv");
+	fs::write(&path, "x,y,status\n1,1,Circle\n2,2,pending\n").unwrap();
+	let result = ScatterBuilder::new()
+		.axis_labels("x", "y")
+		.add_data_set(
+			DataSetBuilder::new("Runs", path.to_str().unwrap(), 0, 1).symbol_csv_column(2, None),
+		)
+		.render();
+	fs::remove_dir_all(&dir).unwrap();
+	let message = result.unwrap_err().to_string();
+	assert!(message.contains("pending"), "{}", message);
+}
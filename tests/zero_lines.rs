@@ -0,0 +1,43 @@
+//! The lines where x and y are zero are emphasised and pass exactly through points of zero
+
+use plotrs::{
+	canvas::plot::DataSymbol,
+	colours::{Colour, DARK_GREY},
+	scatter::{DataSetBuilder, ScatterBuilder},
+};
+
+#[test]
+fn zero_lines_pass_through_points_of_zero() {
+	let image = ScatterBuilder::new()
+		.canvas_size(400, 300)
+		.axis_labels("x", "y")
+		.show_axes(false, false)
+		// zero is drawn along the right edge rather than on the hidden y-axis
+		.reverse_axes(true, false)
+		.highlight_zero(true)
+		.add_data_set(
+			DataSetBuilder::from_points("zeros", &[(0.0, 5.0), (5.0, 0.0)])
+				.colour(Colour::Red)
+				.symbol(DataSymbol::Point, 0, 0),
+		)
+		.add_data_set(DataSetBuilder::from_points("data", &[(10.0, 10.0)]).colour(Colour::Blue))
+		.render()
+		.unwrap();
+	let red: Vec<(u32, u32)> = image
+		.enumerate_pixels()
+		.filter(|(_, _, pixel)| pixel.0 == [255, 0, 0, 255])
+		.map(|(x, y, _)| (x, y))
+		.collect();
+	assert_eq!(2, red.len());
+	let is_dark = |x: u32, y: u32| image.get_pixel(x, y).0 == DARK_GREY;
+	// x is reversed so (0, 5) is the right-hand point
+	let (x_zero, y_zero) = if red[0].0 > red[1].0 {
+		(red[0], red[1])
+	} else {
+		(red[1], red[0])
+	};
+	assert!(is_dark(x_zero.0, x_zero.1 - 3) && is_dark(x_zero.0, x_zero.1 + 3));
+	assert!(!is_dark(x_zero.0 - 1, x_zero.1 - 3));
+	assert!(is_dark(y_zero.0 - 3, y_zero.1) && is_dark(y_zero.0 + 3, y_zero.1));
+	assert!(!is_dark(y_zero.0 - 3, y_zero.1 - 1));
+}